@@ -235,7 +235,9 @@ fn main() -> Result<()> {
 
             // Parse
             let mut parser = HypnoParser::new(tokens);
-            let ast = parser.parse_program().map_err(into_anyhow)?;
+            let ast = parser
+                .parse_program()
+                .map_err(|error| into_anyhow(error.with_file(file.as_str())))?;
 
             if debug {
                 println!("\n--- Type Checking ---");
@@ -243,6 +245,7 @@ fn main() -> Result<()> {
 
             // Type check
             let mut type_checker = TypeChecker::new();
+            type_checker.set_source_path(std::path::Path::new(&file));
             let errors = type_checker.check_program(&ast);
             if !errors.is_empty() {
                 eprintln!("Type errors:");
@@ -260,7 +263,13 @@ fn main() -> Result<()> {
 
             // Execute
             let mut interpreter = Interpreter::new();
-            interpreter.execute_program(ast).map_err(into_anyhow)?;
+            interpreter.set_source_path(std::path::Path::new(&file));
+            if let Err(error) = interpreter.execute_program(ast) {
+                return Err(match interpreter.current_span() {
+                    Some(span) => anyhow!("{}:{}:{}: {}", file, span.line, span.column, error),
+                    None => into_anyhow(error),
+                });
+            }
 
             if verbose {
                 println!("\n✅ Program executed successfully!");
@@ -284,7 +293,9 @@ fn main() -> Result<()> {
             let mut lexer = Lexer::new(&source);
             let tokens = lexer.lex().map_err(into_anyhow)?;
             let mut parser = HypnoParser::new(tokens);
-            let ast = parser.parse_program().map_err(into_anyhow)?;
+            let ast = parser
+                .parse_program()
+                .map_err(|error| into_anyhow(error.with_file(file.as_str())))?;
 
             println!("=== AST ===");
             println!("{:#?}", ast);
@@ -295,9 +306,22 @@ fn main() -> Result<()> {
             let mut lexer = Lexer::new(&source);
             let tokens = lexer.lex().map_err(into_anyhow)?;
             let mut parser = HypnoParser::new(tokens);
-            let ast = parser.parse_program().map_err(into_anyhow)?;
+            let (ast, syntax_errors) = parser.parse_program_with_diagnostics();
+
+            if !syntax_errors.is_empty() {
+                println!("❌ Syntax errors found:");
+                for error in &syntax_errors {
+                    println!("  - {}", error.clone().with_file(file.as_str()));
+                }
+                return Err(anyhow!(
+                    "{} syntax error(s) in {}",
+                    syntax_errors.len(),
+                    file
+                ));
+            }
 
             let mut type_checker = TypeChecker::new();
+            type_checker.set_source_path(std::path::Path::new(&file));
             let errors = type_checker.check_program(&ast);
 
             if errors.is_empty() {
@@ -319,7 +343,9 @@ fn main() -> Result<()> {
             let mut lexer = Lexer::new(&source);
             let tokens = lexer.lex().map_err(into_anyhow)?;
             let mut parser = HypnoParser::new(tokens);
-            let ast = parser.parse_program().map_err(into_anyhow)?;
+            let ast = parser
+                .parse_program()
+                .map_err(|error| into_anyhow(error.with_file(input.as_str())))?;
 
             if binary {
                 // Generate binary WASM
@@ -332,7 +358,7 @@ fn main() -> Result<()> {
             } else {
                 // Generate text WASM (WAT)
                 let mut generator = WasmCodeGenerator::new();
-                let wasm_code = generator.generate(&ast);
+                let wasm_code = generator.generate(&ast).map_err(into_anyhow)?;
 
                 let output_file = output.unwrap_or_else(|| input.replace(".hyp", ".wat"));
                 fs::write(&output_file, wasm_code)?;
@@ -350,7 +376,9 @@ fn main() -> Result<()> {
             let mut lexer = Lexer::new(&source);
             let tokens = lexer.lex().map_err(into_anyhow)?;
             let mut parser = HypnoParser::new(tokens);
-            let ast = parser.parse_program().map_err(into_anyhow)?;
+            let ast = parser
+                .parse_program()
+                .map_err(|error| into_anyhow(error.with_file(input.as_str())))?;
 
             let mut generator = NativeCodeGenerator::new();
 
@@ -412,7 +440,9 @@ fn main() -> Result<()> {
             let mut lexer = Lexer::new(&source);
             let tokens = lexer.lex().map_err(into_anyhow)?;
             let mut parser = HypnoParser::new(tokens);
-            let ast = parser.parse_program().map_err(into_anyhow)?;
+            let ast = parser
+                .parse_program()
+                .map_err(|error| into_anyhow(error.with_file(input.as_str())))?;
 
             let mut optimizer = Optimizer::new();
             optimizer.enable_all_optimizations();
@@ -85,7 +85,7 @@ impl AsyncBuiltins {
         // In real implementation, would convert Value::Promise to AsyncPromise
         // and use crate::async_promise::promise_all
 
-        Ok(Value::Array(promises))
+        Ok(Value::array(promises))
     }
 
     /// Race multiple promises (first to complete wins)
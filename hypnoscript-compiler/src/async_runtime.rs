@@ -138,6 +138,20 @@ impl AsyncRuntime {
         task_id
     }
 
+    /// Spawn a task whose result is awaited through the returned join handle.
+    ///
+    /// Unlike [`spawn`](Self::spawn) the task is not tracked by id; the caller
+    /// owns the handle and can wait for it with [`block_on`](Self::block_on).
+    pub fn spawn_joinable<F>(
+        &self,
+        future: F,
+    ) -> tokio::task::JoinHandle<Result<TaskResult, String>>
+    where
+        F: futures::Future<Output = Result<TaskResult, String>> + Send + 'static,
+    {
+        self.runtime.spawn(future)
+    }
+
     /// Block on a future until completion
     pub fn block_on<F>(&self, future: F) -> F::Output
     where
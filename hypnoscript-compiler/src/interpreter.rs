@@ -1,14 +1,21 @@
+use crate::async_runtime::{AsyncRuntime, TaskResult};
+use crate::module_loader::{Module, ModuleError, ModuleLoader};
+use crate::type_checker::is_builtin_function;
 use hypnoscript_lexer_parser::ast::{
-    AstNode, Pattern, SessionField, SessionMember, SessionMethod, SessionVisibility,
-    VariableStorage,
+    AstNode, DictionaryEntry, InterfaceMember, InterpolationPart, Parameter, Pattern, SessionField,
+    SessionMember, SessionMethod, SessionVisibility, Span, VariableStorage, is_generator_body,
+    operator_method_name, overloadable_operator, overloaded_operator,
 };
 use hypnoscript_runtime::{
-    ArrayBuiltins, CoreBuiltins, FileBuiltins, HashingBuiltins, MathBuiltins, StatisticsBuiltins,
-    StringBuiltins, SystemBuiltins, TimeBuiltins, ValidationBuiltins,
+    ArrayBuiltins, CoreBuiltins, Dictionary, DictionaryBuiltins, FileBuiltins, HashingBuiltins,
+    MathBuiltins, StatisticsBuiltins, StringBuiltins, SystemBuiltins, TimeBuiltins,
+    ValidationBuiltins,
 };
 use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
-use std::rc::Rc;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::rc::{Rc, Weak};
 use thiserror::Error;
 
 /// Interpreter errors that can occur during program execution.
@@ -26,6 +33,12 @@ pub enum InterpreterError {
     #[error("Continue statement outside of loop")]
     ContinueOutsideLoop,
 
+    #[error("No enclosing loop labeled '{0}' to snap out of")]
+    LabeledBreak(String),
+
+    #[error("No enclosing loop labeled '{0}' to sink into")]
+    LabeledContinue(String),
+
     #[error("Return from function: {0:?}")]
     Return(Value),
 
@@ -34,6 +47,306 @@ pub enum InterpreterError {
 
     #[error("Type error: {0}")]
     TypeError(String),
+
+    #[error("Uncaught error: {}", raised_message(.0))]
+    Raised(Value),
+
+    #[error("Module error: {0}")]
+    Module(#[from] ModuleError),
+
+    #[error("{0}")]
+    AssertionFailed(Box<AssertionFailure>),
+}
+
+/// Details of a failed `assert` statement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssertionFailure {
+    /// Source text of the asserted condition
+    pub expression: String,
+    /// Optional user supplied message
+    pub message: Option<String>,
+    /// Evaluated `(left, right)` operands when the condition was an equality check
+    pub operands: Option<(Value, Value)>,
+}
+
+impl std::fmt::Display for AssertionFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Assertion failed: {}", self.expression)?;
+        if let Some(message) = &self.message {
+            write!(f, " ({})", message)?;
+        }
+        if let Some((left, right)) = &self.operands {
+            write!(
+                f,
+                "\n  left:  {}\n  right: {}",
+                describe_operand(left),
+                describe_operand(right)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Render an assertion operand, quoting strings so that whitespace differences show
+fn describe_operand(value: &Value) -> String {
+    match value {
+        Value::String(s) => format!("{:?}", s),
+        other => other.to_string(),
+    }
+}
+
+impl InterpreterError {
+    /// Convert a failure into the error record handed to a `recover` block.
+    ///
+    /// Control flow (`awaken`, `snap`, `sink`) is not catchable and is returned unchanged.
+    fn into_catchable(self) -> Result<Value, InterpreterError> {
+        match self {
+            InterpreterError::Raised(value) => Ok(value),
+            InterpreterError::Runtime(message) => Ok(error_record("Runtime", message, Value::Null)),
+            InterpreterError::TypeError(message) => {
+                Ok(error_record("TypeError", message, Value::Null))
+            }
+            InterpreterError::Module(error) => {
+                Ok(error_record("Module", error.to_string(), Value::Null))
+            }
+            InterpreterError::AssertionFailed(failure) => Ok(error_record(
+                "AssertionFailed",
+                failure.to_string(),
+                Value::Null,
+            )),
+            InterpreterError::UndefinedVariable(name) => Ok(error_record(
+                "UndefinedVariable",
+                format!("Variable '{}' not found", name),
+                Value::Null,
+            )),
+            control_flow => Err(control_flow),
+        }
+    }
+}
+
+/// How a loop proceeds after one run of its body.
+enum LoopFlow {
+    Next,
+    Break,
+}
+
+/// Resolve `snap`/`sink` signals from a loop body for the loop labeled `label`.
+///
+/// Unlabeled jumps always target the innermost loop; labeled jumps for other
+/// loops keep propagating outwards.
+fn loop_flow(
+    result: Result<(), InterpreterError>,
+    label: Option<&str>,
+) -> Result<LoopFlow, InterpreterError> {
+    match result {
+        Ok(()) | Err(InterpreterError::ContinueOutsideLoop) => Ok(LoopFlow::Next),
+        Err(InterpreterError::BreakOutsideLoop) => Ok(LoopFlow::Break),
+        Err(InterpreterError::LabeledBreak(target)) if label == Some(target.as_str()) => {
+            Ok(LoopFlow::Break)
+        }
+        Err(InterpreterError::LabeledContinue(target)) if label == Some(target.as_str()) => {
+            Ok(LoopFlow::Next)
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/// Numbers of a range expression, produced on demand.
+#[derive(Debug)]
+struct NumberRange {
+    start: f64,
+    end: f64,
+    step: f64,
+    inclusive: bool,
+    index: usize,
+}
+
+impl Iterator for NumberRange {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        // Multiplying instead of summing keeps fractional steps from drifting
+        let value = self.start + self.index as f64 * self.step;
+        let within = match (self.step > 0.0, self.inclusive) {
+            (true, true) => value <= self.end,
+            (true, false) => value < self.end,
+            (false, true) => value >= self.end,
+            (false, false) => value > self.end,
+        };
+        if !within {
+            return None;
+        }
+        self.index += 1;
+        Some(value)
+    }
+}
+
+/// Position of a for-each loop in the collection it walks.
+///
+/// Generators, streamed files and session iterators are pulled one value at a
+/// time, so the loop body runs before the next value is produced.
+#[derive(Debug)]
+enum IterationCursor {
+    Entries(std::vec::IntoIter<(Value, Value)>),
+    Range(std::iter::Enumerate<NumberRange>),
+    Iterator {
+        source: Rc<RefCell<IteratorValue>>,
+        index: usize,
+    },
+    /// Session instance implementing `hasNext()` and `next()`
+    Session {
+        has_next: FunctionValue,
+        next: FunctionValue,
+        index: usize,
+    },
+}
+
+/// Lazy sequence returned by a generator suggestion or a streaming builtin.
+///
+/// `next()` pulls one value at a time; `hasNext()` pulls the next value ahead
+/// and keeps it until `next()` consumes it.
+#[derive(Debug)]
+pub struct IteratorValue {
+    /// Value pulled by `hasNext()` that `next()` has not returned yet
+    peeked: Option<Value>,
+    source: IteratorSource,
+}
+
+impl IteratorValue {
+    fn new(source: IteratorSource) -> Self {
+        Self {
+            peeked: None,
+            source,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum IteratorSource {
+    /// Suspended generator body (`None` while it is running)
+    Generator(Option<Box<GeneratorState>>),
+    /// Lines of a file read on demand (`StreamLines`)
+    Lines {
+        path: String,
+        lines: std::io::Lines<std::io::BufReader<std::fs::File>>,
+    },
+    Done,
+}
+
+/// Statements a suspended body stops at
+#[derive(Debug, Clone, Copy)]
+enum SuspendPoint {
+    /// `emerge` in a generator suggestion
+    Emerge,
+    /// A [suspending await](AstNode::suspending_await) in a `mesmerize` call
+    Await,
+}
+
+/// Execution state of a generator suggestion between two `emerge` statements,
+/// or of a `mesmerize` call between two awaits
+#[derive(Debug)]
+struct GeneratorState {
+    suspends_at: SuspendPoint,
+    session_name: Option<String>,
+    /// Scope chain of the generator body, swapped in while it runs
+    scopes: Vec<ScopeRef>,
+    /// Statements still to run, innermost last
+    frames: Vec<GeneratorFrame>,
+}
+
+/// Statement a suspended generator is inside of.
+///
+/// Only statements containing a suspend point get a frame; all others run to
+/// completion through `execute_statement`.
+#[derive(Debug)]
+enum GeneratorFrame {
+    /// Statements of a block; `scoped` blocks pop their scope once done
+    Block {
+        statements: Rc<[AstNode]>,
+        index: usize,
+        scoped: bool,
+    },
+    While {
+        condition: Rc<AstNode>,
+        body: Rc<[AstNode]>,
+        label: Option<String>,
+    },
+    Loop {
+        condition: Option<Rc<AstNode>>,
+        update: Option<Rc<AstNode>>,
+        body: Rc<[AstNode]>,
+        label: Option<String>,
+        /// Whether the body ran once, so the update runs before the next check
+        started: bool,
+    },
+    ForEach {
+        key: Option<String>,
+        value: String,
+        is_constant: bool,
+        cursor: Box<IterationCursor>,
+        body: Rc<[AstNode]>,
+        label: Option<String>,
+    },
+    /// Statement of a `mesmerize` call suspended at its await
+    Await {
+        statement: Rc<AstNode>,
+        promise: Rc<RefCell<Promise>>,
+        span: Option<Span>,
+    },
+}
+
+impl GeneratorFrame {
+    /// Label of a loop frame (`None` for blocks and unlabeled loops)
+    fn loop_label(&self) -> Option<Option<&str>> {
+        match self {
+            GeneratorFrame::Block { .. } | GeneratorFrame::Await { .. } => None,
+            GeneratorFrame::While { label, .. }
+            | GeneratorFrame::Loop { label, .. }
+            | GeneratorFrame::ForEach { label, .. } => Some(label.as_deref()),
+        }
+    }
+}
+
+/// Number of leading arguments of a builtin that accept any iterable.
+///
+/// Builtins changing their array in place (`ArrayPush`, ...) need a real array.
+pub(crate) fn iterable_arg_count(builtin: &str) -> usize {
+    match builtin {
+        "ArrayLength" | "ArrayIsEmpty" | "ArrayGet" | "ArrayIndexOf" | "ArrayContains"
+        | "ArrayReverse" | "ArraySum" | "ArrayAverage" | "ArrayMin" | "ArrayMax" | "ArraySort"
+        | "ArrayFirst" | "ArrayLast" | "ArrayTake" | "ArraySkip" | "ArraySlice" | "ArrayJoin"
+        | "ArrayCount" | "ArrayDistinct" => 1,
+        "Mean" | "Median" | "Mode" | "StandardDeviation" | "Variance" | "Range" | "Percentile" => 1,
+        "Correlation" | "LinearRegression" => 2,
+        _ => 0,
+    }
+}
+
+/// Name of the record type used for catchable errors.
+const ERROR_RECORD_TYPE: &str = "Error";
+
+/// Build an `Error` record with `kind`, `message` and `payload` fields.
+fn error_record(kind: &str, message: String, payload: Value) -> Value {
+    let mut fields = HashMap::new();
+    fields.insert("kind".to_string(), Value::String(kind.to_string()));
+    fields.insert("message".to_string(), Value::String(message));
+    fields.insert("payload".to_string(), payload);
+    Value::Record(RecordValue {
+        type_name: ERROR_RECORD_TYPE.to_string(),
+        fields,
+    })
+}
+
+/// Message of a raised value (the `message` field of error records).
+fn raised_message(value: &Value) -> String {
+    match value {
+        Value::Record(record) if record.type_name == ERROR_RECORD_TYPE => record
+            .fields
+            .get("message")
+            .map(|message| message.to_string())
+            .unwrap_or_default(),
+        other => other.to_string(),
+    }
 }
 
 /// Provide a simple locale-aware message while we prepare full i18n plumbing.
@@ -41,6 +354,206 @@ fn localized(en: &str, de: &str) -> String {
     format!("{} (DE: {})", en, de)
 }
 
+/// Convert a parsed JSON value into a runtime value (objects become dictionaries)
+fn json_to_value(json: serde_json::Value) -> Value {
+    match json {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(b) => Value::Boolean(b),
+        serde_json::Value::Number(n) => Value::Number(n.as_f64().unwrap_or(f64::NAN)),
+        serde_json::Value::String(s) => Value::String(s),
+        serde_json::Value::Array(items) => {
+            Value::array(items.into_iter().map(json_to_value).collect())
+        }
+        serde_json::Value::Object(object) => Value::Dictionary(
+            object
+                .into_iter()
+                .map(|(key, value)| (DictionaryKey::String(key), json_to_value(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// Convert a runtime value into JSON (dictionary keys are stringified)
+fn value_to_json(value: &Value) -> Result<serde_json::Value, InterpreterError> {
+    value_to_json_within(value, &mut Vec::new())
+}
+
+/// Convert a value into JSON, rejecting arrays that contain themselves
+///
+/// `enclosing` holds the arrays currently being converted.
+fn value_to_json_within(
+    value: &Value,
+    enclosing: &mut Vec<*const RefCell<Vec<Value>>>,
+) -> Result<serde_json::Value, InterpreterError> {
+    Ok(match value {
+        Value::Null => serde_json::Value::Null,
+        Value::Boolean(b) => serde_json::Value::Bool(*b),
+        Value::Number(n) if n.fract() == 0.0 && n.abs() < i64::MAX as f64 => {
+            serde_json::Value::from(*n as i64)
+        }
+        Value::Number(n) => serde_json::Number::from_f64(*n)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null),
+        Value::Integer(i) => serde_json::Value::from(*i),
+        Value::String(s) => serde_json::Value::String(s.clone()),
+        Value::Array(items) => {
+            if enclosing.contains(&Rc::as_ptr(items)) {
+                return Err(InterpreterError::TypeError(localized(
+                    "Cannot convert an array that contains itself to JSON",
+                    "Ein Array, das sich selbst enthält, kann nicht in JSON umgewandelt werden",
+                )));
+            }
+            enclosing.push(Rc::as_ptr(items));
+            let converted = items
+                .borrow()
+                .iter()
+                .map(|item| value_to_json_within(item, enclosing))
+                .collect::<Result<_, _>>();
+            enclosing.pop();
+            serde_json::Value::Array(converted?)
+        }
+        Value::Dictionary(dict) => serde_json::Value::Object(
+            dict.iter()
+                .map(|(key, value)| Ok((key.to_string(), value_to_json_within(value, enclosing)?)))
+                .collect::<Result<_, InterpreterError>>()?,
+        ),
+        Value::Record(record) => serde_json::Value::Object(
+            record
+                .fields
+                .iter()
+                .map(|(name, value)| Ok((name.clone(), value_to_json_within(value, enclosing)?)))
+                .collect::<Result<_, InterpreterError>>()?,
+        ),
+        other => {
+            return Err(InterpreterError::TypeError(localized(
+                &format!("Cannot convert '{}' to JSON", other),
+                &format!("'{}' kann nicht in JSON umgewandelt werden", other),
+            )));
+        }
+    })
+}
+
+/// The integer a number represents exactly, if it is whole and within 64 bits
+fn exact_integer(n: f64) -> Option<i64> {
+    (n.fract() == 0.0 && n >= i64::MIN as f64 && n < i64::MAX as f64).then_some(n as i64)
+}
+
+/// The low 63 bits of a hash, so the result is a non-negative integer
+fn non_negative_hash(hash: u64) -> i64 {
+    (hash & i64::MAX as u64) as i64
+}
+
+/// Error for integer arithmetic whose result does not fit into 64 bits
+fn integer_overflow(expression: &str) -> InterpreterError {
+    InterpreterError::Runtime(localized(
+        &format!("Integer overflow in {}", expression),
+        &format!("Ganzzahlüberlauf bei {}", expression),
+    ))
+}
+
+/// Exact arithmetic on two integers, or `None` for non-arithmetic operators.
+///
+/// Division truncates toward zero; overflow and division by zero are errors.
+fn integer_arithmetic(operator: &str, a: i64, b: i64) -> Option<Result<Value, InterpreterError>> {
+    let result = match operator {
+        "+" => a.checked_add(b),
+        "-" => a.checked_sub(b),
+        "*" => a.checked_mul(b),
+        "/" | "%" if b == 0 => {
+            return Some(Err(InterpreterError::Runtime(localized(
+                "Integer division by zero",
+                "Ganzzahlige Division durch null",
+            ))));
+        }
+        "/" => a.checked_div(b),
+        "%" => a.checked_rem(b),
+        _ => return None,
+    };
+    Some(
+        result
+            .map(Value::Integer)
+            .ok_or_else(|| integer_overflow(&format!("{} {} {}", a, operator, b))),
+    )
+}
+
+/// Operand of a bitwise operator; whole numbers convert to integers exactly
+fn bitwise_operand(value: &Value, operator: &str) -> Result<i64, InterpreterError> {
+    match value {
+        Value::Integer(i) => Ok(*i),
+        Value::Number(n) if exact_integer(*n).is_some() => Ok(*n as i64),
+        other => Err(InterpreterError::TypeError(localized(
+            &format!(
+                "Bitwise operator '{}' requires integers, got {}",
+                operator, other
+            ),
+            &format!(
+                "Bitoperator '{}' erfordert Ganzzahlen, erhalten {}",
+                operator, other
+            ),
+        ))),
+    }
+}
+
+/// Evaluate `&`, `|`, `^`, `<<` and `>>`; right shifts keep the sign
+fn bitwise_operation(
+    operator: &str,
+    left: &Value,
+    right: &Value,
+) -> Result<Value, InterpreterError> {
+    let a = bitwise_operand(left, operator)?;
+    let b = bitwise_operand(right, operator)?;
+    let result = match operator {
+        "&" => a & b,
+        "|" => a | b,
+        "^" => a ^ b,
+        _ => {
+            let amount = u32::try_from(b)
+                .ok()
+                .filter(|amount| *amount < 64)
+                .ok_or_else(|| {
+                    InterpreterError::Runtime(localized(
+                        &format!("Shift amount must be between 0 and 63, got {}", b),
+                        &format!(
+                            "Verschiebeweite muss zwischen 0 und 63 liegen, erhalten {}",
+                            b
+                        ),
+                    ))
+                })?;
+            if operator == "<<" {
+                a << amount
+            } else {
+                a >> amount
+            }
+        }
+    };
+    Ok(Value::Integer(result))
+}
+
+/// Order of two numeric values; two integers compare exactly
+fn numeric_ordering(left: &Value, right: &Value) -> Result<Option<Ordering>, InterpreterError> {
+    match (left, right) {
+        (Value::Integer(a), Value::Integer(b)) => Ok(Some(a.cmp(b))),
+        _ => Ok(left.to_number()?.partial_cmp(&right.to_number()?)),
+    }
+}
+
+/// A local scope frame.
+///
+/// Frames are reference counted so that closures can capture the scope chain
+/// they were created in and observe (and perform) later mutations.
+#[derive(Debug, Default)]
+struct LocalScope {
+    values: HashMap<String, Value>,
+    constants: HashSet<String>,
+    /// Top-level scope of a `mindLink` module
+    is_module: bool,
+}
+
+type ScopeRef = Rc<RefCell<LocalScope>>;
+
+/// Released session instances whose `finale` has not run yet
+type FinaleQueue = Rc<RefCell<Vec<SessionInstance>>>;
+
 #[derive(Clone, Copy, Debug)]
 enum ScopeLayer {
     Local,
@@ -56,6 +569,7 @@ enum ScopeLayer {
 /// - Static session methods (`dominant` keyword)
 /// - Constructors (special session methods)
 /// - Triggers (event-driven callbacks)
+/// - Anonymous suggestions (closures over their defining scope)
 ///
 /// # Examples
 ///
@@ -65,6 +579,9 @@ enum ScopeLayer {
 ///     awaken "Hello, " + name;
 /// }
 ///
+/// // Closure
+/// induce double = suggestion (x) => x * 2;
+///
 /// // Session method
 /// session Calculator {
 ///     suggestion add(a: number, b: number) {
@@ -75,40 +592,84 @@ enum ScopeLayer {
 #[derive(Debug, Clone)]
 pub struct FunctionValue {
     name: String,
-    parameters: Vec<String>,
+    parameters: Vec<Parameter>,
     body: Vec<AstNode>,
     this_binding: Option<Rc<RefCell<SessionInstance>>>,
     session_name: Option<String>,
     is_static: bool,
     is_constructor: bool,
+    /// `mesmerize` suggestion: calls are queued and return a promise
+    is_async: bool,
+    /// Generator suggestion: its body `emerge`s values and calls return an iterator
+    is_generator: bool,
+    /// Scope chain captured by a closure (`None` for declared suggestions)
+    captured_scopes: Option<Rc<Vec<ScopeRef>>>,
 }
 
 impl FunctionValue {
-    fn new_global(name: String, parameters: Vec<String>, body: Vec<AstNode>) -> Self {
+    fn new_global(name: String, parameters: Vec<Parameter>, body: Vec<AstNode>) -> Self {
         Self {
             name,
             parameters,
+            is_generator: is_generator_body(&body),
             body,
             this_binding: None,
             session_name: None,
             is_static: false,
             is_constructor: false,
+            is_async: false,
+            captured_scopes: None,
+        }
+    }
+
+    /// Mark a declared suggestion as `mesmerize` (async)
+    fn mesmerized(mut self, is_async: bool) -> Self {
+        self.is_async = is_async;
+        self
+    }
+
+    /// Bind a declared suggestion to the scopes of its defining module
+    fn in_module(mut self, module_scopes: Option<Rc<Vec<ScopeRef>>>) -> Self {
+        self.captured_scopes = module_scopes;
+        self
+    }
+
+    fn new_closure(
+        parameters: Vec<Parameter>,
+        body: Vec<AstNode>,
+        scopes: Vec<ScopeRef>,
+        session_name: Option<String>,
+    ) -> Self {
+        Self {
+            name: "<suggestion>".to_string(),
+            parameters,
+            is_generator: is_generator_body(&body),
+            body,
+            this_binding: None,
+            session_name,
+            is_static: false,
+            is_constructor: false,
+            is_async: false,
+            captured_scopes: Some(Rc::new(scopes)),
         }
     }
 
+    /// Method bound to `this_binding`; it runs in the context of the session declaring it
     fn new_session_member(
-        session_name: String,
         method: &SessionMethodDefinition,
         this_binding: Option<Rc<RefCell<SessionInstance>>>,
     ) -> Self {
         Self {
-            name: format!("{}::{}", session_name, method.name),
+            name: format!("{}::{}", method.owner, method.name),
             parameters: method.parameters.clone(),
             body: method.body.clone(),
+            is_generator: is_generator_body(&method.body),
             this_binding,
-            session_name: Some(session_name),
+            session_name: Some(method.owner.clone()),
             is_static: method.is_static,
             is_constructor: method.is_constructor,
+            is_async: method.is_async,
+            captured_scopes: method.module_scopes.clone(),
         }
     }
 
@@ -129,6 +690,12 @@ impl PartialEq for FunctionValue {
             && self.session_name == other.session_name
             && self.is_static == other.is_static
             && self.is_constructor == other.is_constructor
+            && self.is_async == other.is_async
+            && match (&self.captured_scopes, &other.captured_scopes) {
+                (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+                (None, None) => true,
+                _ => false,
+            }
     }
 }
 
@@ -154,6 +721,8 @@ struct SessionFieldDefinition {
     type_annotation: Option<String>,
     visibility: SessionVisibility,
     initializer: Option<AstNode>,
+    /// Session that declares the field (a parent session for inherited fields)
+    owner: String,
 }
 
 /// Definition of a session method.
@@ -187,11 +756,16 @@ struct SessionFieldDefinition {
 #[derive(Debug, Clone)]
 struct SessionMethodDefinition {
     name: String,
-    parameters: Vec<String>,
+    parameters: Vec<Parameter>,
     body: Vec<AstNode>,
     visibility: SessionVisibility,
     is_static: bool,
     is_constructor: bool,
+    is_async: bool,
+    /// Scope chain of the defining module (`None` in the entry program)
+    module_scopes: Option<Rc<Vec<ScopeRef>>>,
+    /// Session that declares the method
+    owner: String,
 }
 
 /// Runtime data for a static field, including its initializer AST.
@@ -225,6 +799,8 @@ struct SessionStaticField {
 /// - Instance and static methods
 /// - Constructors
 /// - Visibility modifiers (`expose`/`conceal`)
+/// - Inheritance (`extends`): instance fields are copied from the parent,
+///   methods, static members and the constructor are looked up along the chain
 ///
 /// # Examples
 ///
@@ -254,6 +830,8 @@ struct SessionStaticField {
 #[derive(Debug)]
 pub struct SessionDefinition {
     name: String,
+    parent: Option<Rc<SessionDefinition>>,
+    interfaces: Vec<String>,
     fields: HashMap<String, SessionFieldDefinition>,
     field_order: Vec<String>,
     methods: HashMap<String, SessionMethodDefinition>,
@@ -261,19 +839,28 @@ pub struct SessionDefinition {
     static_fields: RefCell<HashMap<String, SessionStaticField>>,
     static_field_order: Vec<String>,
     constructor: Option<SessionMethodDefinition>,
+    finale: Option<SessionMethodDefinition>,
 }
 
 impl SessionDefinition {
-    fn new(name: String) -> Self {
+    fn new(name: String, parent: Option<Rc<SessionDefinition>>) -> Self {
+        // Instances of a child session carry the parent's fields first
+        let (fields, field_order) = match &parent {
+            Some(parent) => (parent.fields.clone(), parent.field_order.clone()),
+            None => (HashMap::new(), Vec::new()),
+        };
         Self {
             name,
-            fields: HashMap::new(),
-            field_order: Vec::new(),
+            parent,
+            interfaces: Vec::new(),
+            fields,
+            field_order,
             methods: HashMap::new(),
             static_methods: HashMap::new(),
             static_fields: RefCell::new(HashMap::new()),
             static_field_order: Vec::new(),
             constructor: None,
+            finale: None,
         }
     }
 
@@ -281,7 +868,32 @@ impl SessionDefinition {
         &self.name
     }
 
+    fn parent(&self) -> Option<&Rc<SessionDefinition>> {
+        self.parent.as_ref()
+    }
+
+    /// Whether this session is `name`, extends it or implements interface `name`
+    fn is_a(&self, name: &str) -> bool {
+        self.name == name
+            || self.interfaces.iter().any(|interface| interface == name)
+            || self.parent.as_ref().is_some_and(|p| p.is_a(name))
+    }
+
     fn push_field(&mut self, field: SessionFieldDefinition) -> Result<(), InterpreterError> {
+        if let Some(inherited) = self.fields.get(&field.name)
+            && inherited.owner != self.name
+        {
+            return Err(InterpreterError::Runtime(localized(
+                &format!(
+                    "Field '{}' in session '{}' is already declared by session '{}'",
+                    field.name, self.name, inherited.owner
+                ),
+                &format!(
+                    "Feld '{}' in Session '{}' ist bereits in Session '{}' deklariert",
+                    field.name, self.name, inherited.owner
+                ),
+            )));
+        }
         if self.fields.contains_key(&field.name)
             || self.static_fields.borrow().contains_key(&field.name)
         {
@@ -378,15 +990,20 @@ impl SessionDefinition {
     }
 
     fn get_method_definition(&self, name: &str) -> Option<&SessionMethodDefinition> {
-        self.methods.get(name)
+        self.methods
+            .get(name)
+            .or_else(|| self.parent.as_ref()?.get_method_definition(name))
     }
 
     fn get_static_method_definition(&self, name: &str) -> Option<&SessionMethodDefinition> {
-        self.static_methods.get(name)
+        self.static_methods
+            .get(name)
+            .or_else(|| self.parent.as_ref()?.get_static_method_definition(name))
     }
 
     fn get_static_field_snapshot(&self, name: &str) -> Option<SessionStaticField> {
-        self.static_fields.borrow().get(name).cloned()
+        let own = self.static_fields.borrow().get(name).cloned();
+        own.or_else(|| self.parent.as_ref()?.get_static_field_snapshot(name))
     }
 
     fn set_static_field_value(&self, name: &str, value: Value) -> Result<(), InterpreterError> {
@@ -396,6 +1013,8 @@ impl SessionDefinition {
                 field.value = value;
                 Ok(())
             }
+            // Inherited static fields are shared with the parent session
+            None if let Some(parent) = &self.parent => parent.set_static_field_value(name, value),
             None => Err(InterpreterError::Runtime(localized(
                 &format!(
                     "Static field '{}' not found on session '{}'",
@@ -425,7 +1044,14 @@ impl SessionDefinition {
     }
 
     fn constructor(&self) -> Option<&SessionMethodDefinition> {
-        self.constructor.as_ref()
+        self.constructor
+            .as_ref()
+            .or_else(|| self.parent.as_ref()?.constructor())
+    }
+
+    /// Whether this session or one of its ancestors declares a `finale`
+    fn has_finale(&self) -> bool {
+        self.finale.is_some() || self.parent.as_ref().is_some_and(|p| p.has_finale())
     }
 }
 
@@ -451,10 +1077,15 @@ impl SessionDefinition {
 /// // Creates a SessionInstance
 /// induce person = Person("Alice", 30);
 /// ```
+///
+/// Instances of sessions with a `finale` are queued for it when their last
+/// reference is dropped; the interpreter runs the queue after each statement.
 #[derive(Debug)]
 pub struct SessionInstance {
     definition: Rc<SessionDefinition>,
     field_values: HashMap<String, Value>,
+    /// Queue to join when dropped; `None` once the finale ran or if there is none
+    finale_queue: Option<Weak<RefCell<Vec<SessionInstance>>>>,
 }
 
 impl SessionInstance {
@@ -466,6 +1097,7 @@ impl SessionInstance {
         Self {
             definition,
             field_values,
+            finale_queue: None,
         }
     }
 
@@ -486,23 +1118,41 @@ impl SessionInstance {
     }
 }
 
+impl Drop for SessionInstance {
+    fn drop(&mut self) {
+        // The finale needs `this`, so the fields move into a fresh instance
+        // that is queued without a queue of its own
+        if let Some(queue) = self.finale_queue.take().and_then(|queue| queue.upgrade()) {
+            queue.borrow_mut().push(SessionInstance {
+                definition: Rc::clone(&self.definition),
+                field_values: std::mem::take(&mut self.field_values),
+                finale_queue: None,
+            });
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct ExecutionContextFrame {
     session_name: Option<String>,
 }
 
-/// Simple Promise/Future wrapper for async operations.
+/// Promise of an async computation.
 ///
-/// Promises represent asynchronous computations in HypnoScript.
-/// They are created by `mesmerize` suggestions and resolved with `await` or `surrenderTo`.
+/// Promises are returned by calls to `mesmerize` suggestions and async builtins
+/// and resolved with `await` or `surrenderTo`. A `mesmerize` call is queued on
+/// the interpreter's task queue and starts once a pending promise is awaited.
+/// A started call that awaits a pending promise is suspended, so other calls
+/// run in the meantime. Async builtins run their I/O as a job on the
+/// [`AsyncRuntime`], so several of them proceed concurrently once started.
 ///
 /// # Examples
 ///
 /// ```hyp
 /// // Async suggestion returns a Promise
 /// mesmerize suggestion fetchData() {
-///     induce data = "some data";
-///     awaken data;
+///     await AsyncDelay(100);
+///     awaken "some data";
 /// }
 ///
 /// entrance {
@@ -510,37 +1160,81 @@ struct ExecutionContextFrame {
 ///     observe result;
 /// }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct Promise {
-    /// The resolved value (if completed)
-    value: Option<Value>,
-    /// Whether the promise is resolved
-    resolved: bool,
+    state: PromiseState,
+    /// Job on the async runtime that settles the promise (async builtins)
+    job: Option<tokio::task::JoinHandle<Result<TaskResult, String>>>,
+}
+
+/// Settlement state of a [`Promise`]
+#[derive(Debug, Clone)]
+enum PromiseState {
+    Pending,
+    Fulfilled(Value),
+    /// Rejected with the error value a `recover` block would receive
+    Rejected(Value),
 }
 
 impl Promise {
-    #[allow(dead_code)]
-    fn new() -> Self {
+    fn pending() -> Self {
         Self {
-            value: None,
-            resolved: false,
+            state: PromiseState::Pending,
+            job: None,
         }
     }
 
-    #[allow(dead_code)]
-    fn resolve(value: Value) -> Self {
+    fn from_job(job: tokio::task::JoinHandle<Result<TaskResult, String>>) -> Self {
         Self {
-            value: Some(value),
-            resolved: true,
+            state: PromiseState::Pending,
+            job: Some(job),
+        }
+    }
+
+    fn is_pending(&self) -> bool {
+        matches!(self.state, PromiseState::Pending)
+    }
+
+    /// Value `await` yields once the promise settled; a rejection is raised
+    fn outcome(&self) -> Option<Result<Value, InterpreterError>> {
+        match &self.state {
+            PromiseState::Pending => None,
+            PromiseState::Fulfilled(value) => Some(Ok(value.clone())),
+            PromiseState::Rejected(error) => Some(Err(InterpreterError::Raised(error.clone()))),
         }
     }
 
-    fn is_resolved(&self) -> bool {
-        self.resolved
+    /// Settle with the outcome of a call; non-catchable errors are passed through
+    fn settle(&mut self, outcome: Result<Value, InterpreterError>) -> Result<(), InterpreterError> {
+        self.state = match outcome {
+            Ok(value) => PromiseState::Fulfilled(value),
+            Err(error) => PromiseState::Rejected(error.into_catchable()?),
+        };
+        Ok(())
     }
+}
+
+/// A `mesmerize` call: queued until the scheduler starts it, then run up to
+/// each await of a pending promise
+struct AsyncTask {
+    /// Bound arguments and the statements still to run
+    body: GeneratorState,
+    promise: Rc<RefCell<Promise>>,
+}
+
+/// A started `mesmerize` call waiting for a promise
+struct SuspendedTask {
+    task: AsyncTask,
+    waiting_on: Rc<RefCell<Promise>>,
+}
 
-    fn get_value(&self) -> Option<Value> {
-        self.value.clone()
+/// Convert the result of an async runtime job into a value
+fn task_result_to_value(result: TaskResult) -> Value {
+    match result {
+        TaskResult::Number(n) => Value::Number(n),
+        TaskResult::String(s) => Value::String(s),
+        TaskResult::Boolean(b) => Value::Boolean(b),
+        TaskResult::Null => Value::Null,
     }
 }
 
@@ -554,39 +1248,101 @@ impl Promise {
 /// - `Number(f64)` - Numeric values (e.g., `42`, `3.14`)
 /// - `String(String)` - Text values (e.g., `"Hello"`)
 /// - `Boolean(bool)` - Boolean values (`true`/`false`)
-/// - `Array(Vec<Value>)` - Arrays (e.g., `[1, 2, 3]`)
+/// - `Array(Rc<RefCell<Vec<Value>>>)` - Arrays (e.g., `[1, 2, 3]`), shared by reference
 /// - `Function(FunctionValue)` - Callable suggestions
 /// - `Session(Rc<SessionDefinition>)` - Session type (class constructor)
 /// - `Instance(Rc<RefCell<SessionInstance>>)` - Session instance
 /// - `Promise(Rc<RefCell<Promise>>)` - Async promise from `mesmerize`
+/// - `Iterator(Rc<RefCell<IteratorValue>>)` - Lazy sequence from a generator suggestion
 /// - `Record(RecordValue)` - Record/struct from `tranceify`
+/// - `Enum(Rc<EnumDefinition>)` - Enum type (variant constructor)
+/// - `Variant(VariantValue)` - Enum variant with its payload values
 /// - `Null` - Null value
 ///
 /// # Examples
 ///
 /// ```hyp
 /// induce num: number = 42;                    // Value::Number
+/// induce id: integer = 42i;                   // Value::Integer
 /// induce text: string = "Hello";              // Value::String
 /// induce flag: boolean = true;                // Value::Boolean
 /// induce list: number[] = [1, 2, 3];          // Value::Array
 /// induce account = BankAccount(100);          // Value::Instance
-/// induce promise = mesmerize getData();       // Value::Promise
+/// induce promise = getData();                // Value::Promise (mesmerize)
+/// induce state = State.Running(3);            // Value::Variant
 /// induce nothing: null = null;                // Value::Null
 /// ```
 #[derive(Debug, Clone)]
 pub enum Value {
     Number(f64),
+    /// 64-bit integer with checked arithmetic
+    Integer(i64),
     String(String),
     Boolean(bool),
-    Array(Vec<Value>),
+    /// Arrays are shared: copies of the value alias the same elements
+    Array(Rc<RefCell<Vec<Value>>>),
     Function(FunctionValue),
     Session(Rc<SessionDefinition>),
     Instance(Rc<RefCell<SessionInstance>>),
     Promise(Rc<RefCell<Promise>>),
+    Iterator(Rc<RefCell<IteratorValue>>),
     Record(RecordValue),
+    Dictionary(Dictionary<DictionaryKey, Value>),
+    Enum(Rc<EnumDefinition>),
+    Variant(VariantValue),
     Null,
 }
 
+/// An enum declaration: variant names with the number of payload values they carry
+#[derive(Debug)]
+pub struct EnumDefinition {
+    pub name: String,
+    pub variants: Vec<(String, usize)>,
+}
+
+impl EnumDefinition {
+    /// Build a variant value, checking the payload arity
+    fn construct(&self, variant: &str, payload: Vec<Value>) -> Result<Value, InterpreterError> {
+        let Some((_, arity)) = self.variants.iter().find(|(name, _)| name == variant) else {
+            return Err(InterpreterError::Runtime(localized(
+                &format!("Enum '{}' has no variant '{}'", self.name, variant),
+                &format!("Enum '{}' besitzt keine Variante '{}'", self.name, variant),
+            )));
+        };
+        if *arity != payload.len() {
+            return Err(InterpreterError::Runtime(localized(
+                &format!(
+                    "Variant '{}.{}' expects {} payload value(s), got {}",
+                    self.name,
+                    variant,
+                    arity,
+                    payload.len()
+                ),
+                &format!(
+                    "Variante '{}.{}' erwartet {} Nutzwert(e), erhalten {}",
+                    self.name,
+                    variant,
+                    arity,
+                    payload.len()
+                ),
+            )));
+        }
+        Ok(Value::Variant(VariantValue {
+            enum_name: self.name.clone(),
+            variant: variant.to_string(),
+            payload,
+        }))
+    }
+}
+
+/// A variant of an enum (e.g., `State.Running(3)`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantValue {
+    pub enum_name: String,
+    pub variant: String,
+    pub payload: Vec<Value>,
+}
+
 /// A record instance (from tranceify declarations).
 ///
 /// Records are user-defined structured data types in HypnoScript,
@@ -619,45 +1375,220 @@ impl PartialEq for RecordValue {
 
 impl Eq for RecordValue {}
 
+/// Key of a dictionary entry.
+///
+/// Dictionaries accept string, number, integer and boolean keys. Numbers are
+/// stored by their bit pattern (with `-0` normalized to `0`) so that keys can be
+/// hashed. Integer keys are distinct from number keys (`1i` and `1`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DictionaryKey {
+    String(String),
+    Number(u64),
+    Integer(i64),
+    Boolean(bool),
+}
+
+impl DictionaryKey {
+    /// Convert a runtime value into a dictionary key
+    pub fn from_value(value: &Value) -> Result<Self, InterpreterError> {
+        match value {
+            Value::String(s) => Ok(DictionaryKey::String(s.clone())),
+            Value::Number(n) if !n.is_nan() => {
+                let normalized = if *n == 0.0 { 0.0 } else { *n };
+                Ok(DictionaryKey::Number(normalized.to_bits()))
+            }
+            Value::Integer(i) => Ok(DictionaryKey::Integer(*i)),
+            Value::Boolean(b) => Ok(DictionaryKey::Boolean(*b)),
+            other => Err(InterpreterError::TypeError(localized(
+                &format!(
+                    "Dictionary keys must be strings, numbers or booleans, got {}",
+                    other
+                ),
+                &format!(
+                    "Dictionary-Schlüssel müssen Strings, Zahlen oder Booleans sein, erhalten {}",
+                    other
+                ),
+            ))),
+        }
+    }
+
+    /// Convert the key back into a runtime value
+    pub fn to_value(&self) -> Value {
+        match self {
+            DictionaryKey::String(s) => Value::String(s.clone()),
+            DictionaryKey::Number(bits) => Value::Number(f64::from_bits(*bits)),
+            DictionaryKey::Integer(i) => Value::Integer(*i),
+            DictionaryKey::Boolean(b) => Value::Boolean(*b),
+        }
+    }
+}
+
+impl std::fmt::Display for DictionaryKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_value())
+    }
+}
+
+/// Address of an array's shared storage, used to recognise arrays that
+/// contain themselves
+type ArrayIdentity = *const RefCell<Vec<Value>>;
+
 impl PartialEq for Value {
     fn eq(&self, other: &Self) -> bool {
+        self.eq_within(other, &mut Vec::new())
+    }
+}
+
+impl Eq for Value {}
+
+impl Value {
+    /// Structural equality that treats arrays already being compared as equal,
+    /// so arrays that contain themselves compare without endless recursion
+    ///
+    /// `comparing` holds the pairs of arrays currently being compared.
+    fn eq_within(&self, other: &Self, comparing: &mut Vec<(ArrayIdentity, ArrayIdentity)>) -> bool {
         match (self, other) {
             (Value::Number(a), Value::Number(b)) => (a - b).abs() < f64::EPSILON,
+            (Value::Integer(a), Value::Integer(b)) => a == b,
+            (Value::Integer(i), Value::Number(n)) | (Value::Number(n), Value::Integer(i)) => {
+                exact_integer(*n) == Some(*i)
+            }
             (Value::String(a), Value::String(b)) => a == b,
             (Value::Boolean(a), Value::Boolean(b)) => a == b,
             (Value::Null, Value::Null) => true,
-            (Value::Array(a), Value::Array(b)) => a == b,
+            (Value::Array(a), Value::Array(b)) => {
+                let pair = (Rc::as_ptr(a), Rc::as_ptr(b));
+                if comparing.contains(&pair) {
+                    return true;
+                }
+                comparing.push(pair);
+                let (a, b) = (a.borrow(), b.borrow());
+                let equal = a.len() == b.len()
+                    && a.iter()
+                        .zip(b.iter())
+                        .all(|(x, y)| x.eq_within(y, comparing));
+                comparing.pop();
+                equal
+            }
             (Value::Function(fa), Value::Function(fb)) => fa == fb,
             (Value::Session(sa), Value::Session(sb)) => Rc::ptr_eq(sa, sb),
             (Value::Instance(ia), Value::Instance(ib)) => Rc::ptr_eq(ia, ib),
             (Value::Promise(pa), Value::Promise(pb)) => Rc::ptr_eq(pa, pb),
-            (Value::Record(ra), Value::Record(rb)) => ra == rb,
+            (Value::Iterator(ia), Value::Iterator(ib)) => Rc::ptr_eq(ia, ib),
+            (Value::Record(ra), Value::Record(rb)) => {
+                ra.type_name == rb.type_name
+                    && ra.fields.len() == rb.fields.len()
+                    && ra.fields.iter().all(|(name, value)| {
+                        rb.fields
+                            .get(name)
+                            .is_some_and(|other| value.eq_within(other, comparing))
+                    })
+            }
+            (Value::Dictionary(da), Value::Dictionary(db)) => {
+                da.len() == db.len()
+                    && da.iter().all(|(key, value)| {
+                        db.get(key)
+                            .is_some_and(|other| value.eq_within(other, comparing))
+                    })
+            }
+            (Value::Enum(ea), Value::Enum(eb)) => Rc::ptr_eq(ea, eb),
+            (Value::Variant(va), Value::Variant(vb)) => {
+                va.enum_name == vb.enum_name
+                    && va.variant == vb.variant
+                    && va.payload.len() == vb.payload.len()
+                    && va
+                        .payload
+                        .iter()
+                        .zip(vb.payload.iter())
+                        .all(|(x, y)| x.eq_within(y, comparing))
+            }
             _ => false,
         }
     }
-}
 
-impl Eq for Value {}
+    /// Wrap elements in a new array
+    pub fn array(items: Vec<Value>) -> Self {
+        Value::Array(Rc::new(RefCell::new(items)))
+    }
 
-impl Value {
-    pub fn is_truthy(&self) -> bool {
+    /// Copy of the value whose arrays no longer alias the original ones
+    ///
+    /// Arrays reachable more than once (including arrays that contain
+    /// themselves) are copied once, so the copy keeps the original's shape.
+    pub fn deep_copy(&self) -> Value {
+        self.deep_copy_with(&mut HashMap::new())
+    }
+
+    /// Deep copy that maps each original array to its copy
+    fn deep_copy_with(
+        &self,
+        copies: &mut HashMap<*const RefCell<Vec<Value>>, Rc<RefCell<Vec<Value>>>>,
+    ) -> Value {
         match self {
-            Value::Boolean(b) => *b,
-            Value::Null => false,
-            Value::Number(n) => *n != 0.0,
+            Value::Array(items) => {
+                if let Some(copy) = copies.get(&Rc::as_ptr(items)) {
+                    return Value::Array(Rc::clone(copy));
+                }
+                let copy = Rc::new(RefCell::new(Vec::new()));
+                copies.insert(Rc::as_ptr(items), Rc::clone(&copy));
+                let elements = items
+                    .borrow()
+                    .iter()
+                    .map(|item| item.deep_copy_with(copies))
+                    .collect();
+                *copy.borrow_mut() = elements;
+                Value::Array(copy)
+            }
+            Value::Record(record) => Value::Record(RecordValue {
+                type_name: record.type_name.clone(),
+                fields: record
+                    .fields
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.deep_copy_with(copies)))
+                    .collect(),
+            }),
+            Value::Dictionary(dict) => Value::Dictionary(
+                dict.iter()
+                    .map(|(key, value)| (key.clone(), value.deep_copy_with(copies)))
+                    .collect(),
+            ),
+            Value::Variant(variant) => Value::Variant(VariantValue {
+                enum_name: variant.enum_name.clone(),
+                variant: variant.variant.clone(),
+                payload: variant
+                    .payload
+                    .iter()
+                    .map(|item| item.deep_copy_with(copies))
+                    .collect(),
+            }),
+            other => other.clone(),
+        }
+    }
+
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Boolean(b) => *b,
+            Value::Null => false,
+            Value::Number(n) => *n != 0.0,
+            Value::Integer(i) => *i != 0,
             Value::String(s) => !s.is_empty(),
-            Value::Array(a) => !a.is_empty(),
+            Value::Array(a) => !a.borrow().is_empty(),
+            Value::Dictionary(d) => !d.is_empty(),
             Value::Function(_)
             | Value::Session(_)
             | Value::Instance(_)
             | Value::Promise(_)
-            | Value::Record(_) => true,
+            | Value::Iterator(_)
+            | Value::Record(_)
+            | Value::Enum(_)
+            | Value::Variant(_) => true,
         }
     }
 
     pub fn to_number(&self) -> Result<f64, InterpreterError> {
         match self {
             Value::Number(n) => Ok(*n),
+            Value::Integer(i) => Ok(*i as f64),
             Value::String(s) => s.parse::<f64>().map_err(|_| {
                 InterpreterError::TypeError(format!("Cannot convert '{}' to number", s))
             }),
@@ -671,14 +1602,39 @@ impl Value {
 
 impl std::fmt::Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_within(f, &mut Vec::new())
+    }
+}
+
+impl Value {
+    /// Format the value, printing an array nested inside itself as `[...]`
+    ///
+    /// `enclosing` holds the arrays currently being printed.
+    fn fmt_within(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        enclosing: &mut Vec<*const RefCell<Vec<Value>>>,
+    ) -> std::fmt::Result {
         match self {
             Value::Number(n) => write!(f, "{}", n),
+            Value::Integer(i) => write!(f, "{}", i),
             Value::String(s) => write!(f, "{}", s),
             Value::Boolean(b) => write!(f, "{}", b),
             Value::Null => write!(f, "null"),
             Value::Array(arr) => {
-                let elements: Vec<String> = arr.iter().map(|v| v.to_string()).collect();
-                write!(f, "[{}]", elements.join(", "))
+                if enclosing.contains(&Rc::as_ptr(arr)) {
+                    return write!(f, "[...]");
+                }
+                enclosing.push(Rc::as_ptr(arr));
+                write!(f, "[")?;
+                for (index, item) in arr.borrow().iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    item.fmt_within(f, enclosing)?;
+                }
+                enclosing.pop();
+                write!(f, "]")
             }
             Value::Function(func) => write!(f, "<function {}>", func.name),
             Value::Session(session) => write!(f, "<session {}>", session.name()),
@@ -686,20 +1642,54 @@ impl std::fmt::Display for Value {
                 let name = instance.borrow().definition_name().to_string();
                 write!(f, "<session-instance {}>", name)
             }
-            Value::Promise(promise) => {
-                if promise.borrow().is_resolved() {
-                    write!(f, "<promise resolved>")
-                } else {
-                    write!(f, "<promise pending>")
-                }
-            }
+            Value::Promise(promise) => match promise.borrow().state {
+                PromiseState::Pending => write!(f, "<promise pending>"),
+                PromiseState::Fulfilled(_) => write!(f, "<promise resolved>"),
+                PromiseState::Rejected(_) => write!(f, "<promise rejected>"),
+            },
+            Value::Iterator(_) => write!(f, "<iterator>"),
             Value::Record(record) => {
                 write!(f, "<record {}>", record.type_name)
             }
+            Value::Dictionary(dict) => {
+                write!(f, "{{")?;
+                for (index, (key, value)) in dict.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: ", key)?;
+                    value.fmt_within(f, enclosing)?;
+                }
+                write!(f, "}}")
+            }
+            Value::Enum(definition) => write!(f, "<enum {}>", definition.name),
+            Value::Variant(variant) if variant.payload.is_empty() => {
+                write!(f, "{}.{}", variant.enum_name, variant.variant)
+            }
+            Value::Variant(variant) => {
+                write!(f, "{}.{}(", variant.enum_name, variant.variant)?;
+                for (index, item) in variant.payload.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    item.fmt_within(f, enclosing)?;
+                }
+                write!(f, ")")
+            }
         }
     }
 }
 
+/// Storage location of an assignment target.
+///
+/// Objects and indices are evaluated once when the place is resolved, so
+/// `items[next()] += 1` reads and writes the same element.
+enum Place {
+    Variable(String),
+    Member { owner: Value, property: String },
+    Index { container: Box<Place>, index: Value },
+}
+
 /// The HypnoScript interpreter.
 ///
 /// Executes HypnoScript AST nodes using a tree-walking interpretation strategy.
@@ -717,10 +1707,13 @@ impl std::fmt::Display for Value {
 /// The interpreter maintains:
 /// - `globals`: Top-level variables in `Focus { ... } Relax` scope
 /// - `shared`: Variables declared with `sharedTrance` (module-level)
-/// - `locals`: Stack of local scopes (function calls, loops, blocks)
-/// - `const_globals`/`const_locals`: Tracks immutable variables (`freeze`)
+/// - `locals`: Stack of shared local scopes (function calls, loops, blocks); each
+///   scope also tracks its immutable (`freeze`) bindings
+/// - `const_globals`: Tracks immutable global variables (`freeze`)
 /// - `execution_context`: Call stack for session method dispatch
 /// - `tranceify_types`: Record type definitions
+/// - `record_operators`: Operator methods of record types
+/// - `interfaces`: Interface definitions
 /// - `async_runtime`: Optional async task executor
 /// - `channel_registry`: Optional channel system for message passing
 ///
@@ -750,17 +1743,40 @@ pub struct Interpreter {
     globals: HashMap<String, Value>,
     shared: HashMap<String, Value>,
     const_globals: HashSet<String>,
-    locals: Vec<HashMap<String, Value>>,
-    const_locals: Vec<HashSet<String>>,
+    locals: Vec<ScopeRef>,
     execution_context: Vec<ExecutionContextFrame>,
     /// Tranceify type definitions (field names for each type)
     tranceify_types: HashMap<String, Vec<String>>,
-
-    /// Optional async runtime for true async execution
+    /// Operator methods of tranceify types by type name and operator symbol;
+    /// `this` is passed as their first parameter
+    record_operators: HashMap<String, HashMap<String, FunctionValue>>,
+    /// Interface definitions (required member names for each interface)
+    interfaces: HashMap<String, Vec<String>>,
+
+    /// Optional async runtime for true async execution (started on first use
+    /// of an async builtin)
     pub async_runtime: Option<std::sync::Arc<crate::async_runtime::AsyncRuntime>>,
 
+    /// Queued `mesmerize` calls, started in order while a pending promise is awaited
+    async_tasks: VecDeque<AsyncTask>,
+    /// Started `mesmerize` calls waiting for a promise
+    suspended_tasks: Vec<SuspendedTask>,
+
+    /// Released session instances waiting for their `finale`
+    finale_queue: FinaleQueue,
+    /// Instances with a pending `finale`, finalized at the end of the program
+    finalizable: Vec<Weak<RefCell<SessionInstance>>>,
+
     /// Optional channel registry for inter-task communication
     pub channel_registry: Option<std::sync::Arc<crate::channel_system::ChannelRegistry>>,
+
+    /// Source span of the statement being executed (kept on error for reporting)
+    current_span: Option<Span>,
+
+    /// Resolves and parses `mindLink` imports
+    module_loader: ModuleLoader,
+    /// Top-level scopes of already executed modules by canonical path
+    loaded_modules: HashMap<PathBuf, ScopeRef>,
 }
 
 impl Default for Interpreter {
@@ -776,11 +1792,19 @@ impl Interpreter {
             shared: HashMap::new(),
             const_globals: HashSet::new(),
             locals: Vec::new(),
-            const_locals: Vec::new(),
             execution_context: Vec::new(),
             tranceify_types: HashMap::new(),
+            record_operators: HashMap::new(),
+            interfaces: HashMap::new(),
             async_runtime: None,
+            async_tasks: VecDeque::new(),
+            suspended_tasks: Vec::new(),
+            finale_queue: FinaleQueue::default(),
+            finalizable: Vec::new(),
             channel_registry: None,
+            current_span: None,
+            module_loader: ModuleLoader::default(),
+            loaded_modules: HashMap::new(),
         }
     }
 
@@ -796,11 +1820,19 @@ impl Interpreter {
             shared: HashMap::new(),
             const_globals: HashSet::new(),
             locals: Vec::new(),
-            const_locals: Vec::new(),
             execution_context: Vec::new(),
             tranceify_types: HashMap::new(),
+            record_operators: HashMap::new(),
+            interfaces: HashMap::new(),
             async_runtime: Some(std::sync::Arc::new(runtime)),
+            async_tasks: VecDeque::new(),
+            suspended_tasks: Vec::new(),
+            finale_queue: FinaleQueue::default(),
+            finalizable: Vec::new(),
             channel_registry: Some(std::sync::Arc::new(registry)),
+            current_span: None,
+            module_loader: ModuleLoader::default(),
+            loaded_modules: HashMap::new(),
         })
     }
 
@@ -818,12 +1850,41 @@ impl Interpreter {
         Ok(())
     }
 
+    /// Set the file being executed so `mindLink` imports resolve relative to it
+    pub fn set_source_path(&mut self, path: &Path) {
+        self.module_loader = ModuleLoader::for_entry(path);
+    }
+
     pub fn execute_program(&mut self, program: AstNode) -> Result<(), InterpreterError> {
         if let AstNode::Program(statements) = program {
-            for stmt in statements {
-                self.execute_statement(&stmt)?;
+            // Top-level finale blocks run last, even when the program fails
+            let (finales, statements): (Vec<_>, Vec<_>) = statements
+                .into_iter()
+                .partition(|stmt| matches!(stmt.unspanned(), AstNode::FinaleBlock(_)));
+
+            let result = (|| {
+                for stmt in &statements {
+                    self.execute_statement(stmt)?;
+                }
+                // `mesmerize` calls that were never awaited still run to completion
+                self.run_async_tasks(None)
+            })();
+            if result.is_err() {
+                self.cancel_async_tasks();
             }
-            Ok(())
+
+            let error_span = self.current_span;
+            let cleanup = (|| {
+                for stmt in &finales {
+                    self.execute_statement(stmt)?;
+                }
+                self.finalize_remaining_instances()
+            })();
+            if result.is_err() {
+                self.current_span = error_span;
+            }
+
+            result.and(cleanup)
         } else {
             Err(InterpreterError::Runtime(
                 "Expected program node".to_string(),
@@ -831,8 +1892,33 @@ impl Interpreter {
         }
     }
 
+    /// Source location of the statement that was executing when the last error occurred
+    pub fn current_span(&self) -> Option<Span> {
+        self.current_span
+    }
+
     fn execute_statement(&mut self, stmt: &AstNode) -> Result<(), InterpreterError> {
         match stmt {
+            AstNode::Spanned { span, node } => {
+                let previous = self.current_span.replace(*span);
+                let result = self.execute_statement(node);
+                // Instances released by the statement run their finale now
+                let result = result.and_then(|()| self.run_pending_finales());
+                // Keep the innermost span when a real error escapes so callers can report it
+                if matches!(
+                    result,
+                    Ok(())
+                        | Err(InterpreterError::Return(_))
+                        | Err(InterpreterError::BreakOutsideLoop)
+                        | Err(InterpreterError::ContinueOutsideLoop)
+                        | Err(InterpreterError::LabeledBreak(_))
+                        | Err(InterpreterError::LabeledContinue(_))
+                ) {
+                    self.current_span = previous;
+                }
+                result
+            }
+
             AstNode::VariableDeclaration {
                 name,
                 type_annotation: _,
@@ -849,9 +1935,20 @@ impl Interpreter {
                 Ok(())
             }
 
+            AstNode::DestructuringDeclaration {
+                pattern,
+                type_annotation: _,
+                initializer,
+                is_constant,
+                storage,
+            } => {
+                let value = self.evaluate_expression(initializer)?;
+                self.destructure(pattern, &value, *storage, *is_constant)
+            }
+
             AstNode::AnchorDeclaration { name, source } => {
-                // Anchor saves the current value of a variable
-                let value = self.evaluate_expression(source)?;
+                // Anchor saves a snapshot: shared arrays are copied, not aliased
+                let value = self.evaluate_expression(source)?.deep_copy();
                 let scope = self.resolve_assignment_scope(name);
                 self.set_variable(name.clone(), value, scope)?;
                 Ok(())
@@ -860,11 +1957,14 @@ impl Interpreter {
             AstNode::FunctionDeclaration {
                 name,
                 parameters,
-                return_type: _,
                 body,
+                is_async,
+                ..
             } => {
-                let param_names: Vec<String> = parameters.iter().map(|p| p.name.clone()).collect();
-                let func = FunctionValue::new_global(name.clone(), param_names, body.clone());
+                let func =
+                    FunctionValue::new_global(name.clone(), parameters.clone(), body.clone())
+                        .mesmerized(*is_async)
+                        .in_module(self.module_scopes());
                 self.define_variable(
                     VariableStorage::Local,
                     name.clone(),
@@ -881,8 +1981,9 @@ impl Interpreter {
                 body,
             } => {
                 // Triggers are handled like functions
-                let param_names: Vec<String> = parameters.iter().map(|p| p.name.clone()).collect();
-                let func = FunctionValue::new_global(name.clone(), param_names, body.clone());
+                let func =
+                    FunctionValue::new_global(name.clone(), parameters.clone(), body.clone())
+                        .in_module(self.module_scopes());
                 self.define_variable(
                     VariableStorage::Local,
                     name.clone(),
@@ -892,8 +1993,15 @@ impl Interpreter {
                 Ok(())
             }
 
-            AstNode::SessionDeclaration { name, members } => {
-                let session = self.build_session_definition(name, members)?;
+            AstNode::SessionDeclaration {
+                name,
+                parent,
+                interfaces,
+                members,
+                ..
+            } => {
+                let session =
+                    self.build_session_definition(name, parent.as_deref(), interfaces, members)?;
                 self.define_variable(
                     VariableStorage::Local,
                     name.clone(),
@@ -904,10 +2012,75 @@ impl Interpreter {
                 Ok(())
             }
 
-            AstNode::TranceifyDeclaration { name, fields } => {
+            AstNode::ImportDeclaration { names, source } => {
+                let (module, scope) = self.import_module(source)?;
+                for name in names {
+                    module.ensure_exposed(name)?;
+                    // Exposed tranceify types are registered globally and carry no value
+                    let value = scope.borrow().values.get(name).cloned();
+                    if let Some(value) = value {
+                        self.define_variable(VariableStorage::Local, name.clone(), value, true);
+                    }
+                }
+                Ok(())
+            }
+
+            AstNode::ExportDeclaration(declaration) => self.execute_statement(declaration),
+
+            AstNode::TranceifyDeclaration {
+                name,
+                fields,
+                operators,
+                ..
+            } => {
                 // Register the tranceify type definition
                 let field_names: Vec<String> = fields.iter().map(|f| f.name.clone()).collect();
                 self.tranceify_types.insert(name.clone(), field_names);
+
+                let mut methods = HashMap::new();
+                for method in operators {
+                    let mut parameters = vec![Parameter::new("this".to_string(), None)];
+                    parameters.extend(method.parameters.iter().cloned());
+                    let function = FunctionValue::new_global(
+                        format!("{}::{}", name, method.name),
+                        parameters,
+                        method.body.clone(),
+                    )
+                    .in_module(self.module_scopes());
+                    if let Some(operator) = overloaded_operator(&method.name) {
+                        methods.insert(operator.to_string(), function);
+                    }
+                }
+                self.record_operators.insert(name.clone(), methods);
+                Ok(())
+            }
+
+            AstNode::EnumDeclaration { name, variants } => {
+                let definition = EnumDefinition {
+                    name: name.clone(),
+                    variants: variants
+                        .iter()
+                        .map(|variant| (variant.name.clone(), variant.payload.len()))
+                        .collect(),
+                };
+                self.define_variable(
+                    VariableStorage::Local,
+                    name.clone(),
+                    Value::Enum(Rc::new(definition)),
+                    true,
+                );
+                Ok(())
+            }
+
+            AstNode::InterfaceDeclaration { name, members } => {
+                let member_names = members
+                    .iter()
+                    .map(|member| match member {
+                        InterfaceMember::Field(field) => field.name.clone(),
+                        InterfaceMember::Method(method) => method.name.clone(),
+                    })
+                    .collect();
+                self.interfaces.insert(name.clone(), member_names);
                 Ok(())
             }
 
@@ -985,71 +2158,126 @@ impl Interpreter {
                 Ok(())
             }
 
-            AstNode::DeepFocusStatement { condition, body } => {
-                // DeepFocus is like if but with deeper scope/emphasis
-                let cond_value = self.evaluate_expression(condition)?;
-                if cond_value.is_truthy() {
-                    for stmt in body {
-                        self.execute_statement(stmt)?;
-                    }
+            AstNode::TryStatement {
+                body,
+                catch_binding,
+                catch_body,
+                finally_body,
+            } => {
+                let mut result = self.execute_block(body);
+
+                if let Some(handler) = catch_body
+                    && let Err(error) = result
+                {
+                    result = match error.into_catchable() {
+                        Ok(error_value) => {
+                            self.execute_bound_block(catch_binding.as_deref(), error_value, handler)
+                        }
+                        Err(control_flow) => Err(control_flow),
+                    };
                 }
-                Ok(())
+
+                // finally always runs; its own failure replaces the pending outcome
+                if let Some(cleanup) = finally_body {
+                    self.execute_block(cleanup)?;
+                }
+
+                result
             }
 
-            AstNode::WhileStatement { condition, body } => {
-                loop {
-                    let cond_value = self.evaluate_expression(condition)?;
-                    if !cond_value.is_truthy() {
-                        break;
-                    }
+            AstNode::UsingStatement {
+                name,
+                resource,
+                body,
+            } => {
+                let resource = self.evaluate_expression(resource)?;
+                let result = self.execute_bound_block(Some(name), resource.clone(), body);
 
-                    match self.execute_block(body) {
-                        Err(InterpreterError::BreakOutsideLoop) => break,
-                        Err(InterpreterError::ContinueOutsideLoop) => continue,
-                        Err(e) => return Err(e),
-                        Ok(()) => {}
-                    }
+                // Like `finally`, a failing finale replaces the pending outcome
+                if let Value::Instance(instance) = resource {
+                    self.finalize_instance(instance)?;
                 }
-                Ok(())
+
+                result
             }
 
-            AstNode::LoopStatement {
-                init,
+            AstNode::AssertStatement {
                 condition,
-                update,
-                body,
+                message,
+                source,
             } => {
-                if let Some(init_stmt) = init.as_ref() {
-                    self.execute_statement(init_stmt)?;
+                let (passed, operands) = match condition.as_ref() {
+                    AstNode::BinaryExpression {
+                        left,
+                        operator,
+                        right,
+                    } if matches!(
+                        operator.to_ascii_lowercase().as_str(),
+                        "==" | "youarefeelingverysleepy"
+                    ) =>
+                    {
+                        let left = self.evaluate_expression(left)?;
+                        let right = self.evaluate_expression(right)?;
+                        (self.values_equal(&left, &right)?, Some((left, right)))
+                    }
+                    _ => (self.evaluate_expression(condition)?.is_truthy(), None),
+                };
+
+                if passed {
+                    return Ok(());
                 }
 
-                loop {
-                    if let Some(cond_expr) = condition.as_ref() {
-                        let cond_value = self.evaluate_expression(cond_expr)?;
-                        if !cond_value.is_truthy() {
-                            break;
-                        }
-                    }
+                let message = match message {
+                    Some(message) => Some(self.evaluate_expression(message)?.to_string()),
+                    None => None,
+                };
+                Err(InterpreterError::AssertionFailed(Box::new(
+                    AssertionFailure {
+                        expression: source.clone(),
+                        message,
+                        operands,
+                    },
+                )))
+            }
 
-                    match self.execute_loop_body(body) {
-                        Err(InterpreterError::BreakOutsideLoop) => break,
-                        Err(InterpreterError::ContinueOutsideLoop) => {
-                            if let Some(update_stmt) = update.as_ref() {
-                                self.execute_statement(update_stmt)?;
-                            }
-                            continue;
-                        }
-                        Err(e) => return Err(e),
-                        Ok(()) => {}
+            AstNode::RaiseStatement { message, payload } => {
+                let message_value = self.evaluate_expression(message)?;
+                let payload_value = match payload {
+                    Some(payload) => Some(self.evaluate_expression(payload)?),
+                    None => None,
+                };
+
+                let error = match (message_value, payload_value) {
+                    // Re-raising a caught error keeps it intact
+                    (Value::Record(record), None) if record.type_name == ERROR_RECORD_TYPE => {
+                        Value::Record(record)
                     }
+                    (message_value, payload_value) => error_record(
+                        ERROR_RECORD_TYPE,
+                        message_value.to_string(),
+                        payload_value.unwrap_or(Value::Null),
+                    ),
+                };
+                Err(InterpreterError::Raised(error))
+            }
 
-                    if let Some(update_stmt) = update.as_ref() {
-                        self.execute_statement(update_stmt)?;
+            AstNode::DeepFocusStatement { condition, body } => {
+                // DeepFocus is like if but with deeper scope/emphasis
+                let cond_value = self.evaluate_expression(condition)?;
+                if cond_value.is_truthy() {
+                    for stmt in body {
+                        self.execute_statement(stmt)?;
                     }
                 }
                 Ok(())
             }
 
+            AstNode::WhileStatement { .. }
+            | AstNode::LoopStatement { .. }
+            | AstNode::ForEachStatement { .. } => self.execute_loop(stmt, None),
+
+            AstNode::LabeledStatement { label, body } => self.execute_loop(body, Some(label)),
+
             AstNode::SuspendStatement => {
                 // Suspend is an infinite pause - in practice, this should wait for external input
                 // For now, we'll just log a warning
@@ -1067,9 +2295,21 @@ impl Interpreter {
                 Err(InterpreterError::Return(ret_value))
             }
 
-            AstNode::BreakStatement => Err(InterpreterError::BreakOutsideLoop),
+            // Generators run `emerge` through `enter_generator_statement`
+            AstNode::YieldStatement(_) => Err(InterpreterError::Runtime(localized(
+                "'emerge' can only be used in the body of a generator suggestion",
+                "'emerge' kann nur im Rumpf einer Generator-Suggestion verwendet werden",
+            ))),
+
+            AstNode::BreakStatement(label) => Err(match label {
+                Some(label) => InterpreterError::LabeledBreak(label.clone()),
+                None => InterpreterError::BreakOutsideLoop,
+            }),
 
-            AstNode::ContinueStatement => Err(InterpreterError::ContinueOutsideLoop),
+            AstNode::ContinueStatement(label) => Err(match label {
+                Some(label) => InterpreterError::LabeledContinue(label.clone()),
+                None => InterpreterError::ContinueOutsideLoop,
+            }),
 
             AstNode::ExpressionStatement(expr) => {
                 self.evaluate_expression(expr)?;
@@ -1095,520 +2335,427 @@ impl Interpreter {
         result
     }
 
-    /// Execute loop bodies without creating a new scope so variables
-    /// persist across iterations (matching HypnoScript semantics)
-    fn execute_loop_body(&mut self, statements: &[AstNode]) -> Result<(), InterpreterError> {
-        for stmt in statements {
-            self.execute_statement(stmt)?;
-        }
-        Ok(())
+    /// Whether the code being executed belongs to an imported module
+    ///
+    /// Module code runs on top of the module's own scope and cannot see the
+    /// entry program's globals.
+    fn in_module_code(&self) -> bool {
+        self.locals
+            .first()
+            .is_some_and(|scope| scope.borrow().is_module)
     }
 
-    fn evaluate_expression(&mut self, expr: &AstNode) -> Result<Value, InterpreterError> {
-        match expr {
-            AstNode::NumberLiteral(n) => Ok(Value::Number(*n)),
-
-            AstNode::StringLiteral(s) => Ok(Value::String(s.clone())),
+    /// Scope chain to bind declarations to when executing module code
+    fn module_scopes(&self) -> Option<Rc<Vec<ScopeRef>>> {
+        self.in_module_code().then(|| Rc::new(self.locals.clone()))
+    }
 
-            AstNode::BooleanLiteral(b) => Ok(Value::Boolean(*b)),
+    /// Execute a `mindLink` module once and return its top-level scope.
+    ///
+    /// Each module runs in its own scope; declarations inside it are bound to
+    /// that scope so they keep working when called from the importer.
+    fn import_module(
+        &mut self,
+        specifier: &str,
+    ) -> Result<(Rc<Module>, ScopeRef), InterpreterError> {
+        let path = self
+            .module_loader
+            .resolve(specifier, self.module_loader.current_module())?;
+        let module = self.module_loader.load(&path)?;
+        if let Some(scope) = self.loaded_modules.get(&path) {
+            return Ok((module, Rc::clone(scope)));
+        }
 
-            AstNode::Identifier(name) => self.get_variable(name),
+        self.module_loader.begin(&path)?;
+        let scope = Rc::new(RefCell::new(LocalScope {
+            is_module: true,
+            ..LocalScope::default()
+        }));
+        let caller_scopes = std::mem::replace(&mut self.locals, vec![Rc::clone(&scope)]);
+        let caller_context = std::mem::take(&mut self.execution_context);
 
-            AstNode::ArrayLiteral(elements) => {
-                let mut values = Vec::new();
-                for elem in elements {
-                    values.push(self.evaluate_expression(elem)?);
+        let result: Result<(), InterpreterError> = (|| {
+            if let AstNode::Program(statements) = &module.program {
+                for stmt in statements {
+                    self.execute_statement(stmt)?;
                 }
-                Ok(Value::Array(values))
             }
+            Ok(())
+        })();
 
-            AstNode::BinaryExpression {
-                left,
-                operator,
-                right,
-            } => {
-                let left_val = self.evaluate_expression(left)?;
-                let right_val = self.evaluate_expression(right)?;
-                self.evaluate_binary_op(&left_val, operator, &right_val)
-            }
+        self.locals = caller_scopes;
+        self.execution_context = caller_context;
+        self.module_loader.finish();
+        result?;
 
-            AstNode::UnaryExpression { operator, operand } => {
-                let operand_val = self.evaluate_expression(operand)?;
-                match operator.as_str() {
-                    "-" => Ok(Value::Number(-operand_val.to_number()?)),
-                    "!" => Ok(Value::Boolean(!operand_val.is_truthy())),
-                    _ => Err(InterpreterError::Runtime(format!(
-                        "Unknown unary operator: {}",
-                        operator
-                    ))),
-                }
+        self.loaded_modules.insert(path, Rc::clone(&scope));
+        Ok((module, scope))
+    }
+
+    /// Execute a block in its own scope with `binding` bound to `value`
+    ///
+    /// Used for `recover` blocks (bound to the caught error) and `using`
+    /// bodies (bound to the resource).
+    fn execute_bound_block(
+        &mut self,
+        binding: Option<&str>,
+        value: Value,
+        statements: &[AstNode],
+    ) -> Result<(), InterpreterError> {
+        self.push_scope();
+        if let Some(name) = binding {
+            self.define_variable(VariableStorage::Local, name.to_string(), value, false);
+        }
+        let result = (|| {
+            for stmt in statements {
+                self.execute_statement(stmt)?;
             }
+            Ok(())
+        })();
+        self.pop_scope();
+        result
+    }
 
-            AstNode::CallExpression { callee, arguments } => self.evaluate_call(callee, arguments),
+    /// Run a `while`/`loop`/`pendulum` statement, honouring jumps that target `label`
+    fn execute_loop(
+        &mut self,
+        stmt: &AstNode,
+        label: Option<&str>,
+    ) -> Result<(), InterpreterError> {
+        match stmt {
+            AstNode::WhileStatement { condition, body } => {
+                loop {
+                    let cond_value = self.evaluate_expression(condition)?;
+                    if !cond_value.is_truthy() {
+                        break;
+                    }
 
-            AstNode::MemberExpression { object, property } => {
-                let owner = self.evaluate_expression(object)?;
-                self.resolve_member_value(owner, property)
+                    let result = self.execute_block(body);
+                    if let LoopFlow::Break = loop_flow(result, label)? {
+                        break;
+                    }
+                }
+                Ok(())
             }
 
-            AstNode::AssignmentExpression { target, value } => match target.as_ref() {
-                AstNode::Identifier(name) => {
-                    let val = self.evaluate_expression(value)?;
-                    let scope = self.resolve_assignment_scope(name);
-                    self.set_variable(name.clone(), val.clone(), scope)?;
-                    Ok(val)
+            AstNode::LoopStatement {
+                init,
+                condition,
+                update,
+                body,
+            } => {
+                if let Some(init_stmt) = init.as_ref() {
+                    self.execute_statement(init_stmt)?;
                 }
-                AstNode::MemberExpression { object, property } => {
-                    let owner = self.evaluate_expression(object)?;
-                    let val = self.evaluate_expression(value)?;
-                    self.assign_member_value(owner, property, val.clone())?;
-                    Ok(val)
-                }
-                _ => Err(InterpreterError::Runtime(localized(
-                    "Invalid assignment target",
-                    "Ungültiges Zuweisungsziel",
-                ))),
-            },
-
-            AstNode::IndexExpression { object, index } => {
-                let obj = self.evaluate_expression(object)?;
-                let idx = self.evaluate_expression(index)?;
-
-                if let Value::Array(arr) = obj {
-                    let i = idx.to_number()? as usize;
-                    arr.get(i).cloned().ok_or_else(|| {
-                        InterpreterError::Runtime(format!("Index {} out of bounds", i))
-                    })
-                } else {
-                    Err(InterpreterError::TypeError(
-                        "Cannot index non-array".to_string(),
-                    ))
-                }
-            }
-
-            AstNode::AwaitExpression { expression } => {
-                // Evaluate the expression - it might return a Promise
-                let value = self.evaluate_expression(expression)?;
-
-                // If it's a Promise, await it (resolve it)
-                if let Value::Promise(promise_ref) = value {
-                    let promise = promise_ref.borrow();
-                    if promise.is_resolved() {
-                        // Promise is already resolved, return its value
-                        Ok(promise.get_value().unwrap_or(Value::Null))
-                    } else {
-                        // Promise not yet resolved - in a real async system, we'd wait
-                        // For now, return null (could simulate delay here)
-                        drop(promise); // Release borrow before potentially waiting
 
-                        // Simulate async operation with small delay
-                        std::thread::sleep(std::time::Duration::from_millis(10));
-
-                        // Re-check if resolved after wait
-                        let promise = promise_ref.borrow();
-                        Ok(promise.get_value().unwrap_or(Value::Null))
+                loop {
+                    if let Some(cond_expr) = condition.as_ref() {
+                        let cond_value = self.evaluate_expression(cond_expr)?;
+                        if !cond_value.is_truthy() {
+                            break;
+                        }
                     }
-                } else {
-                    // Not a promise, just return the value
-                    Ok(value)
-                }
-            }
-
-            AstNode::NullishCoalescing { left, right } => {
-                let left_val = self.evaluate_expression(left)?;
-                if matches!(left_val, Value::Null) {
-                    self.evaluate_expression(right)
-                } else {
-                    Ok(left_val)
-                }
-            }
-
-            AstNode::OptionalChaining { object, property } => {
-                let obj = self.evaluate_expression(object)?;
-                if matches!(obj, Value::Null) {
-                    Ok(Value::Null)
-                } else {
-                    self.resolve_member_value(obj, property)
-                }
-            }
 
-            AstNode::OptionalIndexing { object, index } => {
-                let obj = self.evaluate_expression(object)?;
-                if matches!(obj, Value::Null) {
-                    return Ok(Value::Null);
-                }
+                    let result = self.execute_loop_body(body);
+                    if let LoopFlow::Break = loop_flow(result, label)? {
+                        break;
+                    }
 
-                let idx = self.evaluate_expression(index)?;
-                if let Value::Array(arr) = obj {
-                    let i = idx.to_number()? as usize;
-                    Ok(arr.get(i).cloned().unwrap_or(Value::Null))
-                } else {
-                    Err(InterpreterError::TypeError(
-                        "Cannot index non-array".to_string(),
-                    ))
+                    if let Some(update_stmt) = update.as_ref() {
+                        self.execute_statement(update_stmt)?;
+                    }
                 }
+                Ok(())
             }
 
-            AstNode::EntrainExpression {
-                subject,
-                cases,
-                default,
+            AstNode::ForEachStatement {
+                key,
+                value,
+                is_constant,
+                iterable,
+                body,
             } => {
-                let subject_value = self.evaluate_expression(subject)?;
-
-                // Try to match each case
-                for case in cases {
-                    if let Some(matched_env) = self.match_pattern(&case.pattern, &subject_value)? {
-                        self.push_scope();
-                        for (name, value) in &matched_env {
-                            self.define_variable(
-                                VariableStorage::Local,
-                                name.clone(),
-                                value.clone(),
-                                false,
-                            );
-                        }
-
-                        let case_result = (|| -> Result<Option<Value>, InterpreterError> {
-                            if let Some(guard) = &case.guard {
-                                let guard_result = self.evaluate_expression(guard)?;
-                                if !guard_result.is_truthy() {
-                                    return Ok(None);
-                                }
-                            }
-
-                            let value = self.execute_entrain_body(&case.body)?;
-                            Ok(Some(value))
-                        })();
-
-                        self.pop_scope();
-
-                        match case_result? {
-                            Some(value) => return Ok(value),
-                            None => continue,
-                        }
-                    }
-                }
-
-                // No case matched - try default
-                if let Some(default_body) = default {
+                let mut cursor = self.iteration_cursor(iterable, key.is_some())?;
+                while let Some((entry_key, entry_value)) = self.next_entry(&mut cursor)? {
+                    // Each iteration gets its own scope so closures keep their element
                     self.push_scope();
-                    let result = self.execute_entrain_body(default_body);
+                    if let Some(key) = key {
+                        self.define_variable(
+                            VariableStorage::Local,
+                            key.clone(),
+                            entry_key,
+                            *is_constant,
+                        );
+                    }
+                    self.define_variable(
+                        VariableStorage::Local,
+                        value.clone(),
+                        entry_value,
+                        *is_constant,
+                    );
+                    let result = self.execute_loop_body(body);
                     self.pop_scope();
-                    result
-                } else {
-                    Err(InterpreterError::Runtime(
-                        "No pattern matched and no default case provided".to_string(),
-                    ))
+                    if let LoopFlow::Break = loop_flow(result, label)? {
+                        break;
+                    }
                 }
+                Ok(())
             }
 
-            AstNode::RecordLiteral { type_name, fields } => {
-                // Check if the tranceify type is defined
-                if !self.tranceify_types.contains_key(type_name) {
-                    return Err(InterpreterError::Runtime(format!(
-                        "Undefined tranceify type '{}'",
-                        type_name
-                    )));
-                }
-
-                // Evaluate all field values
-                let mut field_values = HashMap::new();
-                for field_init in fields {
-                    let value = self.evaluate_expression(&field_init.value)?;
-                    field_values.insert(field_init.name.clone(), value);
-                }
+            other => self.execute_statement(other),
+        }
+    }
 
-                Ok(Value::Record(RecordValue {
-                    type_name: type_name.clone(),
-                    fields: field_values,
-                }))
-            }
+    /// Execute loop bodies without creating a new scope so variables
+    /// persist across iterations (matching HypnoScript semantics)
+    fn execute_loop_body(&mut self, statements: &[AstNode]) -> Result<(), InterpreterError> {
+        for stmt in statements {
+            self.execute_statement(stmt)?;
+        }
+        Ok(())
+    }
 
-            _ => Err(InterpreterError::Runtime(format!(
-                "Unsupported expression: {:?}",
-                expr
-            ))),
+    /// Start walking the iterable of a for-each loop.
+    ///
+    /// Ranges, generators and session iterators are walked lazily instead of
+    /// being built as an array first.
+    fn iteration_cursor(
+        &mut self,
+        iterable: &AstNode,
+        keyed: bool,
+    ) -> Result<IterationCursor, InterpreterError> {
+        if let AstNode::RangeExpression {
+            start,
+            end,
+            inclusive,
+            step,
+        } = iterable.unspanned()
+        {
+            let range = self.evaluate_range(start, end, *inclusive, step.as_deref())?;
+            return Ok(IterationCursor::Range(range.enumerate()));
         }
+        let collection = self.evaluate_expression(iterable)?;
+        self.collection_cursor(collection, keyed)
     }
 
-    /// Match a pattern against a value, returning bindings if successful
-    fn match_pattern(
+    /// Cursor over a collection value.
+    ///
+    /// Session instances are iterable if they implement `hasNext()` and
+    /// `next()`, or an `iterate()` method returning something iterable.
+    fn collection_cursor(
         &mut self,
-        pattern: &Pattern,
-        value: &Value,
-    ) -> Result<Option<std::collections::HashMap<String, Value>>, InterpreterError> {
-        use std::collections::HashMap;
+        collection: Value,
+        keyed: bool,
+    ) -> Result<IterationCursor, InterpreterError> {
+        match collection {
+            Value::Iterator(source) => Ok(IterationCursor::Iterator { source, index: 0 }),
+            Value::Instance(instance) => match self.session_method(&instance, "iterate")? {
+                Some(iterate) => match self.call_function(&iterate, &[], &[])? {
+                    Value::Instance(iterator) => self.session_cursor(iterator),
+                    iterable => self.collection_cursor(iterable, keyed),
+                },
+                None => self.session_cursor(instance),
+            },
+            other => Ok(IterationCursor::Entries(
+                self.iteration_entries(other, keyed)?.into_iter(),
+            )),
+        }
+    }
 
-        match pattern {
-            Pattern::Literal(lit_node) => {
-                let lit_value = self.evaluate_expression(lit_node)?;
-                if self.values_equal(&lit_value, value) {
-                    Ok(Some(HashMap::new()))
-                } else {
-                    Ok(None)
-                }
-            }
+    /// Cursor over a session instance implementing `hasNext()` and `next()`
+    fn session_cursor(
+        &self,
+        instance: Rc<RefCell<SessionInstance>>,
+    ) -> Result<IterationCursor, InterpreterError> {
+        let has_next = self.session_method(&instance, "hasNext")?;
+        let next = self.session_method(&instance, "next")?;
+        let (Some(has_next), Some(next)) = (has_next, next) else {
+            let name = instance.borrow().definition_name().to_string();
+            return Err(InterpreterError::TypeError(localized(
+                &format!(
+                    "Session '{}' is not iterable: it needs 'hasNext()' and 'next()' or an 'iterate()' method",
+                    name
+                ),
+                &format!(
+                    "Session '{}' ist nicht iterierbar: sie benötigt 'hasNext()' und 'next()' oder eine 'iterate()'-Methode",
+                    name
+                ),
+            )));
+        };
+        Ok(IterationCursor::Session {
+            has_next,
+            next,
+            index: 0,
+        })
+    }
 
-            Pattern::Identifier(name) => {
-                let mut bindings = HashMap::new();
-                bindings.insert(name.clone(), value.clone());
-                Ok(Some(bindings))
+    /// Advance a for-each cursor; `None` once the collection is exhausted
+    fn next_entry(
+        &mut self,
+        cursor: &mut IterationCursor,
+    ) -> Result<Option<(Value, Value)>, InterpreterError> {
+        let (value, index) = match cursor {
+            IterationCursor::Entries(entries) => return Ok(entries.next()),
+            IterationCursor::Range(range) => {
+                return Ok(range
+                    .next()
+                    .map(|(index, number)| (Value::Number(index as f64), Value::Number(number))));
             }
-
-            Pattern::Typed {
-                name,
-                type_annotation,
+            IterationCursor::Iterator { source, index } => {
+                let source = Rc::clone(source);
+                (self.iterator_next(&source)?, index)
+            }
+            IterationCursor::Session {
+                has_next,
+                next,
+                index,
             } => {
-                // Check type match
-                let type_matches = match type_annotation.to_lowercase().as_str() {
-                    "number" => matches!(value, Value::Number(_)),
-                    "string" => matches!(value, Value::String(_)),
-                    "boolean" => matches!(value, Value::Boolean(_)),
-                    "array" => matches!(value, Value::Array(_)),
-                    _ => true, // Unknown types always match for now
+                let value = if self.call_function(has_next, &[], &[])?.is_truthy() {
+                    Some(self.call_function(next, &[], &[])?)
+                } else {
+                    None
                 };
-
-                if !type_matches {
-                    return Ok(None);
-                }
-
-                let mut bindings = HashMap::new();
-                if let Some(name) = name {
-                    bindings.insert(name.clone(), value.clone());
-                }
-                Ok(Some(bindings))
+                (value, index)
             }
+        };
+        let position = *index;
+        *index += 1;
+        Ok(value.map(|value| (Value::Number(position as f64), value)))
+    }
 
-            Pattern::Array { elements, rest } => {
-                if let Value::Array(arr) = value {
-                    let mut bindings = HashMap::new();
+    /// Collect the values of any iterable into a vector, stopping after `limit` values
+    fn collect_iterable(
+        &mut self,
+        iterable: Value,
+        limit: Option<usize>,
+    ) -> Result<Vec<Value>, InterpreterError> {
+        let mut cursor = self.collection_cursor(iterable, false)?;
+        let mut values = Vec::new();
+        while limit.is_none_or(|limit| values.len() < limit)
+            && let Some((_, value)) = self.next_entry(&mut cursor)?
+        {
+            values.push(value);
+        }
+        Ok(values)
+    }
 
-                    // Match array elements
-                    for (i, elem_pattern) in elements.iter().enumerate() {
-                        if i >= arr.len() {
-                            return Ok(None); // Not enough elements
+    /// Pull the next value from an iterator; `None` once it is exhausted
+    fn iterator_next(
+        &mut self,
+        iterator: &Rc<RefCell<IteratorValue>>,
+    ) -> Result<Option<Value>, InterpreterError> {
+        let mut state = {
+            let mut borrowed = iterator.borrow_mut();
+            if let Some(value) = borrowed.peeked.take() {
+                return Ok(Some(value));
+            }
+            match &mut borrowed.source {
+                IteratorSource::Done => return Ok(None),
+                IteratorSource::Lines { path, lines } => {
+                    return match lines.next() {
+                        Some(Ok(line)) => Ok(Some(Value::String(line))),
+                        None => {
+                            borrowed.source = IteratorSource::Done;
+                            Ok(None)
                         }
-
-                        if let Some(elem_bindings) = self.match_pattern(elem_pattern, &arr[i])? {
-                            bindings.extend(elem_bindings);
-                        } else {
-                            return Ok(None);
+                        Some(Err(error)) => {
+                            let message = format!("Failed to read '{}': {}", path, error);
+                            borrowed.source = IteratorSource::Done;
+                            Err(InterpreterError::Runtime(message))
                         }
-                    }
-
-                    // Handle rest pattern
-                    if let Some(rest_name) = rest {
-                        let rest_elements: Vec<Value> =
-                            arr.iter().skip(elements.len()).cloned().collect();
-                        bindings.insert(rest_name.clone(), Value::Array(rest_elements));
-                    } else if arr.len() > elements.len() {
-                        return Ok(None); // Too many elements and no rest pattern
-                    }
-
-                    Ok(Some(bindings))
-                } else {
-                    Ok(None)
+                    };
                 }
+                IteratorSource::Generator(state) => state.take().ok_or_else(|| {
+                    InterpreterError::Runtime(localized(
+                        "Generator is already running",
+                        "Generator läuft bereits",
+                    ))
+                })?,
             }
+        };
+        let result = self.resume_generator(&mut state);
+        iterator.borrow_mut().source = match result {
+            Ok(Some(_)) => IteratorSource::Generator(Some(state)),
+            _ => IteratorSource::Done,
+        };
+        result
+    }
 
-            Pattern::Record { type_name, fields } => {
-                if let Value::Record(record) = value {
-                    if &record.type_name != type_name {
-                        return Ok(None);
-                    }
-
-                    let mut bindings = HashMap::new();
-                    for field_pattern in fields {
-                        let field_value = match record.fields.get(&field_pattern.name) {
-                            Some(value) => value.clone(),
-                            None => return Ok(None),
-                        };
+    /// Whether an iterator has another value (pulled ahead and kept for `next()`)
+    fn iterator_has_next(
+        &mut self,
+        iterator: &Rc<RefCell<IteratorValue>>,
+    ) -> Result<bool, InterpreterError> {
+        if iterator.borrow().peeked.is_some() {
+            return Ok(true);
+        }
+        let next = self.iterator_next(iterator)?;
+        let has_next = next.is_some();
+        iterator.borrow_mut().peeked = next;
+        Ok(has_next)
+    }
 
-                        if let Some(sub_pattern) = &field_pattern.pattern {
-                            if let Some(sub_bindings) =
-                                self.match_pattern(sub_pattern, &field_value)?
-                            {
-                                bindings.extend(sub_bindings);
-                            } else {
-                                return Ok(None);
-                            }
-                        } else {
-                            bindings.insert(field_pattern.name.clone(), field_value);
-                        }
-                    }
-
-                    Ok(Some(bindings))
-                } else {
-                    Ok(None)
-                }
-            }
-        }
-    }
-
-    fn execute_entrain_body(&mut self, body: &[AstNode]) -> Result<Value, InterpreterError> {
-        let mut last_value = Value::Null;
-
-        for node in body {
-            match node {
-                AstNode::ExpressionStatement(expr) => {
-                    last_value = self.evaluate_expression(expr)?;
-                }
-                _ if node.is_expression() => {
-                    last_value = self.evaluate_expression(node)?;
-                }
-                _ => match self.execute_statement(node) {
-                    Ok(()) => {
-                        last_value = Value::Null;
-                    }
-                    Err(InterpreterError::Return(value)) => {
-                        return Err(InterpreterError::Return(value));
-                    }
-                    Err(InterpreterError::BreakOutsideLoop) => {
-                        return Err(InterpreterError::BreakOutsideLoop);
-                    }
-                    Err(InterpreterError::ContinueOutsideLoop) => {
-                        return Err(InterpreterError::ContinueOutsideLoop);
-                    }
-                    Err(err) => return Err(err),
-                },
-            }
-        }
-
-        Ok(last_value)
-    }
-
-    fn evaluate_binary_op(
-        &self,
-        left: &Value,
-        op: &str,
-        right: &Value,
-    ) -> Result<Value, InterpreterError> {
-        let normalized = op.to_ascii_lowercase();
-
-        match normalized.as_str() {
-            "+" => {
-                // If either operand is a string, perform string concatenation
-                match (left, right) {
-                    (Value::String(s1), Value::String(s2)) => {
-                        Ok(Value::String(format!("{}{}", s1, s2)))
-                    }
-                    (Value::String(s), _) => Ok(Value::String(format!("{}{}", s, right))),
-                    (_, Value::String(s)) => Ok(Value::String(format!("{}{}", left, s))),
-                    _ => {
-                        // Both are numeric, perform addition
-                        Ok(Value::Number(left.to_number()? + right.to_number()?))
-                    }
-                }
-            }
-            "-" => Ok(Value::Number(left.to_number()? - right.to_number()?)),
-            "*" => Ok(Value::Number(left.to_number()? * right.to_number()?)),
-            "/" => Ok(Value::Number(left.to_number()? / right.to_number()?)),
-            "%" => Ok(Value::Number(left.to_number()? % right.to_number()?)),
-            "==" | "youarefeelingverysleepy" => Ok(Value::Boolean(self.values_equal(left, right))),
-            "!=" | "youcannotresist" | "notsodeep" => {
-                Ok(Value::Boolean(!self.values_equal(left, right)))
-            }
-            ">" | "lookatthewatch" => Ok(Value::Boolean(left.to_number()? > right.to_number()?)),
-            "<" | "fallundermyspell" => Ok(Value::Boolean(left.to_number()? < right.to_number()?)),
-            ">=" | "deeplygreater" | "youreyesaregettingheavy" => {
-                Ok(Value::Boolean(left.to_number()? >= right.to_number()?))
-            }
-            "<=" | "deeplyless" | "goingdeeper" => {
-                Ok(Value::Boolean(left.to_number()? <= right.to_number()?))
-            }
-            "&&" | "undermycontrol" => Ok(Value::Boolean(left.is_truthy() && right.is_truthy())),
-            "||" | "resistanceisfutile" => {
-                Ok(Value::Boolean(left.is_truthy() || right.is_truthy()))
-            }
-            _ => Err(InterpreterError::Runtime(format!(
-                "Unknown binary operator: {}",
-                op
-            ))),
-        }
-    }
-
-    fn values_equal(&self, left: &Value, right: &Value) -> bool {
-        match (left, right) {
-            (Value::Number(a), Value::Number(b)) => (a - b).abs() < f64::EPSILON,
-            (Value::String(a), Value::String(b)) => a == b,
-            (Value::Boolean(a), Value::Boolean(b)) => a == b,
-            (Value::Null, Value::Null) => true,
-            _ => false,
-        }
-    }
-
-    fn evaluate_call(
-        &mut self,
-        callee: &AstNode,
-        arguments: &[AstNode],
-    ) -> Result<Value, InterpreterError> {
-        let args: Vec<Value> = arguments
-            .iter()
-            .map(|arg| self.evaluate_expression(arg))
-            .collect::<Result<_, _>>()?;
-
-        if let AstNode::Identifier(name) = callee {
-            if let Some(result) = self.call_builtin(name, &args)? {
-                return Ok(result);
-            }
-
-            let callee_value = self.get_variable(name)?;
-            return self.invoke_callable(&callee_value, &args);
-        }
-
-        let callee_value = self.evaluate_expression(callee)?;
-        self.invoke_callable(&callee_value, &args)
-    }
-
-    fn invoke_callable(
+    /// Call `next()` or `hasNext()` on an iterator value
+    fn call_iterator_method(
         &mut self,
-        callee: &Value,
+        iterator: &Rc<RefCell<IteratorValue>>,
+        method: &str,
         args: &[Value],
     ) -> Result<Value, InterpreterError> {
-        match callee {
-            Value::Function(func) => self.call_function(func, args),
-            Value::Session(session) => self.instantiate_session(session.clone(), args),
-            Value::Null => Err(InterpreterError::Runtime(localized(
-                "Cannot call null value",
-                "Null-Wert kann nicht aufgerufen werden",
-            ))),
+        if !args.is_empty() {
+            return Err(InterpreterError::Runtime(localized(
+                &format!("Iterator method '{}' takes no arguments", method),
+                &format!("Iterator-Methode '{}' erwartet keine Argumente", method),
+            )));
+        }
+        match method {
+            "next" => Ok(self.iterator_next(iterator)?.unwrap_or(Value::Null)),
+            "hasNext" => Ok(Value::Boolean(self.iterator_has_next(iterator)?)),
             _ => Err(InterpreterError::Runtime(localized(
-                "Value is not callable",
-                "Wert ist nicht aufrufbar",
+                &format!("Iterator has no method '{}'", method),
+                &format!("Iterator besitzt keine Methode '{}'", method),
             ))),
         }
     }
 
-    fn call_function(
+    /// Create the suspended iterator of a generator suggestion.
+    ///
+    /// Arguments are bound right away; the body only starts running when the
+    /// first value is pulled.
+    fn start_generator(
         &mut self,
         function: &FunctionValue,
         args: &[Value],
+        named: &[(String, Value)],
     ) -> Result<Value, InterpreterError> {
-        if function.parameters.len() != args.len() {
-            return Err(InterpreterError::Runtime(localized(
-                &format!(
-                    "Expected {} arguments, received {}",
-                    function.parameters.len(),
-                    args.len()
-                ),
-                &format!(
-                    "Erwartet {} Argumente, erhalten {}",
-                    function.parameters.len(),
-                    args.len()
-                ),
-            )));
-        }
+        let state = self.suspended_body(function, args, named, SuspendPoint::Emerge)?;
+        Ok(Value::Iterator(Rc::new(RefCell::new(IteratorValue::new(
+            IteratorSource::Generator(Some(Box::new(state))),
+        )))))
+    }
 
+    /// Bind call arguments in a new scope on top of the scopes `function`
+    /// runs in, without running any of its body
+    fn suspended_body(
+        &mut self,
+        function: &FunctionValue,
+        args: &[Value],
+        named: &[(String, Value)],
+        suspends_at: SuspendPoint,
+    ) -> Result<GeneratorState, InterpreterError> {
         let session_name = function.session_name().map(|name| name.to_string());
         if session_name.is_some() {
             self.execution_context.push(ExecutionContextFrame {
                 session_name: session_name.clone(),
             });
         }
+        let scopes = match &function.captured_scopes {
+            Some(scopes) => scopes.as_ref().clone(),
+            None => self.locals.clone(),
+        };
+        let caller_scopes = std::mem::replace(&mut self.locals, scopes);
 
         self.push_scope();
-
         if let Some(instance) = function.this_binding() {
             self.define_variable(
                 VariableStorage::Local,
@@ -1617,1803 +2764,5779 @@ impl Interpreter {
                 true,
             );
         }
+        let bound = self.bind_parameters(function, args, named);
 
-        for (param, arg) in function.parameters.iter().zip(args.iter()) {
-            self.define_variable(VariableStorage::Local, param.clone(), arg.clone(), false);
+        let scopes = std::mem::replace(&mut self.locals, caller_scopes);
+        if session_name.is_some() {
+            self.execution_context.pop();
         }
+        bound?;
+
+        Ok(GeneratorState {
+            suspends_at,
+            session_name,
+            scopes,
+            frames: vec![GeneratorFrame::Block {
+                statements: function.body.as_slice().into(),
+                index: 0,
+                scoped: false,
+            }],
+        })
+    }
 
-        let result = (|| {
-            for stmt in &function.body {
-                self.execute_statement(stmt)?;
-            }
-            Ok(Value::Null)
-        })();
+    /// Run a suspended body with its own scopes until it stops at its next
+    /// suspend point; `None` once it finished
+    fn run_suspended_body(
+        &mut self,
+        state: &mut GeneratorState,
+    ) -> Result<Option<Value>, InterpreterError> {
+        std::mem::swap(&mut self.locals, &mut state.scopes);
+        if state.session_name.is_some() {
+            self.execution_context.push(ExecutionContextFrame {
+                session_name: state.session_name.clone(),
+            });
+        }
 
-        self.pop_scope();
+        let result = self.run_generator_frames(&mut state.frames, state.suspends_at);
 
-        if session_name.is_some() {
+        if state.session_name.is_some() {
             self.execution_context.pop();
         }
+        std::mem::swap(&mut self.locals, &mut state.scopes);
+        result
+    }
 
-        match result {
-            Err(InterpreterError::Return(val)) => Ok(val),
-            Err(e) => Err(e),
-            Ok(value) => Ok(value),
+    /// Run a generator until its next `emerge`; `None` once its body finished
+    fn resume_generator(
+        &mut self,
+        state: &mut GeneratorState,
+    ) -> Result<Option<Value>, InterpreterError> {
+        let previous_span = self.current_span;
+        match self.run_suspended_body(state) {
+            Ok(value) => {
+                self.current_span = previous_span;
+                Ok(value)
+            }
+            // `awaken` ends the generator; its value is not emitted
+            Err(InterpreterError::Return(_)) => {
+                self.current_span = previous_span;
+                state.frames.clear();
+                Ok(None)
+            }
+            Err(error) => {
+                state.frames.clear();
+                Err(error)
+            }
         }
     }
 
-    fn build_session_definition(
+    fn run_generator_frames(
         &mut self,
-        name: &str,
-        members: &[SessionMember],
-    ) -> Result<Rc<SessionDefinition>, InterpreterError> {
-        let mut definition = SessionDefinition::new(name.to_string());
-
-        for member in members {
-            match member {
-                SessionMember::Field(field) => {
-                    self.register_session_field(&mut definition, field)?
+        frames: &mut Vec<GeneratorFrame>,
+        suspends_at: SuspendPoint,
+    ) -> Result<Option<Value>, InterpreterError> {
+        loop {
+            let Some(frame) = frames.last_mut() else {
+                return Ok(None);
+            };
+            let step = match frame {
+                GeneratorFrame::Block {
+                    statements,
+                    index,
+                    scoped,
+                } => {
+                    if *index >= statements.len() {
+                        if *scoped {
+                            self.pop_scope();
+                        }
+                        frames.pop();
+                        continue;
+                    }
+                    let statements = Rc::clone(statements);
+                    *index += 1;
+                    self.enter_generator_statement(&statements[*index - 1], frames, suspends_at)
                 }
-                SessionMember::Method(method) => {
-                    self.register_session_method(&mut definition, method)?
+                GeneratorFrame::While {
+                    condition, body, ..
+                } => {
+                    let body = Rc::clone(body);
+                    match self.evaluate_expression(&Rc::clone(condition)) {
+                        Ok(value) if value.is_truthy() => {
+                            self.push_scope();
+                            frames.push(GeneratorFrame::Block {
+                                statements: body,
+                                index: 0,
+                                scoped: true,
+                            });
+                            Ok(None)
+                        }
+                        Ok(_) => {
+                            frames.pop();
+                            Ok(None)
+                        }
+                        Err(error) => Err(error),
+                    }
+                }
+                GeneratorFrame::Loop {
+                    condition,
+                    update,
+                    body,
+                    started,
+                    ..
+                } => {
+                    let (condition, update, body) =
+                        (condition.clone(), update.clone(), Rc::clone(body));
+                    let run_update = std::mem::replace(started, true);
+                    let proceed = (|| {
+                        if run_update && let Some(update) = &update {
+                            self.execute_statement(update)?;
+                        }
+                        match &condition {
+                            Some(condition) => Ok(self.evaluate_expression(condition)?.is_truthy()),
+                            None => Ok(true),
+                        }
+                    })();
+                    match proceed {
+                        Ok(true) => {
+                            frames.push(GeneratorFrame::Block {
+                                statements: body,
+                                index: 0,
+                                scoped: false,
+                            });
+                            Ok(None)
+                        }
+                        Ok(false) => {
+                            frames.pop();
+                            Ok(None)
+                        }
+                        Err(error) => Err(error),
+                    }
                 }
+                GeneratorFrame::ForEach {
+                    key,
+                    value,
+                    is_constant,
+                    cursor,
+                    body,
+                    ..
+                } => match self.next_entry(cursor) {
+                    Ok(Some((entry_key, entry_value))) => {
+                        let (key, value, is_constant, body) =
+                            (key.clone(), value.clone(), *is_constant, Rc::clone(body));
+                        self.push_scope();
+                        if let Some(key) = key {
+                            self.define_variable(
+                                VariableStorage::Local,
+                                key,
+                                entry_key,
+                                is_constant,
+                            );
+                        }
+                        self.define_variable(
+                            VariableStorage::Local,
+                            value,
+                            entry_value,
+                            is_constant,
+                        );
+                        frames.push(GeneratorFrame::Block {
+                            statements: body,
+                            index: 0,
+                            scoped: true,
+                        });
+                        Ok(None)
+                    }
+                    Ok(None) => {
+                        frames.pop();
+                        Ok(None)
+                    }
+                    Err(error) => Err(error),
+                },
+                GeneratorFrame::Await {
+                    statement,
+                    promise,
+                    span,
+                } => {
+                    let outcome = promise.borrow().outcome();
+                    match outcome {
+                        // Resumed before the promise settled: keep waiting
+                        None => Ok(Some(Value::Promise(Rc::clone(promise)))),
+                        Some(outcome) => {
+                            let statement = Rc::clone(statement);
+                            self.current_span = *span;
+                            frames.pop();
+                            outcome
+                                .and_then(|value| self.complete_await(&statement, value))
+                                .map(|()| None)
+                        }
+                    }
+                }
+            };
+
+            match step {
+                Ok(Some(value)) => return Ok(Some(value)),
+                Ok(None) => {}
+                Err(error) => self.unwind_generator(frames, error)?,
             }
         }
-
-        Ok(Rc::new(definition))
     }
 
-    fn register_session_field(
-        &self,
-        definition: &mut SessionDefinition,
-        field: &SessionField,
-    ) -> Result<(), InterpreterError> {
-        let initializer = field.initializer.as_ref().map(|expr| (**expr).clone());
-        let field_def = SessionFieldDefinition {
-            name: field.name.clone(),
-            type_annotation: field.type_annotation.clone(),
-            visibility: field.visibility,
-            initializer: initializer.clone(),
+    /// Start running a statement of a generator or `mesmerize` body.
+    ///
+    /// Statements without a suspend point run to completion; the others push
+    /// a frame so the body can be suspended inside them.
+    fn enter_generator_statement(
+        &mut self,
+        stmt: &AstNode,
+        frames: &mut Vec<GeneratorFrame>,
+        suspends_at: SuspendPoint,
+    ) -> Result<Option<Value>, InterpreterError> {
+        let suspends = match suspends_at {
+            SuspendPoint::Emerge => stmt.contains_yield(),
+            SuspendPoint::Await => stmt.contains_await(),
         };
-
-        if field.is_static {
-            definition.push_static_field(field_def, initializer)
-        } else {
-            definition.push_field(field_def)
+        if !suspends {
+            self.execute_statement(stmt)?;
+            return Ok(None);
+        }
+        match stmt {
+            AstNode::Spanned { span, node } => {
+                self.current_span = Some(*span);
+                self.enter_generator_statement(node, frames, suspends_at)
+            }
+            AstNode::YieldStatement(value) => self.evaluate_expression(value).map(Some),
+            AstNode::IfStatement {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                let branch = if self.evaluate_expression(condition)?.is_truthy() {
+                    Some(then_branch)
+                } else {
+                    else_branch.as_ref()
+                };
+                if let Some(branch) = branch {
+                    frames.push(GeneratorFrame::Block {
+                        statements: branch.as_slice().into(),
+                        index: 0,
+                        scoped: false,
+                    });
+                }
+                Ok(None)
+            }
+            AstNode::DeepFocusStatement { condition, body } => {
+                if self.evaluate_expression(condition)?.is_truthy() {
+                    frames.push(GeneratorFrame::Block {
+                        statements: body.as_slice().into(),
+                        index: 0,
+                        scoped: false,
+                    });
+                }
+                Ok(None)
+            }
+            AstNode::LabeledStatement { label, body } => {
+                self.enter_generator_loop(body, Some(label.clone()), frames, suspends_at)?;
+                Ok(None)
+            }
+            AstNode::WhileStatement { .. }
+            | AstNode::LoopStatement { .. }
+            | AstNode::ForEachStatement { .. } => {
+                self.enter_generator_loop(stmt, None, frames, suspends_at)?;
+                Ok(None)
+            }
+            _ => match stmt.suspending_await() {
+                Some(operand) => self.enter_await(stmt, operand, frames),
+                None => Err(InterpreterError::Runtime(localized(
+                    "'emerge' cannot be used inside attempt or using blocks",
+                    "'emerge' kann nicht in attempt- oder using-Blöcken verwendet werden",
+                ))),
+            },
         }
     }
 
-    fn register_session_method(
-        &self,
-        definition: &mut SessionDefinition,
-        method: &SessionMethod,
-    ) -> Result<(), InterpreterError> {
-        if method.is_constructor && method.is_static {
-            return Err(InterpreterError::Runtime(localized(
-                &format!(
-                    "Constructor in session '{}' cannot be static",
-                    definition.name()
-                ),
-                &format!(
-                    "Konstruktor in Session '{}' darf nicht statisch sein",
-                    definition.name()
-                ),
-            )));
-        }
-
-        let parameters = method.parameters.iter().map(|p| p.name.clone()).collect();
-
-        let method_def = SessionMethodDefinition {
-            name: method.name.clone(),
-            parameters,
-            body: method.body.clone(),
-            visibility: method.visibility,
-            is_static: method.is_static,
-            is_constructor: method.is_constructor,
+    /// Evaluate the awaited operand of a statement in a `mesmerize` body.
+    ///
+    /// A pending promise suspends the call; the statement completes once the
+    /// call resumes after the promise settled.
+    fn enter_await(
+        &mut self,
+        stmt: &AstNode,
+        operand: &AstNode,
+        frames: &mut Vec<GeneratorFrame>,
+    ) -> Result<Option<Value>, InterpreterError> {
+        let value = match self.evaluate_expression(operand)? {
+            Value::Promise(promise) => {
+                let outcome = promise.borrow().outcome();
+                match outcome {
+                    Some(outcome) => outcome?,
+                    None => {
+                        frames.push(GeneratorFrame::Await {
+                            statement: Rc::new(stmt.clone()),
+                            promise: Rc::clone(&promise),
+                            span: self.current_span,
+                        });
+                        return Ok(Some(Value::Promise(promise)));
+                    }
+                }
+            }
+            // Awaiting a plain value yields the value itself
+            value => value,
         };
+        self.complete_await(stmt, value)?;
+        Ok(None)
+    }
 
-        definition.push_method(method_def)
+    /// Finish a statement whose await yielded `value`
+    fn complete_await(&mut self, stmt: &AstNode, value: Value) -> Result<(), InterpreterError> {
+        match stmt.unspanned() {
+            AstNode::ExpressionStatement(expr) => {
+                if let AstNode::AssignmentExpression { target, .. } = expr.unspanned()
+                    && let AstNode::Identifier(name) = target.unspanned()
+                {
+                    let scope = self.resolve_assignment_scope(name);
+                    self.set_variable(name.clone(), value, scope)?;
+                }
+                Ok(())
+            }
+            AstNode::VariableDeclaration {
+                name,
+                is_constant,
+                storage,
+                ..
+            } => {
+                self.define_variable(*storage, name.clone(), value, *is_constant);
+                Ok(())
+            }
+            AstNode::DestructuringDeclaration {
+                pattern,
+                is_constant,
+                storage,
+                ..
+            } => self.destructure(pattern, &value, *storage, *is_constant),
+            AstNode::ReturnStatement(_) => Err(InterpreterError::Return(value)),
+            _ => Ok(()),
+        }
     }
 
-    fn initialize_static_fields(
+    /// Push the frame of a loop containing a suspend point
+    fn enter_generator_loop(
         &mut self,
-        session: Rc<SessionDefinition>,
+        stmt: &AstNode,
+        label: Option<String>,
+        frames: &mut Vec<GeneratorFrame>,
+        suspends_at: SuspendPoint,
     ) -> Result<(), InterpreterError> {
-        if session.static_field_order().is_empty() {
-            return Ok(());
-        }
-
-        self.execution_context.push(ExecutionContextFrame {
-            session_name: Some(session.name().to_string()),
-        });
-
-        let result = (|| {
-            for field_name in session.static_field_order().to_vec() {
-                if let Some(initializer) = session.take_static_field_initializer(&field_name) {
-                    let value = self.evaluate_expression(&initializer)?;
-                    session.set_static_field_value(&field_name, value)?;
+        let frame = match stmt.unspanned() {
+            AstNode::WhileStatement { condition, body } => GeneratorFrame::While {
+                condition: Rc::new(condition.as_ref().clone()),
+                body: body.as_slice().into(),
+                label,
+            },
+            AstNode::LoopStatement {
+                init,
+                condition,
+                update,
+                body,
+            } => {
+                if let Some(init) = init {
+                    self.execute_statement(init)?;
+                }
+                GeneratorFrame::Loop {
+                    condition: condition.as_deref().cloned().map(Rc::new),
+                    update: update.as_deref().cloned().map(Rc::new),
+                    body: body.as_slice().into(),
+                    label,
+                    started: false,
                 }
             }
-            Ok(())
-        })();
-
-        self.execution_context.pop();
-        result
+            AstNode::ForEachStatement {
+                key,
+                value,
+                is_constant,
+                iterable,
+                body,
+            } => GeneratorFrame::ForEach {
+                cursor: Box::new(self.iteration_cursor(iterable, key.is_some())?),
+                key: key.clone(),
+                value: value.clone(),
+                is_constant: *is_constant,
+                body: body.as_slice().into(),
+                label,
+            },
+            other => {
+                return self
+                    .enter_generator_statement(other, frames, suspends_at)
+                    .map(|_| ());
+            }
+        };
+        frames.push(frame);
+        Ok(())
     }
 
-    fn instantiate_session(
+    /// Leave generator frames until a loop handles `error` (`snap`/`sink`).
+    ///
+    /// Errors no loop handles propagate out of the generator.
+    fn unwind_generator(
         &mut self,
-        session: Rc<SessionDefinition>,
-        args: &[Value],
-    ) -> Result<Value, InterpreterError> {
-        let instance = Rc::new(RefCell::new(SessionInstance::new(session.clone())));
-        self.initialize_instance_fields(instance.clone())?;
-
-        if let Some(constructor) = session.constructor() {
-            if constructor.parameters.len() != args.len() {
-                return Err(InterpreterError::Runtime(localized(
-                    &format!(
-                        "Constructor for session '{}' expects {} arguments, received {}",
-                        session.name(),
-                        constructor.parameters.len(),
-                        args.len()
-                    ),
-                    &format!(
-                        "Konstruktor der Session '{}' erwartet {} Argumente, erhalten {}",
-                        session.name(),
-                        constructor.parameters.len(),
-                        args.len()
-                    ),
-                )));
+        frames: &mut Vec<GeneratorFrame>,
+        mut error: InterpreterError,
+    ) -> Result<(), InterpreterError> {
+        while let Some(frame) = frames.pop() {
+            let Some(label) = frame.loop_label() else {
+                if let GeneratorFrame::Block { scoped: true, .. } = frame {
+                    self.pop_scope();
+                }
+                continue;
+            };
+            match loop_flow(Err(error), label) {
+                Ok(LoopFlow::Next) => {
+                    frames.push(frame);
+                    return Ok(());
+                }
+                Ok(LoopFlow::Break) => return Ok(()),
+                Err(outer) => error = outer,
             }
+        }
+        Err(error)
+    }
 
-            let function = FunctionValue::new_session_member(
-                session.name().to_string(),
-                constructor,
-                Some(instance.clone()),
-            );
-            self.call_function(&function, args)?;
-        } else if !args.is_empty() {
+    fn evaluate_range(
+        &mut self,
+        start: &AstNode,
+        end: &AstNode,
+        inclusive: bool,
+        step: Option<&AstNode>,
+    ) -> Result<NumberRange, InterpreterError> {
+        let start = self.evaluate_expression(start)?.to_number()?;
+        let end = self.evaluate_expression(end)?.to_number()?;
+        let step = match step {
+            Some(step) => self.evaluate_expression(step)?.to_number()?,
+            None => 1.0,
+        };
+        if step == 0.0 || step.is_nan() {
             return Err(InterpreterError::Runtime(localized(
-                &format!(
-                    "Session '{}' does not define a constructor but arguments were provided",
-                    session.name()
-                ),
-                &format!(
-                    "Session '{}' definiert keinen Konstruktor, dennoch wurden Argumente übergeben",
-                    session.name()
-                ),
+                "Range step must not be zero",
+                "Die Schrittweite eines Bereichs darf nicht null sein",
             )));
         }
-
-        Ok(Value::Instance(instance))
+        Ok(NumberRange {
+            start,
+            end,
+            step,
+            inclusive,
+            index: 0,
+        })
     }
 
-    fn initialize_instance_fields(
-        &mut self,
-        instance: Rc<RefCell<SessionInstance>>,
-    ) -> Result<(), InterpreterError> {
-        let definition = {
-            let borrow = instance.borrow();
-            borrow.definition()
+    /// Key/value pairs visited by a for-each loop.
+    ///
+    /// Arrays and strings pair each element with its index. Dictionaries and
+    /// records pair each value with its key; with a single loop variable the
+    /// value is the `[key, value]` entry instead.
+    fn iteration_entries(
+        &self,
+        collection: Value,
+        keyed: bool,
+    ) -> Result<Vec<(Value, Value)>, InterpreterError> {
+        let indexed = |values: Vec<Value>| {
+            values
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| (Value::Number(index as f64), value))
+                .collect()
+        };
+        let entry = |key: Value, value: Value| {
+            if keyed {
+                (key, value)
+            } else {
+                (Value::Null, Value::array(vec![key, value]))
+            }
         };
 
-        if definition.field_order().is_empty() {
-            return Ok(());
+        match collection {
+            // Iterate a snapshot, so changes made by the body do not affect the loop
+            Value::Array(items) => Ok(indexed(items.borrow().clone())),
+            Value::String(text) => Ok(indexed(
+                text.chars()
+                    .map(|character| Value::String(character.to_string()))
+                    .collect(),
+            )),
+            Value::Dictionary(dict) => Ok(dict
+                .into_iter()
+                .map(|(key, value)| entry(key.to_value(), value))
+                .collect()),
+            Value::Record(mut record) => {
+                // Declared field order when the record type is known
+                let order = match self.tranceify_types.get(&record.type_name) {
+                    Some(names) => names.clone(),
+                    None => {
+                        let mut names: Vec<String> = record.fields.keys().cloned().collect();
+                        names.sort();
+                        names
+                    }
+                };
+                Ok(order
+                    .into_iter()
+                    .filter_map(|name| {
+                        let value = record.fields.remove(&name)?;
+                        Some(entry(Value::String(name), value))
+                    })
+                    .collect())
+            }
+            other => Err(InterpreterError::TypeError(localized(
+                &format!("Cannot iterate over '{}'", other),
+                &format!("Über '{}' kann nicht iteriert werden", other),
+            ))),
         }
+    }
 
-        self.execution_context.push(ExecutionContextFrame {
-            session_name: Some(definition.name().to_string()),
-        });
-        self.push_scope();
-        self.define_variable(
-            VariableStorage::Local,
-            "this".to_string(),
-            Value::Instance(instance.clone()),
-            true,
-        );
+    fn evaluate_expression(&mut self, expr: &AstNode) -> Result<Value, InterpreterError> {
+        match expr {
+            AstNode::NumberLiteral(n) => Ok(Value::Number(*n)),
+            AstNode::IntegerLiteral(i) => Ok(Value::Integer(*i)),
 
-        let result = (|| {
-            for field_name in definition.field_order().to_vec() {
-                if let Some(field_def) = definition.get_field_definition(&field_name)
-                    && let Some(initializer) = &field_def.initializer
-                {
-                    let value = self.evaluate_expression(initializer)?;
-                    instance.borrow_mut().set_field(&field_name, value);
+            AstNode::StringLiteral(s) => Ok(Value::String(s.clone())),
+
+            AstNode::StringInterpolation(parts) => {
+                let mut result = String::new();
+                for part in parts {
+                    match part {
+                        InterpolationPart::Text(text) => result.push_str(text),
+                        InterpolationPart::Expression(expr) => {
+                            let value = self.evaluate_expression(expr)?;
+                            result.push_str(&value.to_string());
+                        }
+                    }
                 }
+                Ok(Value::String(result))
             }
-            Ok(())
-        })();
 
-        self.pop_scope();
-        self.execution_context.pop();
-        result
-    }
+            AstNode::BooleanLiteral(b) => Ok(Value::Boolean(*b)),
+            AstNode::NullLiteral => Ok(Value::Null),
 
-    fn resolve_member_value(
-        &mut self,
-        target: Value,
-        property: &str,
-    ) -> Result<Value, InterpreterError> {
-        match target {
-            Value::Instance(instance_rc) => {
-                let definition = {
-                    let borrow = instance_rc.borrow();
-                    borrow.definition()
-                };
+            AstNode::Identifier(name) => self.get_variable(name),
 
-                if let Some(method_def) = definition.get_method_definition(property) {
-                    self.ensure_visibility(
-                        method_def.visibility,
-                        definition.name(),
-                        "method",
-                        property,
-                    )?;
-                    let function = FunctionValue::new_session_member(
-                        definition.name().to_string(),
-                        method_def,
-                        Some(instance_rc.clone()),
-                    );
-                    return Ok(Value::Function(function));
-                }
+            AstNode::ArrayLiteral(elements) => {
+                Ok(Value::array(self.evaluate_spread_list(elements)?))
+            }
 
-                if let Some(field_def) = definition.get_field_definition(property) {
-                    self.ensure_visibility(
-                        field_def.visibility,
-                        definition.name(),
-                        "field",
-                        property,
-                    )?;
-                    return Ok(instance_rc
-                        .borrow()
-                        .get_field(property)
-                        .unwrap_or(Value::Null));
-                }
+            AstNode::SpreadElement(_) => Err(InterpreterError::Runtime(localized(
+                "Spread '...' is only allowed in array literals and call arguments",
+                "Spread '...' ist nur in Array-Literalen und Aufrufargumenten erlaubt",
+            ))),
 
-                if let Some(static_field) = definition.get_static_field_snapshot(property) {
-                    self.ensure_visibility(
-                        static_field.definition.visibility,
-                        definition.name(),
-                        "field",
-                        property,
-                    )?;
-                    return Ok(static_field.value);
-                }
+            AstNode::NamedArgument { .. } => Err(InterpreterError::Runtime(localized(
+                "Named arguments are only allowed in calls",
+                "Benannte Argumente sind nur in Aufrufen erlaubt",
+            ))),
 
-                if let Some(static_method) = definition.get_static_method_definition(property) {
-                    self.ensure_visibility(
-                        static_method.visibility,
-                        definition.name(),
-                        "method",
-                        property,
-                    )?;
-                    let function = FunctionValue::new_session_member(
-                        definition.name().to_string(),
-                        static_method,
-                        None,
-                    );
-                    return Ok(Value::Function(function));
-                }
+            AstNode::DictionaryLiteral(entries) => self.evaluate_dictionary_literal(entries),
 
-                Err(InterpreterError::Runtime(localized(
-                    &format!(
-                        "Session instance of '{}' has no member '{}'",
-                        definition.name(),
-                        property
-                    ),
-                    &format!(
-                        "Session-Instanz von '{}' besitzt kein Mitglied '{}'",
-                        definition.name(),
-                        property
-                    ),
-                )))
+            AstNode::RangeExpression {
+                start,
+                end,
+                inclusive,
+                step,
+            } => {
+                let range = self.evaluate_range(start, end, *inclusive, step.as_deref())?;
+                Ok(Value::array(range.map(Value::Number).collect()))
             }
-            Value::Session(session_rc) => {
-                if let Some(static_field) = session_rc.get_static_field_snapshot(property) {
-                    self.ensure_visibility(
-                        static_field.definition.visibility,
-                        session_rc.name(),
-                        "field",
-                        property,
-                    )?;
-                    return Ok(static_field.value);
-                }
 
-                if let Some(method_def) = session_rc.get_static_method_definition(property) {
-                    self.ensure_visibility(
-                        method_def.visibility,
-                        session_rc.name(),
-                        "method",
-                        property,
-                    )?;
-                    let function = FunctionValue::new_session_member(
-                        session_rc.name().to_string(),
-                        method_def,
-                        None,
-                    );
-                    return Ok(Value::Function(function));
-                }
-
-                Err(InterpreterError::Runtime(localized(
-                    &format!(
-                        "Session '{}' has no static member '{}'",
-                        session_rc.name(),
-                        property
-                    ),
-                    &format!(
-                        "Session '{}' besitzt kein statisches Mitglied '{}'",
-                        session_rc.name(),
-                        property
-                    ),
-                )))
+            AstNode::BinaryExpression {
+                left,
+                operator,
+                right,
+            } => {
+                let left_val = self.evaluate_expression(left)?;
+                let right_val = self.evaluate_expression(right)?;
+                self.evaluate_binary_op(&left_val, operator, &right_val)
             }
-            Value::Record(record) => {
-                // Access field from record
-                if let Some(field_value) = record.fields.get(property) {
-                    Ok(field_value.clone())
-                } else {
-                    Err(InterpreterError::Runtime(format!(
-                        "Record of type '{}' has no field '{}'",
-                        record.type_name, property
-                    )))
+
+            AstNode::UnaryExpression { operator, operand } => {
+                let operand_val = self.evaluate_expression(operand)?;
+                match operator.as_str() {
+                    "-" => match operand_val {
+                        Value::Integer(i) => i
+                            .checked_neg()
+                            .map(Value::Integer)
+                            .ok_or_else(|| integer_overflow(&format!("-({})", i))),
+                        other => Ok(Value::Number(-other.to_number()?)),
+                    },
+                    "~" => Ok(Value::Integer(!bitwise_operand(&operand_val, "~")?)),
+                    "!" => Ok(Value::Boolean(!operand_val.is_truthy())),
+                    _ => Err(InterpreterError::Runtime(format!(
+                        "Unknown unary operator: {}",
+                        operator
+                    ))),
                 }
             }
-            other => Err(InterpreterError::Runtime(localized(
-                &format!("Cannot access member '{}' on value '{}'", property, other),
-                &format!(
-                    "Mitglied '{}' kann auf Wert '{}' nicht zugegriffen werden",
-                    property, other
-                ),
-            ))),
-        }
-    }
 
-    fn assign_member_value(
-        &mut self,
-        target: Value,
-        property: &str,
-        value: Value,
-    ) -> Result<(), InterpreterError> {
-        match target {
-            Value::Instance(instance_rc) => {
-                let definition = {
-                    let borrow = instance_rc.borrow();
-                    borrow.definition()
-                };
+            AstNode::CallExpression { callee, arguments } => self.evaluate_call(callee, arguments),
 
-                if let Some(field_def) = definition.get_field_definition(property) {
-                    self.ensure_visibility(
-                        field_def.visibility,
-                        definition.name(),
-                        "field",
-                        property,
-                    )?;
-                    instance_rc.borrow_mut().set_field(property, value);
-                    return Ok(());
+            AstNode::MemberExpression { object, property } => {
+                if matches!(object.unspanned(), AstNode::Identifier(name) if name == "super") {
+                    return self.resolve_super_member(property);
                 }
+                let owner = self.evaluate_expression(object)?;
+                self.resolve_member_value(owner, property)
+            }
 
-                if definition.get_method_definition(property).is_some()
-                    || definition.get_static_method_definition(property).is_some()
-                {
-                    return Err(InterpreterError::Runtime(localized(
-                        &format!("Cannot assign to method '{}'", property),
-                        &format!("Zuweisung zur Methode '{}' nicht möglich", property),
-                    )));
+            AstNode::AssignmentExpression { target, value } => match target.as_ref() {
+                AstNode::Identifier(name) => {
+                    let val = self.evaluate_expression(value)?;
+                    let scope = self.resolve_assignment_scope(name);
+                    self.set_variable(name.clone(), val.clone(), scope)?;
+                    Ok(val)
                 }
-
-                if definition.get_static_field_snapshot(property).is_some() {
-                    return Err(InterpreterError::Runtime(localized(
-                        &format!(
-                            "Assign static field '{}' through session '{}', not an instance",
-                            property,
-                            definition.name()
-                        ),
-                        &format!(
-                            "Statisches Feld '{}' muss über die Session '{}' gesetzt werden",
-                            property,
-                            definition.name()
-                        ),
-                    )));
+                AstNode::MemberExpression { object, property } => {
+                    let owner = self.evaluate_expression(object)?;
+                    let val = self.evaluate_expression(value)?;
+                    self.assign_member_value(owner, property, val.clone())?;
+                    Ok(val)
                 }
+                AstNode::IndexExpression { object, index } => {
+                    let container = self.evaluate_expression(object)?;
+                    let idx = self.evaluate_expression(index)?;
+                    let val = self.evaluate_expression(value)?;
+                    let updated = self.assign_index_value(container, &idx, val.clone())?;
+                    self.store_value(object, updated)?;
+                    Ok(val)
+                }
+                _ => Err(InterpreterError::Runtime(localized(
+                    "Invalid assignment target",
+                    "Ungültiges Zuweisungsziel",
+                ))),
+            },
 
-                Err(InterpreterError::Runtime(localized(
-                    &format!(
-                        "Session instance of '{}' has no field '{}'",
-                        definition.name(),
-                        property
-                    ),
-                    &format!(
-                        "Session-Instanz von '{}' besitzt kein Feld '{}'",
-                        definition.name(),
-                        property
-                    ),
-                )))
+            AstNode::IndexExpression { object, index } => {
+                let obj = self.evaluate_expression(object)?;
+                let idx = self.evaluate_expression(index)?;
+                self.index_value(obj, &idx)
             }
-            Value::Session(session_rc) => {
-                if let Some(static_field) = session_rc.get_static_field_snapshot(property) {
-                    self.ensure_visibility(
-                        static_field.definition.visibility,
-                        session_rc.name(),
-                        "field",
-                        property,
-                    )?;
-                    session_rc.set_static_field_value(property, value)?;
-                    return Ok(());
-                }
 
-                if session_rc.get_static_method_definition(property).is_some() {
-                    return Err(InterpreterError::Runtime(localized(
-                        &format!("Cannot assign to static method '{}'", property),
-                        &format!(
-                            "Zuweisung zu statischer Methode '{}' nicht möglich",
-                            property
-                        ),
-                    )));
-                }
+            AstNode::CompoundAssignmentExpression {
+                target,
+                operator,
+                value,
+            } => {
+                let place = self.evaluate_place(target)?;
+                let current = self.read_place(&place)?;
+                let updated = if operator == "??" {
+                    // `??=` only evaluates its value when the target is null
+                    if !matches!(current, Value::Null) {
+                        return Ok(current);
+                    }
+                    self.evaluate_expression(value)?
+                } else {
+                    let operand = self.evaluate_expression(value)?;
+                    self.evaluate_binary_op(&current, operator, &operand)?
+                };
+                self.write_place(&place, updated.clone())?;
+                Ok(updated)
+            }
 
-                Err(InterpreterError::Runtime(localized(
-                    &format!(
-                        "Session '{}' has no static field '{}'",
-                        session_rc.name(),
-                        property
-                    ),
-                    &format!(
-                        "Session '{}' besitzt kein statisches Feld '{}'",
-                        session_rc.name(),
-                        property
-                    ),
-                )))
+            AstNode::UpdateExpression {
+                target,
+                operator,
+                prefix,
+            } => {
+                let place = self.evaluate_place(target)?;
+                let current = self.read_place(&place)?;
+                let step = if operator == "++" { 1 } else { -1 };
+                let updated = match &current {
+                    Value::Number(n) => Value::Number(n + step as f64),
+                    Value::Integer(i) => {
+                        Value::Integer(i.checked_add(step).ok_or_else(|| {
+                            integer_overflow(&format!("{} {} 1", i, &operator[..1]))
+                        })?)
+                    }
+                    other => {
+                        return Err(InterpreterError::TypeError(localized(
+                            &format!("Operator '{}' requires a number, got {}", operator, other),
+                            &format!(
+                                "Operator '{}' erfordert eine Zahl, erhalten {}",
+                                operator, other
+                            ),
+                        )));
+                    }
+                };
+                self.write_place(&place, updated.clone())?;
+                Ok(if *prefix { updated } else { current })
             }
-            _ => Err(InterpreterError::Runtime(localized(
-                "Assignment target is not a session member",
-                "Zuweisungsziel ist kein Session-Mitglied",
-            ))),
-        }
-    }
 
-    fn ensure_visibility(
-        &self,
-        visibility: SessionVisibility,
-        session_name: &str,
-        member_kind: &str,
-        member_name: &str,
-    ) -> Result<(), InterpreterError> {
-        if visibility == SessionVisibility::Private && !self.is_access_allowed(session_name) {
-            return Err(InterpreterError::Runtime(localized(
-                &format!(
-                    "Access denied to private {} '{}' of session '{}'",
-                    member_kind, member_name, session_name
-                ),
-                &format!(
-                    "Zugriff auf privates {} '{}' der Session '{}' verweigert",
-                    member_kind, member_name, session_name
-                ),
-            )));
-        }
-        Ok(())
-    }
+            AstNode::AwaitExpression { expression } => {
+                match self.evaluate_expression(expression)? {
+                    Value::Promise(promise) => self.await_promise(&promise),
+                    // Awaiting a plain value yields the value itself
+                    value => Ok(value),
+                }
+            }
 
-    fn is_access_allowed(&self, session_name: &str) -> bool {
-        self.execution_context
-            .iter()
-            .rev()
-            .find_map(|frame| frame.session_name.as_deref())
-            == Some(session_name)
-    }
+            AstNode::NullishCoalescing { left, right } => {
+                let left_val = self.evaluate_expression(left)?;
+                if matches!(left_val, Value::Null) {
+                    self.evaluate_expression(right)
+                } else {
+                    Ok(left_val)
+                }
+            }
 
-    fn call_builtin(
-        &mut self,
-        name: &str,
-        args: &[Value],
-    ) -> Result<Option<Value>, InterpreterError> {
-        if let Some(result) = self.call_math_builtin(name, args)? {
-            return Ok(Some(result));
-        }
+            AstNode::OptionalChaining { object, property } => {
+                let obj = self.evaluate_expression(object)?;
+                if matches!(obj, Value::Null) {
+                    Ok(Value::Null)
+                } else {
+                    self.resolve_member_value(obj, property)
+                }
+            }
 
-        if let Some(result) = self.call_string_builtin(name, args)? {
-            return Ok(Some(result));
-        }
+            AstNode::OptionalIndexing { object, index } => {
+                let obj = self.evaluate_expression(object)?;
+                if matches!(obj, Value::Null) {
+                    return Ok(Value::Null);
+                }
 
-        if let Some(result) = self.call_array_builtin(name, args)? {
-            return Ok(Some(result));
-        }
+                let idx = self.evaluate_expression(index)?;
+                match obj {
+                    Value::Array(arr) => {
+                        let i = idx.to_number()? as usize;
+                        Ok(arr.borrow().get(i).cloned().unwrap_or(Value::Null))
+                    }
+                    Value::Dictionary(dict) => {
+                        let key = DictionaryKey::from_value(&idx)?;
+                        Ok(dict.get(&key).cloned().unwrap_or(Value::Null))
+                    }
+                    _ => Err(InterpreterError::TypeError(
+                        "Cannot index non-array".to_string(),
+                    )),
+                }
+            }
 
-        if let Some(result) = self.call_core_builtin(name, args)? {
-            return Ok(Some(result));
-        }
+            AstNode::EntrainExpression {
+                subject,
+                cases,
+                default,
+            } => {
+                let subject_value = self.evaluate_expression(subject)?;
 
-        if let Some(result) = self.call_file_builtin(name, args)? {
-            return Ok(Some(result));
-        }
-
-        if let Some(result) = self.call_hashing_builtin(name, args)? {
-            return Ok(Some(result));
-        }
-
-        if let Some(result) = self.call_statistics_builtin(name, args)? {
-            return Ok(Some(result));
-        }
-
-        if let Some(result) = self.call_system_builtin(name, args)? {
-            return Ok(Some(result));
-        }
+                // Try to match each case
+                for case in cases {
+                    if let Some(matched_env) = self.match_pattern(&case.pattern, &subject_value)? {
+                        self.push_scope();
+                        for (name, value) in &matched_env {
+                            self.define_variable(
+                                VariableStorage::Local,
+                                name.clone(),
+                                value.clone(),
+                                false,
+                            );
+                        }
 
-        if let Some(result) = self.call_time_builtin(name, args)? {
-            return Ok(Some(result));
-        }
+                        let case_result = (|| -> Result<Option<Value>, InterpreterError> {
+                            if let Some(guard) = &case.guard {
+                                let guard_result = self.evaluate_expression(guard)?;
+                                if !guard_result.is_truthy() {
+                                    return Ok(None);
+                                }
+                            }
 
-        if let Some(result) = self.call_validation_builtin(name, args)? {
-            return Ok(Some(result));
-        }
+                            let value = self.execute_entrain_body(&case.body)?;
+                            Ok(Some(value))
+                        })();
 
-        Ok(None)
-    }
+                        self.pop_scope();
 
-    fn call_math_builtin(
-        &self,
-        name: &str,
-        args: &[Value],
-    ) -> Result<Option<Value>, InterpreterError> {
-        let result = match name {
-            "Sin" => Some(Value::Number(MathBuiltins::sin(
-                self.number_arg(args, 0, name)?,
-            ))),
-            "Cos" => Some(Value::Number(MathBuiltins::cos(
-                self.number_arg(args, 0, name)?,
-            ))),
-            "Tan" => Some(Value::Number(MathBuiltins::tan(
-                self.number_arg(args, 0, name)?,
-            ))),
-            "Sqrt" => Some(Value::Number(MathBuiltins::sqrt(
-                self.number_arg(args, 0, name)?,
-            ))),
-            "Log" => Some(Value::Number(MathBuiltins::log(
-                self.number_arg(args, 0, name)?,
-            ))),
-            "Log10" => Some(Value::Number(MathBuiltins::log10(
-                self.number_arg(args, 0, name)?,
-            ))),
-            "Abs" => Some(Value::Number(MathBuiltins::abs(
-                self.number_arg(args, 0, name)?,
-            ))),
-            "Floor" => Some(Value::Number(MathBuiltins::floor(
-                self.number_arg(args, 0, name)?,
-            ))),
-            "Ceil" => Some(Value::Number(MathBuiltins::ceil(
-                self.number_arg(args, 0, name)?,
-            ))),
-            "Round" => Some(Value::Number(MathBuiltins::round(
-                self.number_arg(args, 0, name)?,
-            ))),
-            "Min" => Some(Value::Number(MathBuiltins::min(
-                self.number_arg(args, 0, name)?,
-                self.number_arg(args, 1, name)?,
-            ))),
-            "Max" => Some(Value::Number(MathBuiltins::max(
-                self.number_arg(args, 0, name)?,
-                self.number_arg(args, 1, name)?,
-            ))),
-            "Pow" => Some(Value::Number(MathBuiltins::pow(
-                self.number_arg(args, 0, name)?,
-                self.number_arg(args, 1, name)?,
-            ))),
-            "Factorial" => Some(Value::Number(MathBuiltins::factorial(
-                self.integer_arg(args, 0, name)?,
-            ) as f64)),
-            "Gcd" => Some(Value::Number(MathBuiltins::gcd(
-                self.integer_arg(args, 0, name)?,
-                self.integer_arg(args, 1, name)?,
-            ) as f64)),
-            "Lcm" => Some(Value::Number(MathBuiltins::lcm(
-                self.integer_arg(args, 0, name)?,
-                self.integer_arg(args, 1, name)?,
-            ) as f64)),
-            "IsPrime" => Some(Value::Boolean(MathBuiltins::is_prime(
-                self.integer_arg(args, 0, name)?,
-            ))),
-            "Fibonacci" => Some(Value::Number(MathBuiltins::fibonacci(
-                self.integer_arg(args, 0, name)?,
-            ) as f64)),
-            "Clamp" => Some(Value::Number(MathBuiltins::clamp(
-                self.number_arg(args, 0, name)?,
-                self.number_arg(args, 1, name)?,
-                self.number_arg(args, 2, name)?,
-            ))),
-            _ => None,
-        };
+                        match case_result? {
+                            Some(value) => return Ok(value),
+                            None => continue,
+                        }
+                    }
+                }
 
-        Ok(result)
-    }
+                // No case matched - try default
+                if let Some(default_body) = default {
+                    self.push_scope();
+                    let result = self.execute_entrain_body(default_body);
+                    self.pop_scope();
+                    result
+                } else {
+                    Err(InterpreterError::Runtime(
+                        "No pattern matched and no default case provided".to_string(),
+                    ))
+                }
+            }
 
-    fn call_string_builtin(
-        &self,
-        name: &str,
-        args: &[Value],
-    ) -> Result<Option<Value>, InterpreterError> {
-        let result = match name {
-            "Length" => {
-                // Length works for both strings and arrays
-                if args.is_empty() {
+            AstNode::RecordLiteral { type_name, fields } => {
+                // Check if the tranceify type is defined
+                if !self.tranceify_types.contains_key(type_name) {
                     return Err(InterpreterError::Runtime(format!(
-                        "Function '{}' requires at least 1 argument",
-                        name
+                        "Undefined tranceify type '{}'",
+                        type_name
                     )));
                 }
-                match &args[0] {
-                    Value::String(s) => Some(Value::Number(s.len() as f64)),
-                    Value::Array(arr) => Some(Value::Number(arr.len() as f64)),
-                    _ => {
-                        return Err(InterpreterError::TypeError(format!(
-                            "Function 'Length' expects string or array argument, got {}",
-                            args[0]
-                        )));
-                    }
+
+                // Evaluate all field values
+                let mut field_values = HashMap::new();
+                for field_init in fields {
+                    let value = self.evaluate_expression(&field_init.value)?;
+                    field_values.insert(field_init.name.clone(), value);
                 }
+
+                Ok(Value::Record(RecordValue {
+                    type_name: type_name.clone(),
+                    fields: field_values,
+                }))
             }
-            "ToUpper" => Some(Value::String(StringBuiltins::to_upper(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "ToLower" => Some(Value::String(StringBuiltins::to_lower(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "Trim" => Some(Value::String(StringBuiltins::trim(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "IndexOf" => Some(Value::Number(StringBuiltins::index_of(
-                &self.string_arg(args, 0, name)?,
-                &self.string_arg(args, 1, name)?,
-            ) as f64)),
-            "Replace" => Some(Value::String(StringBuiltins::replace(
-                &self.string_arg(args, 0, name)?,
-                &self.string_arg(args, 1, name)?,
-                &self.string_arg(args, 2, name)?,
-            ))),
-            "Reverse" => Some(Value::String(StringBuiltins::reverse(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "Capitalize" => Some(Value::String(StringBuiltins::capitalize(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "StartsWith" => Some(Value::Boolean(StringBuiltins::starts_with(
-                &self.string_arg(args, 0, name)?,
-                &self.string_arg(args, 1, name)?,
-            ))),
-            "EndsWith" => Some(Value::Boolean(StringBuiltins::ends_with(
-                &self.string_arg(args, 0, name)?,
-                &self.string_arg(args, 1, name)?,
-            ))),
-            "Contains" => Some(Value::Boolean(StringBuiltins::contains(
-                &self.string_arg(args, 0, name)?,
-                &self.string_arg(args, 1, name)?,
-            ))),
-            "Split" => {
-                let items = StringBuiltins::split(
-                    &self.string_arg(args, 0, name)?,
-                    &self.string_arg(args, 1, name)?,
-                )
-                .into_iter()
-                .map(Value::String)
-                .collect();
-                Some(Value::Array(items))
+
+            AstNode::FunctionExpression {
+                parameters, body, ..
+            } => {
+                // Closures keep the session context so they may touch concealed members of `this`
+                let session_name = self
+                    .execution_context
+                    .last()
+                    .and_then(|frame| frame.session_name.clone());
+                Ok(Value::Function(FunctionValue::new_closure(
+                    parameters.clone(),
+                    body.clone(),
+                    self.locals.clone(),
+                    session_name,
+                )))
             }
-            "Substring" => Some(Value::String(StringBuiltins::substring(
-                &self.string_arg(args, 0, name)?,
-                self.usize_arg(args, 1, name)?,
-                self.usize_arg(args, 2, name)?,
-            ))),
-            "Repeat" => Some(Value::String(StringBuiltins::repeat(
-                &self.string_arg(args, 0, name)?,
-                self.usize_arg(args, 1, name)?,
-            ))),
-            "PadLeft" => Some(Value::String(StringBuiltins::pad_left(
-                &self.string_arg(args, 0, name)?,
-                self.usize_arg(args, 1, name)?,
-                self.char_arg(args, 2, name)?,
-            ))),
-            "PadRight" => Some(Value::String(StringBuiltins::pad_right(
-                &self.string_arg(args, 0, name)?,
-                self.usize_arg(args, 1, name)?,
-                self.char_arg(args, 2, name)?,
-            ))),
-            "IsEmpty" => Some(Value::Boolean(StringBuiltins::is_empty(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "IsWhitespace" => Some(Value::Boolean(StringBuiltins::is_whitespace(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            _ => None,
-        };
 
-        Ok(result)
+            _ => Err(InterpreterError::Runtime(format!(
+                "Unsupported expression: {:?}",
+                expr
+            ))),
+        }
     }
 
-    fn call_array_builtin(
-        &self,
-        name: &str,
-        args: &[Value],
-    ) -> Result<Option<Value>, InterpreterError> {
-        let result = match name {
-            "ArrayLength" => {
-                let array = self.array_arg(args, 0, name)?;
-                Some(Value::Number(ArrayBuiltins::length(&array) as f64))
-            }
-            "ArrayIsEmpty" => {
-                let array = self.array_arg(args, 0, name)?;
-                Some(Value::Boolean(ArrayBuiltins::is_empty(&array)))
-            }
-            "ArrayGet" => {
-                let array = self.array_arg(args, 0, name)?;
-                let index = self.usize_arg(args, 1, name)?;
-                let value = ArrayBuiltins::get(&array, index).unwrap_or(Value::Null);
-                Some(value)
+    /// Match a pattern against a value, returning bindings if successful
+    fn match_pattern(
+        &mut self,
+        pattern: &Pattern,
+        value: &Value,
+    ) -> Result<Option<std::collections::HashMap<String, Value>>, InterpreterError> {
+        use std::collections::HashMap;
+
+        match pattern {
+            Pattern::Literal(lit_node) => {
+                let lit_value = self.evaluate_expression(lit_node)?;
+                if self.values_equal(&lit_value, value)? {
+                    Ok(Some(HashMap::new()))
+                } else {
+                    Ok(None)
+                }
             }
-            "ArrayIndexOf" => {
-                let array = self.array_arg(args, 0, name)?;
-                let target = self.arg(args, 1, name)?.clone();
-                Some(Value::Number(
-                    ArrayBuiltins::index_of(&array, &target) as f64
-                ))
+
+            Pattern::Identifier(name) => {
+                let mut bindings = HashMap::new();
+                bindings.insert(name.clone(), value.clone());
+                Ok(Some(bindings))
             }
-            "ArrayContains" => {
-                let array = self.array_arg(args, 0, name)?;
-                let target = self.arg(args, 1, name)?.clone();
-                Some(Value::Boolean(ArrayBuiltins::contains(&array, &target)))
+
+            Pattern::Typed {
+                name,
+                type_annotation,
+            } => {
+                if !value_matches_type(value, type_annotation, &self.interfaces) {
+                    return Ok(None);
+                }
+
+                let mut bindings = HashMap::new();
+                if let Some(name) = name {
+                    bindings.insert(name.clone(), value.clone());
+                }
+                Ok(Some(bindings))
             }
-            "ArrayReverse" => {
-                let array = self.array_arg(args, 0, name)?;
-                Some(Value::Array(ArrayBuiltins::reverse(&array)))
+
+            Pattern::Array { elements, rest } => {
+                if let Value::Array(arr) = value {
+                    // Literal sub-patterns run code, so match against a snapshot
+                    let arr = arr.borrow().clone();
+                    let mut bindings = HashMap::new();
+
+                    // Match array elements
+                    for (i, elem_pattern) in elements.iter().enumerate() {
+                        if i >= arr.len() {
+                            return Ok(None); // Not enough elements
+                        }
+
+                        if let Some(elem_bindings) = self.match_pattern(elem_pattern, &arr[i])? {
+                            bindings.extend(elem_bindings);
+                        } else {
+                            return Ok(None);
+                        }
+                    }
+
+                    // Handle rest pattern
+                    if let Some(rest_name) = rest {
+                        let rest_elements: Vec<Value> =
+                            arr.iter().skip(elements.len()).cloned().collect();
+                        bindings.insert(rest_name.clone(), Value::array(rest_elements));
+                    } else if arr.len() > elements.len() {
+                        return Ok(None); // Too many elements and no rest pattern
+                    }
+
+                    Ok(Some(bindings))
+                } else {
+                    Ok(None)
+                }
             }
-            "ArraySum" => {
-                let array = self.array_arg(args, 0, name)?;
-                let numbers = self.values_to_numbers(&array, name)?;
-                Some(Value::Number(ArrayBuiltins::sum(&numbers)))
+
+            Pattern::Variant {
+                enum_name,
+                variant,
+                payload,
+            } => {
+                let Value::Variant(value) = value else {
+                    return Ok(None);
+                };
+                if &value.enum_name != enum_name || &value.variant != variant {
+                    return Ok(None);
+                }
+
+                // `when State.Running` matches the variant regardless of its payload
+                let mut bindings = HashMap::new();
+                if payload.is_empty() {
+                    return Ok(Some(bindings));
+                }
+                if payload.len() != value.payload.len() {
+                    return Ok(None);
+                }
+                for (pattern, item) in payload.iter().zip(&value.payload) {
+                    match self.match_pattern(pattern, item)? {
+                        Some(item_bindings) => bindings.extend(item_bindings),
+                        None => return Ok(None),
+                    }
+                }
+                Ok(Some(bindings))
             }
-            "ArrayAverage" => {
-                let array = self.array_arg(args, 0, name)?;
-                let numbers = self.values_to_numbers(&array, name)?;
-                Some(Value::Number(ArrayBuiltins::average(&numbers)))
+
+            Pattern::Record { type_name, fields } => {
+                // `{ name, age }` without a type name also unpacks dictionaries
+                let field_values: Option<Vec<Value>> = match value {
+                    Value::Record(record)
+                        if type_name.is_empty() || &record.type_name == type_name =>
+                    {
+                        fields
+                            .iter()
+                            .map(|field| record.fields.get(&field.name).cloned())
+                            .collect()
+                    }
+                    Value::Dictionary(dict) if type_name.is_empty() => fields
+                        .iter()
+                        .map(|field| {
+                            dict.get(&DictionaryKey::String(field.name.clone()))
+                                .cloned()
+                        })
+                        .collect(),
+                    _ => None,
+                };
+                if let Some(field_values) = field_values {
+                    let mut bindings = HashMap::new();
+                    for (field_pattern, field_value) in fields.iter().zip(field_values) {
+                        if let Some(sub_pattern) = &field_pattern.pattern {
+                            if let Some(sub_bindings) =
+                                self.match_pattern(sub_pattern, &field_value)?
+                            {
+                                bindings.extend(sub_bindings);
+                            } else {
+                                return Ok(None);
+                            }
+                        } else {
+                            bindings.insert(field_pattern.name.clone(), field_value);
+                        }
+                    }
+
+                    Ok(Some(bindings))
+                } else {
+                    Ok(None)
+                }
             }
-            "ArrayMin" => {
-                let array = self.array_arg(args, 0, name)?;
-                let numbers = self.values_to_numbers(&array, name)?;
-                Some(Value::Number(ArrayBuiltins::min(&numbers)))
+        }
+    }
+
+    /// Define the variables bound by a destructuring declaration or parameter
+    fn destructure(
+        &mut self,
+        pattern: &Pattern,
+        value: &Value,
+        storage: VariableStorage,
+        is_constant: bool,
+    ) -> Result<(), InterpreterError> {
+        let Some(bindings) = self.match_pattern(pattern, value)? else {
+            return Err(InterpreterError::Runtime(localized(
+                &format!(
+                    "Cannot destructure {} with pattern {}",
+                    describe_operand(value),
+                    pattern
+                ),
+                &format!(
+                    "{} lässt sich nicht mit dem Muster {} zerlegen",
+                    describe_operand(value),
+                    pattern
+                ),
+            )));
+        };
+        for (name, value) in bindings {
+            self.define_variable(storage, name, value, is_constant);
+        }
+        Ok(())
+    }
+
+    fn execute_entrain_body(&mut self, body: &[AstNode]) -> Result<Value, InterpreterError> {
+        let mut last_value = Value::Null;
+
+        for node in body {
+            match node.unspanned() {
+                AstNode::ExpressionStatement(expr) => {
+                    last_value = self.evaluate_expression(expr)?;
+                }
+                _ if node.is_expression() => {
+                    last_value = self.evaluate_expression(node)?;
+                }
+                _ => match self.execute_statement(node) {
+                    Ok(()) => {
+                        last_value = Value::Null;
+                    }
+                    Err(InterpreterError::Return(value)) => {
+                        return Err(InterpreterError::Return(value));
+                    }
+                    Err(InterpreterError::BreakOutsideLoop) => {
+                        return Err(InterpreterError::BreakOutsideLoop);
+                    }
+                    Err(InterpreterError::ContinueOutsideLoop) => {
+                        return Err(InterpreterError::ContinueOutsideLoop);
+                    }
+                    Err(err) => return Err(err),
+                },
             }
-            "ArrayMax" => {
-                let array = self.array_arg(args, 0, name)?;
-                let numbers = self.values_to_numbers(&array, name)?;
-                Some(Value::Number(ArrayBuiltins::max(&numbers)))
+        }
+
+        Ok(last_value)
+    }
+
+    fn evaluate_binary_op(
+        &mut self,
+        left: &Value,
+        op: &str,
+        right: &Value,
+    ) -> Result<Value, InterpreterError> {
+        if let Some(result) = self.call_operator_method(left, op, right)? {
+            return Ok(result);
+        }
+
+        let normalized = op.to_ascii_lowercase();
+
+        // Arithmetic on two integers stays exact
+        if let (Value::Integer(a), Value::Integer(b)) = (left, right)
+            && let Some(result) = integer_arithmetic(&normalized, *a, *b)
+        {
+            return result;
+        }
+
+        match normalized.as_str() {
+            "+" => {
+                // If either operand is a string, perform string concatenation
+                match (left, right) {
+                    (Value::String(s1), Value::String(s2)) => {
+                        Ok(Value::String(format!("{}{}", s1, s2)))
+                    }
+                    (Value::String(s), _) => Ok(Value::String(format!("{}{}", s, right))),
+                    (_, Value::String(s)) => Ok(Value::String(format!("{}{}", left, s))),
+                    _ => {
+                        // Both are numeric, perform addition
+                        Ok(Value::Number(left.to_number()? + right.to_number()?))
+                    }
+                }
             }
-            "ArraySort" => {
-                let array = self.array_arg(args, 0, name)?;
-                let numbers = self.values_to_numbers(&array, name)?;
-                let sorted = ArrayBuiltins::sort(&numbers)
-                    .into_iter()
-                    .map(Value::Number)
-                    .collect();
-                Some(Value::Array(sorted))
+            "-" => Ok(Value::Number(left.to_number()? - right.to_number()?)),
+            "*" => Ok(Value::Number(left.to_number()? * right.to_number()?)),
+            "/" => Ok(Value::Number(left.to_number()? / right.to_number()?)),
+            "%" => Ok(Value::Number(left.to_number()? % right.to_number()?)),
+            "==" | "youarefeelingverysleepy" => Ok(Value::Boolean(self.values_equal(left, right)?)),
+            "!=" | "youcannotresist" | "notsodeep" => {
+                Ok(Value::Boolean(!self.values_equal(left, right)?))
             }
-            "ArrayFirst" => {
-                let array = self.array_arg(args, 0, name)?;
-                Some(ArrayBuiltins::first(&array).unwrap_or(Value::Null))
+            "&" | "|" | "^" | "<<" | ">>" => bitwise_operation(&normalized, left, right),
+            ">" | "lookatthewatch" => Ok(Value::Boolean(
+                numeric_ordering(left, right)? == Some(Ordering::Greater),
+            )),
+            "<" | "fallundermyspell" => Ok(Value::Boolean(
+                numeric_ordering(left, right)? == Some(Ordering::Less),
+            )),
+            ">=" | "deeplygreater" | "youreyesaregettingheavy" => Ok(Value::Boolean(matches!(
+                numeric_ordering(left, right)?,
+                Some(Ordering::Greater | Ordering::Equal)
+            ))),
+            "<=" | "deeplyless" | "goingdeeper" => Ok(Value::Boolean(matches!(
+                numeric_ordering(left, right)?,
+                Some(Ordering::Less | Ordering::Equal)
+            ))),
+            "&&" | "undermycontrol" => Ok(Value::Boolean(left.is_truthy() && right.is_truthy())),
+            "||" | "resistanceisfutile" => {
+                Ok(Value::Boolean(left.is_truthy() || right.is_truthy()))
             }
-            "ArrayLast" => {
-                let array = self.array_arg(args, 0, name)?;
-                Some(ArrayBuiltins::last(&array).unwrap_or(Value::Null))
+            _ => Err(InterpreterError::Runtime(format!(
+                "Unknown binary operator: {}",
+                op
+            ))),
+        }
+    }
+
+    fn values_equal(&mut self, left: &Value, right: &Value) -> Result<bool, InterpreterError> {
+        if let Some(result) = self.call_operator_method(left, "==", right)? {
+            return Ok(result.is_truthy());
+        }
+
+        Ok(match (left, right) {
+            (Value::Number(a), Value::Number(b)) => (a - b).abs() < f64::EPSILON,
+            (Value::Integer(a), Value::Integer(b)) => a == b,
+            (Value::Integer(i), Value::Number(n)) | (Value::Number(n), Value::Integer(i)) => {
+                exact_integer(*n) == Some(*i)
             }
-            "ArrayTake" => {
-                let array = self.array_arg(args, 0, name)?;
-                let count = self.usize_arg(args, 1, name)?;
-                Some(Value::Array(ArrayBuiltins::take(&array, count)))
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::Boolean(a), Value::Boolean(b)) => a == b,
+            (Value::Null, Value::Null) => true,
+            // Variants compare by enum, variant and payload
+            (Value::Variant(a), Value::Variant(b)) => a == b,
+            _ => false,
+        })
+    }
+
+    /// Operator method for `operator` declared by the session or tranceify type of `left`
+    fn operator_method(
+        &self,
+        left: &Value,
+        operator: &str,
+    ) -> Result<Option<FunctionValue>, InterpreterError> {
+        match left {
+            Value::Instance(instance) => {
+                self.session_method(instance, &operator_method_name(operator))
             }
-            "ArraySkip" => {
-                let array = self.array_arg(args, 0, name)?;
-                let count = self.usize_arg(args, 1, name)?;
-                Some(Value::Array(ArrayBuiltins::skip(&array, count)))
+            Value::Record(record) => Ok(self
+                .record_operators
+                .get(&record.type_name)
+                .and_then(|operators| operators.get(operator))
+                .cloned()),
+            _ => Ok(None),
+        }
+    }
+
+    /// Instance method `name` bound to `instance`; `None` if the session has none
+    fn session_method(
+        &self,
+        instance: &Rc<RefCell<SessionInstance>>,
+        name: &str,
+    ) -> Result<Option<FunctionValue>, InterpreterError> {
+        let definition = instance.borrow().definition();
+        let Some(method) = definition.get_method_definition(name) else {
+            return Ok(None);
+        };
+        self.ensure_visibility(method.visibility, &method.owner, "method", name)?;
+        Ok(Some(FunctionValue::new_session_member(
+            method,
+            Some(Rc::clone(instance)),
+        )))
+    }
+
+    /// Apply an operator overloaded by the left operand.
+    ///
+    /// Returns `None` if the left operand does not overload `op`; `!=` falls
+    /// back to a negated `==` overload.
+    fn call_operator_method(
+        &mut self,
+        left: &Value,
+        op: &str,
+        right: &Value,
+    ) -> Result<Option<Value>, InterpreterError> {
+        if !matches!(left, Value::Instance(_) | Value::Record(_)) {
+            return Ok(None);
+        }
+        let Some(operator) = overloadable_operator(op) else {
+            return Ok(None);
+        };
+
+        // Record operators receive `this` as their first argument
+        let arguments = match left {
+            Value::Record(_) => vec![left.clone(), right.clone()],
+            _ => vec![right.clone()],
+        };
+        if let Some(method) = self.operator_method(left, operator)? {
+            return self.call_function(&method, &arguments, &[]).map(Some);
+        }
+        if operator == "!="
+            && let Some(method) = self.operator_method(left, "==")?
+        {
+            let equal = self.call_function(&method, &arguments, &[])?;
+            return Ok(Some(Value::Boolean(!equal.is_truthy())));
+        }
+        Ok(None)
+    }
+
+    /// Evaluate array elements or call arguments, expanding `...array` spreads in place
+    fn evaluate_spread_list(&mut self, items: &[AstNode]) -> Result<Vec<Value>, InterpreterError> {
+        let mut values = Vec::with_capacity(items.len());
+        for item in items {
+            match item.unspanned() {
+                AstNode::SpreadElement(inner) => match self.evaluate_expression(inner)? {
+                    Value::Array(elements) => values.extend(elements.borrow().iter().cloned()),
+                    other => {
+                        return Err(InterpreterError::TypeError(localized(
+                            &format!("Cannot spread non-array value {}", other),
+                            &format!("Nicht-Array-Wert {} kann nicht gespreizt werden", other),
+                        )));
+                    }
+                },
+                _ => values.push(self.evaluate_expression(item)?),
             }
-            "ArraySlice" => {
-                let array = self.array_arg(args, 0, name)?;
-                let start = self.usize_arg(args, 1, name)?;
-                let end = self.usize_arg(args, 2, name)?;
-                Some(Value::Array(ArrayBuiltins::slice(&array, start, end)))
+        }
+        Ok(values)
+    }
+
+    fn evaluate_call(
+        &mut self,
+        callee: &AstNode,
+        arguments: &[AstNode],
+    ) -> Result<Value, InterpreterError> {
+        // The parser only allows named arguments after all positional ones
+        let split = arguments
+            .iter()
+            .position(|arg| matches!(arg.unspanned(), AstNode::NamedArgument { .. }))
+            .unwrap_or(arguments.len());
+        let args = self.evaluate_spread_list(&arguments[..split])?;
+        let mut named = Vec::new();
+        for arg in &arguments[split..] {
+            if let AstNode::NamedArgument { name, value } = arg.unspanned() {
+                named.push((name.clone(), self.evaluate_expression(value)?));
             }
-            "ArrayJoin" => {
-                let array = self.array_arg(args, 0, name)?;
-                let separator = self.string_arg(args, 1, name)?;
-                Some(Value::String(ArrayBuiltins::join(&array, &separator)))
+        }
+
+        if let AstNode::Identifier(name) = callee {
+            if name == "super" {
+                return self.call_super_constructor(&args, &named);
             }
-            "ArrayCount" => {
-                let array = self.array_arg(args, 0, name)?;
-                let target = self.arg(args, 1, name)?.clone();
-                Some(Value::Number(ArrayBuiltins::count(&array, &target) as f64))
+            if !named.is_empty() && is_builtin_function(name) {
+                return Err(InterpreterError::Runtime(localized(
+                    &format!(
+                        "Builtin function '{}' does not accept named arguments",
+                        name
+                    ),
+                    &format!(
+                        "Eingebaute Funktion '{}' akzeptiert keine benannten Argumente",
+                        name
+                    ),
+                )));
             }
-            "ArrayDistinct" => {
-                let array = self.array_arg(args, 0, name)?;
-                Some(Value::Array(ArrayBuiltins::distinct(&array)))
+            if named.is_empty()
+                && let Some(result) = self.call_builtin(name, &args)?
+            {
+                return Ok(result);
             }
-            _ => None,
+
+            let callee_value = self.get_variable(name)?;
+            return self.invoke_callable_with(&callee_value, &args, &named);
+        }
+
+        // `State.Running(3)` constructs a variant instead of calling a member
+        if let AstNode::MemberExpression { object, property } = callee
+            && !matches!(object.unspanned(), AstNode::Identifier(name) if name == "super")
+        {
+            let owner = self.evaluate_expression(object)?;
+            if let Value::Enum(definition) = &owner {
+                if !named.is_empty() {
+                    return Err(InterpreterError::Runtime(localized(
+                        "Variant payloads cannot be passed by name",
+                        "Varianten-Nutzwerte können nicht benannt übergeben werden",
+                    )));
+                }
+                return definition.construct(property, args);
+            }
+            if let Value::Iterator(iterator) = &owner {
+                return self.call_iterator_method(iterator, property, &args);
+            }
+            let callee_value = self.resolve_member_value(owner, property)?;
+            return self.invoke_callable_with(&callee_value, &args, &named);
+        }
+
+        let callee_value = self.evaluate_expression(callee)?;
+        self.invoke_callable_with(&callee_value, &args, &named)
+    }
+
+    fn invoke_callable(
+        &mut self,
+        callee: &Value,
+        args: &[Value],
+    ) -> Result<Value, InterpreterError> {
+        self.invoke_callable_with(callee, args, &[])
+    }
+
+    /// Invoke a callable with positional and named arguments
+    fn invoke_callable_with(
+        &mut self,
+        callee: &Value,
+        args: &[Value],
+        named: &[(String, Value)],
+    ) -> Result<Value, InterpreterError> {
+        match callee {
+            Value::Function(func) if func.is_async => self.schedule_async_call(func, args, named),
+            Value::Function(func) => self.call_function(func, args, named),
+            Value::Session(session) => self.instantiate_session(session.clone(), args, named),
+            Value::Null => Err(InterpreterError::Runtime(localized(
+                "Cannot call null value",
+                "Null-Wert kann nicht aufgerufen werden",
+            ))),
+            _ => Err(InterpreterError::Runtime(localized(
+                "Value is not callable",
+                "Wert ist nicht aufrufbar",
+            ))),
+        }
+    }
+
+    fn call_function(
+        &mut self,
+        function: &FunctionValue,
+        args: &[Value],
+        named: &[(String, Value)],
+    ) -> Result<Value, InterpreterError> {
+        if function.is_generator {
+            return self.start_generator(function, args, named);
+        }
+
+        let session_name = function.session_name().map(|name| name.to_string());
+        if session_name.is_some() {
+            self.execution_context.push(ExecutionContextFrame {
+                session_name: session_name.clone(),
+            });
+        }
+
+        // Closures run on top of the scope chain they captured instead of the caller's
+        let caller_scopes = function
+            .captured_scopes
+            .as_ref()
+            .map(|scopes| std::mem::replace(&mut self.locals, scopes.as_ref().clone()));
+
+        self.push_scope();
+
+        if let Some(instance) = function.this_binding() {
+            self.define_variable(
+                VariableStorage::Local,
+                "this".to_string(),
+                Value::Instance(instance),
+                true,
+            );
+        }
+
+        let result = (|| {
+            self.bind_parameters(function, args, named)?;
+            for stmt in &function.body {
+                self.execute_statement(stmt)?;
+            }
+            Ok(Value::Null)
+        })();
+
+        self.pop_scope();
+
+        if let Some(scopes) = caller_scopes {
+            self.locals = scopes;
+        }
+
+        if session_name.is_some() {
+            self.execution_context.pop();
+        }
+
+        match result {
+            Err(InterpreterError::Return(val)) => Ok(val),
+            Err(e) => Err(e),
+            Ok(value) => Ok(value),
+        }
+    }
+
+    /// Queue a `mesmerize` call and return its pending promise.
+    ///
+    /// Arguments are bound right away; the body only starts running once the
+    /// program awaits a pending promise or ends.
+    fn schedule_async_call(
+        &mut self,
+        function: &FunctionValue,
+        args: &[Value],
+        named: &[(String, Value)],
+    ) -> Result<Value, InterpreterError> {
+        let mut promise = Promise::pending();
+        if function.is_generator {
+            promise.settle(self.start_generator(function, args, named))?;
+            return Ok(Value::Promise(Rc::new(RefCell::new(promise))));
+        }
+        let promise = Rc::new(RefCell::new(promise));
+        match self.suspended_body(function, args, named, SuspendPoint::Await) {
+            Ok(body) => self.async_tasks.push_back(AsyncTask {
+                body,
+                promise: Rc::clone(&promise),
+            }),
+            Err(error) => promise.borrow_mut().settle(Err(error))?,
+        }
+        Ok(Value::Promise(promise))
+    }
+
+    /// Wait until `promise` settles, running `mesmerize` calls and runtime
+    /// jobs meanwhile
+    fn await_promise(&mut self, promise: &Rc<RefCell<Promise>>) -> Result<Value, InterpreterError> {
+        self.run_async_tasks(Some(promise))?;
+        let outcome = promise.borrow().outcome();
+        outcome.expect("scheduler returns once the awaited promise settled")
+    }
+
+    /// Run `mesmerize` calls until `until` settles, or until every call has
+    /// finished when `until` is `None`.
+    ///
+    /// Suspended calls whose promise settled resume first, then queued calls
+    /// start in order. When every call waits, the scheduler blocks until the
+    /// first of the awaited runtime jobs finishes.
+    fn run_async_tasks(
+        &mut self,
+        until: Option<&Rc<RefCell<Promise>>>,
+    ) -> Result<(), InterpreterError> {
+        loop {
+            match until {
+                Some(promise) if !promise.borrow().is_pending() => return Ok(()),
+                None if self.async_tasks.is_empty() && self.suspended_tasks.is_empty() => {
+                    return Ok(());
+                }
+                _ => {}
+            }
+
+            if let Some(index) = self
+                .suspended_tasks
+                .iter()
+                .position(|task| !task.waiting_on.borrow().is_pending())
+            {
+                let suspended = self.suspended_tasks.remove(index);
+                self.resume_task(suspended.task)?;
+                continue;
+            }
+
+            if let Some(task) = self.async_tasks.pop_front() {
+                self.resume_task(task)?;
+                continue;
+            }
+
+            if !self.settle_first_job(until)? {
+                return Err(InterpreterError::Runtime(localized(
+                    "Awaited promise can never settle: its mesmerize suggestions wait on each other",
+                    "Erwartetes Promise kann nie erfüllt werden: seine mesmerize-Suggestionen warten aufeinander",
+                )));
+            }
+        }
+    }
+
+    /// Run a `mesmerize` call until it awaits a pending promise or finishes
+    /// and settles its promise
+    fn resume_task(&mut self, mut task: AsyncTask) -> Result<(), InterpreterError> {
+        let previous_span = self.current_span;
+        let outcome = match self.run_suspended_body(&mut task.body) {
+            // Only promises suspend a `mesmerize` body
+            Ok(Some(Value::Promise(waiting_on))) => {
+                self.current_span = previous_span;
+                self.suspended_tasks
+                    .push(SuspendedTask { task, waiting_on });
+                return Ok(());
+            }
+            Ok(_) => Ok(Value::Null),
+            Err(InterpreterError::Return(value)) => Ok(value),
+            Err(error) => Err(error),
+        };
+        task.promise.borrow_mut().settle(outcome)?;
+        self.current_span = previous_span;
+        Ok(())
+    }
+
+    /// Block until the first runtime job that `until` or a suspended task
+    /// waits on finishes, and settle its promise; `false` if there is none
+    fn settle_first_job(
+        &mut self,
+        until: Option<&Rc<RefCell<Promise>>>,
+    ) -> Result<bool, InterpreterError> {
+        let mut waiting: Vec<Rc<RefCell<Promise>>> = Vec::new();
+        for promise in until
+            .into_iter()
+            .chain(self.suspended_tasks.iter().map(|task| &task.waiting_on))
+        {
+            if promise.borrow().job.is_some()
+                && !waiting.iter().any(|known| Rc::ptr_eq(known, promise))
+            {
+                waiting.push(Rc::clone(promise));
+            }
+        }
+        let Some(runtime) = self.async_runtime.clone() else {
+            return Ok(false);
+        };
+        if waiting.is_empty() {
+            return Ok(false);
+        }
+
+        let (result, index) = {
+            let mut promises: Vec<_> = waiting.iter().map(|promise| promise.borrow_mut()).collect();
+            let jobs = promises
+                .iter_mut()
+                .filter_map(|promise| promise.job.as_mut());
+            let (result, index, _) = runtime.block_on(futures::future::select_all(jobs));
+            promises[index].job = None;
+            (result, index)
         };
+        let outcome = match result {
+            Ok(Ok(result)) => Ok(task_result_to_value(result)),
+            Ok(Err(message)) => Err(InterpreterError::Runtime(message)),
+            Err(join_error) => Err(InterpreterError::Runtime(join_error.to_string())),
+        };
+        waiting[index].borrow_mut().settle(outcome)?;
+        Ok(true)
+    }
+
+    /// Drop queued and suspended `mesmerize` calls
+    fn cancel_async_tasks(&mut self) {
+        self.async_tasks.clear();
+        self.suspended_tasks.clear();
+    }
+
+    /// Start `future` as a job on the async runtime and return its promise
+    fn spawn_async_job<F>(&mut self, future: F) -> Result<Value, InterpreterError>
+    where
+        F: std::future::Future<Output = Result<TaskResult, String>> + Send + 'static,
+    {
+        self.enable_async_runtime()?;
+        let runtime: &AsyncRuntime = self
+            .async_runtime
+            .as_deref()
+            .expect("async runtime was just enabled");
+        let job = runtime.spawn_joinable(future);
+        Ok(Value::Promise(Rc::new(RefCell::new(Promise::from_job(
+            job,
+        )))))
+    }
+
+    /// Bind call arguments to the parameters of `function` in the current scope.
+    ///
+    /// Positional arguments fill parameters in order, a rest parameter collects
+    /// the remaining ones, named arguments fill parameters by name and omitted
+    /// parameters take their default value (evaluated after the earlier
+    /// parameters are bound, so defaults may refer to them).
+    fn bind_parameters(
+        &mut self,
+        function: &FunctionValue,
+        args: &[Value],
+        named: &[(String, Value)],
+    ) -> Result<(), InterpreterError> {
+        let parameters = &function.parameters;
+        let fixed_count = parameters.iter().filter(|param| !param.is_rest).count();
+        let has_rest = parameters.iter().any(|param| param.is_rest);
+        let required = parameters
+            .iter()
+            .filter(|param| !param.is_optional())
+            .count();
+
+        if (args.len() > fixed_count && !has_rest) || (named.is_empty() && args.len() < required) {
+            let (expected_en, expected_de) = if has_rest {
+                (
+                    format!("at least {}", required),
+                    format!("mindestens {}", required),
+                )
+            } else if required == fixed_count {
+                (fixed_count.to_string(), fixed_count.to_string())
+            } else {
+                (
+                    format!("{} to {}", required, fixed_count),
+                    format!("{} bis {}", required, fixed_count),
+                )
+            };
+            return Err(InterpreterError::Runtime(localized(
+                &format!(
+                    "'{}' expects {} arguments, received {}",
+                    function.name,
+                    expected_en,
+                    args.len()
+                ),
+                &format!(
+                    "'{}' erwartet {} Argumente, erhalten {}",
+                    function.name,
+                    expected_de,
+                    args.len()
+                ),
+            )));
+        }
+
+        for (name, _) in named {
+            let position = parameters
+                .iter()
+                .position(|param| !param.is_rest && &param.name == name);
+            match position {
+                None => {
+                    return Err(InterpreterError::Runtime(localized(
+                        &format!("'{}' has no parameter named '{}'", function.name, name),
+                        &format!("'{}' hat keinen Parameter namens '{}'", function.name, name),
+                    )));
+                }
+                Some(index)
+                    if index < args.len()
+                        || named.iter().filter(|(other, _)| other == name).count() > 1 =>
+                {
+                    return Err(InterpreterError::Runtime(localized(
+                        &format!("Argument '{}' was passed more than once", name),
+                        &format!("Argument '{}' wurde mehrfach übergeben", name),
+                    )));
+                }
+                Some(_) => {}
+            }
+        }
+
+        for (index, param) in parameters.iter().enumerate() {
+            let value = if param.is_rest {
+                Value::array(args.get(index..).unwrap_or_default().to_vec())
+            } else if let Some(arg) = args.get(index) {
+                arg.clone()
+            } else if let Some((_, value)) = named.iter().find(|(name, _)| name == &param.name) {
+                value.clone()
+            } else if let Some(default_value) = &param.default_value {
+                self.evaluate_expression(default_value)?
+            } else {
+                return Err(InterpreterError::Runtime(localized(
+                    &format!(
+                        "'{}' is missing an argument for parameter '{}'",
+                        function.name, param.name
+                    ),
+                    &format!(
+                        "'{}' fehlt ein Argument für Parameter '{}'",
+                        function.name, param.name
+                    ),
+                )));
+            };
+            match &param.pattern {
+                Some(pattern) => {
+                    self.destructure(pattern, &value, VariableStorage::Local, false)?
+                }
+                None => {
+                    self.define_variable(VariableStorage::Local, param.name.clone(), value, false)
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn build_session_definition(
+        &mut self,
+        name: &str,
+        parent: Option<&str>,
+        interfaces: &[String],
+        members: &[SessionMember],
+    ) -> Result<Rc<SessionDefinition>, InterpreterError> {
+        let parent = match parent {
+            // Type arguments of a generic parent only matter to the type checker
+            Some(parent) => {
+                let parent_name = parent.split_once('<').map_or(parent, |(name, _)| name);
+                match self.get_variable(parent_name) {
+                    Ok(Value::Session(session)) if session.name() != name => Some(session),
+                    _ => {
+                        return Err(InterpreterError::Runtime(localized(
+                            &format!(
+                                "Session '{}' can only extend a declared session, got '{}'",
+                                name, parent
+                            ),
+                            &format!(
+                                "Session '{}' kann nur eine deklarierte Session erweitern, nicht '{}'",
+                                name, parent
+                            ),
+                        )));
+                    }
+                }
+            }
+            None => None,
+        };
+        let mut definition = SessionDefinition::new(name.to_string(), parent);
+
+        for member in members {
+            match member {
+                SessionMember::Field(field) => {
+                    self.register_session_field(&mut definition, field)?
+                }
+                SessionMember::Method(method) => {
+                    self.register_session_method(&mut definition, method)?
+                }
+                SessionMember::Finale(body) => {
+                    self.register_session_finale(&mut definition, body)?
+                }
+            }
+        }
+
+        for interface in interfaces {
+            let Some(member_names) = self.interfaces.get(interface) else {
+                return Err(InterpreterError::Runtime(localized(
+                    &format!(
+                        "Session '{}' implements unknown interface '{}'",
+                        name, interface
+                    ),
+                    &format!(
+                        "Session '{}' implementiert unbekanntes Interface '{}'",
+                        name, interface
+                    ),
+                )));
+            };
+            let missing = member_names.iter().find(|member| {
+                definition.get_field_definition(member).is_none()
+                    && definition.get_method_definition(member).is_none()
+            });
+            if let Some(member) = missing {
+                return Err(InterpreterError::Runtime(localized(
+                    &format!(
+                        "Session '{}' does not implement '{}' of interface '{}'",
+                        name, member, interface
+                    ),
+                    &format!(
+                        "Session '{}' implementiert '{}' aus Interface '{}' nicht",
+                        name, member, interface
+                    ),
+                )));
+            }
+            definition.interfaces.push(interface.clone());
+        }
+
+        Ok(Rc::new(definition))
+    }
+
+    fn register_session_field(
+        &self,
+        definition: &mut SessionDefinition,
+        field: &SessionField,
+    ) -> Result<(), InterpreterError> {
+        let initializer = field.initializer.as_ref().map(|expr| (**expr).clone());
+        let field_def = SessionFieldDefinition {
+            name: field.name.clone(),
+            type_annotation: field.type_annotation.clone(),
+            visibility: field.visibility,
+            initializer: initializer.clone(),
+            owner: definition.name().to_string(),
+        };
+
+        if field.is_static {
+            definition.push_static_field(field_def, initializer)
+        } else {
+            definition.push_field(field_def)
+        }
+    }
+
+    fn register_session_method(
+        &self,
+        definition: &mut SessionDefinition,
+        method: &SessionMethod,
+    ) -> Result<(), InterpreterError> {
+        if method.is_constructor && method.is_static {
+            return Err(InterpreterError::Runtime(localized(
+                &format!(
+                    "Constructor in session '{}' cannot be static",
+                    definition.name()
+                ),
+                &format!(
+                    "Konstruktor in Session '{}' darf nicht statisch sein",
+                    definition.name()
+                ),
+            )));
+        }
+
+        let method_def = SessionMethodDefinition {
+            name: method.name.clone(),
+            parameters: method.parameters.clone(),
+            body: method.body.clone(),
+            visibility: method.visibility,
+            is_static: method.is_static,
+            is_constructor: method.is_constructor,
+            is_async: method.is_async,
+            module_scopes: self.module_scopes(),
+            owner: definition.name().to_string(),
+        };
+
+        definition.push_method(method_def)
+    }
+
+    fn register_session_finale(
+        &self,
+        definition: &mut SessionDefinition,
+        body: &[AstNode],
+    ) -> Result<(), InterpreterError> {
+        if definition.finale.is_some() {
+            return Err(InterpreterError::Runtime(localized(
+                &format!(
+                    "Multiple finale blocks declared in session '{}'",
+                    definition.name()
+                ),
+                &format!(
+                    "Mehrere finale-Blöcke in Session '{}' deklariert",
+                    definition.name()
+                ),
+            )));
+        }
+
+        definition.finale = Some(SessionMethodDefinition {
+            name: "finale".to_string(),
+            parameters: Vec::new(),
+            body: body.to_vec(),
+            visibility: SessionVisibility::Private,
+            is_static: false,
+            is_constructor: false,
+            is_async: false,
+            module_scopes: self.module_scopes(),
+            owner: definition.name().to_string(),
+        });
+        Ok(())
+    }
+
+    fn initialize_static_fields(
+        &mut self,
+        session: Rc<SessionDefinition>,
+    ) -> Result<(), InterpreterError> {
+        if session.static_field_order().is_empty() {
+            return Ok(());
+        }
+
+        self.execution_context.push(ExecutionContextFrame {
+            session_name: Some(session.name().to_string()),
+        });
+
+        let result = (|| {
+            for field_name in session.static_field_order().to_vec() {
+                if let Some(initializer) = session.take_static_field_initializer(&field_name) {
+                    let value = self.evaluate_expression(&initializer)?;
+                    session.set_static_field_value(&field_name, value)?;
+                }
+            }
+            Ok(())
+        })();
+
+        self.execution_context.pop();
+        result
+    }
+
+    fn instantiate_session(
+        &mut self,
+        session: Rc<SessionDefinition>,
+        args: &[Value],
+        named: &[(String, Value)],
+    ) -> Result<Value, InterpreterError> {
+        let instance = Rc::new(RefCell::new(SessionInstance::new(session.clone())));
+        self.initialize_instance_fields(instance.clone())?;
+
+        if let Some(constructor) = session.constructor() {
+            let function = FunctionValue::new_session_member(constructor, Some(instance.clone()));
+            self.call_function(&function, args, named)?;
+        } else if !args.is_empty() || !named.is_empty() {
+            return Err(InterpreterError::Runtime(localized(
+                &format!(
+                    "Session '{}' does not define a constructor but arguments were provided",
+                    session.name()
+                ),
+                &format!(
+                    "Session '{}' definiert keinen Konstruktor, dennoch wurden Argumente übergeben",
+                    session.name()
+                ),
+            )));
+        }
+
+        // Only fully constructed instances get a finale
+        if session.has_finale() {
+            instance.borrow_mut().finale_queue = Some(Rc::downgrade(&self.finale_queue));
+            if self.finalizable.len() == self.finalizable.capacity() {
+                self.finalizable
+                    .retain(|instance| instance.strong_count() > 0);
+            }
+            self.finalizable.push(Rc::downgrade(&instance));
+        }
+
+        Ok(Value::Instance(instance))
+    }
+
+    /// Run the `finale` blocks of an instance, most derived session first
+    fn run_finale(
+        &mut self,
+        instance: Rc<RefCell<SessionInstance>>,
+    ) -> Result<(), InterpreterError> {
+        let mut session = Some(instance.borrow().definition());
+        while let Some(definition) = session {
+            if let Some(finale) = &definition.finale {
+                let function = FunctionValue::new_session_member(finale, Some(instance.clone()));
+                self.call_function(&function, &[], &[])?;
+            }
+            session = definition.parent().cloned();
+        }
+        Ok(())
+    }
+
+    /// Run the finale of a live instance now unless it already ran
+    fn finalize_instance(
+        &mut self,
+        instance: Rc<RefCell<SessionInstance>>,
+    ) -> Result<(), InterpreterError> {
+        if instance.borrow_mut().finale_queue.take().is_none() {
+            return Ok(());
+        }
+        self.run_finale(instance)
+    }
+
+    /// Run the finale of every instance released since the last call
+    fn run_pending_finales(&mut self) -> Result<(), InterpreterError> {
+        loop {
+            let pending = std::mem::take(&mut *self.finale_queue.borrow_mut());
+            if pending.is_empty() {
+                return Ok(());
+            }
+            for instance in pending {
+                self.run_finale(Rc::new(RefCell::new(instance)))?;
+            }
+        }
+    }
+
+    /// Finalize instances still alive when the program ends, newest first
+    fn finalize_remaining_instances(&mut self) -> Result<(), InterpreterError> {
+        self.run_pending_finales()?;
+        let instances: Vec<_> = std::mem::take(&mut self.finalizable)
+            .iter()
+            .rev()
+            .filter_map(Weak::upgrade)
+            .collect();
+        for instance in instances {
+            self.finalize_instance(instance)?;
+            self.run_pending_finales()?;
+        }
+        Ok(())
+    }
+
+    fn initialize_instance_fields(
+        &mut self,
+        instance: Rc<RefCell<SessionInstance>>,
+    ) -> Result<(), InterpreterError> {
+        let definition = {
+            let borrow = instance.borrow();
+            borrow.definition()
+        };
+
+        if definition.field_order().is_empty() {
+            return Ok(());
+        }
+
+        self.push_scope();
+        self.define_variable(
+            VariableStorage::Local,
+            "this".to_string(),
+            Value::Instance(instance.clone()),
+            true,
+        );
+
+        let result = (|| {
+            for field_name in definition.field_order().to_vec() {
+                if let Some(field_def) = definition.get_field_definition(&field_name)
+                    && let Some(initializer) = &field_def.initializer
+                {
+                    // Initializers run in the context of the session declaring the field
+                    self.execution_context.push(ExecutionContextFrame {
+                        session_name: Some(field_def.owner.clone()),
+                    });
+                    let value = self.evaluate_expression(initializer);
+                    self.execution_context.pop();
+                    instance.borrow_mut().set_field(&field_name, value?);
+                }
+            }
+            Ok(())
+        })();
+
+        self.pop_scope();
+        result
+    }
+
+    fn resolve_member_value(
+        &mut self,
+        target: Value,
+        property: &str,
+    ) -> Result<Value, InterpreterError> {
+        match target {
+            Value::Instance(instance_rc) => {
+                let definition = {
+                    let borrow = instance_rc.borrow();
+                    borrow.definition()
+                };
+
+                if let Some(method_def) = definition.get_method_definition(property) {
+                    self.ensure_visibility(
+                        method_def.visibility,
+                        &method_def.owner,
+                        "method",
+                        property,
+                    )?;
+                    let function =
+                        FunctionValue::new_session_member(method_def, Some(instance_rc.clone()));
+                    return Ok(Value::Function(function));
+                }
+
+                if let Some(field_def) = definition.get_field_definition(property) {
+                    self.ensure_visibility(
+                        field_def.visibility,
+                        &field_def.owner,
+                        "field",
+                        property,
+                    )?;
+                    return Ok(instance_rc
+                        .borrow()
+                        .get_field(property)
+                        .unwrap_or(Value::Null));
+                }
+
+                if let Some(static_field) = definition.get_static_field_snapshot(property) {
+                    self.ensure_visibility(
+                        static_field.definition.visibility,
+                        &static_field.definition.owner,
+                        "field",
+                        property,
+                    )?;
+                    return Ok(static_field.value);
+                }
+
+                if let Some(static_method) = definition.get_static_method_definition(property) {
+                    self.ensure_visibility(
+                        static_method.visibility,
+                        &static_method.owner,
+                        "method",
+                        property,
+                    )?;
+                    let function = FunctionValue::new_session_member(static_method, None);
+                    return Ok(Value::Function(function));
+                }
+
+                Err(InterpreterError::Runtime(localized(
+                    &format!(
+                        "Session instance of '{}' has no member '{}'",
+                        definition.name(),
+                        property
+                    ),
+                    &format!(
+                        "Session-Instanz von '{}' besitzt kein Mitglied '{}'",
+                        definition.name(),
+                        property
+                    ),
+                )))
+            }
+            // Variants without payload are values; the others are built by calling them
+            Value::Enum(definition) => definition.construct(property, Vec::new()),
+            Value::Session(session_rc) => {
+                if let Some(static_field) = session_rc.get_static_field_snapshot(property) {
+                    self.ensure_visibility(
+                        static_field.definition.visibility,
+                        &static_field.definition.owner,
+                        "field",
+                        property,
+                    )?;
+                    return Ok(static_field.value);
+                }
+
+                if let Some(method_def) = session_rc.get_static_method_definition(property) {
+                    self.ensure_visibility(
+                        method_def.visibility,
+                        &method_def.owner,
+                        "method",
+                        property,
+                    )?;
+                    let function = FunctionValue::new_session_member(method_def, None);
+                    return Ok(Value::Function(function));
+                }
+
+                Err(InterpreterError::Runtime(localized(
+                    &format!(
+                        "Session '{}' has no static member '{}'",
+                        session_rc.name(),
+                        property
+                    ),
+                    &format!(
+                        "Session '{}' besitzt kein statisches Mitglied '{}'",
+                        session_rc.name(),
+                        property
+                    ),
+                )))
+            }
+            Value::Record(record) => {
+                // Access field from record
+                if let Some(field_value) = record.fields.get(property) {
+                    Ok(field_value.clone())
+                } else {
+                    Err(InterpreterError::Runtime(format!(
+                        "Record of type '{}' has no field '{}'",
+                        record.type_name, property
+                    )))
+                }
+            }
+            other => Err(InterpreterError::Runtime(localized(
+                &format!("Cannot access member '{}' on value '{}'", property, other),
+                &format!(
+                    "Mitglied '{}' kann auf Wert '{}' nicht zugegriffen werden",
+                    property, other
+                ),
+            ))),
+        }
+    }
+
+    fn evaluate_dictionary_literal(
+        &mut self,
+        entries: &[DictionaryEntry],
+    ) -> Result<Value, InterpreterError> {
+        let mut dict = Dictionary::with_capacity(entries.len());
+        for entry in entries {
+            let key = self.evaluate_expression(&entry.key)?;
+            let value = self.evaluate_expression(&entry.value)?;
+            dict.insert(DictionaryKey::from_value(&key)?, value);
+        }
+        Ok(Value::Dictionary(dict))
+    }
+
+    /// Store `value` at `container[index]` and return the updated container
+    fn assign_index_value(
+        &self,
+        container: Value,
+        index: &Value,
+        value: Value,
+    ) -> Result<Value, InterpreterError> {
+        match container {
+            Value::Array(arr) => {
+                let i = index.to_number()? as usize;
+                if let Some(slot) = arr.borrow_mut().get_mut(i) {
+                    *slot = value;
+                } else {
+                    return Err(InterpreterError::Runtime(format!(
+                        "Index {} out of bounds",
+                        i
+                    )));
+                }
+                Ok(Value::Array(arr))
+            }
+            Value::Dictionary(mut dict) => {
+                dict.insert(DictionaryKey::from_value(index)?, value);
+                Ok(Value::Dictionary(dict))
+            }
+            other => Err(InterpreterError::TypeError(localized(
+                &format!("Cannot assign by index into '{}'", other),
+                &format!("Indexzuweisung in '{}' nicht möglich", other),
+            ))),
+        }
+    }
+
+    /// Write an updated value back to an assignable expression
+    /// Element of an array or entry of a dictionary
+    fn index_value(&self, container: Value, index: &Value) -> Result<Value, InterpreterError> {
+        match container {
+            Value::Array(arr) => {
+                let i = index.to_number()? as usize;
+                arr.borrow()
+                    .get(i)
+                    .cloned()
+                    .ok_or_else(|| InterpreterError::Runtime(format!("Index {} out of bounds", i)))
+            }
+            Value::Dictionary(dict) => {
+                let key = DictionaryKey::from_value(index)?;
+                dict.get(&key).cloned().ok_or_else(|| {
+                    InterpreterError::Runtime(localized(
+                        &format!("Key '{}' not found in dictionary", key),
+                        &format!("Schlüssel '{}' nicht im Dictionary gefunden", key),
+                    ))
+                })
+            }
+            _ => Err(InterpreterError::TypeError(
+                "Cannot index non-array".to_string(),
+            )),
+        }
+    }
+
+    /// Resolve an assignment target, evaluating its object and index expressions once
+    fn evaluate_place(&mut self, target: &AstNode) -> Result<Place, InterpreterError> {
+        match target {
+            AstNode::Identifier(name) => Ok(Place::Variable(name.clone())),
+            AstNode::MemberExpression { object, property } => Ok(Place::Member {
+                owner: self.evaluate_expression(object)?,
+                property: property.clone(),
+            }),
+            AstNode::IndexExpression { object, index } => {
+                let container = Box::new(self.evaluate_place(object)?);
+                let index = self.evaluate_expression(index)?;
+                Ok(Place::Index { container, index })
+            }
+            _ => Err(InterpreterError::Runtime(localized(
+                "Invalid assignment target",
+                "Ungültiges Zuweisungsziel",
+            ))),
+        }
+    }
+
+    fn read_place(&mut self, place: &Place) -> Result<Value, InterpreterError> {
+        match place {
+            Place::Variable(name) => self.get_variable(name),
+            Place::Member { owner, property } => self.resolve_member_value(owner.clone(), property),
+            Place::Index { container, index } => {
+                let container = self.read_place(container)?;
+                self.index_value(container, index)
+            }
+        }
+    }
+
+    fn write_place(&mut self, place: &Place, value: Value) -> Result<(), InterpreterError> {
+        match place {
+            Place::Variable(name) => {
+                let scope = self.resolve_assignment_scope(name);
+                self.set_variable(name.clone(), value, scope)
+            }
+            Place::Member { owner, property } => {
+                self.assign_member_value(owner.clone(), property, value)
+            }
+            Place::Index { container, index } => {
+                let current = self.read_place(container)?;
+                let updated = self.assign_index_value(current, index, value)?;
+                self.write_place(container, updated)
+            }
+        }
+    }
+
+    fn store_value(&mut self, target: &AstNode, value: Value) -> Result<(), InterpreterError> {
+        match target {
+            AstNode::Identifier(name) => {
+                let scope = self.resolve_assignment_scope(name);
+                self.set_variable(name.clone(), value, scope)
+            }
+            AstNode::MemberExpression { object, property } => {
+                let owner = self.evaluate_expression(object)?;
+                self.assign_member_value(owner, property, value)
+            }
+            AstNode::IndexExpression { object, index } => {
+                let container = self.evaluate_expression(object)?;
+                let idx = self.evaluate_expression(index)?;
+                let updated = self.assign_index_value(container, &idx, value)?;
+                self.store_value(object, updated)
+            }
+            _ => Err(InterpreterError::Runtime(localized(
+                "Invalid assignment target",
+                "Ungültiges Zuweisungsziel",
+            ))),
+        }
+    }
+
+    fn assign_member_value(
+        &mut self,
+        target: Value,
+        property: &str,
+        value: Value,
+    ) -> Result<(), InterpreterError> {
+        match target {
+            Value::Instance(instance_rc) => {
+                let definition = {
+                    let borrow = instance_rc.borrow();
+                    borrow.definition()
+                };
+
+                if let Some(field_def) = definition.get_field_definition(property) {
+                    self.ensure_visibility(
+                        field_def.visibility,
+                        &field_def.owner,
+                        "field",
+                        property,
+                    )?;
+                    instance_rc.borrow_mut().set_field(property, value);
+                    return Ok(());
+                }
+
+                if definition.get_method_definition(property).is_some()
+                    || definition.get_static_method_definition(property).is_some()
+                {
+                    return Err(InterpreterError::Runtime(localized(
+                        &format!("Cannot assign to method '{}'", property),
+                        &format!("Zuweisung zur Methode '{}' nicht möglich", property),
+                    )));
+                }
+
+                if definition.get_static_field_snapshot(property).is_some() {
+                    return Err(InterpreterError::Runtime(localized(
+                        &format!(
+                            "Assign static field '{}' through session '{}', not an instance",
+                            property,
+                            definition.name()
+                        ),
+                        &format!(
+                            "Statisches Feld '{}' muss über die Session '{}' gesetzt werden",
+                            property,
+                            definition.name()
+                        ),
+                    )));
+                }
+
+                Err(InterpreterError::Runtime(localized(
+                    &format!(
+                        "Session instance of '{}' has no field '{}'",
+                        definition.name(),
+                        property
+                    ),
+                    &format!(
+                        "Session-Instanz von '{}' besitzt kein Feld '{}'",
+                        definition.name(),
+                        property
+                    ),
+                )))
+            }
+            Value::Session(session_rc) => {
+                if let Some(static_field) = session_rc.get_static_field_snapshot(property) {
+                    self.ensure_visibility(
+                        static_field.definition.visibility,
+                        &static_field.definition.owner,
+                        "field",
+                        property,
+                    )?;
+                    session_rc.set_static_field_value(property, value)?;
+                    return Ok(());
+                }
+
+                if session_rc.get_static_method_definition(property).is_some() {
+                    return Err(InterpreterError::Runtime(localized(
+                        &format!("Cannot assign to static method '{}'", property),
+                        &format!(
+                            "Zuweisung zu statischer Methode '{}' nicht möglich",
+                            property
+                        ),
+                    )));
+                }
+
+                Err(InterpreterError::Runtime(localized(
+                    &format!(
+                        "Session '{}' has no static field '{}'",
+                        session_rc.name(),
+                        property
+                    ),
+                    &format!(
+                        "Session '{}' besitzt kein statisches Feld '{}'",
+                        session_rc.name(),
+                        property
+                    ),
+                )))
+            }
+            _ => Err(InterpreterError::Runtime(localized(
+                "Assignment target is not a session member",
+                "Zuweisungsziel ist kein Session-Mitglied",
+            ))),
+        }
+    }
+
+    /// `this` and the parent of the session whose method is running (for `super`)
+    fn super_context(
+        &self,
+    ) -> Result<(Rc<RefCell<SessionInstance>>, Rc<SessionDefinition>), InterpreterError> {
+        let this = match self.get_variable("this") {
+            Ok(Value::Instance(instance)) => Some(instance),
+            _ => None,
+        };
+        let current = self
+            .execution_context
+            .iter()
+            .rev()
+            .find_map(|frame| frame.session_name.clone());
+
+        if let (Some(this), Some(current)) = (this, current) {
+            let mut definition = Some(this.borrow().definition());
+            while let Some(session) = definition {
+                if session.name() == current {
+                    if let Some(parent) = session.parent() {
+                        return Ok((this.clone(), parent.clone()));
+                    }
+                    break;
+                }
+                definition = session.parent().cloned();
+            }
+        }
+
+        Err(InterpreterError::Runtime(localized(
+            "'super' can only be used in methods of a session that extends another session",
+            "'super' ist nur in Methoden einer Session erlaubt, die eine andere Session erweitert",
+        )))
+    }
+
+    /// `super.method`: the parent's implementation bound to `this`
+    fn resolve_super_member(&mut self, property: &str) -> Result<Value, InterpreterError> {
+        let (this, parent) = self.super_context()?;
+        match parent.get_method_definition(property) {
+            Some(method_def) => {
+                self.ensure_visibility(
+                    method_def.visibility,
+                    &method_def.owner,
+                    "method",
+                    property,
+                )?;
+                Ok(Value::Function(FunctionValue::new_session_member(
+                    method_def,
+                    Some(this),
+                )))
+            }
+            None => Err(InterpreterError::Runtime(localized(
+                &format!("Session '{}' has no method '{}'", parent.name(), property),
+                &format!(
+                    "Session '{}' besitzt keine Methode '{}'",
+                    parent.name(),
+                    property
+                ),
+            ))),
+        }
+    }
+
+    /// `super(...)`: run the parent constructor on `this`
+    fn call_super_constructor(
+        &mut self,
+        args: &[Value],
+        named: &[(String, Value)],
+    ) -> Result<Value, InterpreterError> {
+        let (this, parent) = self.super_context()?;
+        match parent.constructor() {
+            Some(constructor) => {
+                let function = FunctionValue::new_session_member(constructor, Some(this));
+                self.call_function(&function, args, named)?;
+            }
+            None if !args.is_empty() || !named.is_empty() => {
+                return Err(InterpreterError::Runtime(localized(
+                    &format!(
+                        "Session '{}' does not define a constructor but arguments were provided",
+                        parent.name()
+                    ),
+                    &format!(
+                        "Session '{}' definiert keinen Konstruktor, dennoch wurden Argumente übergeben",
+                        parent.name()
+                    ),
+                )));
+            }
+            None => {}
+        }
+        Ok(Value::Null)
+    }
+
+    fn ensure_visibility(
+        &self,
+        visibility: SessionVisibility,
+        session_name: &str,
+        member_kind: &str,
+        member_name: &str,
+    ) -> Result<(), InterpreterError> {
+        if visibility == SessionVisibility::Private && !self.is_access_allowed(session_name) {
+            return Err(InterpreterError::Runtime(localized(
+                &format!(
+                    "Access denied to private {} '{}' of session '{}'",
+                    member_kind, member_name, session_name
+                ),
+                &format!(
+                    "Zugriff auf privates {} '{}' der Session '{}' verweigert",
+                    member_kind, member_name, session_name
+                ),
+            )));
+        }
+        Ok(())
+    }
+
+    fn is_access_allowed(&self, session_name: &str) -> bool {
+        self.execution_context
+            .iter()
+            .rev()
+            .find_map(|frame| frame.session_name.as_deref())
+            == Some(session_name)
+    }
+
+    fn call_builtin(
+        &mut self,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Option<Value>, InterpreterError> {
+        if let Some(result) = self.call_math_builtin(name, args)? {
+            return Ok(Some(result));
+        }
+
+        if let Some(result) = self.call_string_builtin(name, args)? {
+            return Ok(Some(result));
+        }
+
+        // Array builtins consume generators and session iterators like arrays
+        let iterable_args = iterable_arg_count(name).min(args.len());
+        if args[..iterable_args]
+            .iter()
+            .any(|arg| matches!(arg, Value::Iterator(_) | Value::Instance(_)))
+        {
+            // Only pull the values `ArrayFirst`/`ArrayTake` return, so endless generators work
+            let limit = match name {
+                "ArrayFirst" => Some(1),
+                "ArrayTake" => Some(self.usize_arg(args, 1, name)?),
+                _ => None,
+            };
+            let mut materialized = args.to_vec();
+            for arg in &mut materialized[..iterable_args] {
+                if matches!(arg, Value::Iterator(_) | Value::Instance(_)) {
+                    *arg = Value::array(self.collect_iterable(arg.clone(), limit)?);
+                }
+            }
+            return self.call_builtin(name, &materialized);
+        }
+
+        if let Some(result) = self.call_array_builtin(name, args)? {
+            return Ok(Some(result));
+        }
+
+        if let Some(result) = self.call_dictionary_builtin(name, args)? {
+            return Ok(Some(result));
+        }
+
+        if let Some(result) = self.call_core_builtin(name, args)? {
+            return Ok(Some(result));
+        }
+
+        if let Some(result) = self.call_file_builtin(name, args)? {
+            return Ok(Some(result));
+        }
+
+        if let Some(result) = self.call_hashing_builtin(name, args)? {
+            return Ok(Some(result));
+        }
+
+        if let Some(result) = self.call_statistics_builtin(name, args)? {
+            return Ok(Some(result));
+        }
+
+        if let Some(result) = self.call_system_builtin(name, args)? {
+            return Ok(Some(result));
+        }
+
+        if let Some(result) = self.call_time_builtin(name, args)? {
+            return Ok(Some(result));
+        }
+
+        if let Some(result) = self.call_validation_builtin(name, args)? {
+            return Ok(Some(result));
+        }
+
+        if let Some(result) = self.call_async_builtin(name, args)? {
+            return Ok(Some(result));
+        }
+
+        Ok(None)
+    }
+
+    /// Builtins returning promises settled by a job on the async runtime
+    fn call_async_builtin(
+        &mut self,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Option<Value>, InterpreterError> {
+        let result = match name {
+            "AsyncDelay" => {
+                let duration = std::time::Duration::from_millis(
+                    self.number_arg(args, 0, name)?.max(0.0) as u64,
+                );
+                Some(self.spawn_async_job(async move {
+                    crate::async_runtime::async_delay(duration).await;
+                    Ok(TaskResult::Null)
+                })?)
+            }
+            "AsyncReadFile" => {
+                let path = self.string_arg(args, 0, name)?;
+                Some(self.spawn_async_job(async move {
+                    tokio::fs::read_to_string(&path)
+                        .await
+                        .map(TaskResult::String)
+                        .map_err(|error| format!("Failed to read '{}': {}", path, error))
+                })?)
+            }
+            "AsyncWriteFile" => {
+                let path = self.string_arg(args, 0, name)?;
+                let content = self.string_arg(args, 1, name)?;
+                Some(self.spawn_async_job(async move {
+                    tokio::fs::write(&path, content)
+                        .await
+                        .map(|_| TaskResult::Null)
+                        .map_err(|error| format!("Failed to write '{}': {}", path, error))
+                })?)
+            }
+            _ => None,
+        };
+        Ok(result)
+    }
+
+    fn call_math_builtin(
+        &self,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Option<Value>, InterpreterError> {
+        let result = match name {
+            "Sin" => Some(Value::Number(MathBuiltins::sin(
+                self.number_arg(args, 0, name)?,
+            ))),
+            "Cos" => Some(Value::Number(MathBuiltins::cos(
+                self.number_arg(args, 0, name)?,
+            ))),
+            "Tan" => Some(Value::Number(MathBuiltins::tan(
+                self.number_arg(args, 0, name)?,
+            ))),
+            "Sqrt" => Some(Value::Number(MathBuiltins::sqrt(
+                self.number_arg(args, 0, name)?,
+            ))),
+            "Log" => Some(Value::Number(MathBuiltins::log(
+                self.number_arg(args, 0, name)?,
+            ))),
+            "Log10" => Some(Value::Number(MathBuiltins::log10(
+                self.number_arg(args, 0, name)?,
+            ))),
+            "Abs" => Some(Value::Number(MathBuiltins::abs(
+                self.number_arg(args, 0, name)?,
+            ))),
+            "Floor" => Some(Value::Number(MathBuiltins::floor(
+                self.number_arg(args, 0, name)?,
+            ))),
+            "Ceil" => Some(Value::Number(MathBuiltins::ceil(
+                self.number_arg(args, 0, name)?,
+            ))),
+            "Round" => Some(Value::Number(MathBuiltins::round(
+                self.number_arg(args, 0, name)?,
+            ))),
+            "Min" => Some(Value::Number(MathBuiltins::min(
+                self.number_arg(args, 0, name)?,
+                self.number_arg(args, 1, name)?,
+            ))),
+            "Max" => Some(Value::Number(MathBuiltins::max(
+                self.number_arg(args, 0, name)?,
+                self.number_arg(args, 1, name)?,
+            ))),
+            "Pow" => Some(Value::Number(MathBuiltins::pow(
+                self.number_arg(args, 0, name)?,
+                self.number_arg(args, 1, name)?,
+            ))),
+            "Factorial" => Some(Value::Number(MathBuiltins::factorial(
+                self.integer_arg(args, 0, name)?,
+            ) as f64)),
+            "Gcd" => Some(Value::Number(MathBuiltins::gcd(
+                self.integer_arg(args, 0, name)?,
+                self.integer_arg(args, 1, name)?,
+            ) as f64)),
+            "Lcm" => Some(Value::Number(MathBuiltins::lcm(
+                self.integer_arg(args, 0, name)?,
+                self.integer_arg(args, 1, name)?,
+            ) as f64)),
+            "IsPrime" => Some(Value::Boolean(MathBuiltins::is_prime(
+                self.integer_arg(args, 0, name)?,
+            ))),
+            "Fibonacci" => Some(Value::Number(MathBuiltins::fibonacci(
+                self.integer_arg(args, 0, name)?,
+            ) as f64)),
+            "Clamp" => Some(Value::Number(MathBuiltins::clamp(
+                self.number_arg(args, 0, name)?,
+                self.number_arg(args, 1, name)?,
+                self.number_arg(args, 2, name)?,
+            ))),
+            _ => None,
+        };
+
+        Ok(result)
+    }
+
+    fn call_string_builtin(
+        &self,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Option<Value>, InterpreterError> {
+        let result = match name {
+            "Length" => {
+                // Length works for both strings and arrays
+                if args.is_empty() {
+                    return Err(InterpreterError::Runtime(format!(
+                        "Function '{}' requires at least 1 argument",
+                        name
+                    )));
+                }
+                match &args[0] {
+                    Value::String(s) => Some(Value::Number(s.len() as f64)),
+                    Value::Array(arr) => Some(Value::Number(arr.borrow().len() as f64)),
+                    _ => {
+                        return Err(InterpreterError::TypeError(format!(
+                            "Function 'Length' expects string or array argument, got {}",
+                            args[0]
+                        )));
+                    }
+                }
+            }
+            "ToUpper" => Some(Value::String(StringBuiltins::to_upper(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "ToLower" => Some(Value::String(StringBuiltins::to_lower(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "Trim" => Some(Value::String(StringBuiltins::trim(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "IndexOf" => Some(Value::Number(StringBuiltins::index_of(
+                &self.string_arg(args, 0, name)?,
+                &self.string_arg(args, 1, name)?,
+            ) as f64)),
+            "Replace" => Some(Value::String(StringBuiltins::replace(
+                &self.string_arg(args, 0, name)?,
+                &self.string_arg(args, 1, name)?,
+                &self.string_arg(args, 2, name)?,
+            ))),
+            "Reverse" => Some(Value::String(StringBuiltins::reverse(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "Capitalize" => Some(Value::String(StringBuiltins::capitalize(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "StartsWith" => Some(Value::Boolean(StringBuiltins::starts_with(
+                &self.string_arg(args, 0, name)?,
+                &self.string_arg(args, 1, name)?,
+            ))),
+            "EndsWith" => Some(Value::Boolean(StringBuiltins::ends_with(
+                &self.string_arg(args, 0, name)?,
+                &self.string_arg(args, 1, name)?,
+            ))),
+            "Contains" => Some(Value::Boolean(StringBuiltins::contains(
+                &self.string_arg(args, 0, name)?,
+                &self.string_arg(args, 1, name)?,
+            ))),
+            "Split" => {
+                let items = StringBuiltins::split(
+                    &self.string_arg(args, 0, name)?,
+                    &self.string_arg(args, 1, name)?,
+                )
+                .into_iter()
+                .map(Value::String)
+                .collect();
+                Some(Value::array(items))
+            }
+            "Substring" => Some(Value::String(StringBuiltins::substring(
+                &self.string_arg(args, 0, name)?,
+                self.usize_arg(args, 1, name)?,
+                self.usize_arg(args, 2, name)?,
+            ))),
+            "Repeat" => Some(Value::String(StringBuiltins::repeat(
+                &self.string_arg(args, 0, name)?,
+                self.usize_arg(args, 1, name)?,
+            ))),
+            "PadLeft" => Some(Value::String(StringBuiltins::pad_left(
+                &self.string_arg(args, 0, name)?,
+                self.usize_arg(args, 1, name)?,
+                self.char_arg(args, 2, name)?,
+            ))),
+            "PadRight" => Some(Value::String(StringBuiltins::pad_right(
+                &self.string_arg(args, 0, name)?,
+                self.usize_arg(args, 1, name)?,
+                self.char_arg(args, 2, name)?,
+            ))),
+            "IsEmpty" => Some(Value::Boolean(StringBuiltins::is_empty(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "IsWhitespace" => Some(Value::Boolean(StringBuiltins::is_whitespace(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            _ => None,
+        };
+
+        Ok(result)
+    }
+
+    fn call_array_builtin(
+        &self,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Option<Value>, InterpreterError> {
+        let result = match name {
+            "ArrayLength" => {
+                let array = self.array_arg(args, 0, name)?;
+                Some(Value::Number(ArrayBuiltins::length(&array) as f64))
+            }
+            "ArrayIsEmpty" => {
+                let array = self.array_arg(args, 0, name)?;
+                Some(Value::Boolean(ArrayBuiltins::is_empty(&array)))
+            }
+            "ArrayGet" => {
+                let array = self.array_arg(args, 0, name)?;
+                let index = self.usize_arg(args, 1, name)?;
+                let value = ArrayBuiltins::get(&array, index).unwrap_or(Value::Null);
+                Some(value)
+            }
+            "ArrayIndexOf" => {
+                let array = self.array_arg(args, 0, name)?;
+                let target = self.arg(args, 1, name)?.clone();
+                Some(Value::Number(
+                    ArrayBuiltins::index_of(&array, &target) as f64
+                ))
+            }
+            "ArrayContains" => {
+                let array = self.array_arg(args, 0, name)?;
+                let target = self.arg(args, 1, name)?.clone();
+                Some(Value::Boolean(ArrayBuiltins::contains(&array, &target)))
+            }
+            "ArrayReverse" => {
+                let array = self.array_arg(args, 0, name)?;
+                Some(Value::array(ArrayBuiltins::reverse(&array)))
+            }
+            "ArraySum" => {
+                let array = self.array_arg(args, 0, name)?;
+                let numbers = self.values_to_numbers(&array, name)?;
+                Some(Value::Number(ArrayBuiltins::sum(&numbers)))
+            }
+            "ArrayAverage" => {
+                let array = self.array_arg(args, 0, name)?;
+                let numbers = self.values_to_numbers(&array, name)?;
+                Some(Value::Number(ArrayBuiltins::average(&numbers)))
+            }
+            "ArrayMin" => {
+                let array = self.array_arg(args, 0, name)?;
+                let numbers = self.values_to_numbers(&array, name)?;
+                Some(Value::Number(ArrayBuiltins::min(&numbers)))
+            }
+            "ArrayMax" => {
+                let array = self.array_arg(args, 0, name)?;
+                let numbers = self.values_to_numbers(&array, name)?;
+                Some(Value::Number(ArrayBuiltins::max(&numbers)))
+            }
+            "ArraySort" => {
+                let array = self.array_arg(args, 0, name)?;
+                let numbers = self.values_to_numbers(&array, name)?;
+                let sorted = ArrayBuiltins::sort(&numbers)
+                    .into_iter()
+                    .map(Value::Number)
+                    .collect();
+                Some(Value::array(sorted))
+            }
+            "ArrayFirst" => {
+                let array = self.array_arg(args, 0, name)?;
+                Some(ArrayBuiltins::first(&array).unwrap_or(Value::Null))
+            }
+            "ArrayLast" => {
+                let array = self.array_arg(args, 0, name)?;
+                Some(ArrayBuiltins::last(&array).unwrap_or(Value::Null))
+            }
+            "ArrayTake" => {
+                let array = self.array_arg(args, 0, name)?;
+                let count = self.usize_arg(args, 1, name)?;
+                Some(Value::array(ArrayBuiltins::take(&array, count)))
+            }
+            "ArraySkip" => {
+                let array = self.array_arg(args, 0, name)?;
+                let count = self.usize_arg(args, 1, name)?;
+                Some(Value::array(ArrayBuiltins::skip(&array, count)))
+            }
+            "ArraySlice" => {
+                let array = self.array_arg(args, 0, name)?;
+                let start = self.usize_arg(args, 1, name)?;
+                let end = self.usize_arg(args, 2, name)?;
+                Some(Value::array(ArrayBuiltins::slice(&array, start, end)))
+            }
+            "ArrayJoin" => {
+                let array = self.array_arg(args, 0, name)?;
+                let separator = self.string_arg(args, 1, name)?;
+                Some(Value::String(ArrayBuiltins::join(&array, &separator)))
+            }
+            "ArrayCount" => {
+                let array = self.array_arg(args, 0, name)?;
+                let target = self.arg(args, 1, name)?.clone();
+                Some(Value::Number(ArrayBuiltins::count(&array, &target) as f64))
+            }
+            "ArrayDistinct" => {
+                let array = self.array_arg(args, 0, name)?;
+                Some(Value::array(ArrayBuiltins::distinct(&array)))
+            }
+            "ArrayPush" => {
+                let array = self.shared_array_arg(args, 0, name)?;
+                let value = self.arg(args, 1, name)?.clone();
+                let mut items = array.borrow_mut();
+                items.push(value);
+                Some(Value::Number(items.len() as f64))
+            }
+            "ArrayPop" => {
+                let array = self.shared_array_arg(args, 0, name)?;
+                let value = array.borrow_mut().pop();
+                Some(value.unwrap_or(Value::Null))
+            }
+            "ArrayInsert" => {
+                let array = self.shared_array_arg(args, 0, name)?;
+                let index = self.usize_arg(args, 1, name)?;
+                let value = self.arg(args, 2, name)?.clone();
+                let mut items = array.borrow_mut();
+                if index > items.len() {
+                    return Err(InterpreterError::Runtime(format!(
+                        "Index {} out of bounds",
+                        index
+                    )));
+                }
+                items.insert(index, value);
+                Some(Value::Number(items.len() as f64))
+            }
+            "ArrayRemoveAt" => {
+                let array = self.shared_array_arg(args, 0, name)?;
+                let index = self.usize_arg(args, 1, name)?;
+                let mut items = array.borrow_mut();
+                if index >= items.len() {
+                    return Err(InterpreterError::Runtime(format!(
+                        "Index {} out of bounds",
+                        index
+                    )));
+                }
+                Some(items.remove(index))
+            }
+            "ArraySortInPlace" => {
+                let array = self.shared_array_arg(args, 0, name)?;
+                let sorted = {
+                    let items = array.borrow();
+                    if items.iter().all(|item| matches!(item, Value::Number(_))) {
+                        let numbers = self.values_to_numbers(&items, name)?;
+                        ArrayBuiltins::sort(&numbers)
+                            .into_iter()
+                            .map(Value::Number)
+                            .collect()
+                    } else if items.iter().all(|item| matches!(item, Value::Integer(_))) {
+                        let mut integers: Vec<i64> = items
+                            .iter()
+                            .filter_map(|item| match item {
+                                Value::Integer(i) => Some(*i),
+                                _ => None,
+                            })
+                            .collect();
+                        integers.sort_unstable();
+                        integers.into_iter().map(Value::Integer).collect()
+                    } else if items.iter().all(|item| matches!(item, Value::String(_))) {
+                        let mut sorted = items.clone();
+                        sorted.sort_by_key(|a| a.to_string());
+                        sorted
+                    } else {
+                        return Err(InterpreterError::TypeError(format!(
+                            "Builtin '{}' can only sort arrays of numbers, of integers or of strings",
+                            name
+                        )));
+                    }
+                };
+                *array.borrow_mut() = sorted;
+                Some(Value::Array(array))
+            }
+            _ => None,
+        };
+
+        Ok(result)
+    }
+
+    fn call_dictionary_builtin(
+        &mut self,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Option<Value>, InterpreterError> {
+        let result = match name {
+            "DictCreate" => Some(Value::Dictionary(DictionaryBuiltins::create())),
+            "DictGet" => {
+                let dict = self.dictionary_arg(args, 0, name)?;
+                let key = DictionaryKey::from_value(self.arg(args, 1, name)?)?;
+                Some(DictionaryBuiltins::get(&dict, &key).unwrap_or(Value::Null))
+            }
+            "DictSet" => {
+                let dict = self.dictionary_arg(args, 0, name)?;
+                let key = DictionaryKey::from_value(self.arg(args, 1, name)?)?;
+                let value = self.arg(args, 2, name)?.clone();
+                Some(Value::Dictionary(DictionaryBuiltins::set(
+                    &dict, key, value,
+                )))
+            }
+            "DictHasKey" => {
+                let dict = self.dictionary_arg(args, 0, name)?;
+                let key = DictionaryKey::from_value(self.arg(args, 1, name)?)?;
+                Some(Value::Boolean(DictionaryBuiltins::has_key(&dict, &key)))
+            }
+            "DictKeys" => {
+                let dict = self.dictionary_arg(args, 0, name)?;
+                Some(Value::array(
+                    DictionaryBuiltins::keys(&dict)
+                        .iter()
+                        .map(DictionaryKey::to_value)
+                        .collect(),
+                ))
+            }
+            "DictValues" => {
+                let dict = self.dictionary_arg(args, 0, name)?;
+                Some(Value::array(DictionaryBuiltins::values(&dict)))
+            }
+            "DictEntries" => {
+                let dict = self.dictionary_arg(args, 0, name)?;
+                Some(Value::array(
+                    DictionaryBuiltins::entries(&dict)
+                        .into_iter()
+                        .map(|(key, value)| Value::array(vec![key.to_value(), value]))
+                        .collect(),
+                ))
+            }
+            "DictSize" => {
+                let dict = self.dictionary_arg(args, 0, name)?;
+                Some(Value::Number(DictionaryBuiltins::size(&dict) as f64))
+            }
+            "DictIsEmpty" => {
+                let dict = self.dictionary_arg(args, 0, name)?;
+                Some(Value::Boolean(DictionaryBuiltins::is_empty(&dict)))
+            }
+            "DictRemove" => {
+                let dict = self.dictionary_arg(args, 0, name)?;
+                let key = DictionaryKey::from_value(self.arg(args, 1, name)?)?;
+                Some(Value::Dictionary(DictionaryBuiltins::remove(&dict, &key)))
+            }
+            "DictClear" => {
+                let dict = self.dictionary_arg(args, 0, name)?;
+                Some(Value::Dictionary(DictionaryBuiltins::clear(&dict)))
+            }
+            "DictMerge" => {
+                let first = self.dictionary_arg(args, 0, name)?;
+                let second = self.dictionary_arg(args, 1, name)?;
+                Some(Value::Dictionary(DictionaryBuiltins::merge(
+                    &first, &second,
+                )))
+            }
+            "DictFilter" => {
+                let dict = self.dictionary_arg(args, 0, name)?;
+                let predicate = self.arg(args, 1, name)?.clone();
+                let filtered = DictionaryBuiltins::filter(&dict, |key, value| {
+                    self.invoke_callable(&predicate, &[key.to_value(), value.clone()])
+                        .map(|keep| keep.is_truthy())
+                })?;
+                Some(Value::Dictionary(filtered))
+            }
+            "DictMap" => {
+                let dict = self.dictionary_arg(args, 0, name)?;
+                let mapper = self.arg(args, 1, name)?.clone();
+                let mapped = DictionaryBuiltins::map(&dict, |key, value| {
+                    self.invoke_callable(&mapper, &[key.to_value(), value.clone()])
+                })?;
+                Some(Value::Dictionary(mapped))
+            }
+            "DictFromJson" => {
+                let json = self.string_arg(args, 0, name)?;
+                let dict = DictionaryBuiltins::from_json(&json)
+                    .map_err(|error| InterpreterError::Runtime(error.to_localized_string(None)))?;
+                Some(Value::Dictionary(
+                    dict.into_iter()
+                        .map(|(key, value)| (DictionaryKey::String(key), json_to_value(value)))
+                        .collect(),
+                ))
+            }
+            "DictToJson" => {
+                let dict = self.dictionary_arg(args, 0, name)?;
+                let json = dict
+                    .iter()
+                    .map(|(key, value)| Ok((key.to_string(), value_to_json(value)?)))
+                    .collect::<Result<_, InterpreterError>>()?;
+                Some(Value::String(DictionaryBuiltins::to_json(&json).map_err(
+                    |error| InterpreterError::Runtime(error.to_localized_string(None)),
+                )?))
+            }
+            _ => None,
+        };
+
+        Ok(result)
+    }
+
+    fn call_core_builtin(
+        &self,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Option<Value>, InterpreterError> {
+        let result = match name {
+            "Observe" => {
+                let message = self.arg(args, 0, name)?.to_string();
+                CoreBuiltins::observe(&message);
+                Some(Value::Null)
+            }
+            "Drift" => {
+                let duration = self.number_arg(args, 0, name)?;
+                CoreBuiltins::drift(duration.max(0.0) as u64);
+                Some(Value::Null)
+            }
+            "DeepTrance" => {
+                let duration = self.number_arg(args, 0, name)?;
+                CoreBuiltins::deep_trance(duration.max(0.0) as u64);
+                Some(Value::Null)
+            }
+            "HypnoticCountdown" => {
+                CoreBuiltins::hypnotic_countdown(self.integer_arg(args, 0, name)?);
+                Some(Value::Null)
+            }
+            "TranceInduction" => {
+                CoreBuiltins::trance_induction(&self.string_arg(args, 0, name)?);
+                Some(Value::Null)
+            }
+            "HypnoticVisualization" => {
+                CoreBuiltins::hypnotic_visualization(&self.string_arg(args, 0, name)?);
+                Some(Value::Null)
+            }
+            "ToInt" => Some(Value::Integer(match self.arg(args, 0, name)? {
+                Value::Integer(i) => *i,
+                // Digit strings convert exactly, even beyond the precision of a number
+                Value::String(s) if s.trim().parse::<i64>().is_ok() => {
+                    s.trim().parse::<i64>().unwrap_or_default()
+                }
+                _ => CoreBuiltins::to_int(self.number_arg(args, 0, name)?),
+            })),
+            "ToDouble" => Some(Value::Number(
+                CoreBuiltins::to_double(&self.string_arg(args, 0, name)?)
+                    .map_err(InterpreterError::Runtime)?,
+            )),
+            "ToString" => Some(Value::String(
+                args.first()
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| "null".to_string()),
+            )),
+            "ToBoolean" => Some(Value::Boolean(CoreBuiltins::to_boolean(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            _ => None,
+        };
+
+        Ok(result)
+    }
+
+    fn call_file_builtin(
+        &self,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Option<Value>, InterpreterError> {
+        let result = match name {
+            "StreamLines" => {
+                let path = self.string_arg(args, 0, name)?;
+                let lines = FileBuiltins::stream_lines(&path).map_err(|e| {
+                    InterpreterError::Runtime(format!("Failed to read '{}': {}", path, e))
+                })?;
+                Some(Value::Iterator(Rc::new(RefCell::new(IteratorValue::new(
+                    IteratorSource::Lines { path, lines },
+                )))))
+            }
+            "ReadFile" => Some(Value::String(
+                FileBuiltins::read_file(&self.string_arg(args, 0, name)?)
+                    .map_err(|e| InterpreterError::Runtime(e.to_string()))?,
+            )),
+            "WriteFile" => {
+                FileBuiltins::write_file(
+                    &self.string_arg(args, 0, name)?,
+                    &self.string_arg(args, 1, name)?,
+                )
+                .map_err(|e| InterpreterError::Runtime(e.to_string()))?;
+                Some(Value::Null)
+            }
+            "AppendFile" => {
+                FileBuiltins::append_file(
+                    &self.string_arg(args, 0, name)?,
+                    &self.string_arg(args, 1, name)?,
+                )
+                .map_err(|e| InterpreterError::Runtime(e.to_string()))?;
+                Some(Value::Null)
+            }
+            "FileExists" => Some(Value::Boolean(FileBuiltins::file_exists(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "IsFile" => Some(Value::Boolean(FileBuiltins::is_file(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "IsDirectory" => Some(Value::Boolean(FileBuiltins::is_directory(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "DeleteFile" => {
+                FileBuiltins::delete_file(&self.string_arg(args, 0, name)?)
+                    .map_err(|e| InterpreterError::Runtime(e.to_string()))?;
+                Some(Value::Null)
+            }
+            "CreateDirectory" => {
+                FileBuiltins::create_directory(&self.string_arg(args, 0, name)?)
+                    .map_err(|e| InterpreterError::Runtime(e.to_string()))?;
+                Some(Value::Null)
+            }
+            "ListDirectory" => {
+                let files = FileBuiltins::list_directory(&self.string_arg(args, 0, name)?)
+                    .map_err(|e| InterpreterError::Runtime(e.to_string()))?
+                    .into_iter()
+                    .map(Value::String)
+                    .collect();
+                Some(Value::array(files))
+            }
+            "GetFileSize" => Some(Value::Number(
+                FileBuiltins::get_file_size(&self.string_arg(args, 0, name)?)
+                    .map_err(|e| InterpreterError::Runtime(e.to_string()))? as f64,
+            )),
+            "CopyFile" => Some(Value::Number(
+                FileBuiltins::copy_file(
+                    &self.string_arg(args, 0, name)?,
+                    &self.string_arg(args, 1, name)?,
+                )
+                .map_err(|e| InterpreterError::Runtime(e.to_string()))? as f64,
+            )),
+            "RenameFile" => {
+                FileBuiltins::rename_file(
+                    &self.string_arg(args, 0, name)?,
+                    &self.string_arg(args, 1, name)?,
+                )
+                .map_err(|e| InterpreterError::Runtime(e.to_string()))?;
+                Some(Value::Null)
+            }
+            "GetFileExtension" => Some(self.option_string_to_value(
+                FileBuiltins::get_file_extension(&self.string_arg(args, 0, name)?),
+            )),
+            "GetFileName" => Some(self.option_string_to_value(FileBuiltins::get_file_name(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "GetParentDirectory" => Some(self.option_string_to_value(
+                FileBuiltins::get_parent_directory(&self.string_arg(args, 0, name)?),
+            )),
+            _ => None,
+        };
+
+        Ok(result)
+    }
+
+    fn call_hashing_builtin(
+        &self,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Option<Value>, InterpreterError> {
+        let result = match name {
+            // Hashes drop the top bit so `% n` never yields a negative index
+            "HashString" => Some(Value::Integer(non_negative_hash(
+                HashingBuiltins::hash_string(&self.string_arg(args, 0, name)?),
+            ))),
+            "HashNumber" => Some(Value::Integer(non_negative_hash(
+                HashingBuiltins::hash_number(self.number_arg(args, 0, name)?),
+            ))),
+            "SimpleRandom" => Some(Value::Integer(non_negative_hash(
+                HashingBuiltins::simple_random(self.integer_arg(args, 0, name)? as u64),
+            ))),
+            "AreAnagrams" => Some(Value::Boolean(HashingBuiltins::are_anagrams(
+                &self.string_arg(args, 0, name)?,
+                &self.string_arg(args, 1, name)?,
+            ))),
+            "IsPalindrome" => Some(Value::Boolean(HashingBuiltins::is_palindrome(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "CountOccurrences" => Some(Value::Number(HashingBuiltins::count_occurrences(
+                &self.string_arg(args, 0, name)?,
+                &self.string_arg(args, 1, name)?,
+            ) as f64)),
+            "RemoveDuplicates" => Some(Value::String(HashingBuiltins::remove_duplicates(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "UniqueCharacters" => Some(Value::String(HashingBuiltins::unique_characters(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "ReverseWords" => Some(Value::String(HashingBuiltins::reverse_words(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "TitleCase" => Some(Value::String(HashingBuiltins::title_case(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            _ => None,
+        };
+
+        Ok(result)
+    }
+
+    fn call_statistics_builtin(
+        &self,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Option<Value>, InterpreterError> {
+        let numbers_primary = |this: &Self| -> Result<Vec<f64>, InterpreterError> {
+            let array = this.array_arg(args, 0, name)?;
+            this.values_to_numbers(&array, name)
+        };
+
+        let result = match name {
+            "Mean" => Some(Value::Number(StatisticsBuiltins::calculate_mean(
+                &numbers_primary(self)?,
+            ))),
+            "Median" => Some(Value::Number(StatisticsBuiltins::calculate_median(
+                &numbers_primary(self)?,
+            ))),
+            "Mode" => Some(Value::Number(StatisticsBuiltins::calculate_mode(
+                &numbers_primary(self)?,
+            ))),
+            "StandardDeviation" => Some(Value::Number(
+                StatisticsBuiltins::calculate_standard_deviation(&numbers_primary(self)?),
+            )),
+            "Variance" => Some(Value::Number(StatisticsBuiltins::calculate_variance(
+                &numbers_primary(self)?,
+            ))),
+            "Range" => Some(Value::Number(StatisticsBuiltins::calculate_range(
+                &numbers_primary(self)?,
+            ))),
+            "Percentile" => Some(Value::Number(StatisticsBuiltins::calculate_percentile(
+                &numbers_primary(self)?,
+                self.number_arg(args, 1, name)?,
+            ))),
+            "Correlation" => {
+                let x = self.values_to_numbers(&self.array_arg(args, 0, name)?, name)?;
+                let y = self.values_to_numbers(&self.array_arg(args, 1, name)?, name)?;
+                Some(Value::Number(StatisticsBuiltins::calculate_correlation(
+                    &x, &y,
+                )))
+            }
+            "LinearRegression" => {
+                let x = self.values_to_numbers(&self.array_arg(args, 0, name)?, name)?;
+                let y = self.values_to_numbers(&self.array_arg(args, 1, name)?, name)?;
+                let (slope, intercept) = StatisticsBuiltins::linear_regression(&x, &y);
+                Some(Value::array(vec![
+                    Value::Number(slope),
+                    Value::Number(intercept),
+                ]))
+            }
+            _ => None,
+        };
+
+        Ok(result)
+    }
+
+    fn call_system_builtin(
+        &self,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Option<Value>, InterpreterError> {
+        let result = match name {
+            "GetCurrentDirectory" => Some(Value::String(SystemBuiltins::get_current_directory())),
+            "GetEnv" => Some(self.option_string_to_value(SystemBuiltins::get_env_var(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "SetEnv" => {
+                SystemBuiltins::set_env_var(
+                    &self.string_arg(args, 0, name)?,
+                    &self.string_arg(args, 1, name)?,
+                )
+                .map_err(InterpreterError::Runtime)?;
+                Some(Value::Null)
+            }
+            "GetOperatingSystem" => Some(Value::String(SystemBuiltins::get_operating_system())),
+            "GetArchitecture" => Some(Value::String(SystemBuiltins::get_architecture())),
+            "GetCpuCount" => Some(Value::Number(SystemBuiltins::get_cpu_count() as f64)),
+            "GetHostname" => Some(Value::String(SystemBuiltins::get_hostname())),
+            "GetUsername" => Some(Value::String(SystemBuiltins::get_username())),
+            "GetHomeDirectory" => Some(Value::String(SystemBuiltins::get_home_directory())),
+            "GetTempDirectory" => Some(Value::String(SystemBuiltins::get_temp_directory())),
+            "GetArgs" => Some(Value::array(
+                SystemBuiltins::get_args()
+                    .into_iter()
+                    .map(Value::String)
+                    .collect(),
+            )),
+            "Exit" => {
+                // Exit mirrors the legacy runtime behavior by terminating the host process immediately.
+                SystemBuiltins::exit(self.integer_arg(args, 0, name)? as i32);
+            }
+            _ => None,
+        };
+
+        Ok(result)
+    }
+
+    fn call_time_builtin(
+        &self,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Option<Value>, InterpreterError> {
+        let result = match name {
+            "CurrentTimestamp" => Some(Value::Number(TimeBuiltins::get_current_time() as f64)),
+            "CurrentDate" => Some(Value::String(TimeBuiltins::get_current_date())),
+            "CurrentTime" => Some(Value::String(TimeBuiltins::get_current_time_string())),
+            "CurrentDateTime" => Some(Value::String(TimeBuiltins::get_current_date_time())),
+            "FormatDateTime" => Some(Value::String(TimeBuiltins::format_date_time(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "DayOfWeek" => Some(Value::Number(TimeBuiltins::get_day_of_week() as f64)),
+            "DayOfYear" => Some(Value::Number(TimeBuiltins::get_day_of_year() as f64)),
+            "IsLeapYear" => Some(Value::Boolean(TimeBuiltins::is_leap_year(
+                self.integer_arg(args, 0, name)? as i32,
+            ))),
+            "DaysInMonth" => Some(self.option_u32_to_value(TimeBuiltins::get_days_in_month(
+                self.integer_arg(args, 0, name)? as i32,
+                self.usize_arg(args, 1, name)? as u32,
+            ))),
+            "CurrentYear" => Some(Value::Number(TimeBuiltins::get_year() as f64)),
+            "CurrentMonth" => Some(Value::Number(TimeBuiltins::get_month() as f64)),
+            "CurrentDay" => Some(Value::Number(TimeBuiltins::get_day() as f64)),
+            "CurrentHour" => Some(Value::Number(TimeBuiltins::get_hour() as f64)),
+            "CurrentMinute" => Some(Value::Number(TimeBuiltins::get_minute() as f64)),
+            "CurrentSecond" => Some(Value::Number(TimeBuiltins::get_second() as f64)),
+            _ => None,
+        };
+
+        Ok(result)
+    }
+
+    fn call_validation_builtin(
+        &self,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Option<Value>, InterpreterError> {
+        let result = match name {
+            "IsValidEmail" => Some(Value::Boolean(ValidationBuiltins::is_valid_email(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "IsValidUrl" => Some(Value::Boolean(ValidationBuiltins::is_valid_url(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "IsValidPhoneNumber" => Some(Value::Boolean(
+                ValidationBuiltins::is_valid_phone_number(&self.string_arg(args, 0, name)?),
+            )),
+            "IsAlphanumeric" => Some(Value::Boolean(ValidationBuiltins::is_alphanumeric(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "IsAlphabetic" => Some(Value::Boolean(ValidationBuiltins::is_alphabetic(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "IsNumeric" => Some(Value::Boolean(ValidationBuiltins::is_numeric(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "IsLowercase" => Some(Value::Boolean(ValidationBuiltins::is_lowercase(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "IsUppercase" => Some(Value::Boolean(ValidationBuiltins::is_uppercase(
+                &self.string_arg(args, 0, name)?,
+            ))),
+            "IsInRange" => Some(Value::Boolean(ValidationBuiltins::is_in_range(
+                self.number_arg(args, 0, name)?,
+                self.number_arg(args, 1, name)?,
+                self.number_arg(args, 2, name)?,
+            ))),
+            "MatchesPattern" => Some(Value::Boolean(ValidationBuiltins::matches_pattern(
+                &self.string_arg(args, 0, name)?,
+                &self.string_arg(args, 1, name)?,
+            ))),
+            _ => None,
+        };
+
+        Ok(result)
+    }
+
+    fn arg<'a>(
+        &self,
+        args: &'a [Value],
+        index: usize,
+        name: &str,
+    ) -> Result<&'a Value, InterpreterError> {
+        args.get(index).ok_or_else(|| {
+            InterpreterError::Runtime(format!(
+                "Builtin '{}' expected argument at position {}",
+                name,
+                index + 1
+            ))
+        })
+    }
+
+    fn number_arg(
+        &self,
+        args: &[Value],
+        index: usize,
+        name: &str,
+    ) -> Result<f64, InterpreterError> {
+        self.arg(args, index, name)?.to_number().map_err(|_| {
+            InterpreterError::TypeError(format!(
+                "Builtin '{}' expected numeric argument at position {}",
+                name,
+                index + 1
+            ))
+        })
+    }
+
+    fn integer_arg(
+        &self,
+        args: &[Value],
+        index: usize,
+        name: &str,
+    ) -> Result<i64, InterpreterError> {
+        if let Value::Integer(i) = self.arg(args, index, name)? {
+            return Ok(*i);
+        }
+        let value = self.number_arg(args, index, name)?;
+        Ok(value.round() as i64)
+    }
+
+    fn usize_arg(
+        &self,
+        args: &[Value],
+        index: usize,
+        name: &str,
+    ) -> Result<usize, InterpreterError> {
+        let value = self.number_arg(args, index, name)?;
+        if value < 0.0 {
+            return Err(InterpreterError::TypeError(format!(
+                "Builtin '{}' expected non-negative number at position {}",
+                name,
+                index + 1
+            )));
+        }
+        Ok(value.round() as usize)
+    }
+
+    fn string_arg(
+        &self,
+        args: &[Value],
+        index: usize,
+        name: &str,
+    ) -> Result<String, InterpreterError> {
+        match self.arg(args, index, name)? {
+            Value::String(s) => Ok(s.clone()),
+            other => Err(InterpreterError::TypeError(format!(
+                "Builtin '{}' expected string argument at position {}, got {:?}",
+                name,
+                index + 1,
+                other
+            ))),
+        }
+    }
+
+    fn char_arg(&self, args: &[Value], index: usize, name: &str) -> Result<char, InterpreterError> {
+        let text = self.string_arg(args, index, name)?;
+        text.chars().next().ok_or_else(|| {
+            InterpreterError::TypeError(format!(
+                "Builtin '{}' expected non-empty string to derive character at position {}",
+                name,
+                index + 1
+            ))
+        })
+    }
+
+    fn shared_array_arg(
+        &self,
+        args: &[Value],
+        index: usize,
+        name: &str,
+    ) -> Result<Rc<RefCell<Vec<Value>>>, InterpreterError> {
+        match self.arg(args, index, name)? {
+            Value::Array(items) => Ok(items.clone()),
+            other => Err(InterpreterError::TypeError(format!(
+                "Builtin '{}' expected array argument at position {}, got {:?}",
+                name,
+                index + 1,
+                other
+            ))),
+        }
+    }
+
+    fn array_arg(
+        &self,
+        args: &[Value],
+        index: usize,
+        name: &str,
+    ) -> Result<Vec<Value>, InterpreterError> {
+        match self.arg(args, index, name)? {
+            Value::Array(items) => Ok(items.borrow().clone()),
+            other => Err(InterpreterError::TypeError(format!(
+                "Builtin '{}' expected array argument at position {}, got {:?}",
+                name,
+                index + 1,
+                other
+            ))),
+        }
+    }
+
+    fn dictionary_arg(
+        &self,
+        args: &[Value],
+        index: usize,
+        name: &str,
+    ) -> Result<Dictionary<DictionaryKey, Value>, InterpreterError> {
+        match self.arg(args, index, name)? {
+            Value::Dictionary(dict) => Ok(dict.clone()),
+            other => Err(InterpreterError::TypeError(format!(
+                "Builtin '{}' expected dictionary argument at position {}, got {:?}",
+                name,
+                index + 1,
+                other
+            ))),
+        }
+    }
+
+    fn option_string_to_value(&self, input: Option<String>) -> Value {
+        input.map(Value::String).unwrap_or(Value::Null)
+    }
+
+    fn option_u32_to_value(&self, input: Option<u32>) -> Value {
+        input
+            .map(|v| Value::Number(v as f64))
+            .unwrap_or(Value::Null)
+    }
+
+    fn values_to_numbers(
+        &self,
+        values: &[Value],
+        name: &str,
+    ) -> Result<Vec<f64>, InterpreterError> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                value.to_number().map_err(|_| {
+                    InterpreterError::TypeError(format!(
+                        "Builtin '{}' expected numeric array element at position {}",
+                        name,
+                        i + 1
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    fn push_scope(&mut self) {
+        self.locals
+            .push(Rc::new(RefCell::new(LocalScope::default())));
+    }
+
+    fn pop_scope(&mut self) {
+        self.locals.pop();
+    }
+
+    fn define_variable(
+        &mut self,
+        storage: VariableStorage,
+        name: String,
+        value: Value,
+        is_constant: bool,
+    ) {
+        match storage {
+            VariableStorage::SharedTrance => {
+                self.shared.insert(name.clone(), value);
+                if is_constant {
+                    self.const_globals.insert(name);
+                } else {
+                    self.const_globals.remove(&name);
+                }
+            }
+            VariableStorage::Local => {
+                if let Some(scope) = self.locals.last() {
+                    let mut scope = scope.borrow_mut();
+                    scope.values.insert(name.clone(), value);
+                    if is_constant {
+                        scope.constants.insert(name);
+                    } else {
+                        scope.constants.remove(&name);
+                    }
+                } else {
+                    self.globals.insert(name.clone(), value);
+                    if is_constant {
+                        self.const_globals.insert(name);
+                    } else {
+                        self.const_globals.remove(&name);
+                    }
+                }
+            }
+        }
+    }
+
+    fn set_variable(
+        &mut self,
+        name: String,
+        value: Value,
+        scope_hint: ScopeLayer,
+    ) -> Result<(), InterpreterError> {
+        let check_const = |is_const: bool| -> Result<(), InterpreterError> {
+            if is_const {
+                return Err(InterpreterError::Runtime(localized(
+                    &format!("Cannot reassign constant variable '{}'", name),
+                    &format!(
+                        "Konstante Variable '{}' kann nicht neu zugewiesen werden",
+                        name
+                    ),
+                )));
+            }
+            Ok(())
+        };
+
+        match scope_hint {
+            ScopeLayer::Local => {
+                if let Some(scope) = self.locals.last() {
+                    let mut scope = scope.borrow_mut();
+                    if scope.values.contains_key(&name) {
+                        check_const(scope.constants.contains(&name))?;
+                        scope.values.insert(name, value);
+                        return Ok(());
+                    }
+                }
+            }
+            ScopeLayer::Global => {
+                if self.globals.contains_key(&name) {
+                    check_const(self.const_globals.contains(&name))?;
+                    self.globals.insert(name, value);
+                    return Ok(());
+                }
+            }
+            ScopeLayer::Shared => {
+                if self.shared.contains_key(&name) {
+                    check_const(self.const_globals.contains(&name))?;
+                    self.shared.insert(name, value);
+                    return Ok(());
+                }
+            }
+        }
+
+        for scope in self.locals.iter().rev() {
+            let mut scope = scope.borrow_mut();
+            if scope.values.contains_key(&name) {
+                check_const(scope.constants.contains(&name))?;
+                scope.values.insert(name, value);
+                return Ok(());
+            }
+        }
+
+        if !self.in_module_code() && self.globals.contains_key(&name) {
+            check_const(self.const_globals.contains(&name))?;
+            self.globals.insert(name, value);
+            return Ok(());
+        }
+
+        if self.shared.contains_key(&name) {
+            check_const(self.const_globals.contains(&name))?;
+            self.shared.insert(name, value);
+            return Ok(());
+        }
+
+        if let Some(scope) = self.locals.last() {
+            scope.borrow_mut().values.insert(name, value);
+            return Ok(());
+        }
+
+        self.globals.insert(name.clone(), value);
+        Ok(())
+    }
+
+    fn resolve_assignment_scope(&self, name: &str) -> ScopeLayer {
+        if self
+            .locals
+            .iter()
+            .rev()
+            .any(|scope| scope.borrow().values.contains_key(name))
+        {
+            ScopeLayer::Local
+        } else if self.shared.contains_key(name) {
+            ScopeLayer::Shared
+        } else if !self.in_module_code() && self.globals.contains_key(name) {
+            ScopeLayer::Global
+        } else {
+            ScopeLayer::Local
+        }
+    }
+
+    fn get_variable(&self, name: &str) -> Result<Value, InterpreterError> {
+        // Search in local scopes (from innermost to outermost)
+        for scope in self.locals.iter().rev() {
+            if let Some(value) = scope.borrow().values.get(name) {
+                return Ok(value.clone());
+            }
+        }
+
+        if !self.in_module_code()
+            && let Some(value) = self.globals.get(name)
+        {
+            return Ok(value.clone());
+        }
+
+        if let Some(value) = self.shared.get(name) {
+            return Ok(value.clone());
+        }
+
+        Err(InterpreterError::UndefinedVariable(name.to_string()))
+    }
+}
+
+/// Whether a value matches a type pattern such as `number`, `"on" | null` or `string[]`
+fn value_matches_type(
+    value: &Value,
+    type_annotation: &str,
+    interfaces: &HashMap<String, Vec<String>>,
+) -> bool {
+    let type_annotation = type_annotation.trim();
+    let members = split_union_members(type_annotation);
+    if members.len() > 1 {
+        return members
+            .into_iter()
+            .any(|member| value_matches_type(value, member, interfaces));
+    }
+    if let Some(inner) = type_annotation.strip_prefix("lucid ") {
+        return matches!(value, Value::Null) || value_matches_type(value, inner, interfaces);
+    }
+    if let Some(inner) = type_annotation
+        .strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        return value_matches_type(value, inner, interfaces);
+    }
+    if let Some(element) = type_annotation.strip_suffix("[]") {
+        return match value {
+            Value::Array(items) => items
+                .borrow()
+                .iter()
+                .all(|item| value_matches_type(item, element, interfaces)),
+            _ => false,
+        };
+    }
+    if type_annotation.len() >= 2 && type_annotation.starts_with('"') {
+        return matches!(value, Value::String(s) if format!("{:?}", s) == type_annotation);
+    }
+    if let Ok(literal) = type_annotation.parse::<f64>()
+        && !type_annotation.starts_with(|c: char| c.is_alphabetic())
+    {
+        return matches!(value, Value::Number(n) if *n == literal);
+    }
+
+    let name = type_annotation
+        .split_once('<')
+        .map_or(type_annotation, |(name, _)| name);
+    match name.to_lowercase().as_str() {
+        // Integers widen to numbers
+        "number" => matches!(value, Value::Number(_) | Value::Integer(_)),
+        "integer" => matches!(value, Value::Integer(_)),
+        "string" => matches!(value, Value::String(_)),
+        "boolean" => matches!(value, Value::Boolean(_)),
+        "true" => matches!(value, Value::Boolean(true)),
+        "false" => matches!(value, Value::Boolean(false)),
+        "null" => matches!(value, Value::Null),
+        "array" => matches!(value, Value::Array(_)),
+        "dictionary" => matches!(value, Value::Dictionary(_)),
+        "promise" => matches!(value, Value::Promise(_)),
+        "iterator" => matches!(value, Value::Iterator(_)),
+        _ => match value {
+            // Records conform to an interface by providing all of its members
+            Value::Record(record) => match interfaces.get(name) {
+                Some(members) => members
+                    .iter()
+                    .all(|member| record.fields.contains_key(member)),
+                None => record.type_name == name,
+            },
+            Value::Instance(instance) => instance.borrow().definition.is_a(name),
+            Value::Session(definition) => definition.name == name,
+            Value::Variant(variant) => variant.enum_name == name,
+            // Only sessions and records implement interfaces; other names match for now
+            _ => !interfaces.contains_key(name),
+        },
+    }
+}
+
+/// Split a union type pattern at top-level `|`
+fn split_union_members(type_annotation: &str) -> Vec<&str> {
+    let mut members = Vec::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut start = 0;
+    for (i, c) in type_annotation.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth = depth.saturating_sub(1),
+            '|' if depth == 0 => {
+                members.push(type_annotation[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    members.push(type_annotation[start..].trim());
+    members
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hypnoscript_lexer_parser::{Lexer, Parser};
+
+    /// Parse a test program
+    fn parse(source: &str) -> AstNode {
+        let tokens = Lexer::new(source).lex().unwrap();
+        Parser::new(tokens).parse_program().unwrap()
+    }
+
+    /// Run a test program that must succeed
+    fn run(source: &str) -> Interpreter {
+        let mut interpreter = Interpreter::new();
+        interpreter.execute_program(parse(source)).unwrap();
+        interpreter
+    }
+
+    /// Display form of a variable after a run
+    fn var_string(interpreter: &Interpreter, name: &str) -> String {
+        interpreter.get_variable(name).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_simple_program() {
+        let source = r#"
+Focus {
+    induce x: number = 42;
+    induce y: number = 10;
+    induce sum: number = x + y;
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens.clone());
+        let ast = match parser.parse_program() {
+            Ok(ast) => ast,
+            Err(err) => {
+                eprintln!("parse error: {err}");
+                for token in tokens {
+                    eprintln!("token: {:?}", token);
+                }
+                panic!("failed to parse test program");
+            }
+        };
+
+        let mut interpreter = Interpreter::new();
+        let result = interpreter.execute_program(ast);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_if_statement() {
+        let source = r#"
+Focus {
+    induce x: number = 10;
+    if (x > 5) deepFocus {
+        induce result: number = 1;
+    }
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens.clone());
+        let ast = match parser.parse_program() {
+            Ok(ast) => ast,
+            Err(err) => {
+                eprintln!("parse error: {err}");
+                for token in tokens {
+                    eprintln!("token: {:?}", token);
+                }
+                panic!("failed to parse test program");
+            }
+        };
+
+        let mut interpreter = Interpreter::new();
+        let result = interpreter.execute_program(ast);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_session_constructor_and_methods() {
+        let source = r#"
+Focus {
+    session Counter {
+        expose value: number;
+
+        suggestion constructor(initial: number) {
+            this.value = initial;
+        }
+
+        suggestion inc() {
+            this.value = this.value + 1;
+        }
+
+        suggestion current(): number {
+            awaken this.value;
+        }
+    }
+
+    induce counter = Counter(5);
+    counter.inc();
+    counter.inc();
+    induce current: number = counter.current();
+} Relax
+"#;
+
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut interpreter = Interpreter::new();
+        if let Err(err) = interpreter.execute_program(ast) {
+            panic!("interpreter error: {err:?}");
+        }
+
+        let current = interpreter.get_variable("current").unwrap();
+        assert_eq!(current, Value::Number(7.0));
+    }
+
+    #[test]
+    fn test_hypnotic_operator_synonyms_execution() {
+        let source = r#"
+Focus {
+    induce a: number = 10;
+    induce b: number = 5;
+
+    induce eq: boolean = a youAreFeelingVerySleepy b;
+    induce neq: boolean = a youCannotResist b;
+    induce ge: boolean = a yourEyesAreGettingHeavy 9;
+    induce le: boolean = b goingDeeper 4;
+    induce both: boolean = ge underMyControl neq;
+    induce either: boolean = le resistanceIsFutile eq;
+} Relax
+"#;
+
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut interpreter = Interpreter::new();
+        if let Err(err) = interpreter.execute_program(ast) {
+            panic!("interpreter error: {err:?}");
+        }
+
+        assert_eq!(
+            interpreter.get_variable("eq").unwrap(),
+            Value::Boolean(false)
+        );
+        assert_eq!(
+            interpreter.get_variable("neq").unwrap(),
+            Value::Boolean(true)
+        );
+        assert_eq!(
+            interpreter.get_variable("ge").unwrap(),
+            Value::Boolean(true)
+        );
+        assert_eq!(
+            interpreter.get_variable("le").unwrap(),
+            Value::Boolean(false)
+        );
+        assert_eq!(
+            interpreter.get_variable("both").unwrap(),
+            Value::Boolean(true)
+        );
+        assert_eq!(
+            interpreter.get_variable("either").unwrap(),
+            Value::Boolean(false)
+        );
+    }
+
+    #[test]
+    fn test_private_field_access_rejected() {
+        let source = r#"
+Focus {
+    session Account {
+        conceal balance: number;
+
+        suggestion constructor(amount: number) {
+            this.balance = amount;
+        }
+
+        suggestion read(): number {
+            awaken this.balance;
+        }
+    }
+
+    induce account = Account(100);
+    // The following line should fail because balance is private
+    induce leaked = account.balance;
+} Relax
+"#;
+
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut interpreter = Interpreter::new();
+        let result = interpreter.execute_program(ast);
+        assert!(matches!(
+            result,
+            Err(InterpreterError::Runtime(message)) if message.contains("Access denied")
+        ));
+    }
+
+    #[test]
+    fn test_static_field_and_method() {
+        let source = r#"
+Focus {
+    session Config {
+        dominant expose version: string = "1.0";
+
+        dominant suggestion setVersion(newVersion: string) {
+            Config.version = newVersion;
+        }
+    }
+
+    Config.setVersion("2.5");
+    induce activeVersion: string = Config.version;
+} Relax
+"#;
+
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut interpreter = Interpreter::new();
+        interpreter.execute_program(ast).unwrap();
+
+        let result = interpreter.get_variable("activeVersion").unwrap();
+        assert_eq!(result, Value::String("2.5".to_string()));
+    }
+
+    #[test]
+    fn test_entrain_record_pattern_matching_with_guard() {
+        let source = r#"
+Focus {
+    tranceify HypnoGuest {
+        name: string;
+        isInTrance: boolean;
+        depth: number;
+    }
+
+    entrance {
+        induce guest = HypnoGuest {
+            name: "Luna",
+            isInTrance: true,
+            depth: 7
+        };
+
+        induce status: string = entrain guest {
+            when HypnoGuest { name: alias } => alias;
+            otherwise => "Unknown";
+        };
+    }
+} Relax
+"#;
+
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens.clone());
+        let ast = match parser.parse_program() {
+            Ok(ast) => ast,
+            Err(err) => {
+                eprintln!("parse error: {err}");
+                for token in tokens {
+                    eprintln!("token: {:?}", token);
+                }
+                panic!("failed to parse test program");
+            }
+        };
+
+        let mut interpreter = Interpreter::new();
+        if let Err(err) = interpreter.execute_program(ast) {
+            panic!("interpreter error: {err:?}");
+        }
+
+        let status = interpreter.get_variable("status").unwrap();
+        assert_eq!(status, Value::String("Luna".to_string()));
+    }
+
+    #[test]
+    fn test_entrain_record_pattern_default_scope_cleanup() {
+        let source = r#"
+Focus {
+    tranceify HypnoGuest {
+        depth: number;
+    }
+
+    entrance {
+        induce guest = HypnoGuest { depth: 2 };
+
+        induce outcome = entrain guest {
+            when HypnoGuest { depth: stage } => stage;
+            otherwise => "fallback";
+        };
+    }
+} Relax
+"#;
+
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens.clone());
+        let ast = match parser.parse_program() {
+            Ok(ast) => ast,
+            Err(err) => {
+                eprintln!("parse error: {err}");
+                for token in tokens {
+                    eprintln!("token: {:?}", token);
+                }
+                panic!("failed to parse test program");
+            }
+        };
+
+        let mut interpreter = Interpreter::new();
+        if let Err(err) = interpreter.execute_program(ast) {
+            panic!("interpreter error: {err:?}");
+        }
+
+        let outcome = interpreter.get_variable("outcome").unwrap();
+        assert_eq!(outcome, Value::Number(2.0));
+        assert!(matches!(
+            interpreter.get_variable("stage"),
+            Err(InterpreterError::UndefinedVariable(_))
+        ));
+    }
+
+    #[test]
+    fn test_runtime_error_reports_statement_span() {
+        let source = "Focus {\n    induce x: number = 1;\n    if (true) {\n        observe missing;\n    }\n} Relax";
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut interpreter = Interpreter::new();
+        assert!(interpreter.execute_program(ast).is_err());
+
+        let span = interpreter
+            .current_span()
+            .expect("span of failing statement");
+        assert_eq!((span.line, span.column), (4, 9));
+    }
+
+    #[test]
+    fn test_closures_capture_defining_scope() {
+        let source = r#"
+Focus {
+    suggestion makeCounter() {
+        induce count: number = 0;
+        awaken suggestion () {
+            count = count + 1;
+            awaken count;
+        };
+    }
+
+    suggestion applyTwice(f, value) {
+        awaken f(f(value));
+    }
+
+    induce counter = makeCounter();
+    counter();
+    induce second = counter();
+
+    induce factor: number = 3;
+    induce tripled = applyTwice(suggestion (x) => x * factor, 2);
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut interpreter = Interpreter::new();
+        if let Err(err) = interpreter.execute_program(ast) {
+            panic!("interpreter error: {err:?}");
+        }
+
+        assert_eq!(
+            interpreter.get_variable("second").unwrap(),
+            Value::Number(2.0)
+        );
+        assert_eq!(
+            interpreter.get_variable("tripled").unwrap(),
+            Value::Number(18.0)
+        );
+        assert!(matches!(
+            interpreter.get_variable("count"),
+            Err(InterpreterError::UndefinedVariable(_))
+        ));
+    }
+
+    #[test]
+    fn test_string_interpolation() {
+        let source = r#"
+Focus {
+    induce name: string = "Luna";
+    induce level: number = 4;
+    induce greeting = "Hello ${name}, depth ${level * 2}";
+    induce nested = "[${"inner ${name}"}]";
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut interpreter = Interpreter::new();
+        interpreter.execute_program(ast).unwrap();
+
+        assert_eq!(
+            interpreter.get_variable("greeting").unwrap(),
+            Value::String("Hello Luna, depth 8".to_string())
+        );
+        assert_eq!(
+            interpreter.get_variable("nested").unwrap(),
+            Value::String("[inner Luna]".to_string())
+        );
+    }
+
+    #[test]
+    fn test_attempt_recover_finally() {
+        let source = r#"
+Focus {
+    induce log: string = "";
+
+    suggestion parse(text: string): number {
+        if (text == "") {
+            disrupt "empty input", 400;
+        }
+        awaken 1;
+    }
+
+    attempt {
+        parse("");
+        log = log + "unreachable";
+    } recover (err) {
+        induce code = err.payload;
+        log = log + err.kind + ":" + err.message + ":" + code;
+    } finally {
+        log = log + ";cleanup";
+    }
+
+    induce builtinKind = "";
+    attempt {
+        ReadFile("/definitely/not/here.hyp");
+    } recover (failure) {
+        builtinKind = failure.kind;
+    }
+
+    induce rethrown = "";
+    attempt {
+        attempt {
+            disrupt "inner";
+        } recover (err) {
+            disrupt err;
+        }
+    } recover (outer) {
+        rethrown = outer.message;
+    }
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut interpreter = Interpreter::new();
+        interpreter.execute_program(ast).unwrap();
+
+        assert_eq!(
+            interpreter.get_variable("log").unwrap(),
+            Value::String("Error:empty input:400;cleanup".to_string())
+        );
+        assert_eq!(
+            interpreter.get_variable("rethrown").unwrap(),
+            Value::String("inner".to_string())
+        );
+        assert_eq!(
+            interpreter.get_variable("builtinKind").unwrap(),
+            Value::String("Runtime".to_string())
+        );
+        assert!(matches!(
+            interpreter.get_variable("failure"),
+            Err(InterpreterError::UndefinedVariable(_))
+        ));
+    }
+
+    #[test]
+    fn test_finally_runs_for_uncaught_errors_and_returns() {
+        let source = r#"
+Focus {
+    induce cleaned: number = 0;
+
+    suggestion early(): number {
+        attempt {
+            awaken 5;
+        } finally {
+            cleaned = cleaned + 1;
+        }
+        awaken 0;
+    }
+
+    induce result = early();
+
+    attempt {
+        disrupt "fatal", "details";
+    } finally {
+        cleaned = cleaned + 1;
+    }
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut interpreter = Interpreter::new();
+        let error = interpreter.execute_program(ast).unwrap_err();
+        assert_eq!(error.to_string(), "Uncaught error: fatal");
+        assert_eq!(
+            interpreter.get_variable("result").unwrap(),
+            Value::Number(5.0)
+        );
+        assert_eq!(
+            interpreter.get_variable("cleaned").unwrap(),
+            Value::Number(2.0)
+        );
+    }
+
+    fn run_module_program(
+        dir: &Path,
+        main_source: &str,
+    ) -> (Interpreter, Result<(), InterpreterError>) {
+        let main_path = dir.join("main.hyp");
+        std::fs::write(&main_path, main_source).unwrap();
+        let ast = parse(main_source);
+
+        let mut interpreter = Interpreter::new();
+        interpreter.set_source_path(&main_path);
+        let result = interpreter.execute_program(ast);
+        (interpreter, result)
+    }
+
+    #[test]
+    fn test_mindlink_imports_exposed_declarations() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("shared")).unwrap();
+        std::fs::write(
+            dir.path().join("shared/state.hyp"),
+            r#"
+Focus {
+    induce visits: number = 0;
+    freeze secret: string = "hidden";
+
+    suggestion describe(count: number): string {
+        awaken "visit " + count;
+    }
+
+    expose suggestion visit(): string {
+        visits = visits + 1;
+        awaken describe(visits);
+    }
+
+    expose tranceify Profile {
+        name: string;
+    }
+
+    expose suggestion defaultProfile(): Profile {
+        awaken Profile { name: "Aurora" };
+    }
+} Relax
+"#,
+        )
+        .unwrap();
+
+        let (interpreter, result) = run_module_program(
+            dir.path(),
+            r#"
+Focus {
+    induce visits: number = 100;
+    mindLink { visit, defaultProfile } from "./shared/state.hyp";
+    visit();
+    induce second = visit();
+    induce name = defaultProfile().name;
+} Relax
+"#,
+        );
+        result.unwrap();
+
+        assert_eq!(
+            interpreter.get_variable("second").unwrap(),
+            Value::String("visit 2".to_string())
+        );
+        assert_eq!(
+            interpreter.get_variable("visits").unwrap(),
+            Value::Number(100.0)
+        );
+        assert_eq!(
+            interpreter.get_variable("name").unwrap(),
+            Value::String("Aurora".to_string())
+        );
+
+        let (_, result) = run_module_program(
+            dir.path(),
+            r#"Focus { mindLink { secret } from "./shared/state.hyp"; } Relax"#,
+        );
+        assert!(matches!(
+            result,
+            Err(InterpreterError::Module(ModuleError::NotExported { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_mindlink_reports_cycles() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("a.hyp"),
+            r#"Focus { mindLink { b } from "./b.hyp"; expose freeze a = 1; } Relax"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("b.hyp"),
+            r#"Focus { mindLink { a } from "./a.hyp"; expose freeze b = 2; } Relax"#,
+        )
+        .unwrap();
+
+        let (_, result) = run_module_program(
+            dir.path(),
+            r#"Focus { mindLink { a } from "./a.hyp"; } Relax"#,
+        );
+        match result {
+            Err(InterpreterError::Module(ModuleError::Cycle(chain))) => {
+                let names: Vec<_> = chain
+                    .iter()
+                    .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+                    .collect();
+                assert_eq!(names, vec!["a.hyp", "b.hyp", "a.hyp"]);
+            }
+            other => panic!("expected cycle error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_assert_reports_expression_and_operands() {
+        let source = r#"
+Focus {
+    induce name: string = "Hypno";
+    assert Length(name) > 3, "name too short";
+    assert name == "Hypnos", "unexpected name";
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut interpreter = Interpreter::new();
+        let error = interpreter.execute_program(ast).unwrap_err();
+        let InterpreterError::AssertionFailed(failure) = &error else {
+            panic!("expected assertion failure, got {:?}", error);
+        };
+        assert_eq!(failure.expression, "name == \"Hypnos\"");
+        assert_eq!(failure.message.as_deref(), Some("unexpected name"));
+        assert_eq!(
+            failure.operands,
+            Some((
+                Value::String("Hypno".to_string()),
+                Value::String("Hypnos".to_string())
+            ))
+        );
+        assert_eq!(
+            error.to_string(),
+            "Assertion failed: name == \"Hypnos\" (unexpected name)\n  left:  \"Hypno\"\n  right: \"Hypnos\""
+        );
+        assert_eq!(interpreter.current_span().map(|span| span.line), Some(5));
+    }
+
+    #[test]
+    fn test_dictionary_literal_indexing_and_order() {
+        let source = r#"
+Focus {
+    induce scores: Dictionary<string, number> = { "zoe": 3, "adam": 1 };
+    scores["mia"] = 2;
+    scores["zoe"] = 30;
+    induce nested = { "inner": { 1: true } };
+    nested["inner"][2] = false;
+    induce keys = DictKeys(scores);
+    induce total = scores["zoe"] + scores["adam"] + scores["mia"];
+    induce missing = scores?.["nobody"];
+    induce size = DictSize(DictRemove(scores, "adam"));
+    induce doubled = DictMap(scores, suggestion (key, value) => value * 2);
+    induce large = DictFilter(scores, suggestion (key, value) => value > 1);
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut interpreter = Interpreter::new();
+        interpreter.execute_program(ast).unwrap();
+
+        let strings = |items: &[&str]| {
+            Value::array(
+                items
+                    .iter()
+                    .map(|item| Value::String(item.to_string()))
+                    .collect(),
+            )
+        };
+        assert_eq!(
+            interpreter.get_variable("keys").unwrap(),
+            strings(&["zoe", "adam", "mia"])
+        );
+        assert_eq!(
+            interpreter.get_variable("total").unwrap(),
+            Value::Number(33.0)
+        );
+        assert_eq!(interpreter.get_variable("missing").unwrap(), Value::Null);
+        assert_eq!(
+            interpreter.get_variable("size").unwrap(),
+            Value::Number(2.0)
+        );
+        assert_eq!(
+            interpreter.get_variable("doubled").unwrap().to_string(),
+            "{zoe: 60, adam: 2, mia: 4}"
+        );
+        assert_eq!(
+            interpreter.get_variable("large").unwrap().to_string(),
+            "{zoe: 30, mia: 2}"
+        );
+        assert_eq!(
+            interpreter.get_variable("nested").unwrap().to_string(),
+            "{inner: {1: true, 2: false}}"
+        );
+    }
+
+    #[test]
+    fn test_dictionary_json_round_trip_keeps_values() {
+        let source = r#"
+Focus {
+    induce profile = DictFromJson("{\"name\": \"Bob\", \"age\": 25, \"tags\": [\"calm\"]}");
+    induce age = profile["age"] + 1;
+    induce json = DictToJson(DictSet(profile, "active", true));
+    induce lookup = { "a": 1 };
+    induce missing = lookup["b"];
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut interpreter = Interpreter::new();
+        let error = interpreter.execute_program(ast).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Key 'b' not found in dictionary"),
+            "{}",
+            error
+        );
 
-        Ok(result)
+        assert_eq!(
+            interpreter.get_variable("age").unwrap(),
+            Value::Number(26.0)
+        );
+        assert_eq!(
+            interpreter.get_variable("json").unwrap(),
+            Value::String(r#"{"name":"Bob","age":25,"tags":["calm"],"active":true}"#.to_string())
+        );
     }
 
-    fn call_core_builtin(
-        &self,
-        name: &str,
-        args: &[Value],
-    ) -> Result<Option<Value>, InterpreterError> {
-        let result = match name {
-            "Observe" => {
-                let message = self.arg(args, 0, name)?.to_string();
-                CoreBuiltins::observe(&message);
-                Some(Value::Null)
-            }
-            "Drift" => {
-                let duration = self.number_arg(args, 0, name)?;
-                CoreBuiltins::drift(duration.max(0.0) as u64);
-                Some(Value::Null)
-            }
-            "DeepTrance" => {
-                let duration = self.number_arg(args, 0, name)?;
-                CoreBuiltins::deep_trance(duration.max(0.0) as u64);
-                Some(Value::Null)
+    #[test]
+    fn test_labeled_loops_snap_and_sink() {
+        let source = r#"
+Focus {
+    induce pairs = "";
+    induce visits: number = 0;
+    outer: pendulum (induce i: number = 0; i < 4; i = i + 1) {
+        induce j: number = 0;
+        label inner: while (j < 4) {
+            j = j + 1;
+            visits = visits + 1;
+            if (j == 2) {
+                sink inner;
             }
-            "HypnoticCountdown" => {
-                CoreBuiltins::hypnotic_countdown(self.integer_arg(args, 0, name)?);
-                Some(Value::Null)
+            if (i == 1) {
+                sinkTo outer;
             }
-            "TranceInduction" => {
-                CoreBuiltins::trance_induction(&self.string_arg(args, 0, name)?);
-                Some(Value::Null)
+            if (i == 3) {
+                snap outer;
             }
-            "HypnoticVisualization" => {
-                CoreBuiltins::hypnotic_visualization(&self.string_arg(args, 0, name)?);
-                Some(Value::Null)
+            pairs = pairs + i + j + " ";
+            if (j == 3) {
+                snap;
             }
-            "ToInt" => Some(Value::Number(
-                CoreBuiltins::to_int(self.number_arg(args, 0, name)?) as f64,
-            )),
-            "ToDouble" => Some(Value::Number(
-                CoreBuiltins::to_double(&self.string_arg(args, 0, name)?)
-                    .map_err(InterpreterError::Runtime)?,
-            )),
-            "ToString" => Some(Value::String(
-                args.first()
-                    .map(|v| v.to_string())
-                    .unwrap_or_else(|| "null".to_string()),
-            )),
-            "ToBoolean" => Some(Value::Boolean(CoreBuiltins::to_boolean(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            _ => None,
-        };
-
-        Ok(result)
+        }
     }
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
 
-    fn call_file_builtin(
-        &self,
-        name: &str,
-        args: &[Value],
-    ) -> Result<Option<Value>, InterpreterError> {
-        let result = match name {
-            "ReadFile" => Some(Value::String(
-                FileBuiltins::read_file(&self.string_arg(args, 0, name)?)
-                    .map_err(|e| InterpreterError::Runtime(e.to_string()))?,
-            )),
-            "WriteFile" => {
-                FileBuiltins::write_file(
-                    &self.string_arg(args, 0, name)?,
-                    &self.string_arg(args, 1, name)?,
-                )
-                .map_err(|e| InterpreterError::Runtime(e.to_string()))?;
-                Some(Value::Null)
-            }
-            "AppendFile" => {
-                FileBuiltins::append_file(
-                    &self.string_arg(args, 0, name)?,
-                    &self.string_arg(args, 1, name)?,
-                )
-                .map_err(|e| InterpreterError::Runtime(e.to_string()))?;
-                Some(Value::Null)
-            }
-            "FileExists" => Some(Value::Boolean(FileBuiltins::file_exists(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "IsFile" => Some(Value::Boolean(FileBuiltins::is_file(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "IsDirectory" => Some(Value::Boolean(FileBuiltins::is_directory(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "DeleteFile" => {
-                FileBuiltins::delete_file(&self.string_arg(args, 0, name)?)
-                    .map_err(|e| InterpreterError::Runtime(e.to_string()))?;
-                Some(Value::Null)
-            }
-            "CreateDirectory" => {
-                FileBuiltins::create_directory(&self.string_arg(args, 0, name)?)
-                    .map_err(|e| InterpreterError::Runtime(e.to_string()))?;
-                Some(Value::Null)
-            }
-            "ListDirectory" => {
-                let files = FileBuiltins::list_directory(&self.string_arg(args, 0, name)?)
-                    .map_err(|e| InterpreterError::Runtime(e.to_string()))?
-                    .into_iter()
-                    .map(Value::String)
-                    .collect();
-                Some(Value::Array(files))
-            }
-            "GetFileSize" => Some(Value::Number(
-                FileBuiltins::get_file_size(&self.string_arg(args, 0, name)?)
-                    .map_err(|e| InterpreterError::Runtime(e.to_string()))? as f64,
-            )),
-            "CopyFile" => Some(Value::Number(
-                FileBuiltins::copy_file(
-                    &self.string_arg(args, 0, name)?,
-                    &self.string_arg(args, 1, name)?,
-                )
-                .map_err(|e| InterpreterError::Runtime(e.to_string()))? as f64,
-            )),
-            "RenameFile" => {
-                FileBuiltins::rename_file(
-                    &self.string_arg(args, 0, name)?,
-                    &self.string_arg(args, 1, name)?,
-                )
-                .map_err(|e| InterpreterError::Runtime(e.to_string()))?;
-                Some(Value::Null)
-            }
-            "GetFileExtension" => Some(self.option_string_to_value(
-                FileBuiltins::get_file_extension(&self.string_arg(args, 0, name)?),
-            )),
-            "GetFileName" => Some(self.option_string_to_value(FileBuiltins::get_file_name(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "GetParentDirectory" => Some(self.option_string_to_value(
-                FileBuiltins::get_parent_directory(&self.string_arg(args, 0, name)?),
-            )),
-            _ => None,
-        };
+        let mut interpreter = Interpreter::new();
+        interpreter.execute_program(ast).unwrap();
 
-        Ok(result)
+        assert_eq!(
+            interpreter.get_variable("pairs").unwrap(),
+            Value::String("01 03 21 23 ".to_string())
+        );
+        assert_eq!(
+            interpreter.get_variable("visits").unwrap(),
+            Value::Number(8.0)
+        );
     }
 
-    fn call_hashing_builtin(
-        &self,
-        name: &str,
-        args: &[Value],
-    ) -> Result<Option<Value>, InterpreterError> {
-        let result = match name {
-            "HashString" => Some(Value::Number(HashingBuiltins::hash_string(
-                &self.string_arg(args, 0, name)?,
-            ) as f64)),
-            "HashNumber" => Some(Value::Number(HashingBuiltins::hash_number(
-                self.number_arg(args, 0, name)?,
-            ) as f64)),
-            "SimpleRandom" => Some(Value::Number(HashingBuiltins::simple_random(
-                self.u64_arg(args, 0, name)?,
-            ) as f64)),
-            "AreAnagrams" => Some(Value::Boolean(HashingBuiltins::are_anagrams(
-                &self.string_arg(args, 0, name)?,
-                &self.string_arg(args, 1, name)?,
-            ))),
-            "IsPalindrome" => Some(Value::Boolean(HashingBuiltins::is_palindrome(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "CountOccurrences" => Some(Value::Number(HashingBuiltins::count_occurrences(
-                &self.string_arg(args, 0, name)?,
-                &self.string_arg(args, 1, name)?,
-            ) as f64)),
-            "RemoveDuplicates" => Some(Value::String(HashingBuiltins::remove_duplicates(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "UniqueCharacters" => Some(Value::String(HashingBuiltins::unique_characters(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "ReverseWords" => Some(Value::String(HashingBuiltins::reverse_words(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "TitleCase" => Some(Value::String(HashingBuiltins::title_case(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            _ => None,
-        };
-
-        Ok(result)
+    #[test]
+    fn test_spread_expands_arrays_and_arguments() {
+        let source = r#"
+Focus {
+    suggestion sum3(a: number, b: number, c: number): number {
+        awaken a + b + c;
+    }
+    induce head = [1, 2];
+    induce all = [0, ...head, ...[], 3];
+    induce total = sum3(...head, 10);
+    induce failed = false;
+    attempt {
+        induce broken = [...5];
+    } recover (error) {
+        failed = true;
     }
+} Relax
+"#;
+        let interpreter = run(source);
 
-    fn call_statistics_builtin(
-        &self,
-        name: &str,
-        args: &[Value],
-    ) -> Result<Option<Value>, InterpreterError> {
-        let numbers_primary = |this: &Self| -> Result<Vec<f64>, InterpreterError> {
-            let array = this.array_arg(args, 0, name)?;
-            this.values_to_numbers(&array, name)
-        };
+        assert_eq!(
+            interpreter.get_variable("all").unwrap(),
+            Value::array(vec![
+                Value::Number(0.0),
+                Value::Number(1.0),
+                Value::Number(2.0),
+                Value::Number(3.0),
+            ])
+        );
+        assert_eq!(
+            interpreter.get_variable("total").unwrap(),
+            Value::Number(13.0)
+        );
+        assert_eq!(
+            interpreter.get_variable("failed").unwrap(),
+            Value::Boolean(true)
+        );
+    }
 
-        let result = match name {
-            "Mean" => Some(Value::Number(StatisticsBuiltins::calculate_mean(
-                &numbers_primary(self)?,
-            ))),
-            "Median" => Some(Value::Number(StatisticsBuiltins::calculate_median(
-                &numbers_primary(self)?,
-            ))),
-            "Mode" => Some(Value::Number(StatisticsBuiltins::calculate_mode(
-                &numbers_primary(self)?,
-            ))),
-            "StandardDeviation" => Some(Value::Number(
-                StatisticsBuiltins::calculate_standard_deviation(&numbers_primary(self)?),
-            )),
-            "Variance" => Some(Value::Number(StatisticsBuiltins::calculate_variance(
-                &numbers_primary(self)?,
-            ))),
-            "Range" => Some(Value::Number(StatisticsBuiltins::calculate_range(
-                &numbers_primary(self)?,
-            ))),
-            "Percentile" => Some(Value::Number(StatisticsBuiltins::calculate_percentile(
-                &numbers_primary(self)?,
-                self.number_arg(args, 1, name)?,
-            ))),
-            "Correlation" => {
-                let x = self.values_to_numbers(&self.array_arg(args, 0, name)?, name)?;
-                let y = self.values_to_numbers(&self.array_arg(args, 1, name)?, name)?;
-                Some(Value::Number(StatisticsBuiltins::calculate_correlation(
-                    &x, &y,
-                )))
-            }
-            "LinearRegression" => {
-                let x = self.values_to_numbers(&self.array_arg(args, 0, name)?, name)?;
-                let y = self.values_to_numbers(&self.array_arg(args, 1, name)?, name)?;
-                let (slope, intercept) = StatisticsBuiltins::linear_regression(&x, &y);
-                Some(Value::Array(vec![
-                    Value::Number(slope),
-                    Value::Number(intercept),
-                ]))
-            }
-            _ => None,
-        };
+    #[test]
+    fn test_default_rest_and_named_arguments() {
+        let source = r#"
+Focus {
+    suggestion greet(name: string = "guest", greeting: string = "Hello " + name): string {
+        awaken greeting + ", " + name;
+    }
+    suggestion total(prefix: string, ...values: number[]): string {
+        induce sum: number = 0;
+        induce i: number = 0;
+        while (i < Length(values)) {
+            sum = sum + values[i];
+            i = i + 1;
+        }
+        awaken prefix + sum;
+    }
+    session Point {
+        expose x: number;
+        expose y: number;
 
-        Ok(result)
+        suggestion constructor(x: number = 0, y: number = 0) {
+            this.x = x;
+            this.y = y;
+        }
     }
 
-    fn call_system_builtin(
-        &self,
-        name: &str,
-        args: &[Value],
-    ) -> Result<Option<Value>, InterpreterError> {
-        let result = match name {
-            "GetCurrentDirectory" => Some(Value::String(SystemBuiltins::get_current_directory())),
-            "GetEnv" => Some(self.option_string_to_value(SystemBuiltins::get_env_var(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "SetEnv" => {
-                SystemBuiltins::set_env_var(
-                    &self.string_arg(args, 0, name)?,
-                    &self.string_arg(args, 1, name)?,
-                )
-                .map_err(InterpreterError::Runtime)?;
-                Some(Value::Null)
-            }
-            "GetOperatingSystem" => Some(Value::String(SystemBuiltins::get_operating_system())),
-            "GetArchitecture" => Some(Value::String(SystemBuiltins::get_architecture())),
-            "GetCpuCount" => Some(Value::Number(SystemBuiltins::get_cpu_count() as f64)),
-            "GetHostname" => Some(Value::String(SystemBuiltins::get_hostname())),
-            "GetUsername" => Some(Value::String(SystemBuiltins::get_username())),
-            "GetHomeDirectory" => Some(Value::String(SystemBuiltins::get_home_directory())),
-            "GetTempDirectory" => Some(Value::String(SystemBuiltins::get_temp_directory())),
-            "GetArgs" => Some(Value::Array(
-                SystemBuiltins::get_args()
-                    .into_iter()
-                    .map(Value::String)
-                    .collect(),
-            )),
-            "Exit" => {
-                // Exit mirrors the legacy runtime behavior by terminating the host process immediately.
-                SystemBuiltins::exit(self.integer_arg(args, 0, name)? as i32);
-            }
-            _ => None,
-        };
+    induce plain = greet();
+    induce named = greet(greeting: "Hi", name: "Ada");
+    induce none = total("none: ");
+    induce some = total("sum: ", 1, 2, 3);
+    induce origin = Point();
+    induce shifted = Point(y: 4);
+    induce coords = "" + origin.x + origin.y + shifted.x + shifted.y;
+    induce failed = false;
+    attempt {
+        greet(nickname: "x");
+    } recover (error) {
+        failed = true;
+    }
+    induce builtinError = "";
+    attempt {
+        ToUpper(text: "abc");
+    } recover (error) {
+        builtinError = error.message;
+    }
+} Relax
+"#;
+        let interpreter = run(source);
 
-        Ok(result)
+        assert_eq!(var_string(&interpreter, "plain"), "Hello guest, guest");
+        assert_eq!(var_string(&interpreter, "named"), "Hi, Ada");
+        assert_eq!(var_string(&interpreter, "none"), "none: 0");
+        assert_eq!(var_string(&interpreter, "some"), "sum: 6");
+        assert_eq!(var_string(&interpreter, "coords"), "0004");
+        assert_eq!(
+            interpreter.get_variable("failed").unwrap(),
+            Value::Boolean(true)
+        );
+        assert!(
+            var_string(&interpreter, "builtinError")
+                .starts_with("Builtin function 'ToUpper' does not accept named arguments")
+        );
     }
 
-    fn call_time_builtin(
-        &self,
-        name: &str,
-        args: &[Value],
-    ) -> Result<Option<Value>, InterpreterError> {
-        let result = match name {
-            "CurrentTimestamp" => Some(Value::Number(TimeBuiltins::get_current_time() as f64)),
-            "CurrentDate" => Some(Value::String(TimeBuiltins::get_current_date())),
-            "CurrentTime" => Some(Value::String(TimeBuiltins::get_current_time_string())),
-            "CurrentDateTime" => Some(Value::String(TimeBuiltins::get_current_date_time())),
-            "FormatDateTime" => Some(Value::String(TimeBuiltins::format_date_time(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "DayOfWeek" => Some(Value::Number(TimeBuiltins::get_day_of_week() as f64)),
-            "DayOfYear" => Some(Value::Number(TimeBuiltins::get_day_of_year() as f64)),
-            "IsLeapYear" => Some(Value::Boolean(TimeBuiltins::is_leap_year(
-                self.integer_arg(args, 0, name)? as i32,
-            ))),
-            "DaysInMonth" => Some(self.option_u32_to_value(TimeBuiltins::get_days_in_month(
-                self.integer_arg(args, 0, name)? as i32,
-                self.usize_arg(args, 1, name)? as u32,
-            ))),
-            "CurrentYear" => Some(Value::Number(TimeBuiltins::get_year() as f64)),
-            "CurrentMonth" => Some(Value::Number(TimeBuiltins::get_month() as f64)),
-            "CurrentDay" => Some(Value::Number(TimeBuiltins::get_day() as f64)),
-            "CurrentHour" => Some(Value::Number(TimeBuiltins::get_hour() as f64)),
-            "CurrentMinute" => Some(Value::Number(TimeBuiltins::get_minute() as f64)),
-            "CurrentSecond" => Some(Value::Number(TimeBuiltins::get_second() as f64)),
-            _ => None,
-        };
+    #[test]
+    fn test_mesmerize_calls_return_pending_promises() {
+        let source = r#"
+Focus {
+    induce trail = "";
+    mesmerize suggestion work(tag: string): string {
+        trail = trail + tag;
+        awaken tag + "!";
+    }
+    mesmerize suggestion fail() {
+        disrupt "broken";
+    }
 
-        Ok(result)
+    induce pending = work("a");
+    induce state = "" + pending;
+    trail = trail + "called ";
+    induce result = surrenderTo pending;
+    induce caught = "";
+    attempt {
+        await fail();
+    } recover (error) {
+        caught = error.message;
     }
+    work("late");
+} Relax
+"#;
+        let interpreter = run(source);
 
-    fn call_validation_builtin(
-        &self,
-        name: &str,
-        args: &[Value],
-    ) -> Result<Option<Value>, InterpreterError> {
-        let result = match name {
-            "IsValidEmail" => Some(Value::Boolean(ValidationBuiltins::is_valid_email(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "IsValidUrl" => Some(Value::Boolean(ValidationBuiltins::is_valid_url(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "IsValidPhoneNumber" => Some(Value::Boolean(
-                ValidationBuiltins::is_valid_phone_number(&self.string_arg(args, 0, name)?),
-            )),
-            "IsAlphanumeric" => Some(Value::Boolean(ValidationBuiltins::is_alphanumeric(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "IsAlphabetic" => Some(Value::Boolean(ValidationBuiltins::is_alphabetic(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "IsNumeric" => Some(Value::Boolean(ValidationBuiltins::is_numeric(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "IsLowercase" => Some(Value::Boolean(ValidationBuiltins::is_lowercase(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "IsUppercase" => Some(Value::Boolean(ValidationBuiltins::is_uppercase(
-                &self.string_arg(args, 0, name)?,
-            ))),
-            "IsInRange" => Some(Value::Boolean(ValidationBuiltins::is_in_range(
-                self.number_arg(args, 0, name)?,
-                self.number_arg(args, 1, name)?,
-                self.number_arg(args, 2, name)?,
-            ))),
-            "MatchesPattern" => Some(Value::Boolean(ValidationBuiltins::matches_pattern(
-                &self.string_arg(args, 0, name)?,
-                &self.string_arg(args, 1, name)?,
-            ))),
-            _ => None,
-        };
+        assert_eq!(var_string(&interpreter, "state"), "<promise pending>");
+        assert_eq!(var_string(&interpreter, "result"), "a!");
+        assert_eq!(var_string(&interpreter, "caught"), "broken");
+        // Calls that are never awaited run when the program ends
+        assert_eq!(var_string(&interpreter, "trail"), "called alate");
+    }
 
-        Ok(result)
+    #[test]
+    fn test_mesmerize_chained_awaits() {
+        let source = r#"
+Focus {
+    induce trail = "";
+    mesmerize suggestion a(): number {
+        await AsyncDelay(50);
+        trail = trail + "a";
+        awaken 1;
+    }
+    mesmerize suggestion b(first: number): number {
+        induce value = await first;
+        trail = trail + "b";
+        awaken value + 1;
     }
 
-    fn arg<'a>(
-        &self,
-        args: &'a [Value],
-        index: usize,
-        name: &str,
-    ) -> Result<&'a Value, InterpreterError> {
-        args.get(index).ok_or_else(|| {
-            InterpreterError::Runtime(format!(
-                "Builtin '{}' expected argument at position {}",
-                name,
-                index + 1
-            ))
-        })
+    induce pa = a();
+    induce pb = b(pa);
+    induce result = await pb;
+    induce again = await pa;
+} Relax
+"#;
+        let interpreter = run(source);
+
+        assert_eq!(var_string(&interpreter, "result"), "2");
+        assert_eq!(var_string(&interpreter, "again"), "1");
+        assert_eq!(var_string(&interpreter, "trail"), "ab");
     }
 
-    fn number_arg(
-        &self,
-        args: &[Value],
-        index: usize,
-        name: &str,
-    ) -> Result<f64, InterpreterError> {
-        self.arg(args, index, name)?.to_number().map_err(|_| {
-            InterpreterError::TypeError(format!(
-                "Builtin '{}' expected numeric argument at position {}",
-                name,
-                index + 1
-            ))
-        })
+    #[test]
+    fn test_mesmerize_await_cycle_is_an_error() {
+        let source = r#"
+Focus {
+    induce pending = null;
+    mesmerize suggestion waitForSelf(): number {
+        awaken await pending;
     }
+    pending = waitForSelf();
+    await pending;
+} Relax
+"#;
+        let ast = parse(source);
 
-    fn integer_arg(
-        &self,
-        args: &[Value],
-        index: usize,
-        name: &str,
-    ) -> Result<i64, InterpreterError> {
-        let value = self.number_arg(args, index, name)?;
-        Ok(value.round() as i64)
+        let mut interpreter = Interpreter::new();
+        let error = interpreter.execute_program(ast).unwrap_err();
+        assert!(error.to_string().contains("can never settle"), "{}", error);
     }
 
-    fn u64_arg(&self, args: &[Value], index: usize, name: &str) -> Result<u64, InterpreterError> {
-        let value = self.number_arg(args, index, name)?;
-        if value < 0.0 {
-            return Err(InterpreterError::TypeError(format!(
-                "Builtin '{}' expected non-negative number at position {}",
-                name,
-                index + 1
-            )));
-        }
-        Ok(value.round() as u64)
+    #[test]
+    fn test_mesmerize_async_io_runs_concurrently() {
+        let source = r#"
+Focus {
+    induce trail = "";
+    mesmerize suggestion nap(name: string, ms: number): number {
+        trail = trail + "<" + name;
+        await AsyncDelay(ms);
+        trail = trail + ">" + name;
+        awaken ms;
     }
 
-    fn usize_arg(
-        &self,
-        args: &[Value],
-        index: usize,
-        name: &str,
-    ) -> Result<usize, InterpreterError> {
-        let value = self.number_arg(args, index, name)?;
-        if value < 0.0 {
-            return Err(InterpreterError::TypeError(format!(
-                "Builtin '{}' expected non-negative number at position {}",
-                name,
-                index + 1
-            )));
-        }
-        Ok(value.round() as usize)
+    induce slow = nap("slow", 300);
+    induce fast = nap("fast", 10);
+    induce total = await slow + await fast;
+} Relax
+"#;
+        let interpreter = run(source);
+
+        assert_eq!(var_string(&interpreter, "total"), "310");
+        // The fast nap starts while the slow one waits and finishes first
+        assert_eq!(var_string(&interpreter, "trail"), "<slow<fast>fast>slow");
     }
 
-    fn string_arg(
-        &self,
-        args: &[Value],
-        index: usize,
-        name: &str,
-    ) -> Result<String, InterpreterError> {
-        match self.arg(args, index, name)? {
-            Value::String(s) => Ok(s.clone()),
-            other => Err(InterpreterError::TypeError(format!(
-                "Builtin '{}' expected string argument at position {}, got {:?}",
-                name,
-                index + 1,
-                other
-            ))),
+    #[test]
+    fn test_mesmerize_suspends_inside_loops_and_branches() {
+        let source = r#"
+Focus {
+    induce trail = "";
+    mesmerize suggestion count(times: number): number {
+        induce sum = 0;
+        loop (induce i in 0..times) {
+            if (i >= 0) {
+                await AsyncDelay(5);
+            }
+            sum = sum + i;
         }
+        trail = trail + "a";
+        awaken sum;
+    }
+    mesmerize suggestion after(first: number): number {
+        // Nested in an expression: waits without suspending the call
+        induce value = 1 + await first;
+        trail = trail + "b";
+        awaken value;
     }
 
-    fn char_arg(&self, args: &[Value], index: usize, name: &str) -> Result<char, InterpreterError> {
-        let text = self.string_arg(args, index, name)?;
-        text.chars().next().ok_or_else(|| {
-            InterpreterError::TypeError(format!(
-                "Builtin '{}' expected non-empty string to derive character at position {}",
-                name,
-                index + 1
-            ))
-        })
+    induce pa = count(3);
+    induce pb = after(pa);
+    induce done = await pb;
+    induce total = await pa;
+} Relax
+"#;
+        let interpreter = run(source);
+
+        assert_eq!(var_string(&interpreter, "done"), "4");
+        assert_eq!(var_string(&interpreter, "total"), "3");
+        assert_eq!(var_string(&interpreter, "trail"), "ab");
+    }
+
+    #[test]
+    fn test_null_literal_and_union_type_patterns() {
+        let source = r#"
+Focus {
+    suggestion describe(value: number | string | null): string {
+        awaken entrain value {
+            when n: number => "number"
+            when s: "on" | "off" => "switch"
+            when s: string[] => "strings"
+            when null => "nothing"
+            otherwise => "other"
+        };
+    }
+
+    induce missing: lucid number = null;
+    induce results = [
+        describe(1),
+        describe("on"),
+        describe(["a", "b"]),
+        describe(missing),
+        describe("text")
+    ];
+    induce fallback = missing ?? 7;
+} Relax
+"#;
+        let interpreter = run(source);
+
+        assert_eq!(
+            var_string(&interpreter, "results"),
+            "[number, switch, strings, nothing, other]"
+        );
+        assert_eq!(var_string(&interpreter, "fallback"), "7");
+    }
+
+    #[test]
+    fn test_session_inheritance_and_super_calls() {
+        let source = r#"
+Focus {
+    session User {
+        expose name: string;
+        conceal secret: string = "pw";
+        dominant expose count: number = 0;
+
+        suggestion constructor(name: string) {
+            this.name = name;
+            User.count = User.count + 1;
+        }
+        expose suggestion greet(): string {
+            awaken "Hello " + this.name;
+        }
+        expose suggestion reveal(): string {
+            awaken this.secret;
+        }
     }
 
-    fn array_arg(
-        &self,
-        args: &[Value],
-        index: usize,
-        name: &str,
-    ) -> Result<Vec<Value>, InterpreterError> {
-        match self.arg(args, index, name)? {
-            Value::Array(items) => Ok(items.clone()),
-            other => Err(InterpreterError::TypeError(format!(
-                "Builtin '{}' expected array argument at position {}, got {:?}",
-                name,
-                index + 1,
-                other
-            ))),
+    session Admin extends User {
+        expose level: number = 1;
+
+        suggestion constructor(name: string, level: number) {
+            super(name);
+            this.level = level;
+        }
+        expose suggestion greet(): string {
+            awaken super.greet() + " (level " + this.level + ")";
+        }
+        expose suggestion leak(): string {
+            awaken this.secret;
         }
     }
 
-    fn option_string_to_value(&self, input: Option<String>) -> Value {
-        input.map(Value::String).unwrap_or(Value::Null)
+    session Guest extends User { }
+
+    suggestion welcome(user: User): string {
+        awaken user.greet();
     }
 
-    fn option_u32_to_value(&self, input: Option<u32>) -> Value {
-        input
-            .map(|v| Value::Number(v as f64))
-            .unwrap_or(Value::Null)
+    induce admin = Admin("Ada", 3);
+    induce greeting = welcome(admin);
+    induce revealed = admin.reveal();
+    induce guest = Guest("Bob").greet();
+    induce count = Admin.count;
+    induce kind = entrain admin {
+        when u: User => "user"
+        otherwise => "other"
+    };
+    induce leaked = "";
+    attempt {
+        leaked = admin.leak();
+    } recover (error) {
+        leaked = error.message;
+    }
+} Relax
+"#;
+        let interpreter = run(source);
+
+        assert_eq!(var_string(&interpreter, "greeting"), "Hello Ada (level 3)");
+        // Inherited methods see the concealed fields of their own session
+        assert_eq!(var_string(&interpreter, "revealed"), "pw");
+        assert_eq!(var_string(&interpreter, "guest"), "Hello Bob");
+        assert_eq!(var_string(&interpreter, "count"), "2");
+        assert_eq!(var_string(&interpreter, "kind"), "user");
+        assert!(
+            var_string(&interpreter, "leaked").contains("private field 'secret' of session 'User'"),
+            "{}",
+            var_string(&interpreter, "leaked")
+        );
     }
 
-    fn values_to_numbers(
-        &self,
-        values: &[Value],
-        name: &str,
-    ) -> Result<Vec<f64>, InterpreterError> {
-        values
-            .iter()
-            .enumerate()
-            .map(|(i, value)| {
-                value.to_number().map_err(|_| {
-                    InterpreterError::TypeError(format!(
-                        "Builtin '{}' expected numeric array element at position {}",
-                        name,
-                        i + 1
-                    ))
-                })
-            })
-            .collect()
+    #[test]
+    fn test_compound_assignment_and_update_evaluate_targets_once() {
+        let source = r#"
+Focus {
+    induce calls = 0;
+    suggestion next(): number {
+        calls += 1;
+        awaken 1;
+    }
+    session Counter {
+        expose count: number = 0;
+        expose items: number[] = [1, 2];
     }
 
-    fn push_scope(&mut self) {
-        self.locals.push(HashMap::new());
-        self.const_locals.push(HashSet::new());
+    induce counter = Counter();
+    induce items = [10, 20, 30];
+    items[next()] += 5;
+    counter.items[next()] *= 10;
+    counter.count++;
+    induce pre = ++counter.count;
+    induce post = counter.count--;
+    induce name: string | null = null;
+    name ??= "guest";
+    name ??= next();
+    induce text = "a";
+    text += "b";
+    induce rest = 17;
+    rest %= 5;
+} Relax
+"#;
+        let interpreter = run(source);
+
+        assert_eq!(var_string(&interpreter, "items"), "[10, 25, 30]");
+        assert_eq!(var_string(&interpreter, "calls"), "2");
+        assert_eq!(var_string(&interpreter, "pre"), "2");
+        assert_eq!(var_string(&interpreter, "post"), "2");
+        assert_eq!(var_string(&interpreter, "name"), "guest");
+        assert_eq!(var_string(&interpreter, "text"), "ab");
+        assert_eq!(var_string(&interpreter, "rest"), "2");
+        let counter = interpreter.get_variable("counter").unwrap();
+        let Value::Instance(counter) = counter else {
+            panic!("expected session instance, got {counter:?}");
+        };
+        assert_eq!(
+            counter.borrow().get_field("count"),
+            Some(Value::Number(1.0))
+        );
+        assert_eq!(
+            counter.borrow().get_field("items").unwrap().to_string(),
+            "[1, 20]"
+        );
     }
 
-    fn pop_scope(&mut self) {
-        self.locals.pop();
-        self.const_locals.pop();
+    #[test]
+    fn test_arrays_are_shared_and_anchor_copies() {
+        let source = r#"
+Focus {
+    suggestion addOne(list: number[]) {
+        ArrayPush(list, 99);
+    }
+    session Holder {
+        expose items: number[] = [];
     }
 
-    fn define_variable(
-        &mut self,
-        storage: VariableStorage,
-        name: String,
-        value: Value,
-        is_constant: bool,
-    ) {
-        match storage {
-            VariableStorage::SharedTrance => {
-                self.shared.insert(name.clone(), value);
-                if is_constant {
-                    self.const_globals.insert(name);
-                } else {
-                    self.const_globals.remove(&name);
-                }
-            }
-            VariableStorage::Local => {
-                if let Some(scope) = self.locals.last_mut() {
-                    scope.insert(name.clone(), value);
-                    if let Some(const_scope) = self.const_locals.last_mut() {
-                        if is_constant {
-                            const_scope.insert(name);
-                        } else {
-                            const_scope.remove(&name);
-                        }
-                    }
-                } else {
-                    self.globals.insert(name.clone(), value);
-                    if is_constant {
-                        self.const_globals.insert(name);
-                    } else {
-                        self.const_globals.remove(&name);
-                    }
-                }
-            }
-        }
+    induce xs = [3, 1, 2];
+    induce alias = xs;
+    anchor saved = xs;
+    addOne(xs);
+    alias[0] = 7;
+    induce holder = Holder();
+    holder.items = xs;
+    ArrayPush(holder.items, 4);
+
+    induce popped = ArrayPop(xs);
+    induce length = ArrayInsert(xs, 1, 5);
+    induce removed = ArrayRemoveAt(xs, 0);
+    induce sorted = ArraySortInPlace(xs);
+    induce names = ["b", "c", "a"];
+    ArraySortInPlace(names);
+    induce empty = ArrayPop([]);
+} Relax
+"#;
+        let interpreter = run(source);
+
+        assert_eq!(var_string(&interpreter, "saved"), "[3, 1, 2]");
+        assert_eq!(var_string(&interpreter, "popped"), "4");
+        assert_eq!(var_string(&interpreter, "length"), "5");
+        assert_eq!(var_string(&interpreter, "removed"), "7");
+        assert_eq!(var_string(&interpreter, "xs"), "[1, 2, 5, 99]");
+        assert_eq!(var_string(&interpreter, "alias"), "[1, 2, 5, 99]");
+        assert_eq!(var_string(&interpreter, "sorted"), "[1, 2, 5, 99]");
+        assert_eq!(var_string(&interpreter, "names"), "[a, b, c]");
+        assert_eq!(var_string(&interpreter, "empty"), "null");
     }
 
-    fn set_variable(
-        &mut self,
-        name: String,
-        value: Value,
-        scope_hint: ScopeLayer,
-    ) -> Result<(), InterpreterError> {
-        let check_const = |is_const: bool| -> Result<(), InterpreterError> {
-            if is_const {
-                return Err(InterpreterError::Runtime(localized(
-                    &format!("Cannot reassign constant variable '{}'", name),
-                    &format!(
-                        "Konstante Variable '{}' kann nicht neu zugewiesen werden",
-                        name
-                    ),
-                )));
-            }
-            Ok(())
-        };
+    #[test]
+    fn test_self_referencing_arrays() {
+        let source = r#"
+Focus {
+    induce a = [1];
+    ArrayPush(a, a);
+    induce shared = [0];
+    induce pair = [shared, shared];
+    anchor copy = a;
+    copy[0] = 2;
+    anchor pairCopy = pair;
+    ArrayPush(pairCopy[0], 5);
+    induce wrapped = { "list": a };
+    induce json = DictToJson({ "pair": pair });
+    induce b = [1];
+    ArrayPush(b, b);
+    induce sameShape = ArrayContains([b], a);
+    induce c = [2];
+    ArrayPush(c, c);
+    induce otherShape = ArrayIndexOf([c], a) == 0;
+} Relax
+"#;
+        let interpreter = run(source);
+
+        assert_eq!(var_string(&interpreter, "a"), "[1, [...]]");
+        assert_eq!(var_string(&interpreter, "copy"), "[2, [...]]");
+        assert_eq!(var_string(&interpreter, "pair"), "[[0], [0]]");
+        assert_eq!(var_string(&interpreter, "pairCopy"), "[[0, 5], [0, 5]]");
+        assert_eq!(var_string(&interpreter, "wrapped"), "{list: [1, [...]]}");
+        assert_eq!(var_string(&interpreter, "json"), r#"{"pair":[[0],[0]]}"#);
+        assert_eq!(var_string(&interpreter, "sameShape"), "true");
+        assert_eq!(var_string(&interpreter, "otherShape"), "false");
+
+        let source = r#"Focus { induce a = [1]; ArrayPush(a, a); DictToJson({ "a": a }); } Relax"#;
+        let error = Interpreter::new()
+            .execute_program(parse(source))
+            .unwrap_err();
+        assert!(
+            error.to_string().contains("array that contains itself"),
+            "{}",
+            error
+        );
+    }
 
-        match scope_hint {
-            ScopeLayer::Local => {
-                if let Some((scope, consts)) = self.locals.last_mut().zip(self.const_locals.last())
-                    && let Some(slot) = scope.get_mut(&name)
-                {
-                    check_const(consts.contains(&name))?;
-                    *slot = value;
-                    return Ok(());
-                }
-            }
-            ScopeLayer::Global => {
-                if self.globals.contains_key(&name) {
-                    check_const(self.const_globals.contains(&name))?;
-                    self.globals.insert(name, value);
-                    return Ok(());
-                }
-            }
-            ScopeLayer::Shared => {
-                if self.shared.contains_key(&name) {
-                    check_const(self.const_globals.contains(&name))?;
-                    self.shared.insert(name, value);
-                    return Ok(());
-                }
-            }
+    #[test]
+    fn test_array_mutation_builtin_errors() {
+        let mut interpreter = Interpreter::new();
+        for source in [
+            "Focus { ArrayRemoveAt([1], 3); } Relax",
+            "Focus { ArrayInsert([1], 5, 2); } Relax",
+            "Focus { ArraySortInPlace([1, \"a\"]); } Relax",
+        ] {
+            let ast = parse(source);
+            assert!(interpreter.execute_program(ast).is_err(), "{source}");
         }
+    }
 
-        for idx in (0..self.locals.len()).rev() {
-            if self.locals[idx].contains_key(&name) {
-                let is_const = self
-                    .const_locals
-                    .get(idx)
-                    .map(|set| set.contains(&name))
-                    .unwrap_or(false);
-                check_const(is_const)?;
-                self.locals[idx].insert(name.clone(), value);
-                return Ok(());
-            }
-        }
+    #[test]
+    fn test_enum_variants_and_variant_patterns() {
+        let source = r#"
+Focus {
+    enum State { Idle, Running(number), Failed(string, number) }
+
+    suggestion describe(state: State): string {
+        awaken entrain state {
+            when State.Idle => "idle"
+            when State.Running(speed) if speed > 5 => "fast " + speed
+            when State.Running(speed) => "running " + speed
+            when State.Failed(reason, code) => reason + " (" + code + ")"
+        };
+    }
 
-        if self.globals.contains_key(&name) {
-            check_const(self.const_globals.contains(&name))?;
-            self.globals.insert(name, value);
-            return Ok(());
-        }
+    induce states = [State.Idle, State.Running(3), State.Running(9), State.Failed("disk", 7)];
+    induce descriptions = [describe(states[0]), describe(states[1]), describe(states[2]), describe(states[3])];
+    induce shown = states[3];
+    induce same = State.Running(3) == State.Running(3);
+    induce different = State.Running(3) == State.Running(4);
+    induce typed = entrain states[1] { when s: State => "state" otherwise => "other" };
+} Relax
+"#;
+        let interpreter = run(source);
 
-        if self.shared.contains_key(&name) {
-            check_const(self.const_globals.contains(&name))?;
-            self.shared.insert(name, value);
-            return Ok(());
-        }
+        assert_eq!(
+            var_string(&interpreter, "descriptions"),
+            "[idle, running 3, fast 9, disk (7)]"
+        );
+        assert_eq!(var_string(&interpreter, "shown"), "State.Failed(disk, 7)");
+        assert_eq!(var_string(&interpreter, "same"), "true");
+        assert_eq!(var_string(&interpreter, "different"), "false");
+        assert_eq!(var_string(&interpreter, "typed"), "state");
 
-        if let Some(scope) = self.locals.last_mut() {
-            scope.insert(name.clone(), value);
-            return Ok(());
-        }
+        let source = r#"
+Focus {
+    enum State { Idle, Running(number) }
+    induce broken = State.Running(1, 2);
+} Relax
+"#;
+        let ast = parse(source);
+        let error = Interpreter::new().execute_program(ast).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Variant 'State.Running' expects 1 payload value(s), got 2")
+        );
+    }
 
-        self.globals.insert(name.clone(), value);
-        Ok(())
+    #[test]
+    fn test_interfaces_in_parameters_and_type_patterns() {
+        let source = r#"
+Focus {
+    interface Plugin {
+        name: string;
+        suggestion run(input: string): string;
     }
+    interface Named { name: string; }
 
-    fn resolve_assignment_scope(&self, name: &str) -> ScopeLayer {
-        if self
-            .locals
-            .iter()
-            .rev()
-            .any(|scope| scope.contains_key(name))
-        {
-            ScopeLayer::Local
-        } else if self.shared.contains_key(name) {
-            ScopeLayer::Shared
-        } else if self.globals.contains_key(name) {
-            ScopeLayer::Global
-        } else {
-            ScopeLayer::Local
-        }
+    session Echo implements Plugin {
+        expose name: string = "echo";
+        expose suggestion run(input: string): string { awaken input; }
+    }
+    session Loud extends Echo {
+        expose suggestion run(input: string): string { awaken input + "!"; }
     }
+    tranceify Tag { name: string; }
 
-    fn get_variable(&self, name: &str) -> Result<Value, InterpreterError> {
-        // Search in local scopes (from innermost to outermost)
-        for scope in self.locals.iter().rev() {
-            if let Some(value) = scope.get(name) {
-                return Ok(value.clone());
-            }
-        }
+    suggestion apply(plugin: Plugin, text: string): string {
+        awaken plugin.name + ": " + plugin.run(text);
+    }
+    suggestion describe(value: Plugin | Named | number): string {
+        awaken entrain value {
+            when p: Plugin => "plugin " + p.name
+            when n: Named => "named " + n.name
+            otherwise => "other"
+        };
+    }
 
-        if let Some(value) = self.globals.get(name) {
-            return Ok(value.clone());
-        }
+    induce applied = apply(Loud(), "hi");
+    induce kinds = [describe(Echo()), describe(Tag { name: "tag" }), describe(3)];
+} Relax
+"#;
+        let interpreter = run(source);
 
-        if let Some(value) = self.shared.get(name) {
-            return Ok(value.clone());
-        }
+        assert_eq!(var_string(&interpreter, "applied"), "echo: hi!");
+        assert_eq!(
+            var_string(&interpreter, "kinds"),
+            "[plugin echo, named tag, other]"
+        );
 
-        Err(InterpreterError::UndefinedVariable(name.to_string()))
+        let source = r#"
+Focus {
+    interface Plugin { suggestion run(input: string): string; }
+    session Broken implements Plugin { }
+} Relax
+"#;
+        let ast = parse(source);
+        let error = Interpreter::new().execute_program(ast).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Session 'Broken' does not implement 'run' of interface 'Plugin'"),
+            "{}",
+            error
+        );
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use hypnoscript_lexer_parser::{Lexer, Parser};
 
     #[test]
-    fn test_simple_program() {
+    fn test_session_finale_runs_on_release() {
         let source = r#"
 Focus {
-    induce x: number = 42;
-    induce y: number = 10;
-    induce sum: number = x + y;
-} Relax
-"#;
-        let mut lexer = Lexer::new(source);
-        let tokens = lexer.lex().unwrap();
-        let mut parser = Parser::new(tokens.clone());
-        let ast = match parser.parse_program() {
-            Ok(ast) => ast,
-            Err(err) => {
-                eprintln!("parse error: {err}");
-                for token in tokens {
-                    eprintln!("token: {:?}", token);
-                }
-                panic!("failed to parse test program");
-            }
-        };
+    induce events = [];
+    session Resource {
+        expose name: string = "";
+        suggestion constructor(name: string) {
+            this.name = name;
+        }
+        finale {
+            ArrayPush(events, "close " + this.name);
+        }
+    }
+    session Buffered extends Resource {
+        finale {
+            ArrayPush(events, "flush " + this.name);
+        }
+    }
+
+    suggestion work() {
+        induce temp = Resource("temp");
+        ArrayPush(events, "work");
+    }
+    work();
+    induce released: Resource | null = Resource("released");
+    released = null;
+    using (buffer = Buffered("buffer")) {
+        ArrayPush(events, "using " + buffer.name);
+    }
+    induce kept = Resource("kept");
+    finale {
+        ArrayPush(events, "program finale");
+    }
+    ArrayPush(events, "end");
+} Relax
+"#;
+        let interpreter = run(source);
 
-        let mut interpreter = Interpreter::new();
-        let result = interpreter.execute_program(ast);
-        assert!(result.is_ok());
+        assert_eq!(
+            interpreter.get_variable("events").unwrap().to_string(),
+            "[work, close temp, close released, using buffer, flush buffer, close buffer, \
+             end, program finale, close kept]"
+        );
     }
 
     #[test]
-    fn test_if_statement() {
+    fn test_program_finale_runs_after_error() {
         let source = r#"
 Focus {
-    induce x: number = 10;
-    if (x > 5) deepFocus {
-        induce result: number = 1;
+    induce events = [];
+    session Handle {
+        finale {
+            ArrayPush(events, "handle closed");
+        }
     }
+    induce handle = Handle();
+    finale {
+        ArrayPush(events, "program finale");
+    }
+    using (scoped = Handle()) {
+        disrupt "boom";
+    }
+    ArrayPush(events, "unreachable");
 } Relax
 "#;
-        let mut lexer = Lexer::new(source);
-        let tokens = lexer.lex().unwrap();
-        let mut parser = Parser::new(tokens.clone());
-        let ast = match parser.parse_program() {
-            Ok(ast) => ast,
-            Err(err) => {
-                eprintln!("parse error: {err}");
-                for token in tokens {
-                    eprintln!("token: {:?}", token);
-                }
-                panic!("failed to parse test program");
-            }
-        };
+        let ast = parse(source);
 
         let mut interpreter = Interpreter::new();
-        let result = interpreter.execute_program(ast);
-        assert!(result.is_ok());
+        assert!(interpreter.execute_program(ast).is_err());
+        assert_eq!(
+            interpreter.get_variable("events").unwrap().to_string(),
+            "[handle closed, program finale, handle closed]"
+        );
     }
 
     #[test]
-    fn test_session_constructor_and_methods() {
+    fn test_for_each_over_collections_and_ranges() {
         let source = r#"
 Focus {
-    session Counter {
-        expose value: number;
-
-        suggestion constructor(initial: number) {
-            this.value = initial;
-        }
-
-        suggestion inc() {
-            this.value = this.value + 1;
-        }
-
-        suggestion current(): number {
-            awaken this.value;
+    tranceify Point { x: number; y: number; }
+    induce visited = [];
+    induce total = 0;
+    loop (induce n in 0..5) { total += n; }
+    loop (induce i, ch in "hé") { ArrayPush(visited, i + ch); }
+    induce scores: Dictionary<string, number> = { "zoe": 3, "adam": 1 };
+    loop (induce name, score in scores) { ArrayPush(visited, name + score); }
+    loop (induce entry in scores) { ArrayPush(visited, entry[0]); }
+    loop (freeze field, value in Point { x: 1, y: 2 }) { ArrayPush(visited, field + value); }
+
+    induce items = [1, 2];
+    loop (induce item in items) { ArrayPush(items, item); }
+
+    induce pairs = [];
+    outer: loop (induce a in [1, 2, 3]) {
+        loop (induce b in 1..=3) {
+            if (b == 2) { sink outer; }
+            if (a == 3) { snap outer; }
+            ArrayPush(pairs, a * 10 + b);
         }
     }
 
-    induce counter = Counter(5);
-    counter.inc();
-    counter.inc();
-    induce current: number = counter.current();
+    induce callbacks = [];
+    loop (induce k in 0..3) { ArrayPush(callbacks, suggestion() { awaken k; }); }
+    induce captured = callbacks[0]() + callbacks[2]();
+
+    induce inclusive = 0..=10 step 5;
+    induce descending = 5..0 step -2;
+    induce fractions = 0..1 step 0.25;
+    induce empty = 3..3;
 } Relax
 "#;
+        let interpreter = run(source);
 
-        let mut lexer = Lexer::new(source);
-        let tokens = lexer.lex().unwrap();
-        let mut parser = Parser::new(tokens);
-        let ast = parser.parse_program().unwrap();
+        assert_eq!(var_string(&interpreter, "total"), "10");
+        assert_eq!(
+            var_string(&interpreter, "visited"),
+            "[0h, 1é, zoe3, adam1, zoe, adam, x1, y2]"
+        );
+        assert_eq!(var_string(&interpreter, "items"), "[1, 2, 1, 2]");
+        assert_eq!(var_string(&interpreter, "pairs"), "[11, 21]");
+        assert_eq!(var_string(&interpreter, "captured"), "2");
+        assert_eq!(var_string(&interpreter, "inclusive"), "[0, 5, 10]");
+        assert_eq!(var_string(&interpreter, "descending"), "[5, 3, 1]");
+        assert_eq!(
+            var_string(&interpreter, "fractions"),
+            "[0, 0.25, 0.5, 0.75]"
+        );
+        assert_eq!(var_string(&interpreter, "empty"), "[]");
+    }
 
+    #[test]
+    fn test_for_each_errors() {
         let mut interpreter = Interpreter::new();
-        if let Err(err) = interpreter.execute_program(ast) {
-            panic!("interpreter error: {err:?}");
+        for source in [
+            "Focus { loop (induce x in 42) { } } Relax",
+            "Focus { loop (induce x in 0..3 step 0) { } } Relax",
+        ] {
+            let ast = parse(source);
+            assert!(interpreter.execute_program(ast).is_err(), "{source}");
         }
-
-        let current = interpreter.get_variable("current").unwrap();
-        assert_eq!(current, Value::Number(7.0));
     }
 
     #[test]
-    fn test_hypnotic_operator_synonyms_execution() {
+    fn test_integer_arithmetic_is_exact() {
         let source = r#"
 Focus {
-    induce a: number = 10;
-    induce b: number = 5;
-
-    induce eq: boolean = a youAreFeelingVerySleepy b;
-    induce neq: boolean = a youCannotResist b;
-    induce ge: boolean = a yourEyesAreGettingHeavy 9;
-    induce le: boolean = b goingDeeper 4;
-    induce both: boolean = ge underMyControl neq;
-    induce either: boolean = le resistanceIsFutile eq;
+    induce big: integer = 9007199254740993i;
+    induce next = big + 1i;
+    induce widened = big + 0.5;
+    induce quotient = -7i / 2i;
+    induce remainder = -7i % 2i;
+    induce division = 7i / 2;
+    induce bits = (12i & 10i) | (1i << 4) ^ 3;
+    induce shifted = -16i >> 2;
+    induce inverted = ~5i;
+    induce whole = 6 & 3;
+    induce counter: integer = 41i;
+    counter++;
+    induce exact = big == 9007199254740992;
+    induce mixed = 3i == 3;
+    induce ordered = big > 9007199254740992i;
+    induce parsed = ToInt("9007199254740993") == big;
+    induce hash = HashString("trance") == HashString("trance");
+    induce random = SimpleRandom(42);
+    induce ids: Dictionary<integer, string> = {};
+    ids[big] = "big";
+    induce lookup = ids[9007199254740993i];
 } Relax
 "#;
+        let interpreter = run(source);
+
+        assert_eq!(var_string(&interpreter, "next"), "9007199254740994");
+        assert_eq!(var_string(&interpreter, "widened"), "9007199254740992");
+        assert_eq!(var_string(&interpreter, "quotient"), "-3");
+        assert_eq!(var_string(&interpreter, "remainder"), "-1");
+        assert_eq!(var_string(&interpreter, "division"), "3.5");
+        assert_eq!(var_string(&interpreter, "bits"), "27");
+        assert_eq!(var_string(&interpreter, "shifted"), "-4");
+        assert_eq!(var_string(&interpreter, "inverted"), "-6");
+        assert_eq!(var_string(&interpreter, "whole"), "2");
+        assert_eq!(var_string(&interpreter, "counter"), "42");
+        assert_eq!(var_string(&interpreter, "exact"), "false");
+        assert_eq!(var_string(&interpreter, "mixed"), "true");
+        assert_eq!(var_string(&interpreter, "ordered"), "true");
+        assert_eq!(var_string(&interpreter, "parsed"), "true");
+        assert_eq!(var_string(&interpreter, "hash"), "true");
+        assert_eq!(var_string(&interpreter, "random"), "1258627373665771185");
+        assert_eq!(var_string(&interpreter, "lookup"), "big");
+        assert!(matches!(
+            interpreter.get_variable("counter").unwrap(),
+            Value::Integer(42)
+        ));
+    }
 
-        let mut lexer = Lexer::new(source);
-        let tokens = lexer.lex().unwrap();
-        let mut parser = Parser::new(tokens);
-        let ast = parser.parse_program().unwrap();
-
+    #[test]
+    fn test_integer_errors() {
         let mut interpreter = Interpreter::new();
-        if let Err(err) = interpreter.execute_program(ast) {
-            panic!("interpreter error: {err:?}");
+        for (source, message) in [
+            (
+                "Focus { induce x = 9223372036854775807i + 1i; } Relax",
+                "Integer overflow in 9223372036854775807 + 1",
+            ),
+            (
+                "Focus { induce x: integer = 9223372036854775807i; x++; } Relax",
+                "Integer overflow",
+            ),
+            (
+                "Focus { induce x = 1i / 0i; } Relax",
+                "Integer division by zero",
+            ),
+            (
+                "Focus { induce x = 1i << 64; } Relax",
+                "Shift amount must be between 0 and 63",
+            ),
+            (
+                "Focus { induce x = 1.5 & 1; } Relax",
+                "Bitwise operator '&' requires integers",
+            ),
+        ] {
+            let ast = parse(source);
+            let error = interpreter.execute_program(ast).unwrap_err().to_string();
+            assert!(error.contains(message), "{source}: {error}");
         }
-
-        assert_eq!(
-            interpreter.get_variable("eq").unwrap(),
-            Value::Boolean(false)
-        );
-        assert_eq!(
-            interpreter.get_variable("neq").unwrap(),
-            Value::Boolean(true)
-        );
-        assert_eq!(
-            interpreter.get_variable("ge").unwrap(),
-            Value::Boolean(true)
-        );
-        assert_eq!(
-            interpreter.get_variable("le").unwrap(),
-            Value::Boolean(false)
-        );
-        assert_eq!(
-            interpreter.get_variable("both").unwrap(),
-            Value::Boolean(true)
-        );
-        assert_eq!(
-            interpreter.get_variable("either").unwrap(),
-            Value::Boolean(false)
-        );
     }
 
     #[test]
-    fn test_private_field_access_rejected() {
+    fn test_destructuring_declarations_and_parameters() {
         let source = r#"
 Focus {
-    session Account {
-        conceal balance: number;
+    tranceify Person {
+        name: string;
+        age: number;
+    }
 
-        suggestion constructor(amount: number) {
-            this.balance = amount;
-        }
+    suggestion bounds(items: number[]): number[] {
+        awaken [ArrayMin(items), ArrayMax(items)];
+    }
 
-        suggestion read(): number {
-            awaken this.balance;
-        }
+    suggestion greet({ name }: Person, [greeting, ...]: string[]): string {
+        awaken greeting + ", " + name;
     }
 
-    induce account = Account(100);
-    // The following line should fail because balance is private
-    induce leaked = account.balance;
+    induce items = [3, 1, 4];
+    induce [first, ...rest] = items;
+    freeze [low, high] = bounds(items);
+    induce person = Person { name: "Ada", age: 36 };
+    induce { name, age: years } = person;
+    induce Person { name: who } = person;
+    induce { mode } = { "mode": "deep" };
+    induce greeting = greet(person, ["Hello"]);
 } Relax
 "#;
-
-        let mut lexer = Lexer::new(source);
-        let tokens = lexer.lex().unwrap();
-        let mut parser = Parser::new(tokens);
-        let ast = parser.parse_program().unwrap();
-
-        let mut interpreter = Interpreter::new();
-        let result = interpreter.execute_program(ast);
-        assert!(matches!(
-            result,
-            Err(InterpreterError::Runtime(message)) if message.contains("Access denied")
-        ));
+        let interpreter = run(source);
+
+        assert_eq!(var_string(&interpreter, "first"), "3");
+        assert_eq!(var_string(&interpreter, "rest"), "[1, 4]");
+        assert_eq!(var_string(&interpreter, "low"), "1");
+        assert_eq!(var_string(&interpreter, "high"), "4");
+        assert_eq!(var_string(&interpreter, "name"), "Ada");
+        assert_eq!(var_string(&interpreter, "years"), "36");
+        assert_eq!(var_string(&interpreter, "who"), "Ada");
+        assert_eq!(var_string(&interpreter, "mode"), "deep");
+        assert_eq!(var_string(&interpreter, "greeting"), "Hello, Ada");
+
+        for (source, message) in [
+            (
+                "Focus { induce [a, b] = [1, 2, 3]; } Relax",
+                "Cannot destructure [1, 2, 3] with pattern [a, b]",
+            ),
+            (
+                "Focus { induce { name } = 5; } Relax",
+                "Cannot destructure 5 with pattern { name }",
+            ),
+            (
+                "Focus { suggestion f([x]) { awaken x; } f(\"x\"); } Relax",
+                "Cannot destructure \"x\" with pattern [x]",
+            ),
+            ("Focus { freeze [a] = [1]; a = 2; } Relax", "constant"),
+        ] {
+            let error = Interpreter::new()
+                .execute_program(parse(source))
+                .unwrap_err()
+                .to_string();
+            assert!(error.contains(message), "{source}: {error}");
+        }
     }
 
     #[test]
-    fn test_static_field_and_method() {
+    fn test_operator_overloading() {
         let source = r#"
 Focus {
-    session Config {
-        dominant expose version: string = "1.0";
+    session Vector {
+        expose x: number;
+        expose y: number;
 
-        dominant suggestion setVersion(newVersion: string) {
-            Config.version = newVersion;
+        suggestion constructor(x: number, y: number) {
+            this.x = x;
+            this.y = y;
+        }
+
+        suggestion operator +(other: Vector): Vector {
+            awaken Vector(this.x + other.x, this.y + other.y);
+        }
+
+        suggestion operator ==(other: Vector): boolean {
+            awaken this.x == other.x && this.y == other.y;
         }
     }
 
-    Config.setVersion("2.5");
-    induce activeVersion: string = Config.version;
-} Relax
-"#;
+    tranceify Money {
+        cents: number;
 
-        let mut lexer = Lexer::new(source);
-        let tokens = lexer.lex().unwrap();
-        let mut parser = Parser::new(tokens);
-        let ast = parser.parse_program().unwrap();
+        suggestion operator -(other: Money): Money {
+            awaken Money { cents: this.cents - other.cents };
+        }
 
-        let mut interpreter = Interpreter::new();
-        interpreter.execute_program(ast).unwrap();
+        suggestion operator <(other: Money): boolean {
+            awaken this.cents < other.cents;
+        }
+    }
 
-        let result = interpreter.get_variable("activeVersion").unwrap();
-        assert_eq!(result, Value::String("2.5".to_string()));
+    induce sum = Vector(1, 2) + Vector(3, 4);
+    induce total = Vector(0, 0);
+    total += sum;
+    induce same = total == Vector(4, 6);
+    induce different = total != Vector(4, 6);
+    induce sleepy = total youAreFeelingVerySleepy sum;
+    assert total == Vector(4, 6);
+
+    induce change = Money { cents: 500 } - Money { cents: 120 };
+    induce cheaper = change fallUnderMySpell Money { cents: 400 };
+} Relax
+"#;
+        let interpreter = run(source);
+
+        assert_eq!(var_string(&interpreter, "same"), "true");
+        assert_eq!(var_string(&interpreter, "different"), "false");
+        assert_eq!(var_string(&interpreter, "sleepy"), "true");
+        assert_eq!(var_string(&interpreter, "cheaper"), "true");
+        let Value::Record(change) = interpreter.get_variable("change").unwrap() else {
+            panic!("expected record");
+        };
+        assert_eq!(change.fields["cents"].to_string(), "380");
+        let Value::Instance(total) = interpreter.get_variable("total").unwrap() else {
+            panic!("expected instance");
+        };
+        assert_eq!(total.borrow().get_field("x").unwrap().to_string(), "4");
     }
 
     #[test]
-    fn test_entrain_record_pattern_matching_with_guard() {
+    fn test_generators_and_iterator_protocol() {
+        let path =
+            std::env::temp_dir().join(format!("hypnoscript_stream_{}.csv", std::process::id()));
+        std::fs::write(&path, "id,score\n1,10\n2,20\n3,30\n").unwrap();
         let source = r#"
 Focus {
-    tranceify HypnoGuest {
-        name: string;
-        isInTrance: boolean;
-        depth: number;
+    induce started = 0;
+
+    suggestion naturals(): Iterator<number> {
+        started = started + 1;
+        induce n = 0;
+        loop {
+            emerge n;
+            n = n + 1;
+        }
     }
 
-    entrance {
-        induce guest = HypnoGuest {
-            name: "Luna",
-            isInTrance: true,
-            depth: 7
-        };
+    suggestion evens(limit: number): Iterator<number> {
+        loop (induce i in 0..limit) {
+            if (i % 2 == 1) { sink; }
+            if (i > 6) { awaken; }
+            emerge i;
+        }
+    }
 
-        induce status: string = entrain guest {
-            when HypnoGuest { name: alias } => alias;
-            otherwise => "Unknown";
-        };
+    session Countdown {
+        expose remaining: number;
+
+        suggestion constructor(remaining: number) {
+            this.remaining = remaining;
+        }
+
+        suggestion hasNext(): boolean {
+            awaken this.remaining > 0;
+        }
+
+        suggestion next(): number {
+            this.remaining = this.remaining - 1;
+            awaken this.remaining + 1;
+        }
     }
-} Relax
-"#;
 
-        let mut lexer = Lexer::new(source);
-        let tokens = lexer.lex().unwrap();
-        let mut parser = Parser::new(tokens.clone());
-        let ast = match parser.parse_program() {
-            Ok(ast) => ast,
-            Err(err) => {
-                eprintln!("parse error: {err}");
-                for token in tokens {
-                    eprintln!("token: {:?}", token);
-                }
-                panic!("failed to parse test program");
-            }
-        };
+    session Bag {
+        expose items: number[];
 
-        let mut interpreter = Interpreter::new();
-        if let Err(err) = interpreter.execute_program(ast) {
-            panic!("interpreter error: {err:?}");
+        suggestion constructor(items: number[]) {
+            this.items = items;
         }
 
-        let status = interpreter.get_variable("status").unwrap();
-        assert_eq!(status, Value::String("Luna".to_string()));
+        suggestion iterate(): Iterator<number> {
+            loop (induce item in this.items) {
+                emerge item * 10;
+            }
+        }
     }
 
-    #[test]
-    fn test_entrain_record_pattern_default_scope_cleanup() {
-        let source = r#"
-Focus {
-    tranceify HypnoGuest {
-        depth: number;
+    induce lazy = naturals();
+    induce startedBeforePull = started;
+    induce firstFour = ArrayTake(naturals(), 4);
+    induce evenSum = ArraySum(evens(100));
+
+    induce it = evens(5);
+    induce peeked = it.hasNext();
+    induce first = it.next();
+    induce second = it.next();
+    induce third = it.next();
+    induce exhausted = it.hasNext();
+    induce afterEnd = it.next();
+
+    induce countdown: number[] = [];
+    loop (induce i, value in Countdown(3)) {
+        ArrayPush(countdown, i * 100 + value);
     }
+    induce bagTotal = ArraySum(Bag([1, 2, 3]));
 
-    entrance {
-        induce guest = HypnoGuest { depth: 2 };
+    induce pairs: string[] = [];
+    outer: loop (induce a in [1, 2]) {
+        loop (induce b in naturals()) {
+            if (b > 1) { sink outer; }
+            ArrayPush(pairs, "${a}-${b}");
+        }
+    }
 
-        induce outcome = entrain guest {
-            when HypnoGuest { depth: stage } => stage;
-            otherwise => "fallback";
-        };
+    induce scoreTotal = 0;
+    loop (induce line in StreamLines(path)) {
+        induce cells = Split(line, ",");
+        if (cells[0] != "id") {
+            scoreTotal = scoreTotal + ToDouble(cells[1]);
+        }
     }
 } Relax
-"#;
-
-        let mut lexer = Lexer::new(source);
-        let tokens = lexer.lex().unwrap();
-        let mut parser = Parser::new(tokens.clone());
-        let ast = match parser.parse_program() {
-            Ok(ast) => ast,
-            Err(err) => {
-                eprintln!("parse error: {err}");
-                for token in tokens {
-                    eprintln!("token: {:?}", token);
-                }
-                panic!("failed to parse test program");
-            }
-        };
+"#
+        .replace(
+            "StreamLines(path)",
+            &format!("StreamLines({:?})", path.to_string_lossy()),
+        );
+        let ast = parse(&source);
 
         let mut interpreter = Interpreter::new();
-        if let Err(err) = interpreter.execute_program(ast) {
-            panic!("interpreter error: {err:?}");
-        }
-
-        let outcome = interpreter.get_variable("outcome").unwrap();
-        assert_eq!(outcome, Value::Number(2.0));
-        assert!(matches!(
-            interpreter.get_variable("stage"),
-            Err(InterpreterError::UndefinedVariable(_))
-        ));
+        let result = interpreter.execute_program(ast);
+        let _ = std::fs::remove_file(&path);
+        result.unwrap();
+
+        assert_eq!(var_string(&interpreter, "lazy"), "<iterator>");
+        assert_eq!(var_string(&interpreter, "startedBeforePull"), "0");
+        assert_eq!(var_string(&interpreter, "firstFour"), "[0, 1, 2, 3]");
+        assert_eq!(var_string(&interpreter, "evenSum"), "12");
+        assert_eq!(var_string(&interpreter, "peeked"), "true");
+        assert_eq!(var_string(&interpreter, "first"), "0");
+        assert_eq!(var_string(&interpreter, "second"), "2");
+        assert_eq!(var_string(&interpreter, "third"), "4");
+        assert_eq!(var_string(&interpreter, "exhausted"), "false");
+        assert_eq!(var_string(&interpreter, "afterEnd"), "null");
+        assert_eq!(var_string(&interpreter, "countdown"), "[3, 102, 201]");
+        assert_eq!(var_string(&interpreter, "bagTotal"), "60");
+        assert_eq!(var_string(&interpreter, "pairs"), "[1-0, 1-1, 2-0, 2-1]");
+        assert_eq!(var_string(&interpreter, "scoreTotal"), "60");
     }
 }
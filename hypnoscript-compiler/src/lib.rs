@@ -109,7 +109,7 @@
 //!
 //! // Text Format (.wat)
 //! let mut wat_gen = WasmCodeGenerator::new();
-//! let wat_code = wat_gen.generate(&ast);
+//! let wat_code = wat_gen.generate(&ast).unwrap();
 //! // fs::write("output.wat", wat_code).unwrap();
 //!
 //! // Binary Format (.wasm)
@@ -138,6 +138,7 @@ pub mod async_promise;
 pub mod async_runtime;
 pub mod channel_system;
 pub mod interpreter;
+pub mod module_loader;
 pub mod native_codegen;
 pub mod optimizer;
 pub mod type_checker;
@@ -156,10 +157,11 @@ pub use channel_system::{
     BroadcastChannel, ChannelMessage, ChannelRegistry, ChannelType, MpscChannel, WatchChannel,
 };
 pub use interpreter::{Interpreter, InterpreterError, Value};
+pub use module_loader::{Module, ModuleError, ModuleLoader};
 pub use native_codegen::{
     NativeCodeGenerator, NativeCodegenError, OptimizationLevel, TargetPlatform,
 };
 pub use optimizer::{OptimizationConfig, OptimizationError, OptimizationStats, Optimizer};
 pub use type_checker::TypeChecker;
 pub use wasm_binary::{WasmBinaryError, WasmBinaryGenerator};
-pub use wasm_codegen::WasmCodeGenerator;
+pub use wasm_codegen::{WasmCodeGenerator, WasmCodegenError};
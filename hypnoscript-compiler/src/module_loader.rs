@@ -0,0 +1,367 @@
+//! Module resolution and loading for `mindLink` imports.
+//!
+//! Import specifiers are resolved as follows:
+//!
+//! - `./x.hyp`, `../x.hyp` and absolute paths are resolved relative to the
+//!   directory of the importing file (or the loader's base directory for the
+//!   entry program). The `.hyp` extension may be omitted.
+//! - Bare specifiers (`name` or `name/sub/path.hyp`) refer to packages declared
+//!   in the `anchors` or `deepAnchors` of the nearest `trance.json`. Packages live
+//!   in `trance_modules/<name>` next to that manifest. Without a sub path the
+//!   package's own `induction.entryScript` is used (default `src/lib.hyp`).
+//!
+//! The loader caches parsed modules and tracks the chain of modules currently
+//! being loaded so that circular imports are reported instead of recursing.
+
+use hypnoscript_lexer_parser::ast::AstNode;
+use hypnoscript_lexer_parser::{Lexer, Parser};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use thiserror::Error;
+
+/// Manifest file name of HypnoScript packages
+const MANIFEST_FILE: &str = "trance.json";
+
+/// Directory (next to the manifest) that holds installed packages
+const PACKAGES_DIR: &str = "trance_modules";
+
+/// Entry script of a package whose manifest does not name one
+const DEFAULT_PACKAGE_ENTRY: &str = "src/lib.hyp";
+
+/// Errors raised while resolving or loading a module
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ModuleError {
+    #[error("Cannot find module '{specifier}' (looked for {})", .path.display())]
+    NotFound { specifier: String, path: PathBuf },
+
+    #[error("Package '{0}' is not declared in trance.json")]
+    UndeclaredPackage(String),
+
+    #[error("No trance.json found for package import '{0}'")]
+    MissingManifest(String),
+
+    #[error("Failed to read {}: {message}", .path.display())]
+    Io { path: PathBuf, message: String },
+
+    #[error("Failed to parse module {}: {message}", .path.display())]
+    Parse { path: PathBuf, message: String },
+
+    #[error("Circular mindLink import: {}", format_cycle(.0))]
+    Cycle(Vec<PathBuf>),
+
+    #[error("Module '{module}' does not expose '{name}'")]
+    NotExported { module: String, name: String },
+}
+
+fn format_cycle(chain: &[PathBuf]) -> String {
+    chain
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+/// A parsed module together with the names it exposes
+#[derive(Debug)]
+pub struct Module {
+    /// Canonical path of the module file
+    pub path: PathBuf,
+    /// Parsed `Program` node
+    pub program: AstNode,
+    /// Names of all `expose`d top-level declarations
+    pub exports: Vec<String>,
+}
+
+impl Module {
+    /// Whether the module exposes the given name
+    pub fn exposes(&self, name: &str) -> bool {
+        self.exports.iter().any(|export| export == name)
+    }
+
+    /// Fail with [`ModuleError::NotExported`] unless `name` is exposed
+    pub fn ensure_exposed(&self, name: &str) -> Result<(), ModuleError> {
+        if self.exposes(name) {
+            Ok(())
+        } else {
+            Err(ModuleError::NotExported {
+                module: self.path.display().to_string(),
+                name: name.to_string(),
+            })
+        }
+    }
+}
+
+/// Resolves import specifiers and caches parsed modules
+#[derive(Debug)]
+pub struct ModuleLoader {
+    base_dir: PathBuf,
+    modules: HashMap<PathBuf, Rc<Module>>,
+    loading: Vec<PathBuf>,
+}
+
+impl Default for ModuleLoader {
+    fn default() -> Self {
+        Self::new(std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
+    }
+}
+
+impl ModuleLoader {
+    /// Create a loader resolving entry-program imports relative to `base_dir`
+    pub fn new(base_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            base_dir: base_dir.into(),
+            modules: HashMap::new(),
+            loading: Vec::new(),
+        }
+    }
+
+    /// Create a loader for an entry program file.
+    ///
+    /// The entry file counts as loading so that imports cycling back to it are
+    /// detected.
+    pub fn for_entry(entry: &Path) -> Self {
+        let entry = entry.canonicalize().unwrap_or_else(|_| entry.to_path_buf());
+        let base_dir = entry
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."));
+        Self {
+            base_dir,
+            modules: HashMap::new(),
+            loading: vec![entry],
+        }
+    }
+
+    /// Path of the module currently being loaded (the entry file at the top level)
+    pub fn current_module(&self) -> Option<&Path> {
+        self.loading.last().map(PathBuf::as_path)
+    }
+
+    /// Resolve an import specifier to a canonical module path
+    pub fn resolve(
+        &self,
+        specifier: &str,
+        importer: Option<&Path>,
+    ) -> Result<PathBuf, ModuleError> {
+        let base_dir = importer
+            .and_then(Path::parent)
+            .unwrap_or(self.base_dir.as_path());
+
+        let candidate = if specifier.starts_with("./")
+            || specifier.starts_with("../")
+            || Path::new(specifier).is_absolute()
+        {
+            base_dir.join(specifier)
+        } else {
+            self.resolve_package(specifier, base_dir)?
+        };
+
+        let candidate = if candidate.extension().is_none() {
+            candidate.with_extension("hyp")
+        } else {
+            candidate
+        };
+
+        candidate.canonicalize().map_err(|_| ModuleError::NotFound {
+            specifier: specifier.to_string(),
+            path: candidate,
+        })
+    }
+
+    /// Resolve a bare package specifier through the nearest `trance.json`
+    fn resolve_package(&self, specifier: &str, base_dir: &Path) -> Result<PathBuf, ModuleError> {
+        // Scoped packages (`@scope/name`) span two path segments
+        let segments: Vec<&str> = specifier.split('/').collect();
+        let name_len = if specifier.starts_with('@') { 2 } else { 1 };
+        if segments.len() < name_len {
+            return Err(ModuleError::UndeclaredPackage(specifier.to_string()));
+        }
+        let package_name = segments[..name_len].join("/");
+        let sub_path = segments[name_len..].join("/");
+
+        let manifest_dir = base_dir
+            .ancestors()
+            .find(|dir| dir.join(MANIFEST_FILE).is_file())
+            .ok_or_else(|| ModuleError::MissingManifest(specifier.to_string()))?;
+        let manifest = read_manifest(&manifest_dir.join(MANIFEST_FILE))?;
+
+        let declared = ["anchors", "deepAnchors"].iter().any(|section| {
+            manifest
+                .get(section)
+                .and_then(|deps| deps.get(&package_name))
+                .is_some()
+        });
+        if !declared {
+            return Err(ModuleError::UndeclaredPackage(package_name));
+        }
+
+        let package_dir = manifest_dir.join(PACKAGES_DIR).join(&package_name);
+        if !sub_path.is_empty() {
+            return Ok(package_dir.join(sub_path));
+        }
+
+        let package_manifest = package_dir.join(MANIFEST_FILE);
+        let entry = if package_manifest.is_file() {
+            read_manifest(&package_manifest)?
+                .get("induction")
+                .and_then(|induction| induction.get("entryScript"))
+                .and_then(|entry| entry.as_str())
+                .map(str::to_string)
+        } else {
+            None
+        };
+
+        Ok(package_dir.join(entry.as_deref().unwrap_or(DEFAULT_PACKAGE_ENTRY)))
+    }
+
+    /// Mark `path` as being loaded, failing if it is already on the import chain
+    pub fn begin(&mut self, path: &Path) -> Result<(), ModuleError> {
+        if let Some(position) = self.loading.iter().position(|loading| loading == path) {
+            let mut chain = self.loading[position..].to_vec();
+            chain.push(path.to_path_buf());
+            return Err(ModuleError::Cycle(chain));
+        }
+        self.loading.push(path.to_path_buf());
+        Ok(())
+    }
+
+    /// Mark the most recently started module as fully loaded
+    pub fn finish(&mut self) {
+        self.loading.pop();
+    }
+
+    /// Read and parse the module at a resolved path (cached)
+    pub fn load(&mut self, path: &Path) -> Result<Rc<Module>, ModuleError> {
+        if let Some(module) = self.modules.get(path) {
+            return Ok(Rc::clone(module));
+        }
+
+        let source = fs::read_to_string(path).map_err(|error| ModuleError::Io {
+            path: path.to_path_buf(),
+            message: error.to_string(),
+        })?;
+        let parse_error = |message: String| ModuleError::Parse {
+            path: path.to_path_buf(),
+            message,
+        };
+
+        let tokens = Lexer::new(&source).lex().map_err(parse_error)?;
+        let program = Parser::new(tokens)
+            .parse_program()
+            .map_err(|error| parse_error(error.to_string()))?;
+
+        let exports = match &program {
+            AstNode::Program(statements) => statements
+                .iter()
+                .filter(|stmt| matches!(stmt.unspanned(), AstNode::ExportDeclaration(_)))
+                .filter_map(|stmt| stmt.declared_name().map(str::to_string))
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        let module = Rc::new(Module {
+            path: path.to_path_buf(),
+            program,
+            exports,
+        });
+        self.modules.insert(path.to_path_buf(), Rc::clone(&module));
+        Ok(module)
+    }
+}
+
+fn read_manifest(path: &Path) -> Result<serde_json::Value, ModuleError> {
+    let io_error = |message: String| ModuleError::Io {
+        path: path.to_path_buf(),
+        message,
+    };
+    let content = fs::read_to_string(path).map_err(|error| io_error(error.to_string()))?;
+    serde_json::from_str(&content).map_err(|error| io_error(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, relative: &str, content: &str) -> PathBuf {
+        let path = dir.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path.canonicalize().unwrap()
+    }
+
+    #[test]
+    fn test_resolves_relative_to_importer() {
+        let dir = TempDir::new().unwrap();
+        let main = write(dir.path(), "src/main.hyp", "Focus { } Relax");
+        let state = write(dir.path(), "src/shared/state.hyp", "Focus { } Relax");
+
+        let loader = ModuleLoader::new(dir.path());
+        assert_eq!(
+            loader.resolve("./shared/state.hyp", Some(&main)).unwrap(),
+            state
+        );
+        assert_eq!(
+            loader.resolve("./shared/state", Some(&main)).unwrap(),
+            state
+        );
+        assert!(matches!(
+            loader.resolve("./missing.hyp", Some(&main)),
+            Err(ModuleError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_resolves_declared_packages() {
+        let dir = TempDir::new().unwrap();
+        write(
+            dir.path(),
+            "trance.json",
+            r#"{ "ritualName": "app", "anchors": { "calm-utils": "^1.0.0" } }"#,
+        );
+        let main = write(dir.path(), "src/main.hyp", "Focus { } Relax");
+        let entry = write(
+            dir.path(),
+            "trance_modules/calm-utils/src/lib.hyp",
+            "Focus { } Relax",
+        );
+        let helper = write(
+            dir.path(),
+            "trance_modules/calm-utils/helpers.hyp",
+            "Focus { } Relax",
+        );
+
+        let loader = ModuleLoader::new(dir.path());
+        assert_eq!(loader.resolve("calm-utils", Some(&main)).unwrap(), entry);
+        assert_eq!(
+            loader.resolve("calm-utils/helpers", Some(&main)).unwrap(),
+            helper
+        );
+        assert_eq!(
+            loader.resolve("other-utils", Some(&main)),
+            Err(ModuleError::UndeclaredPackage("other-utils".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_load_collects_exports_and_detects_cycles() {
+        let dir = TempDir::new().unwrap();
+        let path = write(
+            dir.path(),
+            "lib.hyp",
+            "Focus { expose freeze A = 1; freeze B = 2; expose suggestion c() { } } Relax",
+        );
+
+        let mut loader = ModuleLoader::new(dir.path());
+        let module = loader.load(&path).unwrap();
+        assert_eq!(module.exports, vec!["A".to_string(), "c".to_string()]);
+        assert!(module.ensure_exposed("B").is_err());
+
+        loader.begin(&path).unwrap();
+        assert!(matches!(loader.begin(&path), Err(ModuleError::Cycle(chain)) if chain.len() == 2));
+        loader.finish();
+        assert!(loader.begin(&path).is_ok());
+    }
+}
@@ -492,24 +492,27 @@ impl NativeCodeGenerator {
         stmt: &AstNode,
     ) -> Result<(), NativeCodegenError> {
         match stmt {
+            AstNode::Spanned { node, .. } | AstNode::ExportDeclaration(node) => {
+                self.generate_statement(builder, node)?;
+            }
+
             AstNode::VariableDeclaration {
                 name, initializer, ..
             } => {
+                // Initialwert zuerst erzeugen: Ganzzahlen liegen in I64-, alle
+                // anderen Zahlen in F64-Variablen
+                let value = match initializer {
+                    Some(init) => self.generate_expression(builder, init)?,
+                    None => builder.ins().f64const(0.0),
+                };
+
                 // Erstelle Variable
                 let var = Variable::new(self.next_var_id);
                 self.next_var_id += 1;
 
-                builder.declare_var(var, types::F64);
+                builder.declare_var(var, builder.func.dfg.value_type(value));
                 self.variable_map.insert(name.clone(), var);
-
-                // Initialisiere Variable
-                if let Some(init) = initializer {
-                    let value = self.generate_expression(builder, init)?;
-                    builder.def_var(var, value);
-                } else {
-                    let zero = builder.ins().f64const(0.0);
-                    builder.def_var(var, zero);
-                }
+                builder.def_var(var, value);
             }
 
             AstNode::AssignmentExpression { target, value } => {
@@ -517,6 +520,12 @@ impl NativeCodeGenerator {
                     && let Some(&var) = self.variable_map.get(name)
                 {
                     let val = self.generate_expression(builder, value)?;
+                    // Wert an den Typ der Variable anpassen
+                    let current = builder.use_var(var);
+                    let val = match builder.func.dfg.value_type(current) {
+                        types::I64 => Self::to_integer(builder, val),
+                        _ => Self::to_float(builder, val),
+                    };
                     builder.def_var(var, val);
                 }
             }
@@ -552,6 +561,8 @@ impl NativeCodeGenerator {
         match expr {
             AstNode::NumberLiteral(n) => Ok(builder.ins().f64const(*n)),
 
+            AstNode::IntegerLiteral(i) => Ok(builder.ins().iconst(types::I64, *i)),
+
             AstNode::BooleanLiteral(b) => {
                 let val = if *b { 1 } else { 0 };
                 Ok(builder.ins().iconst(types::I32, val))
@@ -573,6 +584,18 @@ impl NativeCodeGenerator {
                 let lhs = self.generate_expression(builder, left)?;
                 let rhs = self.generate_expression(builder, right)?;
 
+                // Bitoperatoren und Arithmetik auf zwei Ganzzahlen arbeiten auf I64
+                let is_bitwise = matches!(operator.as_str(), "&" | "|" | "^" | "<<" | ">>");
+                let both_integers = builder.func.dfg.value_type(lhs) == types::I64
+                    && builder.func.dfg.value_type(rhs) == types::I64;
+                if is_bitwise || both_integers {
+                    let lhs = Self::to_integer(builder, lhs);
+                    let rhs = Self::to_integer(builder, rhs);
+                    return Ok(Self::integer_operation(builder, operator, lhs, rhs));
+                }
+                let lhs = Self::to_float(builder, lhs);
+                let rhs = Self::to_float(builder, rhs);
+
                 let result = match operator.as_str() {
                     "+" => builder.ins().fadd(lhs, rhs),
                     "-" => builder.ins().fsub(lhs, rhs),
@@ -596,9 +619,18 @@ impl NativeCodeGenerator {
 
             AstNode::UnaryExpression { operator, operand } => {
                 let val = self.generate_expression(builder, operand)?;
+                let is_integer = builder.func.dfg.value_type(val) == types::I64;
 
                 let result = match operator.as_str() {
+                    "-" if is_integer => {
+                        let zero = builder.ins().iconst(types::I64, 0);
+                        Self::integer_operation(builder, "-", zero, val)
+                    }
                     "-" => builder.ins().fneg(val),
+                    "~" => {
+                        let val = Self::to_integer(builder, val);
+                        builder.ins().bnot(val)
+                    }
                     "!" => {
                         // Logische Negation (für Integers)
                         builder.ins().bxor_imm(val, 1)
@@ -616,6 +648,51 @@ impl NativeCodeGenerator {
         }
     }
 
+    /// Ganzzahl-Operation auf zwei I64-Werten.
+    ///
+    /// Überläufe bei `+`, `-` und `*` lösen einen Trap aus; `sdiv`/`srem`
+    /// trappen selbst bei Division durch null.
+    fn integer_operation(
+        builder: &mut FunctionBuilder,
+        operator: &str,
+        lhs: Value,
+        rhs: Value,
+    ) -> Value {
+        let (result, overflow) = match operator {
+            "+" => builder.ins().sadd_overflow(lhs, rhs),
+            "-" => builder.ins().ssub_overflow(lhs, rhs),
+            "*" => builder.ins().smul_overflow(lhs, rhs),
+            "/" => return builder.ins().sdiv(lhs, rhs),
+            "%" => return builder.ins().srem(lhs, rhs),
+            "&" => return builder.ins().band(lhs, rhs),
+            "|" => return builder.ins().bor(lhs, rhs),
+            "^" => return builder.ins().bxor(lhs, rhs),
+            "<<" => return builder.ins().ishl(lhs, rhs),
+            ">>" => return builder.ins().sshr(lhs, rhs),
+            // Unbekannter Operator -> Return 0
+            _ => return builder.ins().iconst(types::I64, 0),
+        };
+        builder.ins().trapnz(overflow, TrapCode::IntegerOverflow);
+        result
+    }
+
+    /// Wandelt einen Wert in eine Ganzzahl um (F64 wird abgeschnitten)
+    fn to_integer(builder: &mut FunctionBuilder, value: Value) -> Value {
+        match builder.func.dfg.value_type(value) {
+            types::I64 => value,
+            types::F64 => builder.ins().fcvt_to_sint(types::I64, value),
+            _ => builder.ins().sextend(types::I64, value),
+        }
+    }
+
+    /// Wandelt einen Wert in eine Gleitkommazahl um
+    fn to_float(builder: &mut FunctionBuilder, value: Value) -> Value {
+        match builder.func.dfg.value_type(value) {
+            types::F64 => value,
+            _ => builder.ins().fcvt_from_sint(types::F64, value),
+        }
+    }
+
     /// Gibt Informationen über die Zielplattform zurück
     pub fn target_info(&self) -> String {
         format!(
@@ -747,4 +824,33 @@ Focus {
         assert!(info.contains("LLVM-Triple:"));
         assert!(info.contains("Optimierung:"));
     }
+
+    #[test]
+    fn test_integer_program_compilation() {
+        let source = r#"
+Focus {
+    induce id: integer = 9007199254740993i;
+    induce mixed: number = id * 2 + 0.5;
+    induce mask: integer = ~id & 255i;
+    induce shifted: integer = (id << 3i) >> 1i;
+    induce quotient: integer = id / 7i % 5i;
+    id = 3;
+} Relax
+"#;
+
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut generator = NativeCodeGenerator::new();
+        generator.set_optimization_level(OptimizationLevel::None);
+
+        let result = generator.generate(&ast);
+        assert!(
+            result.is_ok(),
+            "Compilation should succeed: {:?}",
+            result.err()
+        );
+    }
 }
@@ -249,6 +249,12 @@ impl Optimizer {
                 Ok(AstNode::Program(optimized_stmts?))
             }
 
+            // Der Parser umhüllt jede Anweisung mit ihrer Quellposition
+            AstNode::Spanned { span, node } => Ok(AstNode::Spanned {
+                span: *span,
+                node: Box::new(self.constant_folding_pass(node)?),
+            }),
+
             AstNode::BinaryExpression {
                 left,
                 operator,
@@ -266,6 +272,7 @@ impl Optimizer {
                         "-" => Some(l - r),
                         "*" => Some(l * r),
                         "/" if *r != 0.0 => Some(l / r),
+                        "%" if *r != 0.0 => Some(l % r),
                         _ => None,
                     };
 
@@ -275,6 +282,29 @@ impl Optimizer {
                     }
                 }
 
+                // Ganzzahlen nur falten, wenn kein Überlauf entsteht; Überläufe
+                // und Division durch null meldet der Interpreter zur Laufzeit
+                if let (AstNode::IntegerLiteral(l), AstNode::IntegerLiteral(r)) =
+                    (&left_opt, &right_opt)
+                {
+                    let result = match operator.as_str() {
+                        "+" => l.checked_add(*r),
+                        "-" => l.checked_sub(*r),
+                        "*" => l.checked_mul(*r),
+                        "/" => l.checked_div(*r),
+                        "%" => l.checked_rem(*r),
+                        "&" => Some(l & r),
+                        "|" => Some(l | r),
+                        "^" => Some(l ^ r),
+                        _ => None,
+                    };
+
+                    if let Some(val) = result {
+                        self.stats.folded_constants += 1;
+                        return Ok(AstNode::IntegerLiteral(val));
+                    }
+                }
+
                 Ok(AstNode::BinaryExpression {
                     left: Box::new(left_opt),
                     operator: operator.clone(),
@@ -303,6 +333,21 @@ impl Optimizer {
                 })
             }
 
+            AstNode::ExpressionStatement(expr) => Ok(AstNode::ExpressionStatement(Box::new(
+                self.constant_folding_pass(expr)?,
+            ))),
+
+            // Das Ziel wird zur Laufzeit gelesen; nur der Wert kann gefaltet werden
+            AstNode::CompoundAssignmentExpression {
+                target,
+                operator,
+                value,
+            } => Ok(AstNode::CompoundAssignmentExpression {
+                target: target.clone(),
+                operator: operator.clone(),
+                value: Box::new(self.constant_folding_pass(value)?),
+            }),
+
             // Für andere Knoten: Rekursiv durchlaufen
             _ => Ok(node.clone()),
         }
@@ -348,6 +393,20 @@ impl Optimizer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use hypnoscript_lexer_parser::{Lexer, Parser};
+
+    fn parse(source: &str) -> AstNode {
+        let tokens = Lexer::new(source).lex().unwrap();
+        Parser::new(tokens).parse_program().unwrap()
+    }
+
+    /// Liefert die erste Anweisung eines geparsten Programms ohne Quellposition
+    fn first_statement(program: &AstNode) -> &AstNode {
+        match program {
+            AstNode::Program(statements) => statements[0].unspanned(),
+            other => panic!("expected program, got {other:?}"),
+        }
+    }
 
     #[test]
     fn test_optimizer_creation() {
@@ -408,6 +467,42 @@ mod tests {
         assert_eq!(optimizer.stats.folded_constants, 1);
     }
 
+    #[test]
+    fn test_constant_folding_compound_assignment_value() {
+        let mut optimizer = Optimizer::new();
+
+        let program = parse("Focus { total %= 2 * 5; } Relax");
+        let result = optimizer.optimize(&program).unwrap();
+
+        assert_eq!(
+            *first_statement(&result),
+            AstNode::ExpressionStatement(Box::new(AstNode::CompoundAssignmentExpression {
+                target: Box::new(AstNode::Identifier("total".to_string())),
+                operator: "%".to_string(),
+                value: Box::new(AstNode::NumberLiteral(10.0)),
+            }))
+        );
+        assert_eq!(optimizer.stats.folded_constants, 1);
+    }
+
+    #[test]
+    fn test_constant_folding_parsed_program() {
+        let mut optimizer = Optimizer::new();
+
+        let program = parse("Focus { 2 + 3; } Relax");
+        let result = optimizer.optimize(&program).unwrap();
+
+        assert!(matches!(
+            &result,
+            AstNode::Program(statements) if matches!(statements[0], AstNode::Spanned { .. })
+        ));
+        assert_eq!(
+            *first_statement(&result),
+            AstNode::ExpressionStatement(Box::new(AstNode::NumberLiteral(5.0)))
+        );
+        assert_eq!(optimizer.stats().folded_constants, 1);
+    }
+
     #[test]
     fn test_constant_folding_unary() {
         let mut optimizer = Optimizer::new();
@@ -423,4 +518,28 @@ mod tests {
         assert_eq!(result, AstNode::NumberLiteral(-42.0));
         assert_eq!(optimizer.stats.folded_constants, 1);
     }
+
+    #[test]
+    fn test_constant_folding_integers() {
+        let mut optimizer = Optimizer::new();
+
+        // 7i / 2i
+        let expr = AstNode::BinaryExpression {
+            left: Box::new(AstNode::IntegerLiteral(7)),
+            operator: "/".to_string(),
+            right: Box::new(AstNode::IntegerLiteral(2)),
+        };
+        let result = optimizer.constant_folding_pass(&expr).unwrap();
+        assert_eq!(result, AstNode::IntegerLiteral(3));
+
+        // Überlauf bleibt für den Interpreter stehen
+        let overflow = AstNode::BinaryExpression {
+            left: Box::new(AstNode::IntegerLiteral(i64::MAX)),
+            operator: "+".to_string(),
+            right: Box::new(AstNode::IntegerLiteral(1)),
+        };
+        let result = optimizer.constant_folding_pass(&overflow).unwrap();
+        assert_eq!(result, overflow);
+        assert_eq!(optimizer.stats.folded_constants, 1);
+    }
 }
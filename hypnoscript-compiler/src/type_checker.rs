@@ -1,8 +1,15 @@
+use crate::interpreter::iterable_arg_count;
+use crate::module_loader::{ModuleError, ModuleLoader};
 use hypnoscript_core::{HypnoBaseType, HypnoType};
 use hypnoscript_lexer_parser::ast::{
-    AstNode, SessionField, SessionMember, SessionMethod, SessionVisibility,
+    AstNode, InterfaceMember, InterpolationPart, Parameter, Pattern, SessionField, SessionMember,
+    SessionMethod, SessionVisibility, Span, is_generator_body, operator_method_name,
+    overloadable_operator, overloaded_operator,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::OnceLock;
 
 /// Session field metadata for type checking.
 ///
@@ -12,6 +19,47 @@ struct SessionFieldInfo {
     ty: HypnoType,
     visibility: SessionVisibility,
     is_static: bool,
+    /// Session that declares the field (differs from the session for inherited fields)
+    owner: String,
+}
+
+/// Parameter metadata of a suggestion, constructor or builtin signature.
+///
+/// Builtin parameters are unnamed and required; declared suggestions may have
+/// default values, a trailing rest parameter and accept named arguments.
+#[derive(Debug, Clone)]
+struct ParameterInfo {
+    name: Option<String>,
+    ty: HypnoType,
+    has_default: bool,
+    is_rest: bool,
+    /// Array parameter of a builtin that also consumes generators and session iterators
+    accepts_iterable: bool,
+}
+
+impl ParameterInfo {
+    fn positional(ty: HypnoType) -> Self {
+        Self {
+            name: None,
+            ty,
+            has_default: false,
+            is_rest: false,
+            accepts_iterable: false,
+        }
+    }
+
+    /// Type of a single argument (the element type for rest parameters)
+    fn argument_type(&self) -> HypnoType {
+        if self.is_rest {
+            self.ty
+                .element_type
+                .as_deref()
+                .cloned()
+                .unwrap_or_else(HypnoType::unknown)
+        } else {
+            self.ty.clone()
+        }
+    }
 }
 
 /// Session method metadata for type checking.
@@ -19,20 +67,29 @@ struct SessionFieldInfo {
 /// Stores type signatures, visibility, and modifiers for session methods.
 #[derive(Debug, Clone)]
 struct SessionMethodInfo {
-    parameter_types: Vec<HypnoType>,
+    type_parameters: Vec<String>,
+    parameters: Vec<ParameterInfo>,
     return_type: HypnoType,
     visibility: SessionVisibility,
     is_static: bool,
     is_constructor: bool,
+    /// Session that declares the method (differs from the session for inherited methods)
+    owner: String,
 }
 
 /// Complete session metadata for type checking.
 ///
 /// Aggregates all type information about a session including fields, methods,
-/// and constructor signatures.
+/// and constructor signatures. Members inherited from the parent session are
+/// copied in, so lookups never need to walk the hierarchy.
 #[derive(Debug, Clone)]
 struct SessionInfo {
     name: String,
+    type_parameters: Vec<String>,
+    /// Instance type of the parent session (`extends`)
+    parent: Option<HypnoType>,
+    /// Interfaces implemented by the session or its parents
+    interfaces: Vec<String>,
     instance_fields: HashMap<String, SessionFieldInfo>,
     static_fields: HashMap<String, SessionFieldInfo>,
     instance_methods: HashMap<String, SessionMethodInfo>,
@@ -44,6 +101,9 @@ impl SessionInfo {
     fn new(name: String) -> Self {
         Self {
             name,
+            type_parameters: Vec::new(),
+            parent: None,
+            interfaces: Vec::new(),
             instance_fields: HashMap::new(),
             static_fields: HashMap::new(),
             instance_methods: HashMap::new(),
@@ -51,6 +111,75 @@ impl SessionInfo {
             constructor: None,
         }
     }
+
+    /// Member types of an instance with the given type arguments (missing ones are unknown)
+    fn instantiate(&self, type_arguments: &[HypnoType]) -> SessionInfo {
+        if self.type_parameters.is_empty() {
+            return self.clone();
+        }
+
+        let bindings = bind_type_arguments(&self.type_parameters, type_arguments);
+        let mut info = self.clone();
+        if let Some(parent) = &mut info.parent {
+            *parent = substitute_type_parameters(parent, &bindings);
+        }
+        for field in info
+            .instance_fields
+            .values_mut()
+            .chain(info.static_fields.values_mut())
+        {
+            field.ty = substitute_type_parameters(&field.ty, &bindings);
+        }
+        for method in info
+            .instance_methods
+            .values_mut()
+            .chain(info.static_methods.values_mut())
+            .chain(info.constructor.iter_mut())
+        {
+            for param in &mut method.parameters {
+                param.ty = substitute_type_parameters(&param.ty, &bindings);
+            }
+            method.return_type = substitute_type_parameters(&method.return_type, &bindings);
+        }
+        info
+    }
+}
+
+/// Interface (contract) metadata for type checking.
+///
+/// Lists the fields and method signatures that conforming sessions and
+/// records provide.
+#[derive(Debug, Clone)]
+struct InterfaceInfo {
+    name: String,
+    fields: HashMap<String, HypnoType>,
+    methods: HashMap<String, SessionMethodInfo>,
+}
+
+impl InterfaceInfo {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            fields: HashMap::new(),
+            methods: HashMap::new(),
+        }
+    }
+}
+
+/// Enum definition for type checking: variants with their payload types
+#[derive(Debug, Clone)]
+struct EnumInfo {
+    name: String,
+    variants: Vec<(String, Vec<HypnoType>)>,
+}
+
+impl EnumInfo {
+    fn variant(&self, name: &str) -> Option<&[HypnoType]> {
+        self.variants
+            .iter()
+            .find(|(variant, _)| variant == name)
+            .map(|(_, payload)| payload.as_slice())
+    }
 }
 
 /// Tranceify (record/struct) type definition for type checking.
@@ -65,19 +194,62 @@ impl SessionInfo {
 ///     y: number
 /// }
 /// ```
+/// Type information exported by a checked `mindLink` module.
+///
+/// Holds the module's complete top-level environment; only names listed in
+/// `exports` may be imported by name. Session and record type definitions are
+/// shared with importers so values returned from the module stay inspectable.
+#[derive(Debug)]
+struct ModuleTypes {
+    exports: Vec<String>,
+    function_types: HashMap<String, (Vec<ParameterInfo>, HypnoType)>,
+    type_env: HashMap<String, HypnoType>,
+    sessions: HashMap<String, SessionInfo>,
+    tranceify_types: HashMap<String, TranceifyInfo>,
+    interfaces: HashMap<String, InterfaceInfo>,
+    enums: HashMap<String, EnumInfo>,
+}
+
+/// Record type of errors caught by `recover` blocks
+const ERROR_TYPE_NAME: &str = "Error";
+
 #[derive(Debug, Clone)]
 struct TranceifyInfo {
-    #[allow(dead_code)]
     name: String,
+    type_parameters: Vec<String>,
     fields: HashMap<String, HypnoType>,
+    /// Operand and result type of each overloaded operator
+    operators: HashMap<String, (HypnoType, HypnoType)>,
 }
 
 impl TranceifyInfo {
     fn new(name: String) -> Self {
         Self {
             name,
+            type_parameters: Vec::new(),
             fields: HashMap::new(),
+            operators: HashMap::new(),
+        }
+    }
+
+    /// Record type of an instance with the given type arguments (missing ones are unknown)
+    fn instantiate(&self, type_arguments: &[HypnoType]) -> HypnoType {
+        if self.type_parameters.is_empty() {
+            return HypnoType::create_record(self.name.clone(), self.fields.clone());
         }
+
+        let bindings = bind_type_arguments(&self.type_parameters, type_arguments);
+        let fields = self
+            .fields
+            .iter()
+            .map(|(name, ty)| (name.clone(), substitute_type_parameters(ty, &bindings)))
+            .collect();
+        let type_arguments = self
+            .type_parameters
+            .iter()
+            .map(|param| bindings[param].clone())
+            .collect();
+        HypnoType::create_record(self.name.clone(), fields).with_type_arguments(type_arguments)
     }
 }
 
@@ -128,20 +300,80 @@ impl TranceifyInfo {
 pub struct TypeChecker {
     // Type environment for variables
     type_env: HashMap<String, HypnoType>,
+    // Narrower types of variables after null checks and entrain type patterns
+    narrowed_types: HashMap<String, HypnoType>,
     // Function signatures
-    function_types: HashMap<String, (Vec<HypnoType>, HypnoType)>,
+    function_types: HashMap<String, (Vec<ParameterInfo>, HypnoType)>,
     // Current function return type (for return statement checking)
     current_function_return_type: Option<HypnoType>,
+    // Element type of the generator whose body is being checked (for `emerge`)
+    current_generator: Option<HypnoType>,
     // Session metadata cache
     sessions: HashMap<String, SessionInfo>,
     // Tranceify (record/struct) type definitions
     tranceify_types: HashMap<String, TranceifyInfo>,
+    // Interface (contract) definitions
+    interfaces: HashMap<String, InterfaceInfo>,
+    // Enum (sum type) definitions
+    enums: HashMap<String, EnumInfo>,
     // Currently checked session context (if any)
     current_session: Option<String>,
     // Indicates whether we are inside a static method scope
     in_static_context: bool,
+    // Indicates whether we are inside a session constructor (`super(...)` calls)
+    in_constructor: bool,
+    // Labels of the enclosing loops within the current function body
+    loop_labels: Vec<String>,
+    // Type parameters of the enclosing generic declarations
+    type_parameters: Vec<String>,
     // Error messages
     errors: Vec<String>,
+    // Source span of the statement currently being checked
+    current_span: Option<Span>,
+    // Number of leading errors that already carry a source location
+    located_errors: usize,
+    // Resolves and parses `mindLink` imports
+    module_loader: ModuleLoader,
+    // Already checked modules by canonical path
+    module_types: HashMap<PathBuf, Rc<ModuleTypes>>,
+    // Session types shared by imported modules without being imported by name
+    shared_module_sessions: HashSet<String>,
+}
+
+/// Whether `name` is a builtin function; builtins take positional arguments only
+pub(crate) fn is_builtin_function(name: &str) -> bool {
+    static BUILTINS: OnceLock<HashSet<String>> = OnceLock::new();
+    BUILTINS
+        .get_or_init(|| TypeChecker::new().function_types.into_keys().collect())
+        .contains(name)
+}
+
+/// `(enum, variant)` fully matched by an unguarded entrain pattern.
+///
+/// Catch-all bindings yield `("", "")`; patterns that only match some payloads yield `None`.
+fn covered_variant(pattern: &Pattern) -> Option<(&str, &str)> {
+    match pattern {
+        Pattern::Identifier(_) => Some(("", "")),
+        Pattern::Variant {
+            enum_name,
+            variant,
+            payload,
+        } if payload
+            .iter()
+            .all(|pattern| covered_variant(pattern) == Some(("", ""))) =>
+        {
+            Some((enum_name, variant))
+        }
+        _ => None,
+    }
+}
+
+/// The declaration wrapped by `expose`, or the statement itself
+fn exported_declaration(stmt: &AstNode) -> &AstNode {
+    match stmt {
+        AstNode::ExportDeclaration(declaration) => declaration,
+        other => other,
+    }
 }
 
 impl Default for TypeChecker {
@@ -155,21 +387,60 @@ impl TypeChecker {
     pub fn new() -> Self {
         let mut checker = Self {
             type_env: HashMap::new(),
+            narrowed_types: HashMap::new(),
             function_types: HashMap::new(),
             current_function_return_type: None,
+            current_generator: None,
             sessions: HashMap::new(),
             tranceify_types: HashMap::new(),
+            interfaces: HashMap::new(),
+            enums: HashMap::new(),
             current_session: None,
             in_static_context: false,
+            in_constructor: false,
+            loop_labels: Vec::new(),
+            type_parameters: Vec::new(),
             errors: Vec::new(),
+            current_span: None,
+            located_errors: 0,
+            module_loader: ModuleLoader::default(),
+            module_types: HashMap::new(),
+            shared_module_sessions: HashSet::new(),
         };
 
         // Register builtin functions
         checker.register_builtins();
+        checker.register_error_type();
 
         checker
     }
 
+    /// Set the file being checked so `mindLink` imports resolve relative to it
+    pub fn set_source_path(&mut self, path: &Path) {
+        self.module_loader = ModuleLoader::for_entry(path);
+    }
+
+    /// Register the `Error` record type bound by `recover (err)` blocks
+    fn register_error_type(&mut self) {
+        let mut info = TranceifyInfo::new(ERROR_TYPE_NAME.to_string());
+        info.fields.insert("kind".to_string(), HypnoType::string());
+        info.fields
+            .insert("message".to_string(), HypnoType::string());
+        info.fields
+            .insert("payload".to_string(), HypnoType::unknown());
+        self.tranceify_types
+            .insert(ERROR_TYPE_NAME.to_string(), info);
+    }
+
+    fn error_type(&self) -> HypnoType {
+        let fields = self
+            .tranceify_types
+            .get(ERROR_TYPE_NAME)
+            .map(|info| info.fields.clone())
+            .unwrap_or_default();
+        HypnoType::create_record(ERROR_TYPE_NAME.to_string(), fields)
+    }
+
     /// Register builtin function signatures
     fn register_builtins(&mut self) {
         // Math
@@ -329,6 +600,61 @@ impl TypeChecker {
             HypnoType::number(),
         );
         self.register_builtin("ArrayDistinct", vec![any_array()], any_array());
+        self.register_builtin(
+            "ArrayPush",
+            vec![any_array(), HypnoType::unknown()],
+            HypnoType::number(),
+        );
+        self.register_builtin("ArrayPop", vec![any_array()], HypnoType::unknown());
+        self.register_builtin(
+            "ArrayInsert",
+            vec![any_array(), HypnoType::number(), HypnoType::unknown()],
+            HypnoType::number(),
+        );
+        self.register_builtin(
+            "ArrayRemoveAt",
+            vec![any_array(), HypnoType::number()],
+            HypnoType::unknown(),
+        );
+        self.register_builtin("ArraySortInPlace", vec![any_array()], any_array());
+
+        // Dictionaries
+        let any_dict = || HypnoType::create_dictionary(HypnoType::unknown(), HypnoType::unknown());
+        let any_function = || HypnoType::new(HypnoBaseType::Function, None);
+
+        self.register_builtin("DictCreate", vec![], any_dict());
+        self.register_builtin(
+            "DictGet",
+            vec![any_dict(), HypnoType::unknown()],
+            HypnoType::unknown(),
+        );
+        self.register_builtin(
+            "DictSet",
+            vec![any_dict(), HypnoType::unknown(), HypnoType::unknown()],
+            any_dict(),
+        );
+        self.register_builtin(
+            "DictHasKey",
+            vec![any_dict(), HypnoType::unknown()],
+            HypnoType::boolean(),
+        );
+        for name in ["DictKeys", "DictValues", "DictEntries"] {
+            self.register_builtin(name, vec![any_dict()], any_array());
+        }
+        self.register_builtin("DictSize", vec![any_dict()], HypnoType::number());
+        self.register_builtin("DictIsEmpty", vec![any_dict()], HypnoType::boolean());
+        self.register_builtin(
+            "DictRemove",
+            vec![any_dict(), HypnoType::unknown()],
+            any_dict(),
+        );
+        self.register_builtin("DictClear", vec![any_dict()], any_dict());
+        self.register_builtin("DictMerge", vec![any_dict(), any_dict()], any_dict());
+        for name in ["DictFilter", "DictMap"] {
+            self.register_builtin(name, vec![any_dict(), any_function()], any_dict());
+        }
+        self.register_builtin("DictFromJson", vec![HypnoType::string()], any_dict());
+        self.register_builtin("DictToJson", vec![any_dict()], HypnoType::string());
 
         // Core / Hypnotic
         self.register_builtin("Observe", vec![HypnoType::unknown()], HypnoType::unknown());
@@ -345,13 +671,25 @@ impl TypeChecker {
             vec![HypnoType::string()],
             HypnoType::unknown(),
         );
-        self.register_builtin("ToInt", vec![HypnoType::number()], HypnoType::number());
+        self.register_builtin(
+            "ToInt",
+            vec![HypnoType::create_union(vec![
+                HypnoType::number(),
+                HypnoType::string(),
+            ])],
+            HypnoType::integer(),
+        );
         self.register_builtin("ToDouble", vec![HypnoType::string()], HypnoType::number());
         self.register_builtin("ToString", vec![HypnoType::unknown()], HypnoType::string());
         self.register_builtin("ToBoolean", vec![HypnoType::string()], HypnoType::boolean());
 
         // File / IO
         self.register_builtin("ReadFile", vec![HypnoType::string()], HypnoType::string());
+        self.register_builtin(
+            "StreamLines",
+            vec![HypnoType::string()],
+            HypnoType::create_iterator(HypnoType::string()),
+        );
         for name in ["WriteFile", "AppendFile"] {
             self.register_builtin(
                 name,
@@ -366,6 +704,24 @@ impl TypeChecker {
             self.register_builtin(name, vec![HypnoType::string()], HypnoType::boolean());
         }
         self.register_builtin("ListDirectory", vec![HypnoType::string()], string_array());
+
+        // Async I/O (settled by jobs on the async runtime)
+        let promise_of = HypnoType::create_promise;
+        self.register_builtin(
+            "AsyncDelay",
+            vec![HypnoType::number()],
+            promise_of(HypnoType::unknown()),
+        );
+        self.register_builtin(
+            "AsyncReadFile",
+            vec![HypnoType::string()],
+            promise_of(HypnoType::string()),
+        );
+        self.register_builtin(
+            "AsyncWriteFile",
+            vec![HypnoType::string(), HypnoType::string()],
+            promise_of(HypnoType::unknown()),
+        );
         self.register_builtin(
             "GetFileSize",
             vec![HypnoType::string()],
@@ -386,12 +742,20 @@ impl TypeChecker {
         }
 
         // Hashing / Utility
-        self.register_builtin("HashString", vec![HypnoType::string()], HypnoType::number());
-        self.register_builtin("HashNumber", vec![HypnoType::number()], HypnoType::number());
+        self.register_builtin(
+            "HashString",
+            vec![HypnoType::string()],
+            HypnoType::integer(),
+        );
+        self.register_builtin(
+            "HashNumber",
+            vec![HypnoType::number()],
+            HypnoType::integer(),
+        );
         self.register_builtin(
             "SimpleRandom",
             vec![HypnoType::number()],
-            HypnoType::number(),
+            HypnoType::integer(),
         );
         self.register_builtin(
             "AreAnagrams",
@@ -510,6 +874,13 @@ impl TypeChecker {
             vec![HypnoType::string(), HypnoType::string()],
             HypnoType::boolean(),
         );
+
+        // Array and statistics builtins also consume generators and session iterators
+        for (name, (parameters, _)) in self.function_types.iter_mut() {
+            for parameter in parameters.iter_mut().take(iterable_arg_count(name)) {
+                parameter.accepts_iterable = true;
+            }
+        }
     }
 
     fn register_builtin(
@@ -518,35 +889,171 @@ impl TypeChecker {
         parameter_types: Vec<HypnoType>,
         return_type: HypnoType,
     ) {
+        let parameters = parameter_types
+            .into_iter()
+            .map(ParameterInfo::positional)
+            .collect();
         self.function_types
-            .insert(name.to_string(), (parameter_types, return_type));
+            .insert(name.to_string(), (parameters, return_type));
     }
 
     /// Parse type annotation string to HypnoType
     fn parse_type_annotation(&self, type_str: Option<&str>) -> HypnoType {
-        match type_str {
+        let Some(type_str) = type_str.map(str::trim) else {
+            return HypnoType::unknown();
+        };
+
+        let members = split_top_level(type_str, '|');
+        if members.len() > 1 {
+            return HypnoType::create_union(
+                members
+                    .into_iter()
+                    .map(|member| self.parse_type_annotation(Some(member)))
+                    .collect(),
+            );
+        }
+
+        if let Some(inner) = type_str.strip_prefix("lucid ") {
+            return HypnoType::create_optional(self.parse_type_annotation(Some(inner)));
+        }
+
+        if let Some(literal) = parse_literal_type(type_str) {
+            return literal;
+        }
+
+        if let Some(element) = type_str.strip_suffix("[]") {
+            return HypnoType::create_array(self.parse_type_annotation(Some(element)));
+        }
+
+        if let Some(value) = type_str
+            .strip_prefix("Promise<")
+            .and_then(|rest| rest.strip_suffix('>'))
+        {
+            return HypnoType::create_promise(self.parse_type_annotation(Some(value)));
+        }
+
+        if let Some(value) = type_str
+            .strip_prefix("Iterator<")
+            .and_then(|rest| rest.strip_suffix('>'))
+        {
+            return HypnoType::create_iterator(self.parse_type_annotation(Some(value)));
+        }
+
+        if let Some(inner) = type_str
+            .strip_prefix('(')
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            return self.parse_type_annotation(Some(inner));
+        }
+
+        if let Some(arguments) = type_str
+            .strip_prefix("Dictionary<")
+            .and_then(|rest| rest.strip_suffix('>'))
+        {
+            let arguments = split_top_level(arguments, ',');
+            return match arguments.as_slice() {
+                [key, value] => HypnoType::create_dictionary(
+                    self.parse_type_annotation(Some(key)),
+                    self.parse_type_annotation(Some(value)),
+                ),
+                _ => HypnoType::unknown(),
+            };
+        }
+
+        if self.type_parameters.iter().any(|param| param == type_str) {
+            return HypnoType::create_type_parameter(type_str.to_string());
+        }
+
+        match Some(type_str) {
             Some("number") => HypnoType::number(),
+            Some("integer") => HypnoType::integer(),
             Some("string") => HypnoType::string(),
             Some("boolean") => HypnoType::boolean(),
             Some("trance") => HypnoType::new(HypnoBaseType::Trance, None),
-            _ => HypnoType::unknown(),
+            Some("null") => HypnoType::null(),
+            _ => self.parse_named_type(type_str),
+        }
+    }
+
+    /// Resolve a record or session name, instantiating generic ones (`Box<number>`)
+    fn parse_named_type(&self, type_str: &str) -> HypnoType {
+        let (name, arguments) = match type_str
+            .split_once('<')
+            .and_then(|(name, rest)| Some((name.trim(), rest.strip_suffix('>')?)))
+        {
+            Some((name, arguments)) => (name, split_top_level(arguments, ',')),
+            None => (type_str, Vec::new()),
+        };
+        let arguments: Vec<HypnoType> = arguments
+            .into_iter()
+            .map(|argument| self.parse_type_annotation(Some(argument)))
+            .collect();
+
+        if arguments.is_empty() && self.interfaces.contains_key(name) {
+            return HypnoType::create_interface(name.to_string());
+        }
+        if arguments.is_empty() && self.enums.contains_key(name) {
+            return HypnoType::create_enum(name.to_string());
+        }
+
+        let type_parameters = if let Some(info) = self.tranceify_types.get(name) {
+            &info.type_parameters
+        } else if let Some(info) = self.sessions.get(name) {
+            &info.type_parameters
+        } else {
+            return HypnoType::unknown();
+        };
+        // Type arguments must match the declaration; a bare name leaves them unknown
+        if !arguments.is_empty() && arguments.len() != type_parameters.len() {
+            return HypnoType::unknown();
         }
+
+        match self.tranceify_types.get(name) {
+            Some(info) => info.instantiate(&arguments),
+            None => self.session_instance_type(name, arguments),
+        }
+    }
+
+    /// Run `f` with additional type parameters in scope
+    fn with_type_parameters<R>(
+        &mut self,
+        type_parameters: &[String],
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let depth = self.type_parameters.len();
+        self.type_parameters.extend(type_parameters.iter().cloned());
+        let result = f(self);
+        self.type_parameters.truncate(depth);
+        result
     }
 
     /// Check a program and return errors
     pub fn check_program(&mut self, program: &AstNode) -> Vec<String> {
         self.errors.clear();
+        self.located_errors = 0;
 
         if let AstNode::Program(statements) = program {
-            // First pass: collect type definitions (tranceify and sessions)
+            // Import pass: bring exposed declarations of mindLink modules into scope
+            for stmt in statements {
+                self.with_location(stmt, Self::collect_imports);
+            }
+
+            // First pass: collect type definitions (tranceify, interfaces, enums and sessions)
             for stmt in statements {
-                self.collect_tranceify_signature(stmt);
-                self.collect_session_signature(stmt);
+                self.with_location(stmt, |checker, stmt| {
+                    let stmt = exported_declaration(stmt);
+                    checker.collect_tranceify_signature(stmt);
+                    checker.collect_interface_signature(stmt);
+                    checker.collect_enum_signature(stmt);
+                    checker.collect_session_signature(stmt);
+                });
             }
 
             // Second pass: collect function declarations
             for stmt in statements {
-                self.collect_function_signature(stmt);
+                self.with_location(stmt, |checker, stmt| {
+                    checker.collect_function_signature(exported_declaration(stmt))
+                });
             }
 
             // Third pass: type check all statements
@@ -560,70 +1067,433 @@ impl TypeChecker {
         self.errors.clone()
     }
 
-    /// Collect tranceify type signatures
-    fn collect_tranceify_signature(&mut self, stmt: &AstNode) {
-        if let AstNode::TranceifyDeclaration { name, fields } = stmt {
-            let mut info = TranceifyInfo::new(name.clone());
+    /// Import the requested names of a `mindLink` declaration
+    fn collect_imports(&mut self, stmt: &AstNode) {
+        let AstNode::ImportDeclaration { names, source } = stmt else {
+            return;
+        };
 
-            for field in fields {
-                let field_type = self.parse_type_annotation(Some(&field.type_annotation));
-                info.fields.insert(field.name.clone(), field_type);
+        let module = match self.check_module(source) {
+            Ok(module) => module,
+            Err(error) => {
+                self.errors.push(error.to_string());
+                return;
             }
+        };
 
-            self.tranceify_types.insert(name.clone(), info);
+        for (name, info) in &module.sessions {
+            if !self.sessions.contains_key(name) {
+                self.sessions.insert(name.clone(), info.clone());
+                self.shared_module_sessions.insert(name.clone());
+            }
+        }
+        for (name, info) in &module.tranceify_types {
+            self.tranceify_types
+                .entry(name.clone())
+                .or_insert_with(|| info.clone());
+        }
+        for (name, info) in &module.interfaces {
+            self.interfaces
+                .entry(name.clone())
+                .or_insert_with(|| info.clone());
+        }
+        for (name, info) in &module.enums {
+            self.enums
+                .entry(name.clone())
+                .or_insert_with(|| info.clone());
         }
-    }
 
-    /// Collect function signatures (including triggers)
-    fn collect_function_signature(&mut self, stmt: &AstNode) {
-        match stmt {
-            AstNode::FunctionDeclaration {
-                name,
-                parameters,
-                return_type,
-                ..
+        for name in names {
+            if !module.exports.contains(name) {
+                self.errors
+                    .push(format!("Module '{}' does not expose '{}'", source, name));
+                continue;
             }
-            | AstNode::TriggerDeclaration {
-                name,
-                parameters,
-                return_type,
-                ..
-            } => {
-                let param_types: Vec<HypnoType> = parameters
-                    .iter()
-                    .map(|p| self.parse_type_annotation(p.type_annotation.as_deref()))
-                    .collect();
 
-                let ret_type = self.parse_type_annotation(return_type.as_deref());
+            if let Some(signature) = module.function_types.get(name) {
+                self.function_types.insert(name.clone(), signature.clone());
+            }
+            if let Some(ty) = module.type_env.get(name) {
+                self.type_env.insert(name.clone(), ty.clone());
+            }
+            self.shared_module_sessions.remove(name);
+        }
+    }
+
+    /// Type check an imported module in its own scope (results are cached)
+    fn check_module(&mut self, specifier: &str) -> Result<Rc<ModuleTypes>, ModuleError> {
+        let path = self
+            .module_loader
+            .resolve(specifier, self.module_loader.current_module())?;
+        if let Some(module) = self.module_types.get(&path) {
+            return Ok(Rc::clone(module));
+        }
+
+        let module = self.module_loader.load(&path)?;
+        self.module_loader.begin(&path)?;
+
+        let mut checker = TypeChecker::new();
+        checker.module_loader = std::mem::take(&mut self.module_loader);
+        checker.module_types = std::mem::take(&mut self.module_types);
+        let errors = checker.check_program(&module.program);
+        self.module_loader = std::mem::take(&mut checker.module_loader);
+        self.module_types = std::mem::take(&mut checker.module_types);
+        self.module_loader.finish();
+
+        // Module errors already carry their own locations
+        self.locate_pending_errors();
+        self.errors.extend(
+            errors
+                .into_iter()
+                .map(|error| format!("In module {}: {}", path.display(), error)),
+        );
+        self.located_errors = self.errors.len();
+
+        let types = Rc::new(ModuleTypes {
+            exports: module.exports.clone(),
+            function_types: checker.function_types,
+            type_env: checker.type_env,
+            sessions: checker.sessions,
+            tranceify_types: checker.tranceify_types,
+            interfaces: checker.interfaces,
+            enums: checker.enums,
+        });
+        self.module_types.insert(path, Rc::clone(&types));
+        Ok(types)
+    }
+
+    /// Run `check` on a statement, attributing new errors to its source span
+    fn with_location(&mut self, stmt: &AstNode, check: impl FnOnce(&mut Self, &AstNode)) {
+        let AstNode::Spanned { span, node } = stmt else {
+            check(self, stmt);
+            return;
+        };
+
+        // Errors raised so far belong to the enclosing statement
+        self.locate_pending_errors();
+        let previous = self.current_span.replace(*span);
+        check(self, node);
+        self.locate_pending_errors();
+        self.current_span = previous;
+    }
 
-                self.function_types
-                    .insert(name.clone(), (param_types, ret_type));
+    /// Append the current span to errors that were reported without one
+    fn locate_pending_errors(&mut self) {
+        if let Some(span) = self.current_span {
+            for error in &mut self.errors[self.located_errors..] {
+                error.push_str(&format!(" at {}", span));
             }
-            _ => {}
         }
+        self.located_errors = self.errors.len();
     }
 
-    fn collect_session_signature(&mut self, stmt: &AstNode) {
-        let AstNode::SessionDeclaration { name, members } = stmt else {
+    /// Collect tranceify type signatures
+    fn collect_tranceify_signature(&mut self, stmt: &AstNode) {
+        if let AstNode::TranceifyDeclaration {
+            name,
+            type_parameters,
+            fields,
+            operators,
+        } = stmt
+        {
+            let mut info = TranceifyInfo::new(name.clone());
+            info.type_parameters = type_parameters.clone();
+
+            self.with_type_parameters(type_parameters, |checker| {
+                for field in fields {
+                    let field_type = checker.parse_type_annotation(Some(&field.type_annotation));
+                    info.fields.insert(field.name.clone(), field_type);
+                }
+                // Operators usually take and return the record type itself
+                checker.tranceify_types.insert(name.clone(), info.clone());
+                for method in operators {
+                    let Some(operator) = overloaded_operator(&method.name) else {
+                        continue;
+                    };
+                    let operand_type = checker.declared_parameter_type(&method.parameters[0]);
+                    let result_type = checker.parse_type_annotation(method.return_type.as_deref());
+                    checker.check_operator_result(operator, &result_type);
+                    if info
+                        .operators
+                        .insert(operator.to_string(), (operand_type, result_type))
+                        .is_some()
+                    {
+                        checker.errors.push(format!(
+                            "Duplicate operator '{}' in tranceify type '{}'",
+                            operator, name
+                        ));
+                    }
+                }
+            });
+
+            self.tranceify_types.insert(name.clone(), info);
+        }
+    }
+
+    /// Collect interface field types and method signatures
+    fn collect_interface_signature(&mut self, stmt: &AstNode) {
+        let AstNode::InterfaceDeclaration { name, members } = stmt else {
             return;
         };
 
-        if self.sessions.contains_key(name) {
+        if self.interfaces.contains_key(name) {
             self.errors
-                .push(format!("Duplicate session declaration '{}'", name));
+                .push(format!("Duplicate interface declaration '{}'", name));
             return;
         }
 
-        let mut info = SessionInfo::new(name.clone());
+        let mut info = InterfaceInfo::new(name.clone());
+        // Register the interface early so members can refer to it
+        self.interfaces.insert(name.clone(), info.clone());
 
         for member in members {
+            let member_name = match member {
+                InterfaceMember::Field(field) => &field.name,
+                InterfaceMember::Method(method) => &method.name,
+            };
+            if info.fields.contains_key(member_name) || info.methods.contains_key(member_name) {
+                self.errors.push(format!(
+                    "Duplicate member '{}' in interface '{}'",
+                    member_name, name
+                ));
+                continue;
+            }
+
             match member {
+                InterfaceMember::Field(field) => {
+                    let field_type = self.parse_type_annotation(Some(&field.type_annotation));
+                    info.fields.insert(field.name.clone(), field_type);
+                }
+                InterfaceMember::Method(method) => {
+                    let method_info = SessionMethodInfo {
+                        type_parameters: Vec::new(),
+                        parameters: self.parameter_infos(&method.parameters),
+                        return_type: self.parse_type_annotation(method.return_type.as_deref()),
+                        visibility: SessionVisibility::Public,
+                        is_static: false,
+                        is_constructor: false,
+                        owner: name.clone(),
+                    };
+                    info.methods.insert(method.name.clone(), method_info);
+                }
+            }
+        }
+
+        self.interfaces.insert(name.clone(), info);
+    }
+
+    /// Collect enum variants and their payload types
+    fn collect_enum_signature(&mut self, stmt: &AstNode) {
+        let AstNode::EnumDeclaration { name, variants } = stmt else {
+            return;
+        };
+
+        if self.enums.contains_key(name) {
+            self.errors
+                .push(format!("Duplicate enum declaration '{}'", name));
+            return;
+        }
+
+        let mut info = EnumInfo {
+            name: name.clone(),
+            variants: Vec::new(),
+        };
+        // Register the enum early so payloads can refer to it
+        self.enums.insert(name.clone(), info.clone());
+
+        for variant in variants {
+            if info.variant(&variant.name).is_some() {
+                self.errors.push(format!(
+                    "Duplicate variant '{}' in enum '{}'",
+                    variant.name, name
+                ));
+                continue;
+            }
+            let payload = variant
+                .payload
+                .iter()
+                .map(|ty| self.parse_type_annotation(Some(ty)))
+                .collect();
+            info.variants.push((variant.name.clone(), payload));
+        }
+
+        self.enums.insert(name.clone(), info);
+        self.type_env.insert(
+            name.clone(),
+            HypnoType::new(HypnoBaseType::Enum, Some(format!("{}::type", name))),
+        );
+    }
+
+    /// Enum declaration behind a reference to the enum itself (`State` in `State.Idle`)
+    fn enum_lookup(&self, ty: &HypnoType) -> Option<EnumInfo> {
+        if ty.base_type != HypnoBaseType::Enum {
+            return None;
+        }
+        let name = ty.name.as_deref()?.strip_suffix("::type")?;
+        self.enums.get(name).cloned()
+    }
+
+    /// Check that a session provides every member of an interface it implements
+    fn check_conformance(&mut self, session: &SessionInfo, interface: &InterfaceInfo) {
+        let mut field_names: Vec<&String> = interface.fields.keys().collect();
+        field_names.sort();
+        for field_name in field_names {
+            let required = &interface.fields[field_name];
+            let Some(field) = session.instance_fields.get(field_name) else {
+                self.errors.push(format!(
+                    "Session '{}' does not implement field '{}' of interface '{}'",
+                    session.name, field_name, interface.name
+                ));
+                continue;
+            };
+            if field.visibility != SessionVisibility::Public {
+                self.errors.push(format!(
+                    "Field '{}' of session '{}' must be exposed to implement interface '{}'",
+                    field_name, session.name, interface.name
+                ));
+            } else if !self.types_compatible(required, &field.ty) {
+                self.errors.push(format!(
+                    "Field '{}' of session '{}' does not match interface '{}': expected {}, got {}",
+                    field_name, session.name, interface.name, required, field.ty
+                ));
+            }
+        }
+
+        let mut method_names: Vec<&String> = interface.methods.keys().collect();
+        method_names.sort();
+        for method_name in method_names {
+            let required = &interface.methods[method_name];
+            let Some(method) = session.instance_methods.get(method_name) else {
+                self.errors.push(format!(
+                    "Session '{}' does not implement method '{}' of interface '{}'",
+                    session.name, method_name, interface.name
+                ));
+                continue;
+            };
+            if method.visibility != SessionVisibility::Public {
+                self.errors.push(format!(
+                    "Method '{}' of session '{}' must be exposed to implement interface '{}'",
+                    method_name, session.name, interface.name
+                ));
+            } else if !self.signature_matches(required, method) {
+                self.errors.push(format!(
+                    "Method '{}' of session '{}' does not match interface '{}': expected {}, got {}",
+                    method_name,
+                    session.name,
+                    interface.name,
+                    self.method_function_type(required),
+                    self.method_function_type(method)
+                ));
+            }
+        }
+    }
+
+    /// Collect function signatures (including triggers)
+    fn collect_function_signature(&mut self, stmt: &AstNode) {
+        match stmt {
+            AstNode::FunctionDeclaration {
+                name,
+                parameters,
+                return_type,
+                body,
+                ..
+            }
+            | AstNode::TriggerDeclaration {
+                name,
+                parameters,
+                return_type,
+                body,
+                ..
+            } => {
+                let type_parameters = match stmt {
+                    AstNode::FunctionDeclaration {
+                        type_parameters, ..
+                    } => type_parameters.as_slice(),
+                    _ => &[],
+                };
+                let (params, mut ret_type) =
+                    self.with_type_parameters(type_parameters, |checker| {
+                        (
+                            checker.parameter_infos(parameters),
+                            generator_return_type(
+                                body,
+                                checker.parse_type_annotation(return_type.as_deref()),
+                            ),
+                        )
+                    });
+                // Calls of `mesmerize` suggestions return a promise of the declared type
+                if matches!(stmt, AstNode::FunctionDeclaration { is_async: true, .. }) {
+                    ret_type = HypnoType::create_promise(ret_type);
+                }
+
+                self.function_types.insert(name.clone(), (params, ret_type));
+            }
+            _ => {}
+        }
+    }
+
+    fn collect_session_signature(&mut self, stmt: &AstNode) {
+        let AstNode::SessionDeclaration {
+            name,
+            type_parameters,
+            parent,
+            interfaces,
+            members,
+        } = stmt
+        else {
+            return;
+        };
+
+        // Local declarations may shadow sessions that only came along with a module
+        if self.sessions.contains_key(name) && !self.shared_module_sessions.remove(name) {
+            self.errors
+                .push(format!("Duplicate session declaration '{}'", name));
+            return;
+        }
+
+        let mut info = SessionInfo::new(name.clone());
+        info.type_parameters = type_parameters.clone();
+        // Register the session early so members can refer to its own instance type
+        self.sessions.insert(name.clone(), info.clone());
+        let depth = self.type_parameters.len();
+        self.type_parameters.extend(type_parameters.iter().cloned());
+
+        // Start from the members of the parent session
+        let mut inherited_constructor = None;
+        if let Some(parent) = parent {
+            match self.resolve_parent_session(name, parent) {
+                Ok((parent_type, parent_info)) => {
+                    info.parent = Some(parent_type);
+                    info.instance_fields = parent_info.instance_fields;
+                    info.static_fields = parent_info.static_fields;
+                    info.instance_methods = parent_info.instance_methods;
+                    info.static_methods = parent_info.static_methods;
+                    info.interfaces = parent_info.interfaces;
+                    inherited_constructor = parent_info.constructor;
+                }
+                Err(err) => self.errors.push(err),
+            }
+        }
+
+        let mut has_finale = false;
+        for member in members {
+            match member {
+                SessionMember::Finale(_) => {
+                    if has_finale {
+                        self.errors.push(format!(
+                            "Multiple finale blocks defined for session '{}'",
+                            name
+                        ));
+                    }
+                    has_finale = true;
+                }
                 SessionMember::Field(field) => {
                     let field_type = self.parse_type_annotation(field.type_annotation.as_deref());
                     let field_info = SessionFieldInfo {
                         ty: field_type,
                         visibility: field.visibility,
                         is_static: field.is_static,
+                        owner: name.clone(),
                     };
 
                     let map = if field.is_static {
@@ -632,17 +1502,34 @@ impl TypeChecker {
                         &mut info.instance_fields
                     };
 
-                    if map.contains_key(&field.name) {
-                        self.errors.push(format!(
-                            "Duplicate field '{}' in session '{}'",
-                            field.name, name
-                        ));
-                    } else {
-                        map.insert(field.name.clone(), field_info);
+                    match map.get(&field.name) {
+                        Some(existing) if existing.owner != *name => {
+                            self.errors.push(format!(
+                                "Field '{}' in session '{}' is already declared by session '{}'",
+                                field.name, name, existing.owner
+                            ));
+                        }
+                        Some(_) => {
+                            self.errors.push(format!(
+                                "Duplicate field '{}' in session '{}'",
+                                field.name, name
+                            ));
+                        }
+                        None => {
+                            map.insert(field.name.clone(), field_info);
+                        }
                     }
                 }
                 SessionMember::Method(method) => {
-                    let method_info = self.build_method_info(name, method);
+                    let method_info = self
+                        .with_type_parameters(&method.type_parameters, |checker| {
+                            checker.build_method_info(name, method)
+                        });
+                    if let (Some(operator), Ok(method_info)) =
+                        (overloaded_operator(&method.name), &method_info)
+                    {
+                        self.check_operator_result(operator, &method_info.return_type);
+                    }
 
                     match method_info {
                         Ok(info_item) => {
@@ -664,13 +1551,27 @@ impl TypeChecker {
                                 &mut info.instance_methods
                             };
 
-                            if target_map.contains_key(&method.name) {
-                                self.errors.push(format!(
-                                    "Duplicate method '{}' in session '{}'",
-                                    method.name, name
-                                ));
-                            } else {
-                                target_map.insert(method.name.clone(), info_item);
+                            match target_map.get(&method.name) {
+                                Some(existing) if existing.owner == *name => {
+                                    self.errors.push(format!(
+                                        "Duplicate method '{}' in session '{}'",
+                                        method.name, name
+                                    ));
+                                }
+                                Some(overridden) => {
+                                    if let Some(err) = self.check_override(
+                                        name,
+                                        &method.name,
+                                        overridden,
+                                        &info_item,
+                                    ) {
+                                        self.errors.push(err);
+                                    }
+                                    target_map.insert(method.name.clone(), info_item);
+                                }
+                                None => {
+                                    target_map.insert(method.name.clone(), info_item);
+                                }
                             }
                         }
                         Err(err) => {
@@ -681,12 +1582,34 @@ impl TypeChecker {
             }
         }
 
+        self.type_parameters.truncate(depth);
+
+        // Sessions without a constructor of their own inherit the parent's
+        if info.constructor.is_none() {
+            info.constructor = inherited_constructor;
+        }
+
+        for interface in interfaces {
+            match self.interfaces.get(interface).cloned() {
+                Some(contract) => {
+                    self.check_conformance(&info, &contract);
+                    if !info.interfaces.contains(interface) {
+                        info.interfaces.push(interface.clone());
+                    }
+                }
+                None => self.errors.push(format!(
+                    "Session '{}' implements unknown interface '{}'",
+                    name, interface
+                )),
+            }
+        }
+
         // Ensure constructor signature is registered as callable for session instantiation
         if let Some(constructor) = info.constructor.as_ref() {
             self.function_types.insert(
                 name.clone(),
                 (
-                    constructor.parameter_types.clone(),
+                    constructor.parameters.clone(),
                     self.make_session_instance_type(name),
                 ),
             );
@@ -715,33 +1638,290 @@ impl TypeChecker {
             ));
         }
 
-        let parameter_types = method
-            .parameters
-            .iter()
-            .map(|param| self.parse_type_annotation(param.type_annotation.as_deref()))
-            .collect();
+        let parameters = self.parameter_infos(&method.parameters);
 
         let return_type = if method.is_constructor {
             self.make_session_instance_type(session_name)
         } else {
-            self.parse_type_annotation(method.return_type.as_deref())
+            let declared = generator_return_type(
+                &method.body,
+                self.parse_type_annotation(method.return_type.as_deref()),
+            );
+            if method.is_async {
+                HypnoType::create_promise(declared)
+            } else {
+                declared
+            }
         };
 
         Ok(SessionMethodInfo {
-            parameter_types,
+            type_parameters: method.type_parameters.clone(),
+            parameters,
             return_type,
             visibility: method.visibility,
             is_static: method.is_static,
             is_constructor: method.is_constructor,
+            owner: session_name.to_string(),
         })
     }
 
+    /// Instance type and (instantiated) metadata of the session named in `extends`
+    fn resolve_parent_session(
+        &self,
+        session_name: &str,
+        parent: &str,
+    ) -> Result<(HypnoType, SessionInfo), String> {
+        let parent_type = self.parse_type_annotation(Some(parent));
+        if parent_type.name.as_deref() == Some(session_name) {
+            return Err(format!("Session '{}' cannot extend itself", session_name));
+        }
+        if parent_type.base_type == HypnoBaseType::Unknown {
+            return Err(format!(
+                "Session '{}' extends unknown session '{}'",
+                session_name, parent
+            ));
+        }
+        match self.session_lookup(&parent_type) {
+            Some((parent_info, false)) => Ok((parent_type, parent_info)),
+            _ => Err(format!(
+                "Session '{}' can only extend a session, got {}",
+                session_name, parent_type
+            )),
+        }
+    }
+
+    /// Whether `method` accepts the arguments of `required` and returns a fitting value
+    fn signature_matches(&self, required: &SessionMethodInfo, method: &SessionMethodInfo) -> bool {
+        method.parameters.len() == required.parameters.len()
+            && method
+                .parameters
+                .iter()
+                .zip(&required.parameters)
+                .all(|(own, expected)| {
+                    own.is_rest == expected.is_rest && self.types_compatible(&own.ty, &expected.ty)
+                })
+            && self.types_compatible(&required.return_type, &method.return_type)
+    }
+
+    /// Check that `method` of `session_name` can replace the inherited `overridden` one
+    fn check_override(
+        &self,
+        session_name: &str,
+        method_name: &str,
+        overridden: &SessionMethodInfo,
+        method: &SessionMethodInfo,
+    ) -> Option<String> {
+        if overridden.visibility == SessionVisibility::Public
+            && method.visibility == SessionVisibility::Private
+        {
+            return Some(format!(
+                "Method '{}' in session '{}' cannot conceal the exposed method of session '{}'",
+                method_name, session_name, overridden.owner
+            ));
+        }
+
+        if !self.signature_matches(overridden, method) {
+            return Some(format!(
+                "Method '{}' in session '{}' does not match the overridden method of session '{}': expected {}, got {}",
+                method_name,
+                session_name,
+                overridden.owner,
+                self.method_function_type(overridden),
+                self.method_function_type(method)
+            ));
+        }
+        None
+    }
+
+    /// Signature metadata for declared parameters
+    fn parameter_infos(&self, parameters: &[Parameter]) -> Vec<ParameterInfo> {
+        parameters
+            .iter()
+            .map(|param| ParameterInfo {
+                name: Some(param.name.clone()),
+                ty: self.declared_parameter_type(param),
+                has_default: param.default_value.is_some(),
+                is_rest: param.is_rest,
+                accepts_iterable: false,
+            })
+            .collect()
+    }
+
+    /// Annotated type of a parameter (unannotated rest parameters collect unknown values)
+    fn declared_parameter_type(&self, param: &Parameter) -> HypnoType {
+        if param.is_rest && param.type_annotation.is_none() {
+            HypnoType::create_array(HypnoType::unknown())
+        } else {
+            self.parse_type_annotation(param.type_annotation.as_deref())
+        }
+    }
+
+    /// Bring parameters into scope for checking a suggestion body.
+    ///
+    /// Default values are checked against the annotation (and give unannotated
+    /// parameters their type); they may refer to earlier parameters.
+    fn bind_parameters(&mut self, parameters: &[Parameter]) {
+        for param in parameters {
+            let mut param_type = self.declared_parameter_type(param);
+
+            if param.is_rest
+                && !param_type.is_array()
+                && param_type.base_type != HypnoBaseType::Unknown
+            {
+                self.errors.push(format!(
+                    "Rest parameter '{}' must have an array type, got {}",
+                    param.name, param_type
+                ));
+            }
+
+            if let Some(default_value) = &param.default_value {
+                let default_type = self.infer_type(default_value);
+                if param.type_annotation.is_none() {
+                    param_type = default_type;
+                } else if !self.types_compatible(&param_type, &default_type) {
+                    self.errors.push(format!(
+                        "Default value of parameter '{}' must be of type {}, got {}",
+                        param.name, param_type, default_type
+                    ));
+                }
+            }
+
+            match &param.pattern {
+                Some(pattern) => {
+                    self.check_destructurable(pattern, &param_type);
+                    self.bind_pattern(pattern, &param_type);
+                }
+                None => {
+                    self.type_env.insert(param.name.clone(), param_type);
+                }
+            }
+        }
+    }
+
+    /// Report a destructuring subject whose type the pattern can never unpack
+    fn check_destructurable(&mut self, pattern: &Pattern, subject_type: &HypnoType) {
+        let fits: fn(&HypnoType) -> bool = match pattern {
+            Pattern::Array { .. } => HypnoType::is_array,
+            Pattern::Record { type_name, .. } if type_name.is_empty() => {
+                |ty| ty.is_record() || ty.is_dictionary()
+            }
+            _ => return,
+        };
+        let destructurable = subject_type
+            .union_members()
+            .iter()
+            .any(|member| member.base_type == HypnoBaseType::Unknown || fits(member));
+        if !destructurable {
+            self.errors.push(format!(
+                "Cannot destructure a value of type {} with pattern {}",
+                subject_type, pattern
+            ));
+        }
+    }
+
+    /// Operand and result type of an operator overloaded by the left operand's
+    /// session or tranceify type; `!=` falls back to an `==` overload
+    fn operator_signature(
+        &self,
+        operator: &str,
+        left_type: &HypnoType,
+    ) -> Option<(HypnoType, HypnoType)> {
+        let lookup = |operator: &str| match left_type.base_type {
+            HypnoBaseType::Session => {
+                let (info, is_static_reference) = self.session_lookup(left_type)?;
+                if is_static_reference {
+                    return None;
+                }
+                let method = info.instance_methods.get(&operator_method_name(operator))?;
+                Some((
+                    method.parameters.first()?.ty.clone(),
+                    method.return_type.clone(),
+                ))
+            }
+            HypnoBaseType::Record => {
+                let info = self.tranceify_types.get(left_type.name.as_deref()?)?;
+                let (operand_type, result_type) = info.operators.get(operator)?;
+                let type_arguments = left_type.type_arguments.as_deref().unwrap_or_default();
+                let bindings = bind_type_arguments(&info.type_parameters, type_arguments);
+                Some((
+                    substitute_type_parameters(operand_type, &bindings),
+                    substitute_type_parameters(result_type, &bindings),
+                ))
+            }
+            _ => None,
+        };
+
+        let operator = overloadable_operator(operator)?;
+        match lookup(operator) {
+            None if operator == "!=" => {
+                lookup("==").map(|(operand_type, _)| (operand_type, HypnoType::boolean()))
+            }
+            signature => signature,
+        }
+    }
+
+    /// Equality and comparison overloads must produce a boolean
+    fn check_operator_result(&mut self, operator: &str, result_type: &HypnoType) {
+        let is_comparison = matches!(operator, "==" | "!=" | "<" | ">" | "<=" | ">=");
+        if is_comparison
+            && !matches!(
+                result_type.base_type,
+                HypnoBaseType::Boolean | HypnoBaseType::Unknown
+            )
+        {
+            self.errors.push(format!(
+                "Operator '{}' must return boolean, got {}",
+                operator, result_type
+            ));
+        }
+    }
+
+    /// Callable type of a signature. Signatures with defaults or a rest
+    /// parameter have no fixed arity and are typed as a bare function.
+    fn callable_type(parameters: &[ParameterInfo], return_type: HypnoType) -> HypnoType {
+        if parameters
+            .iter()
+            .any(|param| param.has_default || param.is_rest)
+        {
+            let mut ty = HypnoType::new(HypnoBaseType::Function, None);
+            ty.return_type = Some(Box::new(return_type));
+            return ty;
+        }
+        HypnoType::create_function(
+            parameters.iter().map(|param| param.ty.clone()).collect(),
+            return_type,
+        )
+    }
+
     fn make_session_type(&self, name: &str) -> HypnoType {
         HypnoType::new(HypnoBaseType::Session, Some(format!("{}::type", name)))
     }
 
+    /// Instance type of a session; generic sessions carry their own type parameters
     fn make_session_instance_type(&self, name: &str) -> HypnoType {
-        HypnoType::new(HypnoBaseType::Session, Some(name.to_string()))
+        let type_arguments = self
+            .sessions
+            .get(name)
+            .map(|info| {
+                info.type_parameters
+                    .iter()
+                    .map(|param| HypnoType::create_type_parameter(param.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.session_instance_type(name, type_arguments)
+    }
+
+    /// Instance type of a session with the given type arguments (unknown if omitted)
+    fn session_instance_type(&self, name: &str, mut type_arguments: Vec<HypnoType>) -> HypnoType {
+        let ty = HypnoType::new(HypnoBaseType::Session, Some(name.to_string()));
+        match self.sessions.get(name) {
+            Some(info) if !info.type_parameters.is_empty() => {
+                type_arguments.resize_with(info.type_parameters.len(), HypnoType::unknown);
+                ty.with_type_arguments(type_arguments)
+            }
+            _ => ty,
+        }
     }
 
     fn check_session_field(&mut self, session_name: &str, field: &SessionField) {
@@ -763,38 +1943,87 @@ impl TypeChecker {
     }
 
     fn check_session_method(&mut self, session_name: &str, method: &SessionMethod) {
+        let (this_type, super_type) = if method.is_static {
+            (None, None)
+        } else {
+            // `super` reaches the members of the parent session
+            let parent = self
+                .sessions
+                .get(session_name)
+                .and_then(|info| info.parent.clone());
+            (Some(self.make_session_instance_type(session_name)), parent)
+        };
+        self.check_method(method, this_type, super_type);
+    }
+
+    /// Check a method body with `this` and `super` bound (static methods pass `None`).
+    /// Constructors return the type of `this`.
+    fn check_method(
+        &mut self,
+        method: &SessionMethod,
+        this_type: Option<HypnoType>,
+        super_type: Option<HypnoType>,
+    ) {
         let saved_env = self.type_env.clone();
+        let saved_narrowed = std::mem::take(&mut self.narrowed_types);
         let saved_return = self.current_function_return_type.clone();
+        let saved_generator = self.current_generator.take();
         let saved_static = self.in_static_context;
+        let saved_constructor = self.in_constructor;
+        let saved_labels = std::mem::take(&mut self.loop_labels);
 
         self.in_static_context = method.is_static;
-
-        let return_type = if method.is_constructor {
-            self.make_session_instance_type(session_name)
-        } else {
-            self.parse_type_annotation(method.return_type.as_deref())
+        self.in_constructor = method.is_constructor;
+        let depth = self.type_parameters.len();
+        self.type_parameters
+            .extend(method.type_parameters.iter().cloned());
+
+        let return_type = match &this_type {
+            Some(this_type) if method.is_constructor => this_type.clone(),
+            _ => self.parse_type_annotation(method.return_type.as_deref()),
         };
+        self.current_generator =
+            self.generator_element_type(&method.name, &method.body, &return_type);
         self.current_function_return_type = Some(return_type);
 
-        if !method.is_static {
-            self.type_env.insert(
-                "this".to_string(),
-                self.make_session_instance_type(session_name),
-            );
+        if let Some(this_type) = this_type {
+            self.type_env.insert("this".to_string(), this_type);
+            match super_type {
+                Some(parent) => self.type_env.insert("super".to_string(), parent),
+                None => self.type_env.remove("super"),
+            };
         }
 
-        for param in &method.parameters {
-            let param_type = self.parse_type_annotation(param.type_annotation.as_deref());
-            self.type_env.insert(param.name.clone(), param_type);
-        }
+        self.bind_parameters(&method.parameters);
 
         for stmt in &method.body {
             self.check_statement(stmt);
         }
 
         self.type_env = saved_env;
+        self.narrowed_types = saved_narrowed;
         self.current_function_return_type = saved_return;
+        self.current_generator = saved_generator;
         self.in_static_context = saved_static;
+        self.in_constructor = saved_constructor;
+        self.loop_labels = saved_labels;
+        self.type_parameters.truncate(depth);
+    }
+
+    /// A `finale` is checked like a private method without parameters
+    fn check_session_finale(&mut self, session_name: &str, body: &[AstNode]) {
+        let finale = SessionMethod {
+            name: "finale".to_string(),
+            type_parameters: Vec::new(),
+            parameters: Vec::new(),
+            return_type: None,
+            body: body.to_vec(),
+            visibility: SessionVisibility::Private,
+            is_static: false,
+            is_constructor: false,
+            is_async: false,
+        };
+        self.check_session_method(session_name, &finale);
     }
 
     fn session_lookup(&self, ty: &HypnoType) -> Option<(SessionInfo, bool)> {
@@ -806,10 +2035,12 @@ impl TypeChecker {
         if let Some(stripped) = name.strip_suffix("::type") {
             self.sessions
                 .get(stripped)
-                .cloned()
-                .map(|info| (info, true))
+                .map(|info| (info.instantiate(&[]), true))
         } else {
-            self.sessions.get(name).cloned().map(|info| (info, false))
+            let type_arguments = ty.type_arguments.as_deref().unwrap_or_default();
+            self.sessions
+                .get(name)
+                .map(|info| (info.instantiate(type_arguments), false))
         }
     }
 
@@ -822,39 +2053,215 @@ impl TypeChecker {
     }
 
     fn method_function_type(&self, method: &SessionMethodInfo) -> HypnoType {
-        HypnoType::create_function(method.parameter_types.clone(), method.return_type.clone())
+        Self::callable_type(&method.parameters, method.return_type.clone())
     }
 
     fn infer_session_member(&mut self, object: &AstNode, property: &str) -> HypnoType {
         let object_type = self.infer_type(object);
+        self.member_type(&object_type, property)
+    }
 
-        // Check if this is a Record type (tranceify)
-        if object_type.base_type == HypnoBaseType::Record
-            && let Some(type_name) = &object_type.name
-        {
-            if let Some(tranceify_info) = self.tranceify_types.get(type_name) {
-                if let Some(field_type) = tranceify_info.fields.get(property) {
-                    return field_type.clone();
-                }
-
+    /// Element type the `emerge` statements of `body` must produce, or `None`
+    /// if it is not a generator body.
+    ///
+    /// Generators declare an `Iterator<T>` return type or none at all.
+    fn generator_element_type(
+        &mut self,
+        name: &str,
+        body: &[AstNode],
+        return_type: &HypnoType,
+    ) -> Option<HypnoType> {
+        if !is_generator_body(body) {
+            return None;
+        }
+        match return_type.base_type {
+            HypnoBaseType::Iterator => self.iterable_element_type(return_type),
+            HypnoBaseType::Unknown => Some(HypnoType::unknown()),
+            _ => {
                 self.errors.push(format!(
-                    "Record type '{}' has no field '{}'",
-                    type_name, property
+                    "Generator suggestion '{}' must return Iterator<T>, declared {}",
+                    name, return_type
                 ));
-            } else {
-                self.errors
-                    .push(format!("Unknown record type '{}'", type_name));
+                Some(HypnoType::unknown())
             }
+        }
+    }
 
-            return HypnoType::unknown();
+    /// Type of the values produced by an iterator or a session implementing
+    /// the iterator protocol; `None` for other types
+    fn iterable_element_type(&self, iterable: &HypnoType) -> Option<HypnoType> {
+        match iterable.base_type {
+            HypnoBaseType::Iterator => Some(
+                iterable
+                    .element_type
+                    .as_deref()
+                    .cloned()
+                    .unwrap_or_else(HypnoType::unknown),
+            ),
+            HypnoBaseType::Session => {
+                let (info, false) = self.session_lookup(iterable)? else {
+                    return None;
+                };
+                if let Some(iterate) = info.instance_methods.get("iterate") {
+                    return match iterate.return_type.base_type {
+                        HypnoBaseType::Array => Some(
+                            iterate
+                                .return_type
+                                .element_type
+                                .as_deref()
+                                .cloned()
+                                .unwrap_or_else(HypnoType::unknown),
+                        ),
+                        HypnoBaseType::Unknown => Some(HypnoType::unknown()),
+                        _ => self.iterable_element_type(&iterate.return_type),
+                    };
+                }
+                info.instance_methods.get("hasNext")?;
+                Some(info.instance_methods.get("next")?.return_type.clone())
+            }
+            _ => None,
         }
+    }
 
-        let Some((session_info, is_static_reference)) = self.session_lookup(&object_type) else {
-            self.errors.push(format!(
-                "Cannot access member '{}' on value of type {}",
-                property, object_type
-            ));
-            return HypnoType::unknown();
+    /// Types bound by a for-each loop: index or key, and element or value.
+    ///
+    /// With a single loop variable, dictionaries and records yield `[key, value]` entries.
+    fn iteration_types(&mut self, iterable: &HypnoType, keyed: bool) -> (HypnoType, HypnoType) {
+        let (key_type, value_type) = match iterable.base_type {
+            HypnoBaseType::Array => {
+                let element = iterable.element_type.as_deref().cloned();
+                return (
+                    HypnoType::number(),
+                    element.unwrap_or_else(HypnoType::unknown),
+                );
+            }
+            HypnoBaseType::String => return (HypnoType::number(), HypnoType::string()),
+            HypnoBaseType::Iterator | HypnoBaseType::Session => {
+                if let Some(element) = self.iterable_element_type(iterable) {
+                    return (HypnoType::number(), element);
+                }
+                self.errors.push(format!(
+                    "Cannot iterate over value of type {}: it needs 'hasNext()' and 'next()' or an 'iterate()' method",
+                    iterable
+                ));
+                return (HypnoType::unknown(), HypnoType::unknown());
+            }
+            HypnoBaseType::Dictionary => (
+                iterable
+                    .key_type
+                    .as_deref()
+                    .cloned()
+                    .unwrap_or_else(HypnoType::unknown),
+                iterable
+                    .element_type
+                    .as_deref()
+                    .cloned()
+                    .unwrap_or_else(HypnoType::unknown),
+            ),
+            HypnoBaseType::Record => {
+                let fields = iterable.fields.clone().or_else(|| {
+                    let name = iterable.name.as_ref()?;
+                    Some(self.tranceify_types.get(name)?.fields.clone())
+                });
+                let value_type = match fields {
+                    Some(fields) if !fields.is_empty() => {
+                        HypnoType::create_union(fields.into_values().collect())
+                    }
+                    _ => HypnoType::unknown(),
+                };
+                (HypnoType::string(), value_type)
+            }
+            HypnoBaseType::Unknown => return (HypnoType::unknown(), HypnoType::unknown()),
+            _ => {
+                self.errors
+                    .push(format!("Cannot iterate over value of type {}", iterable));
+                return (HypnoType::unknown(), HypnoType::unknown());
+            }
+        };
+
+        if keyed {
+            (key_type, value_type)
+        } else {
+            let entry = HypnoType::create_union(vec![key_type, value_type]);
+            (HypnoType::unknown(), HypnoType::create_array(entry))
+        }
+    }
+
+    /// Type of `property` on a record or session value of type `object_type`
+    fn member_type(&mut self, object_type: &HypnoType, property: &str) -> HypnoType {
+        // Check if this is a Record type (tranceify)
+        if object_type.base_type == HypnoBaseType::Record
+            && let Some(type_name) = &object_type.name
+        {
+            if let Some(tranceify_info) = self.tranceify_types.get(type_name) {
+                // Instantiated generic records carry their substituted field types
+                let fields = object_type
+                    .fields
+                    .as_ref()
+                    .unwrap_or(&tranceify_info.fields);
+                if let Some(field_type) = fields.get(property) {
+                    return field_type.clone();
+                }
+
+                self.errors.push(format!(
+                    "Record type '{}' has no field '{}'",
+                    type_name, property
+                ));
+            } else {
+                self.errors
+                    .push(format!("Unknown record type '{}'", type_name));
+            }
+
+            return HypnoType::unknown();
+        }
+
+        if object_type.base_type == HypnoBaseType::Interface
+            && let Some(interface) = object_type
+                .name
+                .as_ref()
+                .and_then(|name| self.interfaces.get(name))
+        {
+            if let Some(field_type) = interface.fields.get(property) {
+                return field_type.clone();
+            }
+            if let Some(method) = interface.methods.get(property) {
+                return self.method_function_type(method);
+            }
+            self.errors.push(format!(
+                "Interface '{}' has no member '{}'",
+                interface.name, property
+            ));
+            return HypnoType::unknown();
+        }
+
+        if let Some(info) = self.enum_lookup(object_type) {
+            return match info.variant(property) {
+                Some([]) => HypnoType::create_enum(info.name.clone()),
+                Some(payload) => {
+                    self.errors.push(format!(
+                        "Variant '{}.{}' carries a payload and must be called with {} value(s)",
+                        info.name,
+                        property,
+                        payload.len()
+                    ));
+                    HypnoType::unknown()
+                }
+                None => {
+                    self.errors.push(format!(
+                        "Enum '{}' has no variant '{}'",
+                        info.name, property
+                    ));
+                    HypnoType::unknown()
+                }
+            };
+        }
+
+        let Some((session_info, is_static_reference)) = self.session_lookup(object_type) else {
+            self.errors.push(format!(
+                "Cannot access member '{}' on value of type {}",
+                property, object_type
+            ));
+            return HypnoType::unknown();
         };
 
         let session_name = session_info.name.clone();
@@ -862,7 +2269,7 @@ impl TypeChecker {
         if is_static_reference {
             if let Some(field) = session_info.static_fields.get(property).cloned() {
                 debug_assert!(field.is_static);
-                if !self.visibility_allows(&session_name, field.visibility) {
+                if !self.visibility_allows(&field.owner, field.visibility) {
                     self.errors.push(format!(
                         "Static field '{}' of session '{}' is not visible here",
                         property, session_name
@@ -881,7 +2288,7 @@ impl TypeChecker {
             }
 
             if let Some(method) = session_info.static_methods.get(property).cloned() {
-                if !self.visibility_allows(&session_name, method.visibility) {
+                if !self.visibility_allows(&method.owner, method.visibility) {
                     self.errors.push(format!(
                         "Static method '{}' of session '{}' is not visible here",
                         property, session_name
@@ -908,7 +2315,7 @@ impl TypeChecker {
 
         if let Some(field) = session_info.instance_fields.get(property).cloned() {
             debug_assert!(!field.is_static);
-            if !self.visibility_allows(&session_name, field.visibility) {
+            if !self.visibility_allows(&field.owner, field.visibility) {
                 self.errors.push(format!(
                     "Field '{}' of session '{}' is not visible here",
                     property, session_name
@@ -920,7 +2327,7 @@ impl TypeChecker {
 
         if let Some(field) = session_info.static_fields.get(property).cloned() {
             debug_assert!(field.is_static);
-            if !self.visibility_allows(&session_name, field.visibility) {
+            if !self.visibility_allows(&field.owner, field.visibility) {
                 self.errors.push(format!(
                     "Static field '{}' of session '{}' is not visible here",
                     property, session_name
@@ -931,7 +2338,7 @@ impl TypeChecker {
         }
 
         if let Some(method) = session_info.instance_methods.get(property).cloned() {
-            if !self.visibility_allows(&session_name, method.visibility) {
+            if !self.visibility_allows(&method.owner, method.visibility) {
                 self.errors.push(format!(
                     "Method '{}' of session '{}' is not visible here",
                     property, session_name
@@ -942,7 +2349,7 @@ impl TypeChecker {
         }
 
         if let Some(method) = session_info.static_methods.get(property).cloned() {
-            if !self.visibility_allows(&session_name, method.visibility) {
+            if !self.visibility_allows(&method.owner, method.visibility) {
                 self.errors.push(format!(
                     "Static method '{}' of session '{}' is not visible here",
                     property, session_name
@@ -959,6 +2366,74 @@ impl TypeChecker {
         HypnoType::unknown()
     }
 
+    /// Check `super(...)`, which runs the parent constructor on `this`
+    fn check_super_constructor_call(&mut self, arguments: &[AstNode]) -> HypnoType {
+        let parent = self
+            .type_env
+            .get("super")
+            .cloned()
+            .and_then(|parent| self.session_lookup(&parent));
+        let Some((parent_info, _)) = parent.filter(|_| self.in_constructor) else {
+            for arg in arguments {
+                self.infer_type(arg);
+            }
+            self.errors.push(
+                "'super(...)' can only be called in the constructor of a session that extends another session"
+                    .to_string(),
+            );
+            return HypnoType::unknown();
+        };
+
+        let callee = format!("Constructor of session '{}'", parent_info.name);
+        let parameters = parent_info
+            .constructor
+            .map(|constructor| constructor.parameters)
+            .unwrap_or_default();
+        self.check_call_arguments(arguments, &parameters, &[], &callee);
+        HypnoType::null()
+    }
+
+    /// Check a call through a value of function type (closures, callbacks)
+    fn check_callable_call(&mut self, callee_type: &HypnoType, arguments: &[AstNode]) -> HypnoType {
+        if callee_type.base_type == HypnoBaseType::Unknown {
+            for arg in arguments {
+                self.infer_type(arg);
+            }
+            return HypnoType::unknown();
+        }
+
+        if callee_type.base_type != HypnoBaseType::Function {
+            self.errors
+                .push(format!("Value of type {} is not callable", callee_type));
+            return HypnoType::unknown();
+        }
+
+        let return_type = callee_type
+            .return_type
+            .clone()
+            .map(|boxed| (*boxed).clone())
+            .unwrap_or_else(HypnoType::unknown);
+
+        match &callee_type.parameter_types {
+            Some(param_types) => {
+                let params: Vec<ParameterInfo> = param_types
+                    .iter()
+                    .cloned()
+                    .map(ParameterInfo::positional)
+                    .collect();
+                self.check_call_arguments(arguments, &params, &[], "Callable");
+            }
+            // No fixed signature (defaults, rest parameter or unknown callable)
+            None => {
+                for arg in arguments {
+                    self.infer_type(arg);
+                }
+            }
+        }
+
+        return_type
+    }
+
     fn check_session_method_call(
         &mut self,
         object: &AstNode,
@@ -966,6 +2441,78 @@ impl TypeChecker {
         arguments: &[AstNode],
     ) -> HypnoType {
         let object_type = self.infer_type(object);
+        if object_type.base_type == HypnoBaseType::Interface
+            && let Some(interface) = object_type
+                .name
+                .as_ref()
+                .and_then(|name| self.interfaces.get(name))
+        {
+            let interface = interface.clone();
+            return match interface.methods.get(property) {
+                Some(method) => {
+                    let callee = format!("Method '{}' of interface '{}'", property, interface.name);
+                    self.check_call_arguments(arguments, &method.parameters, &[], &callee);
+                    method.return_type.clone()
+                }
+                None => {
+                    let field_type = self.member_type(&object_type, property);
+                    self.check_callable_call(&field_type, arguments)
+                }
+            };
+        }
+
+        if let Some(info) = self.enum_lookup(&object_type) {
+            match info.variant(property) {
+                Some([]) => {
+                    self.errors.push(format!(
+                        "Variant '{}.{}' has no payload and cannot be called",
+                        info.name, property
+                    ));
+                    for arg in arguments {
+                        self.infer_type(arg);
+                    }
+                }
+                Some(payload) => {
+                    let parameters: Vec<ParameterInfo> = payload
+                        .iter()
+                        .cloned()
+                        .map(ParameterInfo::positional)
+                        .collect();
+                    let callee = format!("Variant '{}.{}'", info.name, property);
+                    self.check_call_arguments(arguments, &parameters, &[], &callee);
+                }
+                None => {
+                    self.errors.push(format!(
+                        "Enum '{}' has no variant '{}'",
+                        info.name, property
+                    ));
+                    return HypnoType::unknown();
+                }
+            }
+            return HypnoType::create_enum(info.name);
+        }
+
+        if object_type.is_iterator() {
+            for arg in arguments {
+                self.infer_type(arg);
+            }
+            if !arguments.is_empty() {
+                self.errors
+                    .push(format!("Iterator method '{}' takes no arguments", property));
+            }
+            return match property {
+                "next" => self
+                    .iterable_element_type(&object_type)
+                    .unwrap_or_else(HypnoType::unknown),
+                "hasNext" => HypnoType::boolean(),
+                _ => {
+                    self.errors
+                        .push(format!("Iterator has no method '{}'", property));
+                    HypnoType::unknown()
+                }
+            };
+        }
+
         let Some((session_info, is_static_reference)) = self.session_lookup(&object_type) else {
             self.errors.push(format!(
                 "Cannot call member '{}' on value of type {}",
@@ -992,7 +2539,7 @@ impl TypeChecker {
         };
 
         if let Some(method_info) = method {
-            if !self.visibility_allows(&session_name, method_info.visibility) {
+            if !self.visibility_allows(&method_info.owner, method_info.visibility) {
                 self.errors.push(format!(
                     "Method '{}' of session '{}' is not visible here",
                     property, session_name
@@ -1016,34 +2563,15 @@ impl TypeChecker {
                 return HypnoType::unknown();
             }
 
-            if arguments.len() != method_info.parameter_types.len() {
-                self.errors.push(format!(
-                    "Method '{}' of session '{}' expects {} arguments, got {}",
-                    property,
-                    session_name,
-                    method_info.parameter_types.len(),
-                    arguments.len()
-                ));
-            } else {
-                for (idx, (arg, expected)) in arguments
-                    .iter()
-                    .zip(method_info.parameter_types.iter())
-                    .enumerate()
-                {
-                    let actual = self.infer_type(arg);
-                    if !self.types_compatible(expected, &actual) {
-                        self.errors.push(format!(
-                            "Method '{}' argument {} type mismatch: expected {}, got {}",
-                            property,
-                            idx + 1,
-                            expected,
-                            actual
-                        ));
-                    }
-                }
-            }
+            let callee = format!("Method '{}' of session '{}'", property, session_name);
+            let bindings = self.check_call_arguments(
+                arguments,
+                &method_info.parameters,
+                &method_info.type_parameters,
+                &callee,
+            );
 
-            return method_info.return_type.clone();
+            return substitute_type_parameters(&method_info.return_type, &bindings);
         }
 
         if session_info.instance_fields.contains_key(property) {
@@ -1100,7 +2628,7 @@ impl TypeChecker {
         if is_static_reference {
             if let Some(field) = static_field {
                 debug_assert!(field.is_static);
-                if !self.visibility_allows(&session_name, field.visibility) {
+                if !self.visibility_allows(&field.owner, field.visibility) {
                     self.errors.push(format!(
                         "Static field '{}' of session '{}' is not visible here",
                         property, session_name
@@ -1145,7 +2673,7 @@ impl TypeChecker {
 
         if let Some(field) = instance_field {
             debug_assert!(!field.is_static);
-            if !self.visibility_allows(&session_name, field.visibility) {
+            if !self.visibility_allows(&field.owner, field.visibility) {
                 self.errors.push(format!(
                     "Field '{}' of session '{}' is not visible here",
                     property, session_name
@@ -1166,7 +2694,7 @@ impl TypeChecker {
 
         if let Some(field) = static_field {
             debug_assert!(field.is_static);
-            if !self.visibility_allows(&session_name, field.visibility) {
+            if !self.visibility_allows(&field.owner, field.visibility) {
                 self.errors.push(format!(
                     "Static field '{}' of session '{}' is not visible here",
                     property, session_name
@@ -1201,6 +2729,8 @@ impl TypeChecker {
     /// Check a statement
     fn check_statement(&mut self, stmt: &AstNode) {
         match stmt {
+            AstNode::Spanned { .. } => self.with_location(stmt, Self::check_statement),
+
             AstNode::VariableDeclaration {
                 name,
                 type_annotation,
@@ -1212,11 +2742,14 @@ impl TypeChecker {
                 let mut final_type = expected_type.clone();
 
                 if let Some(init) = initializer {
-                    let actual_type = self.infer_type(init);
+                    let actual_type = self.infer_expected_type(init, &expected_type);
 
                     // If no type annotation was provided, use the inferred type
+                    // (a bare `null` leaves the variable open to any value)
                     if expected_type.base_type == HypnoBaseType::Unknown {
-                        final_type = actual_type;
+                        if !actual_type.is_null() {
+                            final_type = actual_type;
+                        }
                     } else if !self.types_compatible(&expected_type, &actual_type) {
                         self.errors.push(format!(
                             "Type mismatch for variable '{}': expected {}, got {}",
@@ -1228,59 +2761,95 @@ impl TypeChecker {
                         .push(format!("Constant variable '{}' must be initialized", name));
                 }
 
+                self.narrowed_types.remove(name);
                 self.type_env.insert(name.clone(), final_type);
             }
 
+            AstNode::DestructuringDeclaration {
+                pattern,
+                type_annotation,
+                initializer,
+                ..
+            } => {
+                let expected_type = self.parse_type_annotation(type_annotation.as_deref());
+                let actual_type = self.infer_expected_type(initializer, &expected_type);
+                let subject_type = if expected_type.base_type == HypnoBaseType::Unknown {
+                    actual_type
+                } else {
+                    if !self.types_compatible(&expected_type, &actual_type) {
+                        self.errors.push(format!(
+                            "Type mismatch for pattern {}: expected {}, got {}",
+                            pattern, expected_type, actual_type
+                        ));
+                    }
+                    expected_type
+                };
+
+                self.check_destructurable(pattern, &subject_type);
+                self.bind_pattern(pattern, &subject_type);
+            }
+
             AstNode::AnchorDeclaration { name, source } => {
                 let source_type = self.infer_type(source);
                 self.type_env.insert(name.clone(), source_type);
             }
 
             AstNode::FunctionDeclaration {
+                name,
+                type_parameters,
                 parameters,
                 return_type,
                 body,
                 ..
             } => {
                 let old_env = self.type_env.clone();
-                let ret_type = self.parse_type_annotation(return_type.as_deref());
-                self.current_function_return_type = Some(ret_type);
-
-                for param in parameters {
-                    let param_type = self.parse_type_annotation(param.type_annotation.as_deref());
-                    self.type_env.insert(param.name.clone(), param_type);
-                }
-
-                for stmt in body {
-                    self.check_statement(stmt);
-                }
+                let old_narrowed = std::mem::take(&mut self.narrowed_types);
+                let old_labels = std::mem::take(&mut self.loop_labels);
+                self.with_type_parameters(type_parameters, |checker| {
+                    let ret_type = checker.parse_type_annotation(return_type.as_deref());
+                    checker.current_generator =
+                        checker.generator_element_type(name, body, &ret_type);
+                    checker.current_function_return_type = Some(ret_type);
+
+                    checker.bind_parameters(parameters);
+
+                    for stmt in body {
+                        checker.check_statement(stmt);
+                    }
+                });
 
                 self.type_env = old_env;
+                self.narrowed_types = old_narrowed;
+                self.loop_labels = old_labels;
                 self.current_function_return_type = None;
+                self.current_generator = None;
             }
 
             AstNode::TriggerDeclaration {
+                name,
                 parameters,
                 return_type,
                 body,
-                ..
             } => {
                 // Triggers are handled like functions
                 let old_env = self.type_env.clone();
+                let old_narrowed = std::mem::take(&mut self.narrowed_types);
+                let old_labels = std::mem::take(&mut self.loop_labels);
                 let ret_type = self.parse_type_annotation(return_type.as_deref());
+                self.current_generator = self.generator_element_type(name, body, &ret_type);
                 self.current_function_return_type = Some(ret_type);
 
-                for param in parameters {
-                    let param_type = self.parse_type_annotation(param.type_annotation.as_deref());
-                    self.type_env.insert(param.name.clone(), param_type);
-                }
+                self.bind_parameters(parameters);
 
                 for stmt in body {
                     self.check_statement(stmt);
                 }
 
                 self.type_env = old_env;
+                self.narrowed_types = old_narrowed;
+                self.loop_labels = old_labels;
                 self.current_function_return_type = None;
+                self.current_generator = None;
             }
 
             AstNode::EntranceBlock(statements) | AstNode::FinaleBlock(statements) => {
@@ -1300,14 +2869,28 @@ impl TypeChecker {
                         .push(format!("If condition must be boolean, got {}", cond_type));
                 }
 
+                // Null checks in the condition narrow variables in both branches
+                let saved_narrowed = self.narrowed_types.clone();
+
+                self.narrow_null_checks(condition, true, false);
                 for stmt in then_branch {
                     self.check_statement(stmt);
                 }
+                self.narrowed_types = saved_narrowed.clone();
 
                 if let Some(else_stmts) = else_branch {
+                    self.narrow_null_checks(condition, false, false);
                     for stmt in else_stmts {
                         self.check_statement(stmt);
                     }
+                    self.narrowed_types = saved_narrowed;
+                }
+
+                // A variable is not null after the `if` when its null branch always exits
+                if always_exits(then_branch) {
+                    self.narrow_null_checks(condition, false, true);
+                } else if else_branch.as_deref().is_some_and(always_exits) {
+                    self.narrow_null_checks(condition, true, true);
                 }
             }
 
@@ -1325,6 +2908,106 @@ impl TypeChecker {
                 }
             }
 
+            // Imports are resolved before the declaration passes
+            AstNode::ImportDeclaration { .. } => {}
+
+            AstNode::ExportDeclaration(declaration) => self.check_statement(declaration),
+
+            AstNode::TryStatement {
+                body,
+                catch_binding,
+                catch_body,
+                finally_body,
+            } => {
+                if stmt.contains_yield() {
+                    self.errors
+                        .push("'emerge' cannot be used inside attempt or using blocks".to_string());
+                }
+                for stmt in body {
+                    self.check_statement(stmt);
+                }
+
+                if let Some(handler) = catch_body {
+                    let old_env = self.type_env.clone();
+                    if let Some(name) = catch_binding {
+                        let error_type = self.error_type();
+                        self.type_env.insert(name.clone(), error_type);
+                    }
+                    for stmt in handler {
+                        self.check_statement(stmt);
+                    }
+                    self.type_env = old_env;
+                }
+
+                if let Some(cleanup) = finally_body {
+                    for stmt in cleanup {
+                        self.check_statement(stmt);
+                    }
+                }
+            }
+
+            AstNode::UsingStatement {
+                name,
+                resource,
+                body,
+            } => {
+                if is_generator_body(body) {
+                    self.errors
+                        .push("'emerge' cannot be used inside attempt or using blocks".to_string());
+                }
+                let resource_type = self.infer_type(resource);
+                let is_instance = resource_type.base_type == HypnoBaseType::Session
+                    && resource_type
+                        .name
+                        .as_deref()
+                        .is_some_and(|name| !name.ends_with("::type"));
+                if !is_instance && resource_type.base_type != HypnoBaseType::Unknown {
+                    self.errors.push(format!(
+                        "'using' requires a session instance, got {}",
+                        resource_type
+                    ));
+                }
+
+                let old_env = self.type_env.clone();
+                self.type_env.insert(name.clone(), resource_type);
+                for stmt in body {
+                    self.check_statement(stmt);
+                }
+                self.type_env = old_env;
+            }
+
+            AstNode::AssertStatement {
+                condition, message, ..
+            } => {
+                let cond_type = self.infer_type(condition);
+                if cond_type.base_type != HypnoBaseType::Boolean
+                    && cond_type.base_type != HypnoBaseType::Unknown
+                {
+                    self.errors.push(format!(
+                        "Assert condition must be boolean, got {}",
+                        cond_type
+                    ));
+                }
+                if let Some(message) = message {
+                    let message_type = self.infer_type(message);
+                    if message_type.base_type != HypnoBaseType::String
+                        && message_type.base_type != HypnoBaseType::Unknown
+                    {
+                        self.errors.push(format!(
+                            "Assert message must be a string, got {}",
+                            message_type
+                        ));
+                    }
+                }
+            }
+
+            AstNode::RaiseStatement { message, payload } => {
+                self.infer_type(message);
+                if let Some(payload) = payload {
+                    self.infer_type(payload);
+                }
+            }
+
             AstNode::WhileStatement { condition, body } => {
                 let cond_type = self.infer_type(condition);
                 if cond_type.base_type != HypnoBaseType::Boolean {
@@ -1366,6 +3049,46 @@ impl TypeChecker {
                 }
             }
 
+            AstNode::ForEachStatement {
+                key,
+                value,
+                iterable,
+                body,
+                ..
+            } => {
+                let iterable_type = self.infer_type(iterable).widened();
+                let (key_type, value_type) = self.iteration_types(&iterable_type, key.is_some());
+
+                let old_env = self.type_env.clone();
+                if let Some(key) = key {
+                    self.type_env.insert(key.clone(), key_type);
+                }
+                self.type_env.insert(value.clone(), value_type);
+                for stmt in body {
+                    self.check_statement(stmt);
+                }
+                self.type_env = old_env;
+            }
+
+            AstNode::LabeledStatement { label, body } => {
+                if self.loop_labels.contains(label) {
+                    self.errors.push(format!(
+                        "Loop label '{}' is already used by an enclosing loop",
+                        label
+                    ));
+                }
+                self.loop_labels.push(label.clone());
+                self.check_statement(body);
+                self.loop_labels.pop();
+            }
+
+            AstNode::BreakStatement(Some(label)) | AstNode::ContinueStatement(Some(label))
+                if !self.loop_labels.contains(label) =>
+            {
+                self.errors
+                    .push(format!("Undefined loop label '{}'", label));
+            }
+
             AstNode::OscillateStatement { target } => {
                 let target_type = self.infer_type(target);
                 if target_type.base_type != HypnoBaseType::Boolean {
@@ -1376,33 +3099,96 @@ impl TypeChecker {
                 }
             }
 
-            AstNode::SessionDeclaration { name, members } => {
+            AstNode::SessionDeclaration {
+                name,
+                type_parameters,
+                members,
+                ..
+            } => {
                 let prev_session = self.current_session.clone();
                 let prev_static = self.in_static_context;
 
                 self.current_session = Some(name.clone());
                 self.in_static_context = false;
 
-                for member in members {
-                    match member {
-                        SessionMember::Field(field) => self.check_session_field(name, field),
-                        SessionMember::Method(method) => self.check_session_method(name, method),
+                self.with_type_parameters(type_parameters, |checker| {
+                    for member in members {
+                        match member {
+                            SessionMember::Field(field) => checker.check_session_field(name, field),
+                            SessionMember::Method(method) => {
+                                checker.check_session_method(name, method)
+                            }
+                            SessionMember::Finale(body) => checker.check_session_finale(name, body),
+                        }
                     }
-                }
+                });
 
                 self.current_session = prev_session;
                 self.in_static_context = prev_static;
             }
 
-            AstNode::TranceifyDeclaration { .. } => {
-                // Type signatures already collected in collect_tranceify_signature
-                // No additional checking needed here
+            AstNode::TranceifyDeclaration {
+                name,
+                type_parameters,
+                operators,
+                ..
+            } => {
+                // Operator bodies see the record as `this`
+                self.with_type_parameters(type_parameters, |checker| {
+                    let type_arguments: Vec<HypnoType> = type_parameters
+                        .iter()
+                        .map(|param| HypnoType::create_type_parameter(param.clone()))
+                        .collect();
+                    let this_type = checker
+                        .tranceify_types
+                        .get(name)
+                        .map(|info| info.instantiate(&type_arguments));
+                    for method in operators {
+                        checker.check_method(method, this_type.clone(), None);
+                    }
+                });
+            }
+
+            AstNode::InterfaceDeclaration { .. } | AstNode::EnumDeclaration { .. } => {
+                // Type signatures already collected in collect_tranceify_signature,
+                // collect_interface_signature and collect_enum_signature
+            }
+
+            AstNode::ReturnStatement(Some(value)) if self.current_generator.is_some() => {
+                self.infer_type(value);
+                self.errors.push(
+                    "Generator suggestions end with 'awaken;' and cannot return a value"
+                        .to_string(),
+                );
             }
 
+            AstNode::YieldStatement(value) => match self.current_generator.clone() {
+                Some(element) => {
+                    let actual = self.infer_expected_type(value, &element);
+                    if !self.types_compatible(&element, &actual) {
+                        self.errors.push(format!(
+                            "Emerged value type mismatch: expected {}, got {}",
+                            element, actual
+                        ));
+                    }
+                }
+                None => {
+                    self.infer_type(value);
+                    self.errors.push(
+                        "'emerge' can only be used in the body of a generator suggestion"
+                            .to_string(),
+                    );
+                }
+            },
+
             #[allow(clippy::collapsible_match)]
             AstNode::ReturnStatement(value) => {
                 if let Some(val) = value {
-                    let actual_type = self.infer_type(val);
+                    let expected_type = self
+                        .current_function_return_type
+                        .clone()
+                        .unwrap_or_else(HypnoType::unknown);
+                    let actual_type = self.infer_expected_type(val, &expected_type);
                     if let Some(ret_type) = &self.current_function_return_type.clone()
                         && !self.types_compatible(ret_type, &actual_type)
                     {
@@ -1429,8 +3215,18 @@ impl TypeChecker {
     fn infer_type(&mut self, expr: &AstNode) -> HypnoType {
         match expr {
             AstNode::NumberLiteral(_) => HypnoType::number(),
+            AstNode::IntegerLiteral(_) => HypnoType::integer(),
             AstNode::StringLiteral(_) => HypnoType::string(),
+            AstNode::StringInterpolation(parts) => {
+                for part in parts {
+                    if let InterpolationPart::Expression(expr) = part {
+                        self.infer_type(expr);
+                    }
+                }
+                HypnoType::string()
+            }
             AstNode::BooleanLiteral(_) => HypnoType::boolean(),
+            AstNode::NullLiteral => HypnoType::null(),
 
             AstNode::Identifier(name) => {
                 if name == "this" && self.in_static_context {
@@ -1439,7 +3235,7 @@ impl TypeChecker {
                     return HypnoType::unknown();
                 }
 
-                self.type_env.get(name).cloned().unwrap_or_else(|| {
+                self.variable_type(name).unwrap_or_else(|| {
                     self.errors.push(format!("Undefined variable '{}'", name));
                     HypnoType::unknown()
                 })
@@ -1450,76 +3246,24 @@ impl TypeChecker {
                 operator,
                 right,
             } => {
-                let left_type = self.infer_type(left);
-                let right_type = self.infer_type(right);
-                let normalized_op = operator.to_ascii_lowercase();
-
-                match normalized_op.as_str() {
-                    "+" => {
-                        // Allow string concatenation or numeric addition
-                        if left_type.base_type == HypnoBaseType::String
-                            || right_type.base_type == HypnoBaseType::String
-                        {
-                            HypnoType::string()
-                        } else if left_type.base_type == HypnoBaseType::Number
-                            && right_type.base_type == HypnoBaseType::Number
-                        {
-                            HypnoType::number()
-                        } else {
-                            self.errors.push(format!(
-                                "Operator '+' requires either two numbers or at least one string, got {} and {}",
-                                left_type, right_type
-                            ));
-                            HypnoType::unknown()
-                        }
-                    }
-                    "-" | "*" | "/" | "%" => {
-                        if left_type.base_type != HypnoBaseType::Number
-                            || right_type.base_type != HypnoBaseType::Number
-                        {
-                            self.errors.push(format!(
-                                "Arithmetic operator '{}' requires numeric operands, got {} and {}",
-                                operator, left_type, right_type
-                            ));
-                        }
-                        HypnoType::number()
-                    }
-                    "==" | "!=" | "youarefeelingverysleepy" | "youcannotresist" | "notsodeep" => {
-                        HypnoType::boolean()
-                    }
-                    ">"
-                    | "<"
-                    | ">="
-                    | "<="
-                    | "lookatthewatch"
-                    | "fallundermyspell"
-                    | "youreyesaregettingheavy"
-                    | "goingdeeper"
-                    | "deeplygreater"
-                    | "deeplyless" => {
-                        if left_type.base_type != HypnoBaseType::Number
-                            || right_type.base_type != HypnoBaseType::Number
-                        {
-                            self.errors.push(format!(
-                                "Comparison operator '{}' requires numeric operands, got {} and {}",
-                                operator, left_type, right_type
-                            ));
-                        }
-                        HypnoType::boolean()
-                    }
-                    "&&" | "undermycontrol" | "||" | "resistanceisfutile" => {
-                        if left_type.base_type != HypnoBaseType::Boolean
-                            || right_type.base_type != HypnoBaseType::Boolean
-                        {
-                            self.errors.push(format!(
-                                "Logical operator '{}' requires boolean operands, got {} and {}",
-                                operator, left_type, right_type
-                            ));
-                        }
-                        HypnoType::boolean()
-                    }
-                    _ => HypnoType::unknown(),
-                }
+                // Literal types behave like their primitive type in operations
+                let left_type = self.infer_type(left).widened();
+                // The right side of `&&` only runs when the left holds, of `||` when it fails
+                let left_holds = match operator.to_ascii_lowercase().as_str() {
+                    "&&" | "undermycontrol" => Some(true),
+                    "||" | "resistanceisfutile" => Some(false),
+                    _ => None,
+                };
+                let right_type = if let Some(holds) = left_holds {
+                    let saved_narrowed = self.narrowed_types.clone();
+                    self.narrow_null_checks(left, holds, false);
+                    let right_type = self.infer_type(right).widened();
+                    self.narrowed_types = saved_narrowed;
+                    right_type
+                } else {
+                    self.infer_type(right).widened()
+                };
+                self.binary_operator_type(operator, &left_type, &right_type)
             }
 
             AstNode::UnaryExpression { operator, operand } => {
@@ -1527,6 +3271,9 @@ impl TypeChecker {
 
                 match operator.as_str() {
                     "-" => {
+                        if operand_type.base_type == HypnoBaseType::Integer {
+                            return HypnoType::integer();
+                        }
                         if operand_type.base_type != HypnoBaseType::Number {
                             self.errors.push(format!(
                                 "Unary minus requires numeric operand, got {}",
@@ -1535,6 +3282,15 @@ impl TypeChecker {
                         }
                         HypnoType::number()
                     }
+                    "~" => {
+                        if !operand_type.is_numeric() {
+                            self.errors.push(format!(
+                                "Bitwise not requires an integer operand, got {}",
+                                operand_type
+                            ));
+                        }
+                        HypnoType::integer()
+                    }
                     "!" => {
                         if operand_type.base_type != HypnoBaseType::Boolean {
                             self.errors.push(format!(
@@ -1550,8 +3306,40 @@ impl TypeChecker {
 
             AstNode::CallExpression { callee, arguments } => match callee.as_ref() {
                 AstNode::Identifier(func_name) => {
+                    if is_builtin_function(func_name)
+                        && arguments
+                            .iter()
+                            .any(|arg| matches!(arg.unspanned(), AstNode::NamedArgument { .. }))
+                    {
+                        self.errors.push(format!(
+                            "Builtin function '{}' does not accept named arguments",
+                            func_name
+                        ));
+                        for arg in arguments {
+                            match arg.unspanned() {
+                                AstNode::NamedArgument { value, .. } => self.infer_type(value),
+                                _ => self.infer_type(arg),
+                            };
+                        }
+                        return self
+                            .function_types
+                            .get(func_name)
+                            .map(|(_, return_type)| return_type.clone())
+                            .unwrap_or_else(HypnoType::unknown);
+                    }
+
                     // Special case: Length accepts both string and array
                     if func_name == "Length" {
+                        if arguments.iter().any(|arg| {
+                            matches!(
+                                arg.unspanned(),
+                                AstNode::SpreadElement(_) | AstNode::NamedArgument { .. }
+                            )
+                        }) {
+                            let params = [ParameterInfo::positional(HypnoType::unknown())];
+                            self.check_call_arguments(arguments, &params, &[], "Function 'Length'");
+                            return HypnoType::number();
+                        }
                         if arguments.len() != 1 {
                             self.errors.push(format!(
                                 "Function 'Length' expects 1 argument, got {}",
@@ -1572,34 +3360,21 @@ impl TypeChecker {
                         return HypnoType::number();
                     }
 
-                    let func_sig = self.function_types.get(func_name).cloned();
+                    if func_name == "super" {
+                        return self.check_super_constructor_call(arguments);
+                    }
 
-                    if let Some((param_types, return_type)) = func_sig {
-                        if arguments.len() != param_types.len() {
-                            self.errors.push(format!(
-                                "Function '{}' expects {} arguments, got {}",
-                                func_name,
-                                param_types.len(),
-                                arguments.len()
-                            ));
-                        } else {
-                            for (i, (arg, expected_type)) in
-                                arguments.iter().zip(param_types.iter()).enumerate()
-                            {
-                                let actual_type = self.infer_type(arg);
-                                if !self.types_compatible(expected_type, &actual_type) {
-                                    self.errors.push(format!(
-                                        "Function '{}' argument {} type mismatch: expected {}, got {}",
-                                        func_name,
-                                        i + 1,
-                                        expected_type,
-                                        actual_type
-                                    ));
-                                }
-                            }
-                        }
+                    let func_sig = self.function_types.get(func_name).cloned();
 
-                        return_type
+                    if let Some((params, return_type)) = func_sig {
+                        let callee = format!("Function '{}'", func_name);
+                        let generics = signature_type_parameters(&params, &return_type);
+                        let bindings =
+                            self.check_call_arguments(arguments, &params, &generics, &callee);
+                        substitute_type_parameters(&return_type, &bindings)
+                    } else if let Some(callee_type) = self.type_env.get(func_name).cloned() {
+                        // Variable holding a suggestion value (e.g. a closure)
+                        self.check_callable_call(&callee_type, arguments)
                     } else {
                         self.errors
                             .push(format!("Undefined function '{}'", func_name));
@@ -1611,42 +3386,7 @@ impl TypeChecker {
                 }
                 _ => {
                     let callee_type = self.infer_type(callee);
-                    if callee_type.base_type != HypnoBaseType::Function {
-                        self.errors
-                            .push(format!("Value of type {} is not callable", callee_type));
-                        return HypnoType::unknown();
-                    }
-
-                    let param_types = callee_type.parameter_types.clone().unwrap_or_default();
-                    let return_type = callee_type
-                        .return_type
-                        .clone()
-                        .map(|boxed| (*boxed).clone())
-                        .unwrap_or_else(HypnoType::unknown);
-
-                    if arguments.len() != param_types.len() {
-                        self.errors.push(format!(
-                            "Callable expects {} arguments, got {}",
-                            param_types.len(),
-                            arguments.len()
-                        ));
-                    } else {
-                        for (i, (arg, expected_type)) in
-                            arguments.iter().zip(param_types.iter()).enumerate()
-                        {
-                            let actual_type = self.infer_type(arg);
-                            if !self.types_compatible(expected_type, &actual_type) {
-                                self.errors.push(format!(
-                                    "Callable argument {} type mismatch: expected {}, got {}",
-                                    i + 1,
-                                    expected_type,
-                                    actual_type
-                                ));
-                            }
-                        }
-                    }
-
-                    return_type
+                    self.check_callable_call(&callee_type, arguments)
                 }
             },
 
@@ -1656,8 +3396,14 @@ impl TypeChecker {
 
             AstNode::AssignmentExpression { target, value } => match target.as_ref() {
                 AstNode::Identifier(name) => {
-                    let value_type = self.infer_type(value);
-                    if let Some(expected_type) = self.type_env.get(name).cloned() {
+                    let declared_type = self.type_env.get(name).cloned();
+                    let value_type = match &declared_type {
+                        Some(declared) => self.infer_expected_type(value, declared),
+                        None => self.infer_type(value),
+                    };
+                    // Assignments end any narrowing of the variable
+                    self.narrowed_types.remove(name);
+                    if let Some(expected_type) = declared_type {
                         if !self.types_compatible(&expected_type, &value_type) {
                             self.errors.push(format!(
                                 "Cannot assign value of type {} to variable '{}' of type {}",
@@ -1674,12 +3420,122 @@ impl TypeChecker {
                 AstNode::MemberExpression { object, property } => {
                     self.check_member_assignment(object, property, value)
                 }
+                AstNode::IndexExpression { object, index } => {
+                    let object_type = self.infer_type(object);
+                    let index_type = self.infer_type(index);
+                    let element_type = self.check_index_type(&object_type, &index_type);
+                    let value_type = self.infer_type(value);
+                    if !self.types_compatible(&element_type, &value_type) {
+                        self.errors.push(format!(
+                            "Cannot assign value of type {} to element of {}",
+                            value_type, object_type
+                        ));
+                    }
+                    element_type
+                }
                 _ => {
                     self.errors.push("Invalid assignment target".to_string());
                     HypnoType::unknown()
                 }
             },
 
+            AstNode::CompoundAssignmentExpression {
+                target,
+                operator,
+                value,
+            } => {
+                let target_type = self.assignment_target_type(target);
+                if target_type.base_type == HypnoBaseType::Unknown {
+                    self.infer_type(value);
+                    return HypnoType::unknown();
+                }
+                let error_count = self.errors.len();
+                let result_type = if operator == "??" {
+                    // The value only replaces null, so it has to fit the non-null part
+                    self.infer_expected_type(value, &target_type.without_null())
+                } else {
+                    let value_type = self.infer_type(value).widened();
+                    self.binary_operator_type(operator, &target_type.widened(), &value_type)
+                };
+                // Operand errors already explain an unusable result
+                if self.errors.len() == error_count
+                    && !self.types_compatible(&target_type, &result_type)
+                {
+                    match target.unspanned() {
+                        AstNode::Identifier(name) => self.errors.push(format!(
+                            "Cannot assign value of type {} to variable '{}' of type {}",
+                            result_type, name, target_type
+                        )),
+                        _ => self.errors.push(format!(
+                            "Cannot assign value of type {} to target of type {}",
+                            result_type, target_type
+                        )),
+                    }
+                }
+                if operator == "??" && target_type.is_nullable() {
+                    HypnoType::create_union(vec![target_type.without_null(), result_type])
+                } else {
+                    target_type
+                }
+            }
+
+            AstNode::UpdateExpression {
+                target, operator, ..
+            } => {
+                let target_type = self.assignment_target_type(target).widened();
+                if target_type.base_type == HypnoBaseType::Integer {
+                    return HypnoType::integer();
+                }
+                if !matches!(
+                    target_type.base_type,
+                    HypnoBaseType::Number | HypnoBaseType::Unknown
+                ) {
+                    self.errors.push(format!(
+                        "Operator '{}' requires a numeric target, got {}",
+                        operator, target_type
+                    ));
+                }
+                HypnoType::number()
+            }
+
+            AstNode::RangeExpression {
+                start, end, step, ..
+            } => {
+                let bounds = [Some(start), Some(end), step.as_ref()];
+                for bound in bounds.into_iter().flatten() {
+                    let bound_type = self.infer_type(bound).widened();
+                    if !matches!(
+                        bound_type.base_type,
+                        HypnoBaseType::Number | HypnoBaseType::Integer | HypnoBaseType::Unknown
+                    ) {
+                        self.errors
+                            .push(format!("Range bounds must be numbers, got {}", bound_type));
+                    }
+                }
+                HypnoType::create_array(HypnoType::number())
+            }
+
+            // Spreads only appear in array literals and call arguments, where
+            // they contribute their element type
+            AstNode::SpreadElement(inner) => {
+                let spread_type = self.infer_type(inner);
+                match spread_type.base_type {
+                    HypnoBaseType::Array => spread_type
+                        .element_type
+                        .as_deref()
+                        .cloned()
+                        .unwrap_or_else(HypnoType::unknown),
+                    HypnoBaseType::Unknown => HypnoType::unknown(),
+                    _ => {
+                        self.errors.push(format!(
+                            "Cannot spread non-array value of type {}",
+                            spread_type
+                        ));
+                        HypnoType::unknown()
+                    }
+                }
+            }
+
             AstNode::ArrayLiteral(elements) => {
                 if elements.is_empty() {
                     HypnoType::create_array(HypnoType::unknown())
@@ -1698,38 +3554,95 @@ impl TypeChecker {
                 }
             }
 
+            AstNode::DictionaryLiteral(entries) => {
+                let mut key_type: Option<HypnoType> = None;
+                let mut value_type: Option<HypnoType> = None;
+                for entry in entries {
+                    let entry_key = self.infer_type(&entry.key);
+                    self.check_dictionary_key(&entry_key);
+                    let entry_value = self.infer_type(&entry.value);
+
+                    // Mixed entries widen the dictionary to unknown keys/values
+                    key_type = Some(match key_type {
+                        Some(current) if !self.types_compatible(&current, &entry_key) => {
+                            HypnoType::unknown()
+                        }
+                        Some(current) => current,
+                        None => entry_key,
+                    });
+                    value_type = Some(match value_type {
+                        Some(current) if !self.types_compatible(&current, &entry_value) => {
+                            HypnoType::unknown()
+                        }
+                        Some(current) => current,
+                        None => entry_value,
+                    });
+                }
+                HypnoType::create_dictionary(
+                    key_type.unwrap_or_else(HypnoType::unknown),
+                    value_type.unwrap_or_else(HypnoType::unknown),
+                )
+            }
+
+            AstNode::IndexExpression { object, index } => {
+                let object_type = self.infer_type(object);
+                let index_type = self.infer_type(index);
+                self.check_index_type(&object_type, &index_type)
+            }
+
             AstNode::NullishCoalescing { left, right } => {
                 let left_type = self.infer_type(left);
-                let _right_type = self.infer_type(right);
-                // Nullish coalescing returns the type of the right side if left is null
-                // For simplicity, we return the left type (as it's usually the expected type)
-                left_type
+                let right_type = self.infer_type(right);
+                // The right side only replaces a null left side
+                if left_type.is_null() {
+                    right_type
+                } else if left_type.is_nullable() {
+                    HypnoType::create_union(vec![left_type.without_null(), right_type])
+                } else {
+                    left_type
+                }
             }
 
             AstNode::OptionalChaining { object, property } => {
-                let _obj_type = self.infer_type(object);
-                // Optional chaining always returns a potentially nullable type
-                // For now, we just infer the property type
-                let _ = property;
-                HypnoType::unknown()
+                let object_type = self.infer_type(object);
+                if object_type.base_type == HypnoBaseType::Unknown || object_type.is_null() {
+                    return HypnoType::unknown();
+                }
+                // The member of the non-null part; a null object short-circuits to null
+                let member_type = self.member_type(&object_type.without_null(), property);
+                if object_type.is_nullable() {
+                    HypnoType::create_optional(member_type)
+                } else {
+                    member_type
+                }
             }
 
             AstNode::OptionalIndexing { object, index } => {
-                let obj_type = self.infer_type(object);
-                let _idx_type = self.infer_type(index);
-
-                // Return the element type of the array, or unknown
-                if let Some(element_type) = obj_type.element_type {
-                    (*element_type).clone()
+                let object_type = self.infer_type(object);
+                let index_type = self.infer_type(index);
+                if object_type.base_type == HypnoBaseType::Unknown || object_type.is_null() {
+                    return HypnoType::unknown();
+                }
+                let element_type = self.check_index_type(&object_type.without_null(), &index_type);
+                if object_type.is_nullable() {
+                    HypnoType::create_optional(element_type)
                 } else {
-                    HypnoType::unknown()
+                    element_type
                 }
             }
 
             AstNode::AwaitExpression { expression } => {
-                // For now, await just returns the type of the expression
-                // In a full async system, this would unwrap a Promise type
-                self.infer_type(expression)
+                let awaited = self.infer_type(expression);
+                if awaited.is_promise() {
+                    awaited
+                        .element_type
+                        .as_deref()
+                        .cloned()
+                        .unwrap_or_else(HypnoType::unknown)
+                } else {
+                    // Awaiting a plain value yields the value itself
+                    awaited
+                }
             }
 
             AstNode::EntrainExpression {
@@ -1737,45 +3650,96 @@ impl TypeChecker {
                 cases,
                 default,
             } => {
-                // Check subject type
-                let _subject_type = self.infer_type(subject);
+                let subject_type = self.infer_type(subject);
+                // A matched variable subject is narrowed to the case's type pattern
+                let subject_name = match subject.unspanned() {
+                    AstNode::Identifier(name) => Some(name.clone()),
+                    _ => None,
+                };
+                let mut covered = Vec::new();
+                let mut covered_variants = Vec::new();
+                let mut result_type: Option<HypnoType> = None;
+
+                for case in cases {
+                    let saved_env = self.type_env.clone();
+                    let saved_narrowed = self.narrowed_types.clone();
+
+                    let pattern_type = self.pattern_type(&case.pattern);
+                    if let (Some(name), Some(pattern_type)) = (&subject_name, &pattern_type) {
+                        let narrowed = self.narrow_to(&subject_type, pattern_type);
+                        self.narrowed_types.insert(name.clone(), narrowed);
+                    }
+                    self.bind_pattern(&case.pattern, &subject_type);
 
-                // Infer return type from cases
-                if let Some(first_case) = cases.first()
-                    && let Some(first_stmt) = first_case.body.first()
-                {
-                    let case_type = self.infer_type(first_stmt);
+                    if let Some(guard) = &case.guard {
+                        let guard_type = self.infer_type(guard).widened();
+                        if !self.types_compatible(&HypnoType::boolean(), &guard_type) {
+                            self.errors
+                                .push(format!("Entrain guard must be boolean, got {}", guard_type));
+                        }
+                    }
+                    let case_type = self.infer_entrain_body(&case.body);
+
+                    self.type_env = saved_env;
+                    self.narrowed_types = saved_narrowed;
+                    if case.guard.is_none() {
+                        if let Some(variant) = covered_variant(&case.pattern) {
+                            covered_variants.push(variant);
+                        }
+                        if let Some(pattern_type) = pattern_type {
+                            covered.push(pattern_type);
+                        }
+                    }
 
                     // Check that all cases return compatible types
-                    for case in &cases[1..] {
-                        if let Some(stmt) = case.body.first() {
-                            let stmt_type = self.infer_type(stmt);
-                            if !self.types_compatible(&case_type, &stmt_type) {
-                                self.errors.push(format!(
-                                    "Entrain cases must return same type, got {} and {}",
-                                    case_type, stmt_type
-                                ));
-                            }
+                    match &result_type {
+                        Some(first_type) if !self.types_compatible(first_type, &case_type) => {
+                            self.errors.push(format!(
+                                "Entrain cases must return same type, got {} and {}",
+                                first_type, case_type
+                            ));
                         }
+                        Some(_) => {}
+                        None => result_type = Some(case_type),
                     }
+                }
 
-                    // Check default case if present
-                    if let Some(default_body) = default
-                        && let Some(stmt) = default_body.first()
-                    {
-                        let default_type = self.infer_type(stmt);
-                        if !self.types_compatible(&case_type, &default_type) {
+                // Check default case if present; it sees the types no case matched
+                if let Some(default_body) = default {
+                    let saved_narrowed = self.narrowed_types.clone();
+                    if let Some(name) = &subject_name {
+                        let remaining: Vec<HypnoType> = subject_type
+                            .union_members()
+                            .into_iter()
+                            .filter(|member| {
+                                !covered
+                                    .iter()
+                                    .any(|pattern| self.types_compatible(pattern, member))
+                            })
+                            .collect();
+                        if subject_type.is_union() && !remaining.is_empty() {
+                            self.narrowed_types
+                                .insert(name.clone(), HypnoType::create_union(remaining));
+                        }
+                    }
+                    let default_type = self.infer_entrain_body(default_body);
+                    self.narrowed_types = saved_narrowed;
+
+                    match &result_type {
+                        Some(case_type) if !self.types_compatible(case_type, &default_type) => {
                             self.errors.push(format!(
                                 "Entrain default case must return same type as other cases, got {} and {}",
                                 case_type, default_type
                             ));
                         }
+                        Some(_) => {}
+                        None => result_type = Some(default_type),
                     }
-
-                    return case_type;
+                } else {
+                    self.check_exhaustive(&subject_type, &covered, &covered_variants);
                 }
 
-                HypnoType::unknown()
+                result_type.unwrap_or_else(HypnoType::unknown)
             }
 
             AstNode::RecordLiteral { type_name, fields } => {
@@ -1783,11 +3747,23 @@ impl TypeChecker {
                 let tranceify_info_opt = self.tranceify_types.get(type_name).cloned();
 
                 if let Some(tranceify_info) = tranceify_info_opt {
+                    // Field values infer the type arguments of generic records
+                    let mut bindings = HashMap::new();
+
                     // Verify all fields match the type definition
                     for field_init in fields {
                         if let Some(expected_type) = tranceify_info.fields.get(&field_init.name) {
-                            let actual_type = self.infer_type(&field_init.value);
-                            if !self.types_compatible(expected_type, &actual_type) {
+                            let actual_type =
+                                self.infer_expected_type(&field_init.value, expected_type);
+                            infer_type_arguments(
+                                expected_type,
+                                &actual_type,
+                                &tranceify_info.type_parameters,
+                                &mut bindings,
+                            );
+                            let expected_type =
+                                substitute_type_parameters(expected_type, &bindings);
+                            if !self.types_compatible(&expected_type, &actual_type) {
                                 self.errors.push(format!(
                                     "Field '{}' in record '{}' expects type {}, got {}",
                                     field_init.name, type_name, expected_type, actual_type
@@ -1812,7 +3788,17 @@ impl TypeChecker {
                     }
 
                     // Return a record type for the record literal
-                    HypnoType::create_record(type_name.clone(), tranceify_info.fields.clone())
+                    let type_arguments: Vec<HypnoType> = tranceify_info
+                        .type_parameters
+                        .iter()
+                        .map(|param| {
+                            bindings
+                                .get(param)
+                                .cloned()
+                                .unwrap_or_else(HypnoType::unknown)
+                        })
+                        .collect();
+                    tranceify_info.instantiate(&type_arguments)
                 } else {
                     self.errors.push(format!(
                         "Undefined tranceify type '{}' in record literal",
@@ -1822,6 +3808,48 @@ impl TypeChecker {
                 }
             }
 
+            AstNode::FunctionExpression {
+                parameters,
+                return_type,
+                body,
+            } => {
+                let old_env = self.type_env.clone();
+                let old_narrowed = std::mem::take(&mut self.narrowed_types);
+                let old_return_type = self.current_function_return_type.take();
+                let old_generator = self.current_generator.take();
+                let old_labels = std::mem::take(&mut self.loop_labels);
+
+                self.bind_parameters(parameters);
+                let params = self.parameter_infos(parameters);
+
+                let declared_return = return_type
+                    .as_deref()
+                    .map(|annotation| self.parse_type_annotation(Some(annotation)));
+
+                let ret_type = match (declared_return, body.as_slice()) {
+                    // Expression-bodied closures take the type of their expression
+                    (None, [AstNode::ReturnStatement(Some(expr))]) => self.infer_type(expr),
+                    (declared, _) => {
+                        let return_type = declared.clone().unwrap_or_else(HypnoType::unknown);
+                        self.current_generator =
+                            self.generator_element_type("<suggestion>", body, &return_type);
+                        self.current_function_return_type = declared;
+                        for stmt in body {
+                            self.check_statement(stmt);
+                        }
+                        generator_return_type(body, return_type)
+                    }
+                };
+
+                self.type_env = old_env;
+                self.narrowed_types = old_narrowed;
+                self.current_function_return_type = old_return_type;
+                self.current_generator = old_generator;
+                self.loop_labels = old_labels;
+
+                Self::callable_type(&params, ret_type)
+            }
+
             _ => HypnoType::unknown(),
         }
     }
@@ -1833,166 +3861,2177 @@ impl TypeChecker {
         {
             return true;
         }
-        expected.is_compatible_with(actual)
-    }
 
-    /// Get all errors
-    pub fn get_errors(&self) -> &[String] {
-        &self.errors
+        // Every member of a union value must fit; a union accepts any of its members
+        if actual.is_union() {
+            return actual
+                .union_members()
+                .iter()
+                .all(|member| self.types_compatible(expected, member));
+        }
+        if expected.is_union() {
+            return expected
+                .union_members()
+                .iter()
+                .any(|member| self.types_compatible(member, actual));
+        }
+        if expected.literal.is_some() {
+            return expected.base_type == actual.base_type && expected.literal == actual.literal;
+        }
+
+        // Unknown element/key types (e.g. from `[]` or builtin signatures) match anything
+        let nested_compatible = |expected: &Option<Box<HypnoType>>,
+                                 actual: &Option<Box<HypnoType>>| {
+            match (expected, actual) {
+                (Some(expected), Some(actual)) => self.types_compatible(expected, actual),
+                _ => true,
+            }
+        };
+        match (&expected.base_type, &actual.base_type) {
+            // Integers widen to numbers
+            (HypnoBaseType::Number, HypnoBaseType::Integer) => true,
+            (HypnoBaseType::Array, HypnoBaseType::Array)
+            | (HypnoBaseType::Promise, HypnoBaseType::Promise)
+            | (HypnoBaseType::Iterator, HypnoBaseType::Iterator) => {
+                nested_compatible(&expected.element_type, &actual.element_type)
+            }
+            (HypnoBaseType::Dictionary, HypnoBaseType::Dictionary) => {
+                nested_compatible(&expected.key_type, &actual.key_type)
+                    && nested_compatible(&expected.element_type, &actual.element_type)
+            }
+            (HypnoBaseType::Record, HypnoBaseType::Record) => {
+                match (&expected.fields, &actual.fields) {
+                    (Some(expected_fields), Some(actual_fields)) => {
+                        expected_fields.len() == actual_fields.len()
+                            && expected_fields.iter().all(|(name, ty)| {
+                                actual_fields
+                                    .get(name)
+                                    .is_some_and(|actual| self.types_compatible(ty, actual))
+                            })
+                    }
+                    _ => expected.is_compatible_with(actual),
+                }
+            }
+            // Instances of one generic session need compatible type arguments
+            (HypnoBaseType::Session, HypnoBaseType::Session) if expected.name == actual.name => {
+                match (&expected.type_arguments, &actual.type_arguments) {
+                    (Some(expected_args), Some(actual_args)) => expected_args
+                        .iter()
+                        .zip(actual_args)
+                        .all(|(expected, actual)| self.types_compatible(expected, actual)),
+                    _ => true,
+                }
+            }
+            // Sessions conform to the interfaces they implement, records to any
+            // interface whose members they provide
+            (HypnoBaseType::Interface, HypnoBaseType::Session) => {
+                match (
+                    &expected.name,
+                    actual
+                        .name
+                        .as_ref()
+                        .and_then(|name| self.sessions.get(name)),
+                ) {
+                    (Some(interface), Some(session)) => session.interfaces.contains(interface),
+                    _ => false,
+                }
+            }
+            (HypnoBaseType::Interface, HypnoBaseType::Record) => {
+                self.record_conforms(expected, actual)
+            }
+            // Instances of a session fit wherever one of its parent sessions is expected
+            (HypnoBaseType::Session, HypnoBaseType::Session) => self
+                .parent_session_type(actual)
+                .is_some_and(|parent| self.types_compatible(expected, &parent)),
+            // A bare `Function` signature accepts any callable
+            (HypnoBaseType::Function, HypnoBaseType::Function)
+                if expected.parameter_types.is_none() =>
+            {
+                true
+            }
+            _ => expected.is_compatible_with(actual),
+        }
     }
 
-    /// Check if there are any errors
-    pub fn has_errors(&self) -> bool {
-        !self.errors.is_empty()
+    /// Whether a record type provides the fields and methods of an interface
+    fn record_conforms(&self, interface: &HypnoType, record: &HypnoType) -> bool {
+        let Some(interface) = interface
+            .name
+            .as_ref()
+            .and_then(|name| self.interfaces.get(name))
+        else {
+            return false;
+        };
+        let fields = match (&record.fields, &record.name) {
+            (Some(fields), _) => fields,
+            (None, Some(name)) => match self.tranceify_types.get(name) {
+                Some(info) => &info.fields,
+                None => return false,
+            },
+            (None, None) => return false,
+        };
+
+        // Methods are provided by fields holding suggestions of a fitting type
+        let required_fields = interface.fields.iter().map(|(name, ty)| (name, ty.clone()));
+        let required_methods = interface
+            .methods
+            .iter()
+            .map(|(name, method)| (name, self.method_function_type(method)));
+        required_fields
+            .chain(required_methods)
+            .all(|(name, required)| {
+                fields
+                    .get(name)
+                    .is_some_and(|actual| self.types_compatible(&required, actual))
+            })
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use hypnoscript_lexer_parser::{Lexer, Parser};
+    /// Instance type of the parent of a session instance type
+    fn parent_session_type(&self, ty: &HypnoType) -> Option<HypnoType> {
+        let info = self.sessions.get(ty.name.as_deref()?)?;
+        let type_arguments = ty.type_arguments.as_deref().unwrap_or_default();
+        info.instantiate(type_arguments).parent
+    }
 
-    #[test]
-    fn test_type_check_simple() {
-        let source = r#"
-Focus {
-    induce x: number = 42;
-    induce y: number = 10;
-    induce sum: number = x + y;
-} Relax
-"#;
-        let mut lexer = Lexer::new(source);
-        let tokens = lexer.lex().unwrap();
-        let mut parser = Parser::new(tokens);
-        let ast = parser.parse_program().unwrap();
+    /// Infer `expr` where a value of type `expected` is required.
+    ///
+    /// Literals take their literal type when `expected` mentions literal types,
+    /// so `"on"` fits `"on" | "off"`.
+    fn infer_expected_type(&mut self, expr: &AstNode, expected: &HypnoType) -> HypnoType {
+        let actual = self.infer_type(expr);
+        let expects_literal = expected
+            .union_members()
+            .iter()
+            .any(|member| member.literal.is_some());
+        if expects_literal && let Some(literal) = literal_type(expr) {
+            return literal;
+        }
+        actual
+    }
 
-        let mut checker = TypeChecker::new();
-        let errors = checker.check_program(&ast);
-        assert!(errors.is_empty(), "Errors: {:?}", errors);
+    /// Current type of a variable, including narrowing
+    /// Narrow the nullable variables whose nullness follows from `condition`
+    /// evaluating to `holds`; with `only_non_null`, only `null` is removed
+    fn narrow_null_checks(&mut self, condition: &AstNode, holds: bool, only_non_null: bool) {
+        for (name, is_null) in null_facts(condition, holds) {
+            if is_null && only_non_null {
+                continue;
+            }
+            let Some(ty) = self.variable_type(name).filter(HypnoType::is_nullable) else {
+                continue;
+            };
+            let narrowed = if is_null {
+                HypnoType::null()
+            } else {
+                ty.without_null()
+            };
+            self.narrowed_types.insert(name.to_string(), narrowed);
+        }
     }
 
-    #[test]
-    fn test_operator_synonym_diagnostics() {
-        let source = r#"
-Focus {
-    induce left: string = "hello";
-    induce right: string = "world";
-    if (left lookAtTheWatch right) {
-        observe "won't happen";
+    fn variable_type(&self, name: &str) -> Option<HypnoType> {
+        self.narrowed_types
+            .get(name)
+            .or_else(|| self.type_env.get(name))
+            .cloned()
     }
-} Relax
-"#;
-        let mut lexer = Lexer::new(source);
-        let tokens = lexer.lex().unwrap();
-        let mut parser = Parser::new(tokens);
-        let ast = parser.parse_program().unwrap();
 
-        let mut checker = TypeChecker::new();
-        let errors = checker.check_program(&ast);
-        assert!(
-            errors.iter().any(|msg| msg.contains("lookAtTheWatch")),
-            "Expected comparison diagnostic mentioning operator, got {:?}",
-            errors
-        );
+    /// The members of `subject` matched by a type pattern
+    fn narrow_to(&self, subject: &HypnoType, pattern: &HypnoType) -> HypnoType {
+        let matching: Vec<HypnoType> = subject
+            .union_members()
+            .into_iter()
+            .filter(|member| {
+                member.base_type != HypnoBaseType::Unknown && self.types_compatible(pattern, member)
+            })
+            .collect();
+        if subject.is_union() && !matching.is_empty() {
+            HypnoType::create_union(matching)
+        } else {
+            pattern.clone()
+        }
+    }
+
+    /// Type matched by an entrain pattern (type patterns and `null`)
+    fn pattern_type(&self, pattern: &Pattern) -> Option<HypnoType> {
+        match pattern {
+            Pattern::Typed {
+                type_annotation, ..
+            } => Some(self.parse_type_annotation(Some(type_annotation))),
+            Pattern::Literal(literal) if matches!(literal.unspanned(), AstNode::NullLiteral) => {
+                Some(HypnoType::null())
+            }
+            _ => None,
+        }
+    }
+
+    /// Report the variants of an enum subject that an entrain without `otherwise` misses
+    fn check_exhaustive(
+        &mut self,
+        subject_type: &HypnoType,
+        covered: &[HypnoType],
+        covered_variants: &[(&str, &str)],
+    ) {
+        if subject_type.base_type != HypnoBaseType::Enum
+            || covered
+                .iter()
+                .any(|pattern| self.types_compatible(pattern, subject_type))
+        {
+            return;
+        }
+        let Some(info) = subject_type
+            .name
+            .as_ref()
+            .and_then(|name| self.enums.get(name))
+        else {
+            return;
+        };
+
+        if covered_variants.contains(&("", "")) {
+            return;
+        }
+
+        let missing: Vec<&str> = info
+            .variants
+            .iter()
+            .map(|(variant, _)| variant.as_str())
+            .filter(|variant| !covered_variants.contains(&(info.name.as_str(), variant)))
+            .collect();
+        if !missing.is_empty() {
+            self.errors.push(format!(
+                "Non-exhaustive entrain over {}: missing variant(s) {}; add them or an 'otherwise' case",
+                info.name,
+                missing.join(", ")
+            ));
+        }
+    }
+
+    /// Bring the variables bound by an entrain pattern into scope
+    fn bind_pattern(&mut self, pattern: &Pattern, subject_type: &HypnoType) {
+        let bind = |checker: &mut Self, name: &str, ty: HypnoType| {
+            checker.narrowed_types.remove(name);
+            checker.type_env.insert(name.to_string(), ty);
+        };
+
+        match pattern {
+            Pattern::Literal(literal) => {
+                self.infer_type(literal);
+            }
+            Pattern::Identifier(name) => bind(self, name, subject_type.clone()),
+            Pattern::Typed {
+                name: Some(name),
+                type_annotation,
+            } => {
+                let pattern_type = self.parse_type_annotation(Some(type_annotation));
+                let narrowed = self.narrow_to(subject_type, &pattern_type);
+                bind(self, name, narrowed);
+            }
+            Pattern::Typed { name: None, .. } => {}
+            // `{ name, age }` takes its field types from the subject
+            Pattern::Record { type_name, fields } if type_name.is_empty() => {
+                for field in fields {
+                    let field_type = match subject_type.base_type {
+                        HypnoBaseType::Record if subject_type.fields.is_some() => {
+                            match subject_type
+                                .fields
+                                .as_ref()
+                                .and_then(|f| f.get(&field.name))
+                            {
+                                Some(field_type) => field_type.clone(),
+                                None => {
+                                    self.errors.push(format!(
+                                        "{} has no field '{}'",
+                                        subject_type, field.name
+                                    ));
+                                    HypnoType::unknown()
+                                }
+                            }
+                        }
+                        HypnoBaseType::Dictionary => subject_type
+                            .element_type
+                            .as_deref()
+                            .cloned()
+                            .unwrap_or_else(HypnoType::unknown),
+                        _ => HypnoType::unknown(),
+                    };
+                    match &field.pattern {
+                        Some(pattern) => self.bind_pattern(pattern, &field_type),
+                        None => bind(self, &field.name, field_type),
+                    }
+                }
+            }
+            Pattern::Record { type_name, fields } => {
+                let record_type = match self.tranceify_types.get(type_name) {
+                    Some(info) => {
+                        let type_arguments = match &subject_type.type_arguments {
+                            Some(arguments) if subject_type.name.as_ref() == Some(type_name) => {
+                                arguments.clone()
+                            }
+                            _ => Vec::new(),
+                        };
+                        Some(info.instantiate(&type_arguments))
+                    }
+                    None => {
+                        self.errors.push(format!(
+                            "Undefined tranceify type '{}' in record pattern",
+                            type_name
+                        ));
+                        None
+                    }
+                };
+
+                for field in fields {
+                    let field_type = match &record_type {
+                        Some(record) => {
+                            match record.fields.as_ref().and_then(|f| f.get(&field.name)) {
+                                Some(field_type) => field_type.clone(),
+                                None => {
+                                    self.errors.push(format!(
+                                        "Record type '{}' has no field '{}'",
+                                        type_name, field.name
+                                    ));
+                                    HypnoType::unknown()
+                                }
+                            }
+                        }
+                        None => HypnoType::unknown(),
+                    };
+                    match &field.pattern {
+                        Some(pattern) => self.bind_pattern(pattern, &field_type),
+                        None => bind(self, &field.name, field_type),
+                    }
+                }
+            }
+            Pattern::Array { elements, rest } => {
+                let element_type = match subject_type.base_type {
+                    HypnoBaseType::Array => subject_type
+                        .element_type
+                        .as_deref()
+                        .cloned()
+                        .unwrap_or_else(HypnoType::unknown),
+                    _ => HypnoType::unknown(),
+                };
+                for element in elements {
+                    self.bind_pattern(element, &element_type);
+                }
+                if let Some(rest) = rest {
+                    bind(self, rest, HypnoType::create_array(element_type));
+                }
+            }
+            Pattern::Variant {
+                enum_name,
+                variant,
+                payload,
+            } => {
+                let payload_types = match self.enums.get(enum_name) {
+                    Some(info) => match info.variant(variant) {
+                        Some(types) if types.len() == payload.len() || payload.is_empty() => {
+                            types.to_vec()
+                        }
+                        Some(types) => {
+                            self.errors.push(format!(
+                                "Variant '{}.{}' carries {} payload value(s), but the pattern has {}",
+                                enum_name,
+                                variant,
+                                types.len(),
+                                payload.len()
+                            ));
+                            Vec::new()
+                        }
+                        None => {
+                            self.errors
+                                .push(format!("Enum '{}' has no variant '{}'", enum_name, variant));
+                            Vec::new()
+                        }
+                    },
+                    None => {
+                        self.errors
+                            .push(format!("Undefined enum '{}' in variant pattern", enum_name));
+                        Vec::new()
+                    }
+                };
+
+                let subject_enums: Vec<HypnoType> = subject_type
+                    .union_members()
+                    .into_iter()
+                    .filter(|member| member.base_type != HypnoBaseType::Null)
+                    .collect();
+                if self.enums.contains_key(enum_name)
+                    && !subject_enums.iter().any(|member| {
+                        member.base_type == HypnoBaseType::Unknown
+                            || member.name.as_deref() == Some(enum_name.as_str())
+                    })
+                {
+                    self.errors.push(format!(
+                        "Pattern '{}.{}' cannot match a value of type {}",
+                        enum_name, variant, subject_type
+                    ));
+                }
+
+                for (index, pattern) in payload.iter().enumerate() {
+                    let payload_type = payload_types
+                        .get(index)
+                        .cloned()
+                        .unwrap_or_else(HypnoType::unknown);
+                    self.bind_pattern(pattern, &payload_type);
+                }
+            }
+        }
+    }
+
+    /// Declared type of a variable, member or element that is read and then reassigned
+    fn assignment_target_type(&mut self, target: &AstNode) -> HypnoType {
+        match target.unspanned() {
+            AstNode::Identifier(name) => {
+                // Reassignment ends any narrowing of the variable
+                self.narrowed_types.remove(name);
+                match self.type_env.get(name) {
+                    Some(declared) => declared.clone(),
+                    None => {
+                        self.errors
+                            .push(format!("Cannot assign to undefined variable '{}'", name));
+                        HypnoType::unknown()
+                    }
+                }
+            }
+            AstNode::MemberExpression { .. } | AstNode::IndexExpression { .. } => {
+                self.infer_type(target)
+            }
+            _ => {
+                self.errors.push("Invalid assignment target".to_string());
+                HypnoType::unknown()
+            }
+        }
+    }
+
+    /// Result type of a binary operator applied to operands of the given types
+    fn binary_operator_type(
+        &mut self,
+        operator: &str,
+        left_type: &HypnoType,
+        right_type: &HypnoType,
+    ) -> HypnoType {
+        if let Some((operand_type, result_type)) = self.operator_signature(operator, left_type) {
+            if !self.types_compatible(&operand_type, right_type) {
+                self.errors.push(format!(
+                    "Operator '{}' of {} expects a right operand of type {}, got {}",
+                    operator, left_type, operand_type, right_type
+                ));
+            }
+            return result_type;
+        }
+
+        let normalized_op = operator.to_ascii_lowercase();
+
+        match normalized_op.as_str() {
+            "+" => {
+                // Allow string concatenation or numeric addition
+                if left_type.base_type == HypnoBaseType::String
+                    || right_type.base_type == HypnoBaseType::String
+                {
+                    HypnoType::string()
+                } else if left_type.is_numeric() && right_type.is_numeric() {
+                    Self::arithmetic_result_type(left_type, right_type)
+                } else {
+                    self.errors.push(format!(
+                        "Operator '+' requires either two numbers or at least one string, got {} and {}",
+                        left_type, right_type
+                    ));
+                    HypnoType::unknown()
+                }
+            }
+            "-" | "*" | "/" | "%" => {
+                if !left_type.is_numeric() || !right_type.is_numeric() {
+                    self.errors.push(format!(
+                        "Arithmetic operator '{}' requires numeric operands, got {} and {}",
+                        operator, left_type, right_type
+                    ));
+                    return HypnoType::number();
+                }
+                Self::arithmetic_result_type(left_type, right_type)
+            }
+            "&" | "|" | "^" | "<<" | ">>" => {
+                if !left_type.is_numeric() || !right_type.is_numeric() {
+                    self.errors.push(format!(
+                        "Bitwise operator '{}' requires integer operands, got {} and {}",
+                        operator, left_type, right_type
+                    ));
+                }
+                HypnoType::integer()
+            }
+            "==" | "!=" | "youarefeelingverysleepy" | "youcannotresist" | "notsodeep" => {
+                HypnoType::boolean()
+            }
+            ">"
+            | "<"
+            | ">="
+            | "<="
+            | "lookatthewatch"
+            | "fallundermyspell"
+            | "youreyesaregettingheavy"
+            | "goingdeeper"
+            | "deeplygreater"
+            | "deeplyless" => {
+                if !left_type.is_numeric() || !right_type.is_numeric() {
+                    self.errors.push(format!(
+                        "Comparison operator '{}' requires numeric operands, got {} and {}",
+                        operator, left_type, right_type
+                    ));
+                }
+                HypnoType::boolean()
+            }
+            "&&" | "undermycontrol" | "||" | "resistanceisfutile" => {
+                if left_type.base_type != HypnoBaseType::Boolean
+                    || right_type.base_type != HypnoBaseType::Boolean
+                {
+                    self.errors.push(format!(
+                        "Logical operator '{}' requires boolean operands, got {} and {}",
+                        operator, left_type, right_type
+                    ));
+                }
+                HypnoType::boolean()
+            }
+            _ => HypnoType::unknown(),
+        }
+    }
+
+    /// Result of arithmetic on numeric operands: integers stay integers,
+    /// anything involving a `number` is a `number`
+    fn arithmetic_result_type(left_type: &HypnoType, right_type: &HypnoType) -> HypnoType {
+        if left_type.base_type == HypnoBaseType::Integer
+            && right_type.base_type == HypnoBaseType::Integer
+        {
+            HypnoType::integer()
+        } else {
+            HypnoType::number()
+        }
+    }
+
+    /// Check the statements of an entrain case; its value is the last expression
+    fn infer_entrain_body(&mut self, body: &[AstNode]) -> HypnoType {
+        let mut body_type = HypnoType::unknown();
+        for node in body {
+            body_type = match node.unspanned() {
+                AstNode::ExpressionStatement(expr) => self.infer_type(expr),
+                expr if expr.is_expression() => self.infer_type(expr),
+                _ => {
+                    self.check_statement(node);
+                    HypnoType::unknown()
+                }
+            };
+        }
+        body_type
+    }
+
+    /// Report dictionary keys that are not strings, numbers or booleans
+    fn check_dictionary_key(&mut self, key_type: &HypnoType) {
+        if !matches!(
+            key_type.base_type,
+            HypnoBaseType::String
+                | HypnoBaseType::Number
+                | HypnoBaseType::Integer
+                | HypnoBaseType::Boolean
+                | HypnoBaseType::Unknown
+        ) {
+            self.errors.push(format!(
+                "Dictionary keys must be string, number, integer or boolean, got {}",
+                key_type
+            ));
+        }
+    }
+
+    /// Check `object[index]` and return the element type
+    fn check_index_type(&mut self, object_type: &HypnoType, index_type: &HypnoType) -> HypnoType {
+        let element_type = object_type
+            .element_type
+            .as_deref()
+            .cloned()
+            .unwrap_or_else(HypnoType::unknown);
+
+        match object_type.base_type {
+            HypnoBaseType::Array => {
+                if !self.types_compatible(&HypnoType::number(), index_type) {
+                    self.errors
+                        .push(format!("Array index must be a number, got {}", index_type));
+                }
+                element_type
+            }
+            HypnoBaseType::Dictionary => {
+                let key_type = object_type
+                    .key_type
+                    .as_deref()
+                    .cloned()
+                    .unwrap_or_else(HypnoType::unknown);
+                if !self.types_compatible(&key_type, index_type) {
+                    self.errors.push(format!(
+                        "Dictionary key type mismatch: expected {}, got {}",
+                        key_type, index_type
+                    ));
+                }
+                element_type
+            }
+            HypnoBaseType::Unknown => HypnoType::unknown(),
+            _ => {
+                self.errors
+                    .push(format!("Cannot index value of type {}", object_type));
+                HypnoType::unknown()
+            }
+        }
+    }
+
+    /// Check call arguments against a signature.
+    ///
+    /// Handles `...array` spreads, named arguments, default values and rest
+    /// parameters. Errors are prefixed with `callee` (e.g. "Function 'f'").
+    /// The type parameters in `generics` are inferred from the arguments; the
+    /// returned bindings map each of them to its type (unknown if not inferred).
+    fn check_call_arguments(
+        &mut self,
+        arguments: &[AstNode],
+        parameters: &[ParameterInfo],
+        generics: &[String],
+        callee: &str,
+    ) -> HashMap<String, HypnoType> {
+        let mut bindings = HashMap::new();
+        let fixed_count = parameters.iter().filter(|param| !param.is_rest).count();
+        let rest = parameters.iter().find(|param| param.is_rest);
+        let required = parameters
+            .iter()
+            .filter(|param| !param.has_default && !param.is_rest)
+            .count();
+
+        let mut filled = vec![false; fixed_count];
+        let mut positional = 0;
+        let mut spread_seen = false;
+        let mut named_seen = false;
+
+        for arg in arguments {
+            match arg.unspanned() {
+                AstNode::NamedArgument { name, value } => {
+                    named_seen = true;
+                    let index = parameters
+                        .iter()
+                        .position(|param| !param.is_rest && param.name.as_deref() == Some(name));
+                    let Some(index) = index else {
+                        self.infer_type(value);
+                        self.errors
+                            .push(format!("{} has no parameter named '{}'", callee, name));
+                        continue;
+                    };
+                    let actual = self.infer_expected_type(value, &parameters[index].ty);
+                    if filled[index] {
+                        self.errors.push(format!(
+                            "{} received argument '{}' more than once",
+                            callee, name
+                        ));
+                        continue;
+                    }
+                    filled[index] = true;
+                    let expected = &parameters[index].ty;
+                    infer_type_arguments(expected, &actual, generics, &mut bindings);
+                    let expected = substitute_type_parameters(expected, &bindings);
+                    if !self.types_compatible(&expected, &actual) {
+                        self.errors.push(format!(
+                            "{} argument '{}' type mismatch: expected {}, got {}",
+                            callee, name, expected, actual
+                        ));
+                    }
+                }
+                AstNode::SpreadElement(_) => {
+                    // A spread may fill any of the remaining parameters
+                    let actual = self.infer_type(arg);
+                    let start = if spread_seen {
+                        fixed_count
+                    } else {
+                        positional.min(fixed_count)
+                    };
+                    spread_seen = true;
+                    filled[start..].fill(true);
+                    let remaining = parameters[start..].iter().map(ParameterInfo::argument_type);
+                    for expected in remaining.collect::<Vec<_>>() {
+                        infer_type_arguments(&expected, &actual, generics, &mut bindings);
+                        let expected = substitute_type_parameters(&expected, &bindings);
+                        if !self.types_compatible(&expected, &actual) {
+                            self.errors.push(format!(
+                                "{} spread argument type mismatch: expected {}, got {}",
+                                callee, expected, actual
+                            ));
+                            break;
+                        }
+                    }
+                }
+                _ => {
+                    positional += 1;
+                    let mut accepts_iterable = false;
+                    let expected = if spread_seen {
+                        None
+                    } else if positional <= fixed_count {
+                        filled[positional - 1] = true;
+                        accepts_iterable = parameters[positional - 1].accepts_iterable;
+                        Some(parameters[positional - 1].ty.clone())
+                    } else {
+                        rest.map(ParameterInfo::argument_type)
+                    };
+                    let Some(expected) = expected else {
+                        self.infer_type(arg);
+                        continue;
+                    };
+                    let actual = self.infer_expected_type(arg, &expected);
+                    // Iterables are checked as the array of the values they produce
+                    let checked = match self.iterable_element_type(&actual) {
+                        Some(element) if accepts_iterable => HypnoType::create_array(element),
+                        _ => actual.clone(),
+                    };
+                    infer_type_arguments(&expected, &checked, generics, &mut bindings);
+                    let expected = substitute_type_parameters(&expected, &bindings);
+                    if !self.types_compatible(&expected, &checked) {
+                        self.errors.push(format!(
+                            "{} argument {} type mismatch: expected {}, got {}",
+                            callee, positional, expected, actual
+                        ));
+                    }
+                }
+            }
+        }
+
+        let too_many = rest.is_none() && positional > fixed_count;
+        let too_few = !spread_seen && !named_seen && positional < required;
+        if too_many || too_few {
+            let expected = if rest.is_some() {
+                format!("at least {}", required)
+            } else if required == fixed_count {
+                fixed_count.to_string()
+            } else {
+                format!("{} to {}", required, fixed_count)
+            };
+            let got = if spread_seen { "got at least" } else { "got" };
+            self.errors.push(format!(
+                "{} expects {} arguments, {} {}",
+                callee, expected, got, positional
+            ));
+        } else {
+            for (param, filled) in parameters.iter().zip(filled) {
+                if !filled && !param.has_default {
+                    self.errors.push(format!(
+                        "{} is missing argument '{}'",
+                        callee,
+                        param.name.as_deref().unwrap_or("?")
+                    ));
+                }
+            }
+        }
+
+        for generic in generics {
+            bindings
+                .entry(generic.clone())
+                .or_insert_with(HypnoType::unknown);
+        }
+        bindings
+    }
+
+    /// Get all errors
+    pub fn get_errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// Check if there are any errors
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+}
+
+/// Bind type parameters to explicit type arguments (missing ones are unknown)
+fn bind_type_arguments(
+    type_parameters: &[String],
+    type_arguments: &[HypnoType],
+) -> HashMap<String, HypnoType> {
+    type_parameters
+        .iter()
+        .enumerate()
+        .map(|(i, param)| {
+            let argument = type_arguments
+                .get(i)
+                .cloned()
+                .unwrap_or_else(HypnoType::unknown);
+            (param.clone(), argument)
+        })
+        .collect()
+}
+
+/// Type parameters occurring in a suggestion signature, in order of appearance
+fn signature_type_parameters(parameters: &[ParameterInfo], return_type: &HypnoType) -> Vec<String> {
+    fn collect(ty: &HypnoType, found: &mut Vec<String>) {
+        if ty.is_type_parameter() {
+            if let Some(name) = &ty.name
+                && !found.contains(name)
+            {
+                found.push(name.clone());
+            }
+            return;
+        }
+        let nested = [&ty.element_type, &ty.key_type, &ty.return_type];
+        for inner in nested.into_iter().flatten() {
+            collect(inner, found);
+        }
+        for inner in ty
+            .parameter_types
+            .iter()
+            .chain(&ty.type_arguments)
+            .flatten()
+        {
+            collect(inner, found);
+        }
+    }
+
+    let mut found = Vec::new();
+    for param in parameters {
+        collect(&param.ty, &mut found);
+    }
+    collect(return_type, &mut found);
+    found
+}
+
+/// Infer the type parameters in `generics` by matching `expected` against `actual`.
+///
+/// The first match binds a parameter; later occurrences are checked against it.
+fn infer_type_arguments(
+    expected: &HypnoType,
+    actual: &HypnoType,
+    generics: &[String],
+    bindings: &mut HashMap<String, HypnoType>,
+) {
+    if actual.base_type == HypnoBaseType::Unknown {
+        return;
+    }
+    if expected.is_type_parameter() {
+        if let Some(name) = &expected.name
+            && generics.contains(name)
+            && !bindings.contains_key(name)
+        {
+            bindings.insert(name.clone(), actual.clone());
+        }
+        return;
+    }
+    if expected.base_type != actual.base_type {
+        return;
+    }
+
+    let nested = [
+        (&expected.element_type, &actual.element_type),
+        (&expected.key_type, &actual.key_type),
+        (&expected.return_type, &actual.return_type),
+    ];
+    for (expected, actual) in nested {
+        if let (Some(expected), Some(actual)) = (expected, actual) {
+            infer_type_arguments(expected, actual, generics, bindings);
+        }
+    }
+    let lists = [
+        (&expected.parameter_types, &actual.parameter_types),
+        (&expected.type_arguments, &actual.type_arguments),
+    ];
+    for (expected, actual) in lists {
+        if let (Some(expected), Some(actual)) = (expected, actual) {
+            for (expected, actual) in expected.iter().zip(actual) {
+                infer_type_arguments(expected, actual, generics, bindings);
+            }
+        }
+    }
+    if let (Some(expected), Some(actual)) = (&expected.fields, &actual.fields) {
+        for (name, expected) in expected {
+            if let Some(actual) = actual.get(name) {
+                infer_type_arguments(expected, actual, generics, bindings);
+            }
+        }
+    }
+}
+
+/// Replace bound type parameters in `ty`
+fn substitute_type_parameters(ty: &HypnoType, bindings: &HashMap<String, HypnoType>) -> HypnoType {
+    if bindings.is_empty() {
+        return ty.clone();
+    }
+    if ty.is_type_parameter() {
+        return ty
+            .name
+            .as_ref()
+            .and_then(|name| bindings.get(name))
+            .cloned()
+            .unwrap_or_else(|| ty.clone());
+    }
+
+    let substitute = |inner: &HypnoType| substitute_type_parameters(inner, bindings);
+    let substitute_all = |types: &Vec<HypnoType>| types.iter().map(substitute).collect();
+    HypnoType {
+        base_type: ty.base_type.clone(),
+        name: ty.name.clone(),
+        element_type: ty.element_type.as_deref().map(|t| Box::new(substitute(t))),
+        key_type: ty.key_type.as_deref().map(|t| Box::new(substitute(t))),
+        fields: ty.fields.as_ref().map(|fields| {
+            fields
+                .iter()
+                .map(|(name, t)| (name.clone(), substitute(t)))
+                .collect()
+        }),
+        parameter_types: ty.parameter_types.as_ref().map(substitute_all),
+        return_type: ty.return_type.as_deref().map(|t| Box::new(substitute(t))),
+        type_arguments: ty.type_arguments.as_ref().map(substitute_all),
+        members: ty.members.as_ref().map(substitute_all),
+        literal: ty.literal.clone(),
+    }
+}
+
+/// Return type of a suggestion: unannotated generators return `Iterator<unknown>`
+fn generator_return_type(body: &[AstNode], declared: HypnoType) -> HypnoType {
+    if declared.base_type == HypnoBaseType::Unknown && is_generator_body(body) {
+        HypnoType::create_iterator(declared)
+    } else {
+        declared
+    }
+}
+
+/// Split a type annotation at top-level separators: commas between generic
+/// arguments (`string, Dictionary<string, number>`) or `|` between union members
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth = depth.saturating_sub(1),
+            c if c == separator && depth == 0 => {
+                parts.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(text[start..].trim());
+    parts
+}
+
+/// Literal type written in an annotation (`"on"`, `42`, `true`)
+fn parse_literal_type(text: &str) -> Option<HypnoType> {
+    if text.len() >= 2 && text.starts_with('"') && text.ends_with('"') {
+        return Some(HypnoType::create_literal(
+            HypnoBaseType::String,
+            text.to_string(),
+        ));
+    }
+    match text {
+        "true" | "false" => Some(HypnoType::create_literal(
+            HypnoBaseType::Boolean,
+            text.to_string(),
+        )),
+        _ => text
+            .parse::<f64>()
+            .ok()
+            .filter(|_| !text.starts_with(|c: char| c.is_alphabetic()))
+            .map(|value| HypnoType::create_literal(HypnoBaseType::Number, value.to_string())),
+    }
+}
+
+/// Literal type of a literal expression
+fn literal_type(expr: &AstNode) -> Option<HypnoType> {
+    match expr.unspanned() {
+        AstNode::StringLiteral(value) => Some(HypnoType::create_literal(
+            HypnoBaseType::String,
+            format!("{:?}", value),
+        )),
+        AstNode::NumberLiteral(value) => Some(HypnoType::create_literal(
+            HypnoBaseType::Number,
+            value.to_string(),
+        )),
+        AstNode::BooleanLiteral(value) => Some(HypnoType::create_literal(
+            HypnoBaseType::Boolean,
+            value.to_string(),
+        )),
+        AstNode::UnaryExpression { operator, operand } if operator == "-" => {
+            match operand.unspanned() {
+                AstNode::NumberLiteral(value) => Some(HypnoType::create_literal(
+                    HypnoBaseType::Number,
+                    (-value).to_string(),
+                )),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Nullness implied by `condition` evaluating to `holds`, as variables
+/// paired with whether they are null
+///
+/// Looks through `!`, through `&&` when it holds and through `||` when it
+/// fails, since every operand is then known.
+fn null_facts(condition: &AstNode, holds: bool) -> Vec<(&str, bool)> {
+    match condition.unspanned() {
+        AstNode::UnaryExpression { operator, operand } if operator == "!" => {
+            null_facts(operand, !holds)
+        }
+        AstNode::BinaryExpression {
+            left,
+            operator,
+            right,
+        } => {
+            let operands_known = match operator.to_ascii_lowercase().as_str() {
+                "&&" | "undermycontrol" => holds,
+                "||" | "resistanceisfutile" => !holds,
+                _ => {
+                    return null_check(condition)
+                        .map(|(name, when_null)| vec![(name, when_null == holds)])
+                        .unwrap_or_default();
+                }
+            };
+            if !operands_known {
+                return Vec::new();
+            }
+            let mut facts = null_facts(left, holds);
+            facts.extend(null_facts(right, holds));
+            facts
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// `x == null` / `x != null` conditions: the variable and whether the
+/// condition holds when it is null
+fn null_check(condition: &AstNode) -> Option<(&str, bool)> {
+    let AstNode::BinaryExpression {
+        left,
+        operator,
+        right,
+    } = condition.unspanned()
+    else {
+        return None;
+    };
+    let when_null = match operator.to_ascii_lowercase().as_str() {
+        "==" | "youarefeelingverysleepy" => true,
+        "!=" | "youcannotresist" | "notsodeep" => false,
+        _ => return None,
+    };
+    match (left.unspanned(), right.unspanned()) {
+        (AstNode::Identifier(name), AstNode::NullLiteral)
+        | (AstNode::NullLiteral, AstNode::Identifier(name)) => Some((name, when_null)),
+        _ => None,
+    }
+}
+
+/// Whether a block always leaves its enclosing flow (`awaken`, `snap`, `sink`, `disrupt`)
+fn always_exits(block: &[AstNode]) -> bool {
+    block.last().is_some_and(|stmt| {
+        matches!(
+            stmt.unspanned(),
+            AstNode::ReturnStatement(_)
+                | AstNode::BreakStatement(_)
+                | AstNode::ContinueStatement(_)
+                | AstNode::RaiseStatement { .. }
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hypnoscript_lexer_parser::{Lexer, Parser};
+
+    #[test]
+    fn test_type_check_simple() {
+        let source = r#"
+Focus {
+    induce x: number = 42;
+    induce y: number = 10;
+    induce sum: number = x + y;
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert!(errors.is_empty(), "Errors: {:?}", errors);
+    }
+
+    #[test]
+    fn test_operator_synonym_diagnostics() {
+        let source = r#"
+Focus {
+    induce left: string = "hello";
+    induce right: string = "world";
+    if (left lookAtTheWatch right) {
+        observe "won't happen";
+    }
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert!(
+            errors.iter().any(|msg| msg.contains("lookAtTheWatch")),
+            "Expected comparison diagnostic mentioning operator, got {:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_type_check_private_session_member_access() {
+        let source = r#"
+Focus {
+    session Account {
+        conceal balance: number = 0;
+
+        expose suggestion constructor(initialBalance: number) {
+            this.balance = initialBalance;
+        }
+
+        expose suggestion read(): number {
+            awaken this.balance;
+        }
+    }
+
+    induce leaked = Account(100).balance;
+} Relax
+"#;
+
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert!(!errors.is_empty());
+        assert!(
+            errors.iter().any(|msg| {
+                msg.contains("Field 'balance' of session 'Account' is not visible here")
+            }),
+            "Expected private member visibility error, got {:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_type_check_static_misuse_errors() {
+        let source = r#"
+Focus {
+    session Config {
+        expose secret: number = 42;
+    }
+
+    session Env {
+        dominant expose name: string = "default";
+    }
+
+    induce secretValue = Config.secret;
+    Env().name = "prod";
+} Relax
+"#;
+
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert!(
+            errors.len() >= 2,
+            "Expected at least two errors, got {:?}",
+            errors
+        );
+        assert!(
+            errors.iter().any(|msg| {
+                msg.contains("Cannot access instance field 'secret' on session type 'Config'")
+            }),
+            "Expected instance vs static access error, got {:?}",
+            errors
+        );
+        assert!(
+            errors.iter().any(|msg| {
+                msg.contains("Assign static field 'name' through session 'Env', not an instance")
+            }),
+            "Expected static assignment error, got {:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_type_check_mismatch() {
+        let source = r#"
+Focus {
+    induce x: number = "hello";
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert!(!errors.is_empty());
+        assert!(errors[0].contains("Type mismatch"));
+    }
+
+    #[test]
+    fn test_errors_include_source_location() {
+        let source =
+            "Focus {\n    induce ok: number = 1;\n    induce bad: number = \"text\";\n} Relax";
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 1, "Errors: {:?}", errors);
+        assert!(
+            errors[0].ends_with("at line 3, column 5"),
+            "Expected location suffix, got {:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_closure_types() {
+        let source = r#"
+Focus {
+    induce double = suggestion (x: number) => x * 2;
+    induce ok: number = double(21);
+    induce bad: string = double(1);
+    double("text");
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 2, "Errors: {:?}", errors);
+        assert!(errors[0].contains("Type mismatch"), "{:?}", errors);
+        assert!(
+            errors[1].contains("Callable argument 1 type mismatch"),
+            "{:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_string_interpolation_is_string() {
+        let source = r#"
+Focus {
+    induce level: number = 2;
+    induce text: string = "depth ${level * 2}";
+    induce wrong: number = "depth ${level}";
+    induce missing: string = "value ${unknownThing}";
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 2, "Errors: {:?}", errors);
+        assert!(errors[0].contains("Type mismatch"), "{:?}", errors);
+        assert!(errors[1].contains("unknownThing"), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_recover_binding_is_error_record() {
+        let source = r#"
+Focus {
+    attempt {
+        disrupt "bad input", 7;
+    } recover (err) {
+        induce text: string = err.message;
+        induce count: number = err.kind;
+        observe err.stack;
+    }
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 2, "Errors: {:?}", errors);
+        assert!(errors[0].contains("Type mismatch"), "{:?}", errors);
+        assert!(
+            errors[1].contains("Record type 'Error' has no field 'stack'"),
+            "{:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_mindlink_imports_module_types() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("metrics.hyp"),
+            r#"
+Focus {
+    suggestion internal(): number {
+        awaken 62;
+    }
+
+    expose suggestion pulse(unit: string): string {
+        awaken internal() + " " + unit;
+    }
+} Relax
+"#,
+        )
+        .unwrap();
+        let main_path = dir.path().join("main.hyp");
+        let source = r#"
+Focus {
+    mindLink { pulse, internal } from "./metrics.hyp";
+    induce reading: string = pulse("bpm");
+    induce wrong: number = pulse("bpm");
+    pulse(62);
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        checker.set_source_path(&main_path);
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 3, "Errors: {:?}", errors);
+        assert!(
+            errors[0].contains("Module './metrics.hyp' does not expose 'internal'"),
+            "{:?}",
+            errors
+        );
+        assert!(errors[1].contains("Type mismatch"), "{:?}", errors);
+        assert!(
+            errors[2].contains("Function 'pulse' argument 1 type mismatch"),
+            "{:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_assert_requires_boolean_condition() {
+        let source = r#"
+Focus {
+    induce count: number = 3;
+    assert count == 3, "count";
+    assert count, "not a condition";
+    assert count > 1, 42;
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 2, "Errors: {:?}", errors);
+        assert!(
+            errors[0].contains("Assert condition must be boolean, got Number"),
+            "{:?}",
+            errors
+        );
+        assert!(errors[1].contains("Assert message must be a string, got Number"));
+    }
+
+    #[test]
+    fn test_dictionary_types() {
+        let source = r#"
+Focus {
+    induce ages: Dictionary<string, number> = { "Ada": 36, "Alan": 41 };
+    induce age: number = ages["Ada"];
+    ages["Grace"] = 45;
+    ages["Linus"] = "old";
+    induce wrong: Dictionary<string, string> = ages;
+    induce name: string = ages[1];
+    induce count: number = DictSize(ages);
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 4, "Errors: {:?}", errors);
+        assert!(errors[0].contains(
+            "Cannot assign value of type String to element of Dictionary<String, Number>"
+        ));
+        assert!(errors[1].contains("Type mismatch"), "{:?}", errors);
+        assert!(errors[2].contains("Dictionary key type mismatch: expected String, got Number"));
+        assert!(errors[3].contains("Type mismatch"), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_loop_labels_must_be_defined() {
+        let source = r#"
+Focus {
+    outer: loop {
+        inner: while (true) {
+            snap outer;
+            sinkTo inner;
+            snap missing;
+        }
+        outer: while (true) {
+            snap;
+        }
+    }
+    sink outer;
+    loop {
+        induce escape = suggestion () {
+            snap outer;
+        };
+    }
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 4, "Errors: {:?}", errors);
+        assert!(errors[0].contains("Undefined loop label 'missing'"));
+        assert!(errors[1].contains("Loop label 'outer' is already used by an enclosing loop"));
+        assert!(errors[2].contains("Undefined loop label 'outer'"));
+        assert!(errors[3].contains("Undefined loop label 'outer'"));
     }
 
     #[test]
-    fn test_type_check_private_session_member_access() {
+    fn test_spread_argument_and_element_types() {
         let source = r#"
 Focus {
-    session Account {
-        conceal balance: number = 0;
+    suggestion pair(a: number, b: number): number {
+        awaken a + b;
+    }
+    induce nums = [1, 2];
+    induce words = ["a"];
+    induce joined: number[] = [0, ...nums];
+    induce ok = pair(...nums);
+    induce mixed = [...nums, ...words];
+    induce wrong = pair(...words);
+    induce many = pair(1, 2, 3, ...nums);
+    induce flat = [...5];
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
 
-        expose suggestion constructor(initialBalance: number) {
-            this.balance = initialBalance;
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 4, "Errors: {:?}", errors);
+        assert!(errors[0].contains("Array elements must have same type"));
+        assert!(errors[1].contains("Function 'pair' spread argument type mismatch"));
+        assert!(errors[2].contains("Function 'pair' expects 2 arguments, got at least 3"));
+        assert!(errors[3].contains("Cannot spread non-array value of type Number"));
+    }
+
+    #[test]
+    fn test_default_rest_and_named_argument_types() {
+        let source = r#"
+Focus {
+    suggestion greet(name: string = "guest", times: number = 1): string {
+        awaken name;
+    }
+    suggestion total(...values: number[]): number {
+        awaken 0;
+    }
+    session Point {
+        expose x: number;
+
+        suggestion constructor(x: number = 0) {
+            this.x = x;
         }
+    }
 
-        expose suggestion read(): number {
-            awaken this.balance;
+    greet();
+    greet("Ada", times: 2);
+    total();
+    total(1, 2, 3);
+    Point(x: 3);
+    greet(1);
+    greet("a", 2, 3);
+    greet(nickname: "x");
+    greet("a", name: "b");
+    total(1, "two");
+    Point(x: "three");
+    suggestion broken(count: number = "many") { }
+    ToUpper(text: "abc");
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 8, "Errors: {:?}", errors);
+        assert!(errors[0].contains("Function 'greet' argument 1 type mismatch"));
+        assert!(errors[1].contains("Function 'greet' expects 0 to 2 arguments, got 3"));
+        assert!(errors[2].contains("Function 'greet' has no parameter named 'nickname'"));
+        assert!(errors[3].contains("Function 'greet' received argument 'name' more than once"));
+        assert!(errors[4].contains("Function 'total' argument 2 type mismatch"));
+        assert!(errors[5].contains("Function 'Point' argument 'x' type mismatch"));
+        assert!(
+            errors[6]
+                .contains("Default value of parameter 'count' must be of type Number, got String")
+        );
+        assert!(errors[7].contains("Builtin function 'ToUpper' does not accept named arguments"));
+    }
+
+    #[test]
+    fn test_mesmerize_calls_have_promise_types() {
+        let source = r#"
+Focus {
+    mesmerize suggestion count(): number {
+        awaken 3;
+    }
+
+    induce pending: Promise<number> = count();
+    induce value: number = await pending;
+    induce text: string = AsyncReadFile("notes.txt");
+    induce wrong: string = await count();
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 2, "Errors: {:?}", errors);
+        assert!(errors[0].contains("Promise<String>"));
+        assert!(errors[1].contains("String") && errors[1].contains("Number"));
+    }
+
+    #[test]
+    fn test_generic_declarations_are_instantiated() {
+        let source = r#"
+Focus {
+    tranceify Box<T> { value: T; }
+
+    suggestion first<T>(items: T[]): T {
+        induce fallback: T = 0;
+        awaken items[0];
+    }
+    suggestion unbox<T>(wrapped: Box<T>): T {
+        awaken wrapped.value;
+    }
+    suggestion same<T>(a: T, b: T): T {
+        awaken a;
+    }
+
+    session Stack<T> {
+        expose items: T[];
+        suggestion constructor(items: T[]) {
+            this.items = items;
+        }
+        expose suggestion peek(): T {
+            awaken this.items[0];
+        }
+        expose suggestion push(item: T) {
+            this.items[0] = item;
         }
     }
 
-    induce leaked = Account(100).balance;
+    induce n: number = first([1, 2, 3]);
+    induce s: string = first([1, 2]);
+    induce boxed: Box<number> = Box { value: 5 };
+    induce t: string = unbox(boxed);
+    induce u: number = boxed.value;
+    induce bad: Box<string> = Box { value: 1 };
+    same(1, "x");
+    induce stack = Stack([1, 2]);
+    induce top: number = stack.peek();
+    stack.push("x");
+    induce words: Stack<string> = stack;
 } Relax
 "#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
 
-        let mut lexer = Lexer::new(source);
-        let tokens = lexer.lex().unwrap();
-        let mut parser = Parser::new(tokens);
-        let ast = parser.parse_program().unwrap();
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 7, "Errors: {:?}", errors);
+        assert!(errors[0].contains("variable 'fallback': expected T, got Number"));
+        assert!(errors[1].contains("variable 's': expected String, got Number"));
+        assert!(errors[2].contains("variable 't': expected String, got Number"));
+        assert!(errors[3].contains("expected Record<Box<String>>, got Record<Box<Number>>"));
+        assert!(errors[4].contains("Function 'same' argument 2 type mismatch: expected Number"));
+        assert!(errors[5].contains("Method 'push' of session 'Stack' argument 1"));
+        assert!(errors[6].contains("expected Stack<String>, got Stack<Number>"));
+    }
+
+    #[test]
+    fn test_union_optional_and_literal_types() {
+        let source = r#"
+Focus {
+    tranceify Person { name: string; }
+
+    induce mode: "on" | "off" = "on";
+    mode = "off";
+    mode = "maybe";
+    induce count: lucid number = null;
+    induce total: number = count;
+    induce fallback: number = count ?? 0;
+    induce person: Person? = null;
+    induce name: string = person?.name ?? "anonymous";
+    induce nickname: string = person?.name;
+
+    suggestion greet(name: string | null): string {
+        if (name == null) {
+            awaken "nobody";
+        }
+        awaken name;
+    }
+    suggestion shout(name: string?): string {
+        if (name != null) {
+            awaken name + "!";
+        }
+        awaken name;
+    }
+    suggestion describe(value: number | string | null): string {
+        awaken entrain value {
+            when n: number => "number " + n
+            when null => "nothing"
+            otherwise => value
+        };
+    }
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
 
         let mut checker = TypeChecker::new();
         let errors = checker.check_program(&ast);
-        assert!(!errors.is_empty());
+        assert_eq!(errors.len(), 4, "Errors: {:?}", errors);
+        assert!(errors[0].contains("type \"maybe\" to variable 'mode' of type \"on\" | \"off\""));
+        assert!(errors[1].contains("'total': expected Number, got Number | Null"));
+        assert!(errors[2].contains("'nickname': expected String, got String | Null"));
+        assert!(errors[3].contains("Return type mismatch: expected String, got String | Null"));
+    }
+
+    #[test]
+    fn test_null_narrowing_through_logical_operators() {
+        let source = r#"
+Focus {
+    suggestion both(m: string?): boolean {
+        awaken m != null && Length(m) > 2;
+    }
+    suggestion guarded(m: string?): number {
+        if (m != null && Length(m) > 2) {
+            awaken Length(m);
+        }
+        awaken 0;
+    }
+    suggestion early(m: string?): number {
+        if (m == null || Length(m) == 0) {
+            awaken 0;
+        }
+        awaken Length(m);
+    }
+    suggestion negated(m: string?): number {
+        if (!(m == null)) {
+            awaken Length(m);
+        } else {
+            awaken 0;
+        }
+    }
+    suggestion either(m: string?, n: string?): number {
+        if (m != null || n != null) {
+            awaken Length(m);
+        }
+        awaken 0;
+    }
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        // `||` that holds does not pin down either operand
+        assert_eq!(errors.len(), 1, "Errors: {:?}", errors);
+        assert!(errors[0].contains("got String | Null"), "{}", errors[0]);
+    }
+
+    #[test]
+    fn test_session_inheritance() {
+        let source = r#"
+Focus {
+    session User {
+        conceal secret: string = "pw";
+        expose name: string = "";
+        suggestion constructor(name: string) {
+            this.name = name;
+        }
+        expose suggestion greet(): string { awaken "Hello " + this.name; }
+        expose suggestion age(): number { awaken 1; }
+    }
+    session Admin extends User {
+        suggestion constructor(name: string) {
+            super(name);
+        }
+        expose suggestion greet(): string { awaken super.greet() + "!"; }
+        expose suggestion leak(): string { awaken this.secret; }
+        conceal suggestion age(): number { awaken 2; }
+    }
+    session Guest extends User {
+        expose suggestion greet(loud: boolean): string { awaken "HI"; }
+    }
+    session Lone {
+        suggestion constructor() { super(); }
+    }
+    session Broken extends Missing { }
+
+    suggestion welcome(user: User): string {
+        awaken user.greet();
+    }
+
+    induce admin = Admin("Ada");
+    induce greeting: string = welcome(admin);
+    induce inherited: string = admin.name;
+    induce guest = Guest(42);
+    induce demoted: Admin = User("Bob");
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 7, "Errors: {:?}", errors);
+        assert!(errors[0].contains("Method 'age' in session 'Admin' cannot conceal"));
+        assert!(errors[1].contains("Method 'greet' in session 'Guest' does not match"));
+        assert!(errors[2].contains("Session 'Broken' extends unknown session 'Missing'"));
+        assert!(errors[3].contains("Field 'secret' of session 'Admin' is not visible here"));
+        assert!(errors[4].contains("'super(...)' can only be called in the constructor"));
+        assert!(errors[5].contains("Function 'Guest' argument 1 type mismatch"));
+        assert!(errors[6].contains("variable 'demoted': expected Admin, got User"));
+    }
+
+    #[test]
+    fn test_interface_conformance() {
+        let source = r#"
+Focus {
+    interface Plugin {
+        name: string;
+        suggestion run(input: string): string;
+    }
+    interface Named { name: string; }
+
+    session Echo implements Plugin {
+        expose name: string = "echo";
+        expose suggestion run(input: string): string { awaken input; }
+    }
+    session Loud extends Echo { }
+    session Silent implements Plugin {
+        conceal name: string = "silent";
+    }
+    session Counter implements Plugin {
+        expose name: number = 0;
+        expose suggestion run(times: number): string { awaken ""; }
+    }
+    session Ghost implements Missing { }
+    tranceify Tag { name: string; }
+
+    suggestion apply(plugin: Plugin, text: string): string {
+        awaken plugin.name + ": " + plugin.run(text);
+    }
+    suggestion nameOf(named: Named): string { awaken named.name; }
+
+    induce a: string = apply(Loud(), "hi");
+    induce b: string = nameOf(Tag { name: "tag" });
+    induce c: string = apply(Tag { name: "tag" }, "hi");
+    induce d: Plugin = Echo();
+    induce e: number = d.run(1);
+    induce f = entrain a {
+        when p: Plugin => p.name
+        otherwise => "none"
+    };
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 8, "Errors: {:?}", errors);
+        assert!(errors[0].contains("Field 'name' of session 'Silent' must be exposed"));
+        assert!(errors[1].contains("Session 'Silent' does not implement method 'run'"));
+        assert!(errors[2].contains(
+            "Field 'name' of session 'Counter' does not match interface 'Plugin': expected String, got Number"
+        ));
         assert!(
-            errors.iter().any(|msg| {
-                msg.contains("Field 'balance' of session 'Account' is not visible here")
-            }),
-            "Expected private member visibility error, got {:?}",
-            errors
+            errors[3]
+                .contains("Method 'run' of session 'Counter' does not match interface 'Plugin'")
         );
+        assert!(errors[4].contains("Session 'Ghost' implements unknown interface 'Missing'"));
+        assert!(errors[5].contains("Function 'apply' argument 1 type mismatch: expected Plugin"));
+        assert!(errors[6].contains("Method 'run' of interface 'Plugin' argument 1 type mismatch"));
+        assert!(errors[7].contains("variable 'e': expected Number, got String"));
     }
 
     #[test]
-    fn test_type_check_static_misuse_errors() {
+    fn test_compound_assignment_and_update() {
         let source = r#"
 Focus {
-    session Config {
-        expose secret: number = 42;
+    induce count: number = 0;
+    induce text: string = "a";
+    induce name: string | null = null;
+    induce flag: boolean = true;
+    induce totals: number[] = [1, 2];
+
+    count += 2;
+    text += count;
+    totals[0] *= 3;
+    name ??= "guest";
+    induce next: number = count++;
+
+    count += "x";
+    flag -= 1;
+    name ??= 5;
+    flag++;
+    missing += 1;
+    totals[1] += "x";
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 6, "Errors: {:?}", errors);
+        assert!(
+            errors[0]
+                .contains("Cannot assign value of type String to variable 'count' of type Number")
+        );
+        assert!(errors[1].contains("Arithmetic operator '-' requires numeric operands"));
+        assert!(errors[2].contains(
+            "Cannot assign value of type Number to variable 'name' of type String | Null"
+        ));
+        assert!(errors[3].contains("Operator '++' requires a numeric target, got Boolean"));
+        assert!(errors[4].contains("Cannot assign to undefined variable 'missing'"));
+        assert!(errors[5].contains("Cannot assign value of type String to target of type Number"));
     }
 
-    session Env {
-        dominant expose name: string = "default";
+    #[test]
+    fn test_enum_variants_and_exhaustive_entrain() {
+        let source = r#"
+Focus {
+    enum State { Idle, Running(number), Failed(string, number) }
+    enum Color { Red, Green }
+
+    suggestion describe(state: State): string {
+        awaken entrain state {
+            when State.Idle => "idle"
+            when State.Running(speed) => "running " + speed
+            when State.Failed(reason, code) => reason + code
+        };
     }
 
-    induce secretValue = Config.secret;
-    Env().name = "prod";
+    induce s: State = State.Running(2);
+    induce a = entrain s {
+        when State.Idle => "idle"
+        when State.Running(speed) if speed > 1 => "fast"
+    };
+    induce b = entrain s {
+        when State.Running(1) => "one"
+        when State.Failed => "failed"
+        otherwise => "other"
+    };
+    induce c = entrain s { when anything => "any" };
+    induce d = entrain s {
+        when Color.Red => "red"
+        when State.Running(x, y) => "bad"
+        otherwise => "x"
+    };
+    induce e = State.Running("fast");
+    induce f = State.Running;
+    induce g = State.Idle(1);
+    induce h: State = Color.Red;
+    induce i: number = entrain s {
+        when State.Running(speed) => speed
+        otherwise => 0
+    };
 } Relax
 "#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
 
-        let mut lexer = Lexer::new(source);
-        let tokens = lexer.lex().unwrap();
-        let mut parser = Parser::new(tokens);
-        let ast = parser.parse_program().unwrap();
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 7, "Errors: {:?}", errors);
+        assert!(
+            errors[0]
+                .contains("Non-exhaustive entrain over State: missing variant(s) Running, Failed")
+        );
+        assert!(errors[1].contains("Pattern 'Color.Red' cannot match a value of type State"));
+        assert!(
+            errors[2].contains(
+                "Variant 'State.Running' carries 1 payload value(s), but the pattern has 2"
+            )
+        );
+        assert!(errors[3].contains(
+            "Variant 'State.Running' argument 1 type mismatch: expected Number, got String"
+        ));
+        assert!(errors[4].contains("Variant 'State.Running' carries a payload"));
+        assert!(errors[5].contains("Variant 'State.Idle' has no payload"));
+        assert!(errors[6].contains("variable 'h': expected State, got Color"));
+    }
+
+    #[test]
+    fn test_session_finale_and_using() {
+        let source = r#"
+Focus {
+    session Log {
+        conceal lines: number = 0;
+        finale {
+            observe this.lines + 1;
+            this.missing();
+        }
+        finale {}
+    }
+
+    using (log = Log()) {
+        observe log;
+    }
+    using (count = 42) {
+        induce n: number = count;
+    }
+    induce after: number = count;
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 4, "Errors: {:?}", errors);
+        assert!(errors[0].contains("Multiple finale blocks defined for session 'Log'"));
+        assert!(errors[1].contains("Session 'Log' has no method 'missing'"));
+        assert!(errors[2].contains("'using' requires a session instance, got Number"));
+        assert!(errors[3].contains("Undefined variable 'count'"));
+    }
+
+    #[test]
+    fn test_for_each_element_types() {
+        let source = r#"
+Focus {
+    tranceify Point { x: number; y: number; }
+    induce names: string[] = ["a"];
+    induce scores: Dictionary<string, number> = { "zoe": 3 };
+    induce origin = Point { x: 0, y: 0 };
+
+    loop (induce name in names) { induce ok: string = name; }
+    loop (induce i, ch in "abc") { induce sum: number = i; induce text: string = ch; }
+    loop (induce key, score in scores) { induce tag: string = key; induce n: number = score; }
+    loop (induce field, coordinate in origin) { induce n: number = coordinate; }
+    loop (induce n in 0..=10 step 2) { induce m: number = n; }
+
+    loop (induce name in names) { induce bad: number = name; }
+    loop (induce key, score in scores) { induce bad: string = score; }
+    loop (induce x in 42) { }
+    induce range = "a"..3;
+    induce after = name;
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 5, "Errors: {:?}", errors);
+        assert!(errors[0].contains("variable 'bad': expected Number, got String"));
+        assert!(errors[1].contains("variable 'bad': expected String, got Number"));
+        assert!(errors[2].contains("Cannot iterate over value of type Number"));
+        assert!(errors[3].contains("Range bounds must be numbers, got String"));
+        assert!(errors[4].contains("Undefined variable 'name'"));
+    }
+
+    #[test]
+    fn test_integer_types() {
+        let source = r#"
+Focus {
+    induce id: integer = 42i;
+    induce next: integer = id * 2i + 1i;
+    induce ratio: number = id / 2;
+    induce mask: integer = id & 255 | ~0i;
+    induce widened: number = id;
+    induce hash: integer = HashString("trance");
+    induce counted: integer = ToInt("12");
+    id++;
+
+    induce narrowed: integer = 2.5;
+    induce mixed: integer = id + 1;
+    induce text = "a" << 1;
+    id = ratio;
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
 
         let mut checker = TypeChecker::new();
         let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 4, "Errors: {:?}", errors);
+        assert!(errors[0].contains("variable 'narrowed': expected Integer, got Number"));
+        assert!(errors[1].contains("variable 'mixed': expected Integer, got Number"));
         assert!(
-            errors.len() >= 2,
-            "Expected at least two errors, got {:?}",
-            errors
+            errors[2]
+                .contains("Bitwise operator '<<' requires integer operands, got String and Number")
         );
+        assert!(errors[3].contains("variable 'id' of type Integer"));
+    }
+
+    #[test]
+    fn test_destructuring_types() {
+        let source = r#"
+Focus {
+    tranceify Person {
+        name: string;
+        age: number;
+    }
+    induce person = Person { name: "Ada", age: 36 };
+    induce [first, ...rest] = [1, 2, 3];
+    induce { name, age } = person;
+    induce { level } = { "level": true };
+    induce sum: number = first + age;
+    induce others: number[] = rest;
+    induce text: string = name;
+    induce flag: boolean = level;
+
+    suggestion describe({ name, age }: Person): string {
+        awaken name + age;
+    }
+
+    induce wrong: number = name;
+    induce { height } = person;
+    induce [x] = 5;
+    induce [y]: string[] = [1];
+    suggestion broken([a]: number): number { awaken a; }
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 5, "Errors: {:?}", errors);
+        assert!(errors[0].contains("variable 'wrong': expected Number, got String"));
+        assert!(errors[1].contains("Record<Person> has no field 'height'"));
+        assert!(errors[2].contains("Cannot destructure a value of type Number with pattern [x]"));
         assert!(
-            errors.iter().any(|msg| {
-                msg.contains("Cannot access instance field 'secret' on session type 'Config'")
-            }),
-            "Expected instance vs static access error, got {:?}",
-            errors
+            errors[3].contains("Type mismatch for pattern [y]: expected [String], got [Number]")
         );
+        assert!(errors[4].contains("Cannot destructure a value of type Number with pattern [a]"));
+    }
+
+    #[test]
+    fn test_operator_overloading_types() {
+        let source = r#"
+Focus {
+    session Vector {
+        expose x: number;
+        suggestion constructor(x: number) { this.x = x; }
+        suggestion operator +(other: Vector): Vector { awaken Vector(this.x + other.x); }
+        suggestion operator ==(other: Vector): number { awaken 1; }
+    }
+    tranceify Money {
+        cents: number;
+        suggestion operator <(other: Money): boolean { awaken this.cents < other.cents; }
+    }
+    induce a = Vector(1);
+    induce sum: Vector = a + a;
+    induce cheaper: boolean = Money { cents: 1 } fallUnderMySpell Money { cents: 2 };
+
+    induce wrong: Vector = a + 2;
+    induce text: string = a + a;
+    induce product = Money { cents: 1 } * Money { cents: 2 };
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let mut checker = TypeChecker::new();
+        let errors = checker.check_program(&ast);
+        assert_eq!(errors.len(), 4, "Errors: {:?}", errors);
+        assert!(errors[0].contains("Operator '==' must return boolean, got Number"));
         assert!(
-            errors.iter().any(|msg| {
-                msg.contains("Assign static field 'name' through session 'Env', not an instance")
-            }),
-            "Expected static assignment error, got {:?}",
-            errors
+            errors[1].contains(
+                "Operator '+' of Vector expects a right operand of type Vector, got Number"
+            )
         );
+        assert!(errors[2].contains("variable 'text': expected String, got Vector"));
+        assert!(errors[3].contains("Arithmetic operator '*' requires numeric operands"));
     }
 
     #[test]
-    fn test_type_check_mismatch() {
+    fn test_generator_types() {
         let source = r#"
 Focus {
-    induce x: number = "hello";
+    suggestion names(): Iterator<string> {
+        emerge "Ada";
+        emerge 1;
+        awaken 2;
+    }
+
+    suggestion counter() {
+        emerge 1;
+    }
+
+    suggestion wrong(): number {
+        emerge 1;
+    }
+
+    session Countdown {
+        expose remaining: number;
+        suggestion hasNext(): boolean { awaken this.remaining > 0; }
+        suggestion next(): number { awaken this.remaining; }
+    }
+
+    induce it = names();
+    induce name: string = it.next();
+    induce more: boolean = it.hasNext();
+    loop (induce n in names()) {
+        induce upper: string = n;
+    }
+    loop (induce value in Countdown()) {
+        induce doubled: number = value * 2;
+    }
+    induce total: number = ArraySum(Countdown());
+    induce lines: Iterator<string> = StreamLines("data.csv");
+    induce unknownCount: Iterator<number> = counter();
+
+    induce bad: number = it.next();
+    induce sum = ArraySum(names());
+    emerge 3;
 } Relax
 "#;
-        let mut lexer = Lexer::new(source);
-        let tokens = lexer.lex().unwrap();
-        let mut parser = Parser::new(tokens);
-        let ast = parser.parse_program().unwrap();
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
 
         let mut checker = TypeChecker::new();
         let errors = checker.check_program(&ast);
-        assert!(!errors.is_empty());
-        assert!(errors[0].contains("Type mismatch"));
+        assert_eq!(errors.len(), 6, "Errors: {:?}", errors);
+        assert!(errors[0].contains("Emerged value type mismatch: expected String, got Number"));
+        assert!(errors[1].contains("cannot return a value"));
+        assert!(errors[2].contains("Generator suggestion 'wrong' must return Iterator<T>"));
+        assert!(errors[3].contains("variable 'bad': expected Number, got String"));
+        assert!(errors[4].contains(
+            "'ArraySum' argument 1 type mismatch: expected [Number], got Iterator<String>"
+        ));
+        assert!(errors[5].contains("'emerge' can only be used in the body of a generator"));
     }
 }
@@ -1,5 +1,13 @@
-use hypnoscript_lexer_parser::ast::AstNode;
-use std::collections::HashMap;
+use hypnoscript_lexer_parser::ast::{AstNode, InterpolationPart};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use thiserror::Error;
+
+/// Fehlertypen für die WASM-Text-Generierung
+#[derive(Error, Debug)]
+pub enum WasmCodegenError {
+    #[error("Nicht unterstützt im WASM-Backend: {0}")]
+    Unsupported(String),
+}
 
 /// WASM code generator for HypnoScript
 ///
@@ -15,11 +23,24 @@ pub struct WasmCodeGenerator {
     local_counter: usize,
     label_counter: usize,
     variable_map: HashMap<String, usize>,
+    /// Variables holding integers; they live in i64 locals, all other
+    /// numbers in f64 locals
+    integer_locals: HashSet<String>,
+    /// Variables holding strings, booleans or arrays in i32 locals
+    i32_locals: HashSet<String>,
     function_map: HashMap<String, usize>,
     session_map: HashMap<String, SessionInfo>,
     indent_level: usize,
     break_labels: Vec<String>,
     continue_labels: Vec<String>,
+    /// Source labels of the enclosing loops (parallel to `break_labels`)
+    loop_names: Vec<Option<String>>,
+    /// Label attached to the loop that is about to be emitted
+    pending_loop_name: Option<String>,
+    /// First construct that cannot be lowered; fails the whole generation
+    error: Option<WasmCodegenError>,
+    /// Hilfsfunktionen, die der erzeugte Code aufruft; sie folgen auf `$main`
+    helpers: BTreeSet<&'static str>,
 }
 
 /// Session information for WASM generation
@@ -45,24 +66,37 @@ impl WasmCodeGenerator {
             local_counter: 0,
             label_counter: 0,
             variable_map: HashMap::new(),
+            integer_locals: HashSet::new(),
+            i32_locals: HashSet::new(),
             function_map: HashMap::new(),
             session_map: HashMap::new(),
             indent_level: 0,
             break_labels: Vec::new(),
             continue_labels: Vec::new(),
+            loop_names: Vec::new(),
+            pending_loop_name: None,
+            error: None,
+            helpers: BTreeSet::new(),
         }
     }
 
     /// Generate WASM code from AST
-    pub fn generate(&mut self, program: &AstNode) -> String {
+    ///
+    /// Fails with a `WasmCodegenError` when the program uses a construct the
+    /// backend cannot lower correctly.
+    pub fn generate(&mut self, program: &AstNode) -> Result<String, WasmCodegenError> {
         self.output.clear();
+        self.error = None;
         self.local_counter = 0;
         self.label_counter = 0;
         self.variable_map.clear();
+        self.integer_locals.clear();
+        self.i32_locals.clear();
         self.function_map.clear();
         self.session_map.clear();
         self.break_labels.clear();
         self.continue_labels.clear();
+        self.helpers.clear();
 
         self.emit_line("(module");
         self.indent_level += 1;
@@ -87,18 +121,26 @@ impl WasmCodeGenerator {
         if let AstNode::Program(statements) = program {
             self.emit_main_function(statements);
         }
+        self.emit_helpers();
 
         self.indent_level -= 1;
         self.emit_line(")");
 
-        self.output.clone()
+        match self.error.take() {
+            Some(error) => Err(error),
+            None => Ok(self.output.clone()),
+        }
     }
 
     /// Pre-scan for sessions and functions
     fn prescan_declarations(&mut self, statements: &[AstNode]) {
         for stmt in statements {
-            match stmt {
-                AstNode::SessionDeclaration { name, members } => {
+            let declaration = match stmt.unspanned() {
+                AstNode::ExportDeclaration(inner) => inner.as_ref(),
+                other => other,
+            };
+            match declaration {
+                AstNode::SessionDeclaration { name, members, .. } => {
                     let mut session_info = SessionInfo {
                         name: name.clone(),
                         field_count: 0,
@@ -118,6 +160,7 @@ impl WasmCodeGenerator {
                                     .method_indices
                                     .insert(method.name.clone(), func_idx);
                             }
+                            hypnoscript_lexer_parser::ast::SessionMember::Finale(_) => {}
                         }
                     }
 
@@ -139,6 +182,7 @@ impl WasmCodeGenerator {
         self.emit_line(";; Imports");
         self.emit_line("(import \"env\" \"console_log\" (func $console_log (param i32)))");
         self.emit_line("(import \"env\" \"console_log_f64\" (func $console_log_f64 (param f64)))");
+        self.emit_line("(import \"env\" \"console_log_i64\" (func $console_log_i64 (param i64)))");
         self.emit_line(
             "(import \"env\" \"console_log_str\" (func $console_log_str (param i32 i32)))",
         );
@@ -167,16 +211,38 @@ impl WasmCodeGenerator {
     /// Emit a statement
     fn emit_statement(&mut self, stmt: &AstNode) {
         match stmt {
+            AstNode::Spanned { node, .. } | AstNode::ExportDeclaration(node) => {
+                self.emit_statement(node);
+            }
+
             AstNode::VariableDeclaration {
-                name, initializer, ..
+                name,
+                type_annotation,
+                initializer,
+                ..
             } => {
-                let var_idx = self.local_counter;
-                self.variable_map.insert(name.clone(), var_idx);
-                self.local_counter += 1;
+                let is_integer = type_annotation.as_deref() == Some("integer")
+                    || initializer
+                        .as_deref()
+                        .is_some_and(|init| self.is_integer_expression(init));
+                let is_i32 = !is_integer
+                    && initializer
+                        .as_deref()
+                        .is_some_and(|init| self.is_i32_expression(init));
+                let var_idx = self.declare_local(name);
+                if is_integer {
+                    self.integer_locals.insert(name.clone());
+                } else if is_i32 {
+                    self.i32_locals.insert(name.clone());
+                }
 
                 // Emit local declaration at function level (would need restructuring)
                 if let Some(init) = initializer {
-                    self.emit_expression(init);
+                    if is_integer {
+                        self.emit_integer_operand(init);
+                    } else {
+                        self.emit_expression(init);
+                    }
                     self.emit_line(&format!("local.set ${}", var_idx));
                 }
             }
@@ -184,18 +250,21 @@ impl WasmCodeGenerator {
             AstNode::ObserveStatement(expr) => {
                 self.emit_line(";; observe statement");
                 match expr.as_ref() {
-                    AstNode::NumberLiteral(_) => {
-                        self.emit_expression(expr);
-                        self.emit_line("call $console_log_f64");
-                    }
-                    AstNode::StringLiteral(_) => {
-                        self.emit_expression(expr);
-                        self.emit_line("call $console_log");
-                    }
-                    _ => {
-                        self.emit_expression(expr);
-                        self.emit_line("call $console_log_f64");
+                    AstNode::StringInterpolation(parts) => {
+                        // Log each part separately with the import matching its type
+                        for part in parts {
+                            match part {
+                                InterpolationPart::Text(text) => {
+                                    self.emit_expression(&AstNode::StringLiteral(text.clone()));
+                                    self.emit_line("call $console_log");
+                                }
+                                InterpolationPart::Expression(part_expr) => {
+                                    self.emit_log(part_expr);
+                                }
+                            }
+                        }
                     }
+                    other => self.emit_log(other),
                 }
             }
 
@@ -232,6 +301,7 @@ impl WasmCodeGenerator {
                 let continue_label = format!("${}_start", loop_label);
                 self.break_labels.push(break_label.clone());
                 self.continue_labels.push(continue_label.clone());
+                self.loop_names.push(self.pending_loop_name.take());
 
                 self.emit_line(&format!("(block {}", break_label));
                 self.indent_level += 1;
@@ -258,6 +328,7 @@ impl WasmCodeGenerator {
 
                 self.continue_labels.pop();
                 self.break_labels.pop();
+                self.loop_names.pop();
             }
 
             AstNode::LoopStatement {
@@ -276,6 +347,7 @@ impl WasmCodeGenerator {
                 let continue_label = format!("${}_continue", loop_label);
                 self.break_labels.push(break_label.clone());
                 self.continue_labels.push(continue_label.clone());
+                self.loop_names.push(self.pending_loop_name.take());
 
                 self.emit_line(&format!("(block {}", break_label));
                 self.indent_level += 1;
@@ -309,23 +381,139 @@ impl WasmCodeGenerator {
 
                 self.continue_labels.pop();
                 self.break_labels.pop();
+                self.loop_names.pop();
             }
 
-            AstNode::BreakStatement => {
-                self.emit_line(";; break");
-                if let Some(label) = self.break_labels.last() {
-                    self.emit_line(&format!("br {}", label));
+            AstNode::ForEachStatement {
+                key,
+                value,
+                iterable,
+                body,
+                ..
+            } => {
+                let AstNode::RangeExpression {
+                    start,
+                    end,
+                    inclusive,
+                    step,
+                } = iterable.unspanned()
+                else {
+                    if let AstNode::ArrayLiteral(elements) = iterable.unspanned() {
+                        self.emit_array_literal_for_each(key.as_deref(), value, elements, body);
+                    } else {
+                        // Array values, strings and dictionaries have no WASM representation yet
+                        self.unsupported(
+                            "for-each over anything but ranges and array literals".to_string(),
+                        );
+                    }
+                    return;
+                };
+
+                let value_idx = self.declare_local(value);
+                let index_idx = key.as_ref().map(|key| self.declare_local(key));
+                let end_idx = self.declare_local(&format!("{}__end", value));
+                let step_idx = self.declare_local(&format!("{}__step", value));
+
+                self.emit_expression(start);
+                self.emit_line(&format!("local.set ${}", value_idx));
+                self.emit_expression(end);
+                self.emit_line(&format!("local.set ${}", end_idx));
+                match step {
+                    Some(step) => self.emit_expression(step),
+                    None => self.emit_line("f64.const 1"),
+                }
+                self.emit_line(&format!("local.set ${}", step_idx));
+                if let Some(index_idx) = index_idx {
+                    self.emit_line("f64.const 0");
+                    self.emit_line(&format!("local.set ${}", index_idx));
+                }
+
+                let loop_label = self.next_label();
+                let break_label = format!("${}_end", loop_label);
+                let start_label = format!("${}_start", loop_label);
+                let continue_label = format!("${}_continue", loop_label);
+                self.break_labels.push(break_label.clone());
+                self.continue_labels.push(continue_label.clone());
+                self.loop_names.push(self.pending_loop_name.take());
+
+                self.emit_line(&format!("(block {}", break_label));
+                self.indent_level += 1;
+                self.emit_line(&format!("(loop {}", start_label));
+                self.indent_level += 1;
+
+                // Counting up or down depends on the sign of the step
+                let (up, down) = if *inclusive {
+                    ("f64.le", "f64.ge")
                 } else {
-                    self.emit_line(";; warning: break outside loop ignored");
+                    ("f64.lt", "f64.gt")
+                };
+                for comparison in [up, down] {
+                    self.emit_line(&format!("local.get ${}", value_idx));
+                    self.emit_line(&format!("local.get ${}", end_idx));
+                    self.emit_line(comparison);
+                }
+                self.emit_line(&format!("local.get ${}", step_idx));
+                self.emit_line("f64.const 0");
+                self.emit_line("f64.gt");
+                self.emit_line("select");
+                self.emit_line("i32.eqz");
+                self.emit_line(&format!("br_if {}", break_label));
+
+                self.emit_line(&format!("(block {}", continue_label));
+                self.indent_level += 1;
+                for stmt in body {
+                    self.emit_statement(stmt);
+                }
+                self.indent_level -= 1;
+                self.emit_line(")");
+
+                self.emit_line(&format!("local.get ${}", value_idx));
+                self.emit_line(&format!("local.get ${}", step_idx));
+                self.emit_line("f64.add");
+                self.emit_line(&format!("local.set ${}", value_idx));
+                if let Some(index_idx) = index_idx {
+                    self.emit_line(&format!("local.get ${}", index_idx));
+                    self.emit_line("f64.const 1");
+                    self.emit_line("f64.add");
+                    self.emit_line(&format!("local.set ${}", index_idx));
                 }
+                self.emit_line(&format!("br {}", start_label));
+
+                self.indent_level -= 1;
+                self.emit_line(")");
+                self.indent_level -= 1;
+                self.emit_line(")");
+
+                self.continue_labels.pop();
+                self.break_labels.pop();
+                self.loop_names.pop();
             }
 
-            AstNode::ContinueStatement => {
+            AstNode::LabeledStatement { label, body } => {
+                self.pending_loop_name = Some(label.clone());
+                self.emit_statement(body);
+                self.pending_loop_name = None;
+            }
+
+            AstNode::BreakStatement(label) => {
+                self.emit_line(";; break");
+                match self.loop_index(label.as_deref()) {
+                    Some(index) => {
+                        let target = self.break_labels[index].clone();
+                        self.emit_line(&format!("br {}", target));
+                    }
+                    None => self.emit_line(";; warning: break outside loop ignored"),
+                }
+            }
+
+            AstNode::ContinueStatement(label) => {
                 self.emit_line(";; continue");
-                if let Some(label) = self.continue_labels.last() {
-                    self.emit_line(&format!("br {}", label));
-                } else {
-                    self.emit_line(";; warning: continue outside loop ignored");
+                match self.loop_index(label.as_deref()) {
+                    Some(index) => {
+                        let target = self.continue_labels[index].clone();
+                        self.emit_line(&format!("br {}", target));
+                    }
+                    None => self.emit_line(";; warning: continue outside loop ignored"),
                 }
             }
 
@@ -344,7 +532,7 @@ impl WasmCodeGenerator {
                 self.emit_function(name, parameters, body);
             }
 
-            AstNode::SessionDeclaration { name, members } => {
+            AstNode::SessionDeclaration { name, members, .. } => {
                 self.emit_line(&format!(";; Session: {}", name));
                 self.emit_session_methods(name, members);
             }
@@ -365,6 +553,86 @@ impl WasmCodeGenerator {
         }
     }
 
+    /// Emit a for-each over an array literal, unrolled once per element
+    fn emit_array_literal_for_each(
+        &mut self,
+        key: Option<&str>,
+        value: &str,
+        elements: &[AstNode],
+        body: &[AstNode],
+    ) {
+        let value_idx = self.declare_local(value);
+        let index_idx = key.map(|key| self.declare_local(key));
+
+        let loop_label = self.next_label();
+        let break_label = format!("${}_end", loop_label);
+        self.break_labels.push(break_label.clone());
+        self.continue_labels.push(String::new());
+        self.loop_names.push(self.pending_loop_name.take());
+
+        self.emit_line(&format!("(block {}", break_label));
+        self.indent_level += 1;
+        for (index, element) in elements.iter().enumerate() {
+            if matches!(element, AstNode::SpreadElement(_)) {
+                self.unsupported("spread elements in a for-each array literal".to_string());
+                continue;
+            }
+            self.emit_expression(element);
+            self.emit_line(&format!("local.set ${}", value_idx));
+            if let Some(index_idx) = index_idx {
+                self.emit_line(&format!("f64.const {}", index));
+                self.emit_line(&format!("local.set ${}", index_idx));
+            }
+
+            // `sink` jumps to the end of the current element's block
+            let continue_label = format!("${}_continue{}", loop_label, index);
+            if let Some(top) = self.continue_labels.last_mut() {
+                *top = continue_label.clone();
+            }
+            self.emit_line(&format!("(block {}", continue_label));
+            self.indent_level += 1;
+            for stmt in body {
+                self.emit_statement(stmt);
+            }
+            self.indent_level -= 1;
+            self.emit_line(")");
+        }
+        self.indent_level -= 1;
+        self.emit_line(")");
+
+        self.continue_labels.pop();
+        self.break_labels.pop();
+        self.loop_names.pop();
+    }
+
+    /// Record a construct the backend cannot lower (the first one is reported)
+    fn unsupported(&mut self, construct: String) {
+        self.emit_line(&format!(";; Error: {} is not supported in WASM", construct));
+        self.error
+            .get_or_insert(WasmCodegenError::Unsupported(construct));
+    }
+
+    /// Call a helper function and emit its definition with the module
+    fn call_helper(&mut self, name: &'static str) {
+        self.helpers.insert(name);
+        self.emit_line(&format!("call ${}", name));
+    }
+
+    /// Emit the helper functions called by the generated code
+    fn emit_helpers(&mut self) {
+        for name in std::mem::take(&mut self.helpers) {
+            let (signature, body) = helper_source(name);
+            self.emit_line("");
+            self.emit_line(&format!("(func ${} {}", name, signature));
+            self.indent_level += 1;
+            for line in body {
+                self.emit_line(line);
+            }
+            self.indent_level -= 1;
+            self.emit_line(")");
+        }
+    }
+
     /// Emit eine Funktion
     fn emit_function(
         &mut self,
@@ -443,6 +711,14 @@ impl WasmCodeGenerator {
 
     /// Emit an expression
     fn emit_expression(&mut self, expr: &AstNode) {
+        // Integers are computed exactly on i64 and only converted where a
+        // number is expected
+        if self.is_integer_expression(expr) {
+            self.emit_integer_operand(expr);
+            self.emit_line("f64.convert_i64_s");
+            return;
+        }
+
         match expr {
             AstNode::NumberLiteral(n) => {
                 self.emit_line(&format!("f64.const {}", n));
@@ -457,10 +733,39 @@ impl WasmCodeGenerator {
                 ));
             }
 
+            AstNode::StringInterpolation(parts) => {
+                // Evaluate embedded expressions for their side effects; like string
+                // literals, the result is a placeholder holding the static text length
+                let mut template = String::new();
+                let mut text_len = 0;
+                for part in parts {
+                    match part {
+                        InterpolationPart::Text(text) => {
+                            template.push_str(text);
+                            text_len += text.len();
+                        }
+                        InterpolationPart::Expression(part_expr) => {
+                            self.emit_expression(part_expr);
+                            self.emit_line("drop");
+                            template.push_str("${...}");
+                        }
+                    }
+                }
+                self.emit_line(&format!(
+                    "i32.const {} ;; interpolated string: {}",
+                    text_len,
+                    template.escape_default()
+                ));
+            }
+
             AstNode::BooleanLiteral(b) => {
                 self.emit_line(&format!("i32.const {}", if *b { 1 } else { 0 }));
             }
 
+            AstNode::NullLiteral => {
+                self.emit_line("f64.const 0 ;; null");
+            }
+
             AstNode::Identifier(name) => {
                 if let Some(&idx) = self.variable_map.get(name) {
                     self.emit_line(&format!("local.get ${}", idx));
@@ -475,14 +780,26 @@ impl WasmCodeGenerator {
                 operator,
                 right,
             } => {
+                // Two integers compare exactly on i64
+                if let Some(instruction) = integer_comparison(operator)
+                    && self.is_integer_expression(left)
+                    && self.is_integer_expression(right)
+                {
+                    self.emit_integer_operand(left);
+                    self.emit_integer_operand(right);
+                    self.emit_line(instruction);
+                    return;
+                }
+
                 self.emit_expression(left);
                 self.emit_expression(right);
 
+                if let Some(instruction) = arithmetic_instruction(operator) {
+                    self.emit_line(instruction);
+                    return;
+                }
                 match operator.as_str() {
-                    "+" => self.emit_line("f64.add"),
-                    "-" => self.emit_line("f64.sub"),
-                    "*" => self.emit_line("f64.mul"),
-                    "/" => self.emit_line("f64.div"),
+                    "%" => self.call_helper("f64_rem"),
                     ">" | "LookAtTheWatch" => {
                         self.emit_line("f64.gt");
                     }
@@ -536,6 +853,60 @@ impl WasmCodeGenerator {
                 }
             }
 
+            AstNode::CompoundAssignmentExpression {
+                target,
+                operator,
+                value,
+            } => {
+                let Some(idx) = self.local_target(target) else {
+                    return;
+                };
+                self.emit_line(&format!("local.get ${}", idx));
+                if operator == "??" {
+                    // Numeric locals are never null, so `??=` keeps the current value
+                    self.emit_line(";; ??= keeps non-null local");
+                    return;
+                }
+                self.emit_expression(value);
+                match arithmetic_instruction(operator) {
+                    Some(instruction) => self.emit_line(instruction),
+                    None if operator == "%" => self.call_helper("f64_rem"),
+                    None => {
+                        self.unsupported(format!("the compound operator {}=", operator));
+                        return;
+                    }
+                }
+                self.emit_line(&format!("local.tee ${}", idx));
+            }
+
+            AstNode::UpdateExpression {
+                target,
+                operator,
+                prefix,
+            } => {
+                let Some(idx) = self.local_target(target) else {
+                    return;
+                };
+                let instruction = if operator == "++" {
+                    "f64.add"
+                } else {
+                    "f64.sub"
+                };
+                if *prefix {
+                    self.emit_line(&format!("local.get ${}", idx));
+                    self.emit_line("f64.const 1");
+                    self.emit_line(instruction);
+                    self.emit_line(&format!("local.tee ${}", idx));
+                } else {
+                    // Leave the old value on the stack below the update
+                    self.emit_line(&format!("local.get ${}", idx));
+                    self.emit_line(&format!("local.get ${}", idx));
+                    self.emit_line("f64.const 1");
+                    self.emit_line(instruction);
+                    self.emit_line(&format!("local.set ${}", idx));
+                }
+            }
+
             AstNode::CallExpression { callee, arguments } => {
                 // Extract function name from callee
                 let name = if let AstNode::Identifier(n) = callee.as_ref() {
@@ -571,6 +942,221 @@ impl WasmCodeGenerator {
         }
     }
 
+    /// Whether an expression always yields an integer (`42i`, integer
+    /// variables, bitwise results, arithmetic on integers and updates of
+    /// integer variables)
+    fn is_integer_expression(&self, expr: &AstNode) -> bool {
+        match expr.unspanned() {
+            AstNode::IntegerLiteral(_) => true,
+            AstNode::Identifier(name) => self.integer_locals.contains(name),
+            AstNode::AssignmentExpression { target, .. }
+            | AstNode::CompoundAssignmentExpression { target, .. }
+            | AstNode::UpdateExpression { target, .. } => {
+                matches!(target.unspanned(), AstNode::Identifier(name) if self.integer_locals.contains(name))
+            }
+            AstNode::UnaryExpression { operator, operand } => {
+                operator == "~" || (operator == "-" && self.is_integer_expression(operand))
+            }
+            AstNode::BinaryExpression {
+                left,
+                operator,
+                right,
+            } => match operator.as_str() {
+                "&" | "|" | "^" | "<<" | ">>" => true,
+                "+" | "-" | "*" | "/" | "%" => {
+                    self.is_integer_expression(left) && self.is_integer_expression(right)
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Whether an expression yields an i32 (strings, booleans, arrays and
+    /// comparisons)
+    fn is_i32_expression(&self, expr: &AstNode) -> bool {
+        match expr.unspanned() {
+            AstNode::StringLiteral(_)
+            | AstNode::StringInterpolation(_)
+            | AstNode::BooleanLiteral(_)
+            | AstNode::ArrayLiteral(_) => true,
+            AstNode::Identifier(name) => self.i32_locals.contains(name),
+            AstNode::AssignmentExpression { target, .. } => {
+                matches!(target.unspanned(), AstNode::Identifier(name) if self.i32_locals.contains(name))
+            }
+            AstNode::UnaryExpression { operator, .. } => operator == "!",
+            AstNode::BinaryExpression { operator, .. } => {
+                integer_comparison(operator).is_some() || matches!(operator.as_str(), "&&" | "||")
+            }
+            _ => false,
+        }
+    }
+
+    /// Log a value through the import matching its WASM type
+    fn emit_log(&mut self, expr: &AstNode) {
+        if self.is_integer_expression(expr) {
+            self.emit_integer_operand(expr);
+            self.emit_line("call $console_log_i64");
+        } else if self.is_i32_expression(expr) {
+            self.emit_expression(expr);
+            self.emit_line("call $console_log");
+        } else {
+            self.emit_expression(expr);
+            self.emit_line("call $console_log_f64");
+        }
+    }
+
+    /// Emit an expression as an i64 value.
+    ///
+    /// `+`, `-`, `*` and shifts trap on overflow like the native backend, and
+    /// numbers convert only when they are whole.
+    fn emit_integer_operand(&mut self, expr: &AstNode) {
+        if !self.is_integer_expression(expr) {
+            self.emit_expression(expr);
+            self.call_helper("int_from_f64");
+            return;
+        }
+
+        match expr.unspanned() {
+            AstNode::IntegerLiteral(i) => {
+                self.emit_line(&format!("i64.const {}", i));
+            }
+
+            AstNode::Identifier(name) => {
+                let idx = self.variable_map[name];
+                self.emit_line(&format!("local.get ${}", idx));
+            }
+
+            AstNode::UnaryExpression { operator, operand } if operator == "~" => {
+                self.emit_integer_operand(operand);
+                self.emit_line("i64.const -1");
+                self.emit_line("i64.xor");
+            }
+
+            AstNode::UnaryExpression { operand, .. } => {
+                self.emit_line("i64.const 0");
+                self.emit_integer_operand(operand);
+                self.call_helper("int_sub");
+            }
+
+            AstNode::BinaryExpression {
+                left,
+                operator,
+                right,
+            } => {
+                self.emit_integer_operand(left);
+                self.emit_integer_operand(right);
+                self.emit_integer_operator(operator);
+            }
+
+            AstNode::AssignmentExpression { target, value } => {
+                let idx = self.variable_map[integer_target_name(target)];
+                self.emit_integer_operand(value);
+                self.emit_line(&format!("local.tee ${}", idx));
+            }
+
+            AstNode::CompoundAssignmentExpression {
+                target,
+                operator,
+                value,
+            } => {
+                let idx = self.variable_map[integer_target_name(target)];
+                self.emit_line(&format!("local.get ${}", idx));
+                if operator == "??" {
+                    // Integer locals are never null, so `??=` keeps the current value
+                    return;
+                }
+                self.emit_integer_operand(value);
+                self.emit_integer_operator(operator);
+                self.emit_line(&format!("local.tee ${}", idx));
+            }
+
+            AstNode::UpdateExpression {
+                target,
+                operator,
+                prefix,
+            } => {
+                let idx = self.variable_map[integer_target_name(target)];
+                let helper = if operator == "++" {
+                    "int_add"
+                } else {
+                    "int_sub"
+                };
+                self.emit_line(&format!("local.get ${}", idx));
+                if !*prefix {
+                    // Leave the old value on the stack below the update
+                    self.emit_line(&format!("local.get ${}", idx));
+                }
+                self.emit_line("i64.const 1");
+                self.call_helper(helper);
+                if *prefix {
+                    self.emit_line(&format!("local.tee ${}", idx));
+                } else {
+                    self.emit_line(&format!("local.set ${}", idx));
+                }
+            }
+
+            other => unreachable!("not an integer expression: {:?}", other),
+        }
+    }
+
+    /// Emit the i64 instruction or checked helper of an integer operator
+    fn emit_integer_operator(&mut self, operator: &str) {
+        match operator {
+            "+" => self.call_helper("int_add"),
+            "-" => self.call_helper("int_sub"),
+            "*" => self.call_helper("int_mul"),
+            "<<" | ">>" => {
+                self.call_helper("int_shift_amount");
+                self.emit_line(if operator == "<<" {
+                    "i64.shl"
+                } else {
+                    "i64.shr_s"
+                });
+            }
+            _ => match integer_instruction(operator) {
+                Some(instruction) => self.emit_line(instruction),
+                None => self.unsupported(format!("the integer operator {}", operator)),
+            },
+        }
+    }
+
+    /// Allocate a new local for `name`
+    fn declare_local(&mut self, name: &str) -> usize {
+        let idx = self.local_counter;
+        self.integer_locals.remove(name);
+        self.i32_locals.remove(name);
+        self.variable_map.insert(name.to_string(), idx);
+        self.local_counter += 1;
+        idx
+    }
+
+    /// Local index of a compound assignment or update target.
+    ///
+    /// Other targets push a placeholder value and yield `None`.
+    fn local_target(&mut self, target: &AstNode) -> Option<usize> {
+        let local = match target {
+            AstNode::Identifier(name) => self.variable_map.get(name).copied(),
+            _ => None,
+        };
+        if local.is_none() {
+            self.emit_line(";; Note: Assignment target not yet fully supported in WASM");
+            self.emit_line("f64.const 0");
+        }
+        local
+    }
+
+    /// Index of the loop a `snap`/`sink` targets (innermost loop when unlabeled)
+    fn loop_index(&self, label: Option<&str>) -> Option<usize> {
+        match label {
+            Some(label) => self
+                .loop_names
+                .iter()
+                .rposition(|name| name.as_deref() == Some(label)),
+            None => self.loop_names.len().checked_sub(1),
+        }
+    }
+
     /// Emit a line with proper indentation
     fn emit_line(&mut self, line: &str) {
         let indent = "  ".repeat(self.indent_level);
@@ -590,6 +1176,173 @@ impl WasmCodeGenerator {
     }
 }
 
+/// WASM instruction of an arithmetic operator on f64 values
+fn arithmetic_instruction(operator: &str) -> Option<&'static str> {
+    match operator {
+        "+" => Some("f64.add"),
+        "-" => Some("f64.sub"),
+        "*" => Some("f64.mul"),
+        "/" => Some("f64.div"),
+        _ => None,
+    }
+}
+
+/// Signature and body of a helper function
+fn helper_source(name: &str) -> (&'static str, &'static [&'static str]) {
+    match name {
+        // Rest mit dem Vorzeichen des Dividenden: a - b * trunc(a / b)
+        "f64_rem" => (
+            "(param $a f64) (param $b f64) (result f64)",
+            &[
+                "local.get $a",
+                "local.get $b",
+                "local.get $a",
+                "local.get $b",
+                "f64.div",
+                "f64.trunc",
+                "f64.mul",
+                "f64.sub",
+            ],
+        ),
+        // Überlauf, wenn beide Operanden ein anderes Vorzeichen als das Ergebnis haben
+        "int_add" => (
+            "(param $a i64) (param $b i64) (result i64)",
+            &[
+                "(local $r i64)",
+                "local.get $a",
+                "local.get $b",
+                "i64.add",
+                "local.set $r",
+                "local.get $a",
+                "local.get $r",
+                "i64.xor",
+                "local.get $b",
+                "local.get $r",
+                "i64.xor",
+                "i64.and",
+                "i64.const 0",
+                "i64.lt_s",
+                "if",
+                "  unreachable ;; integer overflow",
+                "end",
+                "local.get $r",
+            ],
+        ),
+        // Überlauf, wenn die Vorzeichen der Operanden verschieden sind und
+        // das Ergebnis das Vorzeichen von b hat
+        "int_sub" => (
+            "(param $a i64) (param $b i64) (result i64)",
+            &[
+                "(local $r i64)",
+                "local.get $a",
+                "local.get $b",
+                "i64.sub",
+                "local.set $r",
+                "local.get $a",
+                "local.get $b",
+                "i64.xor",
+                "local.get $a",
+                "local.get $r",
+                "i64.xor",
+                "i64.and",
+                "i64.const 0",
+                "i64.lt_s",
+                "if",
+                "  unreachable ;; integer overflow",
+                "end",
+                "local.get $r",
+            ],
+        ),
+        // Überlauf, wenn r / a nicht b ergibt; MIN * -1 trappt bereits in div_s
+        "int_mul" => (
+            "(param $a i64) (param $b i64) (result i64)",
+            &[
+                "(local $r i64)",
+                "local.get $a",
+                "local.get $b",
+                "i64.mul",
+                "local.set $r",
+                "local.get $a",
+                "i64.const 0",
+                "i64.ne",
+                "if",
+                "  local.get $r",
+                "  local.get $a",
+                "  i64.div_s",
+                "  local.get $b",
+                "  i64.ne",
+                "  if",
+                "    unreachable ;; integer overflow",
+                "  end",
+                "end",
+                "local.get $r",
+            ],
+        ),
+        // WASM rechnet die Verschiebeweite modulo 64; Werte außerhalb 0..63 sind Fehler
+        "int_shift_amount" => (
+            "(param $b i64) (result i64)",
+            &[
+                "local.get $b",
+                "i64.const 63",
+                "i64.gt_u",
+                "if",
+                "  unreachable ;; shift amount outside 0..63",
+                "end",
+                "local.get $b",
+            ],
+        ),
+        // Nur ganze Zahlen werden exakt umgewandelt
+        "int_from_f64" => (
+            "(param $x f64) (result i64)",
+            &[
+                "local.get $x",
+                "f64.trunc",
+                "local.get $x",
+                "f64.ne",
+                "if",
+                "  unreachable ;; not a whole number",
+                "end",
+                "local.get $x",
+                "i64.trunc_f64_s",
+            ],
+        ),
+        _ => unreachable!("unknown WASM helper {}", name),
+    }
+}
+
+/// Name of the integer local an assignment or update writes
+fn integer_target_name(target: &AstNode) -> &str {
+    match target.unspanned() {
+        AstNode::Identifier(name) => name,
+        other => unreachable!("not an integer local: {:?}", other),
+    }
+}
+
+/// WASM instruction comparing two i64 values
+fn integer_comparison(operator: &str) -> Option<&'static str> {
+    match operator {
+        ">" | "LookAtTheWatch" => Some("i64.gt_s"),
+        "<" | "FallUnderMySpell" => Some("i64.lt_s"),
+        ">=" | "DeeplyGreater" => Some("i64.ge_s"),
+        "<=" | "DeeplyLess" => Some("i64.le_s"),
+        "==" | "YouAreFeelingVerySleepy" => Some("i64.eq"),
+        "!=" | "NotSoDeep" => Some("i64.ne"),
+        _ => None,
+    }
+}
+
+/// WASM instruction of an integer operator on i64 values
+fn integer_instruction(operator: &str) -> Option<&'static str> {
+    match operator {
+        "/" => Some("i64.div_s"),
+        "%" => Some("i64.rem_s"),
+        "&" => Some("i64.and"),
+        "|" => Some("i64.or"),
+        "^" => Some("i64.xor"),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -610,7 +1363,7 @@ Focus {
         let ast = parser.parse_program().unwrap();
 
         let mut generator = WasmCodeGenerator::new();
-        let wasm = generator.generate(&ast);
+        let wasm = generator.generate(&ast).unwrap();
 
         assert!(wasm.contains("(module"));
         assert!(wasm.contains("func $main"));
@@ -631,7 +1384,7 @@ Focus {
         let ast = parser.parse_program().unwrap();
 
         let mut generator = WasmCodeGenerator::new();
-        let wasm = generator.generate(&ast);
+        let wasm = generator.generate(&ast).unwrap();
 
         assert!(wasm.contains("f64.add"));
     }
@@ -650,7 +1403,7 @@ Focus {
         let ast = parser.parse_program().unwrap();
 
         let mut generator = WasmCodeGenerator::new();
-        let wasm = generator.generate(&ast);
+        let wasm = generator.generate(&ast).unwrap();
 
         assert!(wasm.contains("(module"));
         assert!(wasm.contains("f64.const 10"));
@@ -670,7 +1423,7 @@ Focus {
         let ast = parser.parse_program().unwrap();
 
         let mut generator = WasmCodeGenerator::new();
-        let wasm = generator.generate(&ast);
+        let wasm = generator.generate(&ast).unwrap();
 
         assert!(wasm.contains("(module"));
         assert!(wasm.contains("f64.add"));
@@ -685,7 +1438,7 @@ Focus {
         let ast = parser.parse_program().unwrap();
 
         let mut generator = WasmCodeGenerator::new();
-        let wasm = generator.generate(&ast);
+        let wasm = generator.generate(&ast).unwrap();
 
         // Prüfe grundlegende WASM-Struktur
         assert!(wasm.starts_with("(module"));
@@ -701,6 +1454,7 @@ Focus {
             ("-", "f64.sub"),
             ("*", "f64.mul"),
             ("/", "f64.div"),
+            ("%", "call $f64_rem"),
         ];
 
         for (op, wasm_op) in operators {
@@ -711,7 +1465,7 @@ Focus {
             let ast = parser.parse_program().unwrap();
 
             let mut generator = WasmCodeGenerator::new();
-            let wasm = generator.generate(&ast);
+            let wasm = generator.generate(&ast).unwrap();
 
             assert!(
                 wasm.contains(wasm_op),
@@ -722,6 +1476,115 @@ Focus {
         }
     }
 
+    #[test]
+    fn test_wasm_compound_assignment_and_update() {
+        let source = r#"
+Focus {
+    induce total: number = 10;
+    total *= 3;
+    total %= 4;
+    total++;
+    induce before: number = total--;
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut generator = WasmCodeGenerator::new();
+        let wasm = generator.generate(&ast).unwrap();
+
+        assert!(wasm.contains("f64.const 3\n    f64.mul\n    local.tee $0"));
+        assert!(wasm.contains("f64.const 4\n    call $f64_rem\n    local.tee $0"));
+        assert!(wasm.contains("(func $f64_rem (param $a f64) (param $b f64) (result f64)"));
+        assert!(!wasm.contains("unsupported compound operator"));
+        assert!(wasm.contains("f64.const 1\n    f64.add\n    local.set $0"));
+        assert!(wasm.contains("f64.const 1\n    f64.sub\n    local.set $0"));
+        assert!(!wasm.contains("not yet fully supported"));
+    }
+
+    #[test]
+    fn test_wasm_for_each_over_range() {
+        let source = r#"
+Focus {
+    induce total: number = 0;
+    loop (induce i, n in 10..=0 step -2) {
+        if (n == 4) { sink; }
+        total += n;
+    }
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut generator = WasmCodeGenerator::new();
+        let wasm = generator.generate(&ast).unwrap();
+
+        // total is $0, n is $1, i is $2, the end bound $3 and the step $4
+        assert!(wasm.contains("f64.const 10\n    local.set $1"));
+        assert!(wasm.contains("f64.const 2\n    f64.neg\n    local.set $4"));
+        assert!(wasm.contains("f64.le"));
+        assert!(wasm.contains("f64.ge"));
+        assert!(wasm.contains("select\n        i32.eqz\n        br_if $label0_end"));
+        assert!(wasm.contains("br $label0_continue"));
+        assert!(wasm.contains("local.get $1\n        local.get $4\n        f64.add"));
+    }
+
+    #[test]
+    fn test_wasm_for_each_over_array_literal() {
+        let source = r#"
+Focus {
+    induce total: number = 0;
+    loop (induce i, n in [4, 5]) {
+        if (n == 4) { sink; }
+        total += n + i;
+    }
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut generator = WasmCodeGenerator::new();
+        let wasm = generator.generate(&ast).unwrap();
+
+        // total is $0, n is $1 and i is $2; the body is emitted once per element
+        assert!(
+            wasm.contains("f64.const 4\n      local.set $1\n      f64.const 0\n      local.set $2")
+        );
+        assert!(
+            wasm.contains("f64.const 5\n      local.set $1\n      f64.const 1\n      local.set $2")
+        );
+        assert!(wasm.contains("br $label0_continue0"));
+        assert!(wasm.contains("br $label0_continue1"));
+    }
+
+    #[test]
+    fn test_wasm_for_each_over_unsupported_iterables_fails() {
+        for iterable in ["\"abc\"", "items", "{ \"a\": 1 }"] {
+            let source = format!(
+                "Focus {{ induce items = [1]; loop (induce c in {}) {{ observe c; }} }} Relax",
+                iterable
+            );
+            let tokens = Lexer::new(&source).lex().unwrap();
+            let ast = Parser::new(tokens).parse_program().unwrap();
+
+            let mut generator = WasmCodeGenerator::new();
+            let error = generator.generate(&ast).unwrap_err();
+            assert!(
+                error
+                    .to_string()
+                    .contains("for-each over anything but ranges"),
+                "{}",
+                error
+            );
+        }
+    }
+
     #[test]
     fn test_wasm_function_declaration() {
         let source = r#"
@@ -736,10 +1599,112 @@ Focus {
         let ast = parser.parse_program().unwrap();
 
         let mut generator = WasmCodeGenerator::new();
-        let wasm = generator.generate(&ast);
+        let wasm = generator.generate(&ast).unwrap();
 
         assert!(wasm.contains("f64.const 10"));
         assert!(wasm.contains("f64.const 20"));
         assert!(wasm.contains("f64.add"));
     }
+
+    #[test]
+    fn test_wasm_string_interpolation() {
+        let source = r#"
+Focus {
+    induce level: number = 3;
+    induce name = "Luna";
+    observe "Depth ${level * 2}!";
+    observe "${"x"} ${name} ${7i} ${level > 2}";
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut generator = WasmCodeGenerator::new();
+        let wasm = generator.generate(&ast).unwrap();
+
+        assert!(wasm.contains(";; string: Depth "));
+        assert!(wasm.contains("f64.mul"));
+        assert!(wasm.contains("f64.mul\n    call $console_log_f64"));
+        assert!(wasm.contains(";; string: !"));
+        assert!(!wasm.contains("not yet fully supported"));
+        // String, integer and boolean parts use the import of their type
+        assert!(wasm.contains("i32.const 1 ;; string: x\n    call $console_log\n"));
+        assert!(wasm.contains("local.get $1\n    call $console_log\n"));
+        assert!(wasm.contains("i64.const 7\n    call $console_log_i64"));
+        assert!(wasm.contains("f64.gt\n    call $console_log\n"));
+    }
+
+    #[test]
+    fn test_wasm_labeled_break_targets_outer_loop() {
+        let source = r#"
+Focus {
+    outer: while (true) {
+        while (true) {
+            snap outer;
+            sink;
+        }
+    }
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut generator = WasmCodeGenerator::new();
+        let wasm = generator.generate(&ast).unwrap();
+
+        assert!(wasm.contains("br $label0_end"), "{}", wasm);
+        assert!(wasm.contains("br $label1_start"), "{}", wasm);
+        assert!(!wasm.contains("br $label1_end"), "{}", wasm);
+    }
+
+    #[test]
+    fn test_wasm_integer_operations() {
+        let source = r#"
+Focus {
+    induce id: integer = 9007199254740993i;
+    induce half = id / 2i;
+    induce ratio: number = 7 / 2;
+    induce mask = ~id & 255;
+    induce next = id + 1i;
+    next *= 3i;
+    observe next << 2i;
+    induce scaled = id * 0.5;
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let mut generator = WasmCodeGenerator::new();
+        let wasm = generator.generate(&ast).unwrap();
+
+        // Integers stay exact in i64 locals
+        assert!(wasm.contains("i64.const 9007199254740993\n    local.set $0"));
+        // Integer division truncates on i64, number division stays f64
+        assert!(wasm.contains("local.get $0\n    i64.const 2\n    i64.div_s\n    local.set $1"));
+        assert!(wasm.contains("f64.const 7\n    f64.const 2\n    f64.div"));
+        assert!(wasm.contains(
+            "i64.const -1\n    i64.xor\n    f64.const 255\n    call $int_from_f64\n    i64.and"
+        ));
+        // `+`, `*` and shifts are checked
+        assert!(
+            wasm.contains("local.get $0\n    i64.const 1\n    call $int_add\n    local.set $4")
+        );
+        assert!(
+            wasm.contains("local.get $4\n    i64.const 3\n    call $int_mul\n    local.tee $4")
+        );
+        assert!(wasm.contains("call $int_shift_amount\n    i64.shl\n    call $console_log_i64"));
+        assert!(wasm.contains("(func $int_add (param $a i64) (param $b i64) (result i64)"));
+        assert!(wasm.contains("(func $int_mul (param $a i64) (param $b i64) (result i64)"));
+        assert!(wasm.contains("unreachable ;; integer overflow"));
+        // Mixing with a number converts to f64
+        assert!(
+            wasm.contains("local.get $0\n    f64.convert_i64_s\n    f64.const 0.5\n    f64.mul")
+        );
+    }
 }
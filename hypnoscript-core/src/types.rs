@@ -6,14 +6,29 @@ use std::fmt;
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum HypnoBaseType {
     Number,
+    /// 64-bit signed integer (`integer`, literals like `42i`)
+    Integer,
     String,
     Boolean,
     Trance,
     Array,
+    Dictionary,
+    Promise,
+    /// Lazy sequence returned by a generator suggestion (`Iterator<T>`)
+    Iterator,
     Object,
     Function,
     Session,
     Record,
+    /// Type parameter of a generic declaration (`T` in `first<T>`)
+    TypeParameter,
+    /// Contract of fields and methods (`interface Plugin { ... }`)
+    Interface,
+    /// Sum type with variants (`enum State { Idle, Running(number) }`)
+    Enum,
+    Null,
+    /// One of several member types (`string | number`, `lucid string`)
+    Union,
     Unknown,
 }
 
@@ -23,9 +38,16 @@ pub struct HypnoType {
     pub base_type: HypnoBaseType,
     pub name: Option<String>,
     pub element_type: Option<Box<HypnoType>>,
+    pub key_type: Option<Box<HypnoType>>,
     pub fields: Option<HashMap<String, HypnoType>>,
     pub parameter_types: Option<Vec<HypnoType>>,
     pub return_type: Option<Box<HypnoType>>,
+    /// Type arguments of an instantiated generic session or record (`Box<number>`)
+    pub type_arguments: Option<Vec<HypnoType>>,
+    /// Member types of a union
+    pub members: Option<Vec<HypnoType>>,
+    /// Source text of a literal type (`"on"`, `42`, `true`); its base type is the primitive
+    pub literal: Option<String>,
 }
 
 impl HypnoType {
@@ -35,9 +57,13 @@ impl HypnoType {
             base_type,
             name,
             element_type: None,
+            key_type: None,
             fields: None,
             parameter_types: None,
             return_type: None,
+            type_arguments: None,
+            members: None,
+            literal: None,
         }
     }
 
@@ -47,9 +73,45 @@ impl HypnoType {
             base_type: HypnoBaseType::Array,
             name: None,
             element_type: Some(Box::new(element_type)),
+            key_type: None,
             fields: None,
             parameter_types: None,
             return_type: None,
+            type_arguments: None,
+            members: None,
+            literal: None,
+        }
+    }
+
+    /// Create a dictionary type (`Dictionary<K, V>`)
+    pub fn create_dictionary(key_type: HypnoType, value_type: HypnoType) -> Self {
+        Self {
+            base_type: HypnoBaseType::Dictionary,
+            name: None,
+            element_type: Some(Box::new(value_type)),
+            key_type: Some(Box::new(key_type)),
+            fields: None,
+            parameter_types: None,
+            return_type: None,
+            type_arguments: None,
+            members: None,
+            literal: None,
+        }
+    }
+
+    /// Create a promise type (`Promise<T>`) resolving to `value_type`
+    pub fn create_promise(value_type: HypnoType) -> Self {
+        Self {
+            base_type: HypnoBaseType::Promise,
+            name: None,
+            element_type: Some(Box::new(value_type)),
+            key_type: None,
+            fields: None,
+            parameter_types: None,
+            return_type: None,
+            type_arguments: None,
+            members: None,
+            literal: None,
         }
     }
 
@@ -59,9 +121,13 @@ impl HypnoType {
             base_type: HypnoBaseType::Record,
             name: Some(name),
             element_type: None,
+            key_type: None,
             fields: Some(fields),
             parameter_types: None,
             return_type: None,
+            type_arguments: None,
+            members: None,
+            literal: None,
         }
     }
 
@@ -71,17 +137,87 @@ impl HypnoType {
             base_type: HypnoBaseType::Function,
             name: None,
             element_type: None,
+            key_type: None,
             fields: None,
             parameter_types: Some(parameter_types),
             return_type: Some(Box::new(return_type)),
+            type_arguments: None,
+            members: None,
+            literal: None,
         }
     }
 
+    /// Create a type parameter of a generic declaration
+    pub fn create_type_parameter(name: String) -> Self {
+        Self::new(HypnoBaseType::TypeParameter, Some(name))
+    }
+
+    /// Create the type of values conforming to an interface
+    pub fn create_interface(name: String) -> Self {
+        Self::new(HypnoBaseType::Interface, Some(name))
+    }
+
+    /// Create the type of values of an enum
+    pub fn create_enum(name: String) -> Self {
+        Self::new(HypnoBaseType::Enum, Some(name))
+    }
+
+    /// Create a union of the given types.
+    ///
+    /// Nested unions are flattened and duplicate members removed; a single
+    /// remaining member is returned as is. Unknown members absorb the union.
+    pub fn create_union(members: Vec<HypnoType>) -> Self {
+        let mut flattened: Vec<HypnoType> = Vec::new();
+        for member in members {
+            let inner = match member.base_type {
+                HypnoBaseType::Union => member.members.unwrap_or_default(),
+                HypnoBaseType::Unknown => return Self::unknown(),
+                _ => vec![member],
+            };
+            for ty in inner {
+                if !flattened.contains(&ty) {
+                    flattened.push(ty);
+                }
+            }
+        }
+
+        if flattened.len() == 1 {
+            return flattened.remove(0);
+        }
+        Self {
+            members: Some(flattened),
+            ..Self::new(HypnoBaseType::Union, None)
+        }
+    }
+
+    /// Create an optional type (`lucid T`), the union of `T` and `null`
+    pub fn create_optional(inner: HypnoType) -> Self {
+        Self::create_union(vec![inner, Self::null()])
+    }
+
+    /// Create a literal type such as `"on"` (`text` is the literal's source text)
+    pub fn create_literal(base_type: HypnoBaseType, text: String) -> Self {
+        Self {
+            literal: Some(text),
+            ..Self::new(base_type, None)
+        }
+    }
+
+    /// Attach the type arguments of a generic instantiation
+    pub fn with_type_arguments(mut self, type_arguments: Vec<HypnoType>) -> Self {
+        self.type_arguments = Some(type_arguments);
+        self
+    }
+
     /// Predefined type constants
     pub fn number() -> Self {
         Self::new(HypnoBaseType::Number, None)
     }
 
+    pub fn integer() -> Self {
+        Self::new(HypnoBaseType::Integer, None)
+    }
+
     pub fn string() -> Self {
         Self::new(HypnoBaseType::String, None)
     }
@@ -94,11 +230,35 @@ impl HypnoType {
         Self::new(HypnoBaseType::Unknown, None)
     }
 
+    pub fn null() -> Self {
+        Self::new(HypnoBaseType::Null, None)
+    }
+
+    /// Create an iterator type (`Iterator<T>`) producing `value_type` values
+    pub fn create_iterator(value_type: HypnoType) -> Self {
+        Self {
+            base_type: HypnoBaseType::Iterator,
+            ..Self::create_promise(value_type)
+        }
+    }
+
     /// Type checking predicates
     pub fn is_array(&self) -> bool {
         self.base_type == HypnoBaseType::Array
     }
 
+    pub fn is_dictionary(&self) -> bool {
+        self.base_type == HypnoBaseType::Dictionary
+    }
+
+    pub fn is_promise(&self) -> bool {
+        self.base_type == HypnoBaseType::Promise
+    }
+
+    pub fn is_iterator(&self) -> bool {
+        self.base_type == HypnoBaseType::Iterator
+    }
+
     pub fn is_record(&self) -> bool {
         self.base_type == HypnoBaseType::Record
     }
@@ -107,27 +267,125 @@ impl HypnoType {
         self.base_type == HypnoBaseType::Function
     }
 
+    pub fn is_type_parameter(&self) -> bool {
+        self.base_type == HypnoBaseType::TypeParameter
+    }
+
+    pub fn is_union(&self) -> bool {
+        self.base_type == HypnoBaseType::Union
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.base_type == HypnoBaseType::Null
+    }
+
+    /// Whether `null` is a possible value of this type
+    pub fn is_nullable(&self) -> bool {
+        self.is_null() || self.union_members().iter().any(HypnoType::is_null)
+    }
+
+    /// Members of a union, or the type itself
+    pub fn union_members(&self) -> Vec<HypnoType> {
+        match &self.members {
+            Some(members) if self.is_union() => members.clone(),
+            _ => vec![self.clone()],
+        }
+    }
+
+    /// The type without its `null` member (`lucid string` becomes `string`)
+    pub fn without_null(&self) -> HypnoType {
+        if !self.is_union() {
+            return self.clone();
+        }
+        let members: Vec<HypnoType> = self
+            .union_members()
+            .into_iter()
+            .filter(|member| !member.is_null())
+            .collect();
+        if members.is_empty() {
+            return Self::null();
+        }
+        Self::create_union(members)
+    }
+
+    /// The type with literal types replaced by their primitive type
+    /// (`"on" | "off"` becomes `string`)
+    pub fn widened(&self) -> HypnoType {
+        if self.literal.is_some() {
+            return Self::new(self.base_type.clone(), None);
+        }
+        if self.is_union() {
+            return Self::create_union(
+                self.union_members()
+                    .iter()
+                    .map(HypnoType::widened)
+                    .collect(),
+            );
+        }
+        self.clone()
+    }
+
     pub fn is_primitive(&self) -> bool {
         matches!(
             self.base_type,
-            HypnoBaseType::Number | HypnoBaseType::String | HypnoBaseType::Boolean
+            HypnoBaseType::Number
+                | HypnoBaseType::Integer
+                | HypnoBaseType::String
+                | HypnoBaseType::Boolean
+        )
+    }
+
+    /// Whether values of this type are numbers (`number` or `integer`)
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self.base_type,
+            HypnoBaseType::Number | HypnoBaseType::Integer
         )
     }
 
     /// Check if this type is compatible with another type
     pub fn is_compatible_with(&self, other: &HypnoType) -> bool {
+        // Every member of a union value must fit; a union accepts any of its members
+        if other.is_union() {
+            return other
+                .union_members()
+                .iter()
+                .all(|member| self.is_compatible_with(member));
+        }
+        if self.is_union() {
+            return self
+                .union_members()
+                .iter()
+                .any(|member| member.is_compatible_with(other));
+        }
+
         if self.base_type != other.base_type {
             return false;
         }
+        // A literal type only accepts the same literal
+        if self.literal.is_some() && self.literal != other.literal {
+            return false;
+        }
 
         match self.base_type {
-            HypnoBaseType::Array => {
+            HypnoBaseType::Array | HypnoBaseType::Promise | HypnoBaseType::Iterator => {
                 if let (Some(elem1), Some(elem2)) = (&self.element_type, &other.element_type) {
                     elem1.is_compatible_with(elem2)
                 } else {
                     false
                 }
             }
+            HypnoBaseType::Dictionary => {
+                match (
+                    (&self.key_type, &self.element_type),
+                    (&other.key_type, &other.element_type),
+                ) {
+                    ((Some(key1), Some(value1)), (Some(key2), Some(value2))) => {
+                        key1.is_compatible_with(key2) && value1.is_compatible_with(value2)
+                    }
+                    _ => false,
+                }
+            }
             HypnoBaseType::Record => {
                 if let (Some(fields1), Some(fields2)) = (&self.fields, &other.fields) {
                     if fields1.len() != fields2.len() {
@@ -165,6 +423,9 @@ impl HypnoType {
                     false
                 }
             }
+            HypnoBaseType::TypeParameter | HypnoBaseType::Interface | HypnoBaseType::Enum => {
+                self.name == other.name
+            }
             _ => true,
         }
     }
@@ -172,7 +433,20 @@ impl HypnoType {
 
 impl fmt::Display for HypnoType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ref literal) = self.literal {
+            return write!(f, "{}", literal);
+        }
+
         match self.base_type {
+            HypnoBaseType::Union => {
+                let members = self
+                    .union_members()
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" | ");
+                write!(f, "{}", members)
+            }
             HypnoBaseType::Array => {
                 if let Some(ref elem) = self.element_type {
                     write!(f, "[{}]", elem)
@@ -180,9 +454,29 @@ impl fmt::Display for HypnoType {
                     write!(f, "Array")
                 }
             }
+            HypnoBaseType::Dictionary => match (&self.key_type, &self.element_type) {
+                (Some(key), Some(value)) => write!(f, "Dictionary<{}, {}>", key, value),
+                _ => write!(f, "Dictionary"),
+            },
+            HypnoBaseType::Promise => {
+                if let Some(ref value) = self.element_type {
+                    write!(f, "Promise<{}>", value)
+                } else {
+                    write!(f, "Promise")
+                }
+            }
+            HypnoBaseType::Iterator => {
+                if let Some(ref value) = self.element_type {
+                    write!(f, "Iterator<{}>", value)
+                } else {
+                    write!(f, "Iterator")
+                }
+            }
             HypnoBaseType::Record => {
                 if let Some(ref name) = self.name {
-                    write!(f, "Record<{}>", name)
+                    write!(f, "Record<{}", name)?;
+                    self.fmt_type_arguments(f)?;
+                    write!(f, ">")
                 } else {
                     write!(f, "Record")
                 }
@@ -207,7 +501,8 @@ impl fmt::Display for HypnoType {
             }
             _ => {
                 if let Some(ref name) = self.name {
-                    write!(f, "{}", name)
+                    write!(f, "{}", name)?;
+                    self.fmt_type_arguments(f)
                 } else {
                     write!(f, "{:?}", self.base_type)
                 }
@@ -216,6 +511,23 @@ impl fmt::Display for HypnoType {
     }
 }
 
+impl HypnoType {
+    /// Write `<A, B>` for instantiated generic types
+    fn fmt_type_arguments(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.type_arguments {
+            Some(arguments) if !arguments.is_empty() => {
+                let arguments = arguments
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "<{}>", arguments)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
 impl std::hash::Hash for HypnoType {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.base_type.hash(state);
@@ -23,6 +23,14 @@ pub enum AstNode {
     EntranceBlock(Vec<AstNode>), // Constructor/setup block
     FinaleBlock(Vec<AstNode>),   // Destructor/cleanup block
 
+    /// Statement annotated with its source location.
+    /// The parser wraps every statement in a block with this node so later
+    /// phases (type checker, interpreter, CLI) can point at the offending code.
+    Spanned {
+        span: Span,
+        node: Box<AstNode>,
+    },
+
     // Declarations
     VariableDeclaration {
         name: String,
@@ -32,6 +40,16 @@ pub enum AstNode {
         storage: VariableStorage,
     },
 
+    /// Declaration unpacking its initializer with a pattern
+    /// Example: induce [first, ...rest] = items;
+    DestructuringDeclaration {
+        pattern: Pattern,
+        type_annotation: Option<String>,
+        initializer: Box<AstNode>,
+        is_constant: bool,
+        storage: VariableStorage,
+    },
+
     /// Anchor statement: saves the current value of a variable for later restoration
     /// Example: anchor savedValue = currentValue;
     AnchorDeclaration {
@@ -39,11 +57,16 @@ pub enum AstNode {
         source: Box<AstNode>,
     },
 
+    /// Suggestion declaration; `mesmerize suggestion` declares an async one
+    /// whose calls return promises
+    /// Example: suggestion first<T>(items: T[]): T { ... }
     FunctionDeclaration {
         name: String,
+        type_parameters: Vec<String>,
         parameters: Vec<Parameter>,
         return_type: Option<String>,
         body: Vec<AstNode>,
+        is_async: bool,
     },
 
     /// Trigger declaration: event handler or callback function
@@ -55,18 +78,53 @@ pub enum AstNode {
         body: Vec<AstNode>,
     },
 
+    /// Session declaration, optionally generic (`session Stack<T> { ... }`)
     SessionDeclaration {
         name: String,
+        type_parameters: Vec<String>,
+        /// Parent session of `session Admin extends User`
+        parent: Option<String>,
+        /// Interfaces of `session Echo implements Plugin`
+        interfaces: Vec<String>,
         members: Vec<SessionMember>,
     },
 
     /// tranceify: User-defined record/struct type
     /// Example: tranceify Person { name: string; age: number; }
+    /// Generic example: tranceify Box<T> { value: T; }
     TranceifyDeclaration {
         name: String,
+        type_parameters: Vec<String>,
         fields: Vec<TranceifyField>,
+        /// Operator methods (`suggestion operator +(other: Money): Money { ... }`)
+        operators: Vec<SessionMethod>,
+    },
+
+    /// interface: Fields and method signatures a session provides
+    /// Example: interface Plugin { name: string; suggestion run(input: string): string; }
+    InterfaceDeclaration {
+        name: String,
+        members: Vec<InterfaceMember>,
+    },
+
+    /// enum: Sum type whose variants may carry payload values
+    /// Example: enum State { Idle, Running(number), Failed(string, number) }
+    EnumDeclaration {
+        name: String,
+        variants: Vec<EnumVariant>,
     },
 
+    /// mindLink: Import exposed declarations from another module
+    /// Example: mindLink { DefaultProfile, SessionProfile } from "./shared/state.hyp";
+    ImportDeclaration {
+        names: Vec<String>,
+        source: String,
+    },
+
+    /// expose: Top-level declaration made available to importing modules
+    /// Example: expose suggestion greet(name: string) { ... }
+    ExportDeclaration(Box<AstNode>),
+
     // Statements
     ExpressionStatement(Box<AstNode>),
 
@@ -111,12 +169,69 @@ pub enum AstNode {
         body: Vec<AstNode>,
     },
 
+    /// For-each loop over arrays, strings, dictionaries, records and ranges
+    /// Example: loop (induce item in items) { ... }
+    /// With two names the first binds the index (arrays, strings, ranges) or the key
+    /// (dictionaries, records): loop (induce key, value in scores) { ... }
+    ForEachStatement {
+        key: Option<String>,
+        value: String,
+        is_constant: bool,
+        iterable: Box<AstNode>,
+        body: Vec<AstNode>,
+    },
+
     /// suspend: Pause without fixed end (infinite loop or wait)
     SuspendStatement,
 
+    /// Loop with a label that `snap`/`sink`/`sinkTo` can target
+    /// Example: outer: loop { ... snap outer; }
+    LabeledStatement {
+        label: String,
+        body: Box<AstNode>,
+    },
+
     ReturnStatement(Option<Box<AstNode>>),
-    BreakStatement,
-    ContinueStatement,
+    /// emerge: Hand a value to the consumer of a generator suggestion and pause
+    /// Example: emerge line;
+    YieldStatement(Box<AstNode>),
+    /// snap [label];
+    BreakStatement(Option<String>),
+    /// sink [label]; or sinkTo label;
+    ContinueStatement(Option<String>),
+
+    /// attempt/recover/finally: Structured error handling
+    /// Example: attempt { ... } recover (err) { observe err.message; } finally { ... }
+    TryStatement {
+        body: Vec<AstNode>,
+        catch_binding: Option<String>,
+        catch_body: Option<Vec<AstNode>>,
+        finally_body: Option<Vec<AstNode>>,
+    },
+
+    /// using: Bind a resource for one block and run its `finale` when the block exits
+    /// Example: using (log = Logger("app.log")) { log.write("ready"); }
+    UsingStatement {
+        name: String,
+        resource: Box<AstNode>,
+        body: Vec<AstNode>,
+    },
+
+    /// assert: Fail unless the condition holds
+    /// Example: assert x == 42, "x must be 42";
+    AssertStatement {
+        condition: Box<AstNode>,
+        message: Option<Box<AstNode>>,
+        /// Source text of the condition (for failure reports)
+        source: String,
+    },
+
+    /// disrupt: Raise an error carrying a message and optional payload
+    /// Example: disrupt "File missing", path;
+    RaiseStatement {
+        message: Box<AstNode>,
+        payload: Option<Box<AstNode>>,
+    },
 
     /// oscillate: Toggle a boolean variable
     /// Example: oscillate myFlag;
@@ -134,8 +249,13 @@ pub enum AstNode {
 
     // Expressions
     NumberLiteral(f64),
+    /// 64-bit integer literal: `42i`
+    IntegerLiteral(i64),
     StringLiteral(String),
+    /// String with embedded expressions: "Hello ${name}"
+    StringInterpolation(Vec<InterpolationPart>),
     BooleanLiteral(bool),
+    NullLiteral,
     Identifier(String),
 
     BinaryExpression {
@@ -161,6 +281,21 @@ pub enum AstNode {
 
     ArrayLiteral(Vec<AstNode>),
 
+    /// Spread of an array into array literal elements or call arguments
+    /// Example: [...a, ...b] or f(...args)
+    SpreadElement(Box<AstNode>),
+
+    /// Call argument passed by parameter name
+    /// Example: greet(name: "Ada")
+    NamedArgument {
+        name: String,
+        value: Box<AstNode>,
+    },
+
+    /// Dictionary literal with entries in source order
+    /// Example: { "name": "Alice", "age": 30 }
+    DictionaryLiteral(Vec<DictionaryEntry>),
+
     IndexExpression {
         object: Box<AstNode>,
         index: Box<AstNode>,
@@ -171,6 +306,31 @@ pub enum AstNode {
         value: Box<AstNode>,
     },
 
+    /// Compound assignment applying a binary operator to the target
+    /// Example: count += 1; (operator "+"), name ??= "guest"; (operator "??")
+    CompoundAssignmentExpression {
+        target: Box<AstNode>,
+        operator: String,
+        value: Box<AstNode>,
+    },
+
+    /// Increment or decrement of a numeric target
+    /// Example: ++count (prefix, yields the new value), count-- (postfix, yields the old value)
+    UpdateExpression {
+        target: Box<AstNode>,
+        operator: String,
+        prefix: bool,
+    },
+
+    /// Numeric range, exclusive (`0..10`) or inclusive (`0..=10`), with an optional step
+    /// Example: 10..0 step -2
+    RangeExpression {
+        start: Box<AstNode>,
+        end: Box<AstNode>,
+        inclusive: bool,
+        step: Option<Box<AstNode>>,
+    },
+
     /// await expression for async operations
     /// Example: await asyncFunction();
     AwaitExpression {
@@ -198,6 +358,14 @@ pub enum AstNode {
         index: Box<AstNode>,
     },
 
+    /// Anonymous suggestion (closure) capturing its defining scope
+    /// Example: suggestion (x) => x * 2
+    FunctionExpression {
+        parameters: Vec<Parameter>,
+        return_type: Option<String>,
+        body: Vec<AstNode>,
+    },
+
     /// Record literal (instance of a tranceify type)
     /// Example: Person { name: "Alice", age: 30 }
     RecordLiteral {
@@ -206,6 +374,32 @@ pub enum AstNode {
     },
 }
 
+/// Source location of a node (1-based lines and columns).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl Span {
+    pub fn new(line: usize, column: usize, end_line: usize, end_column: usize) -> Self {
+        Self {
+            line,
+            column,
+            end_line,
+            end_column,
+        }
+    }
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
 /// Storage location for variable bindings
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum VariableStorage {
@@ -220,6 +414,12 @@ pub enum VariableStorage {
 pub struct Parameter {
     pub name: String,
     pub type_annotation: Option<String>,
+    /// Value used when the argument is omitted (`name: string = "guest"`)
+    pub default_value: Option<Box<AstNode>>,
+    /// Rest parameter collecting all remaining arguments (`...items: number[]`)
+    pub is_rest: bool,
+    /// Pattern unpacking the argument (`[x, y]: number[]`); `name` then holds its source text
+    pub pattern: Option<Pattern>,
 }
 
 impl Parameter {
@@ -227,37 +427,95 @@ impl Parameter {
         Self {
             name,
             type_annotation,
+            default_value: None,
+            is_rest: false,
+            pattern: None,
         }
     }
+
+    /// Unpack the argument with a destructuring pattern
+    pub fn with_pattern(mut self, pattern: Pattern) -> Self {
+        self.pattern = Some(pattern);
+        self
+    }
+
+    /// Set the default value of the parameter
+    pub fn with_default(mut self, default_value: AstNode) -> Self {
+        self.default_value = Some(Box::new(default_value));
+        self
+    }
+
+    /// Mark the parameter as rest parameter
+    pub fn rest(mut self) -> Self {
+        self.is_rest = true;
+        self
+    }
+
+    /// Whether a call may omit this parameter
+    pub fn is_optional(&self) -> bool {
+        self.default_value.is_some() || self.is_rest
+    }
 }
 
 impl AstNode {
+    /// Strip any source-location wrappers and return the underlying node
+    pub fn unspanned(&self) -> &AstNode {
+        match self {
+            AstNode::Spanned { node, .. } => node.unspanned(),
+            other => other,
+        }
+    }
+
+    /// Source location of the node, if the parser recorded one
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            AstNode::Spanned { span, .. } => Some(*span),
+            _ => None,
+        }
+    }
+
     /// Check if the node is an expression
     pub fn is_expression(&self) -> bool {
+        if let AstNode::Spanned { node, .. } = self {
+            return node.is_expression();
+        }
         matches!(
             self,
             AstNode::NumberLiteral(_)
+                | AstNode::IntegerLiteral(_)
                 | AstNode::StringLiteral(_)
+                | AstNode::StringInterpolation(_)
                 | AstNode::BooleanLiteral(_)
+                | AstNode::NullLiteral
                 | AstNode::Identifier(_)
                 | AstNode::BinaryExpression { .. }
                 | AstNode::UnaryExpression { .. }
                 | AstNode::CallExpression { .. }
                 | AstNode::MemberExpression { .. }
                 | AstNode::ArrayLiteral(_)
+                | AstNode::SpreadElement(_)
+                | AstNode::NamedArgument { .. }
+                | AstNode::DictionaryLiteral(_)
                 | AstNode::IndexExpression { .. }
                 | AstNode::AssignmentExpression { .. }
+                | AstNode::CompoundAssignmentExpression { .. }
+                | AstNode::UpdateExpression { .. }
+                | AstNode::RangeExpression { .. }
                 | AstNode::AwaitExpression { .. }
                 | AstNode::NullishCoalescing { .. }
                 | AstNode::OptionalChaining { .. }
                 | AstNode::OptionalIndexing { .. }
                 | AstNode::EntrainExpression { .. }
                 | AstNode::RecordLiteral { .. }
+                | AstNode::FunctionExpression { .. }
         )
     }
 
     /// Check if the node is a statement
     pub fn is_statement(&self) -> bool {
+        if let AstNode::Spanned { node, .. } = self {
+            return node.is_statement();
+        }
         matches!(
             self,
             AstNode::ExpressionStatement(_)
@@ -269,26 +527,191 @@ impl AstNode {
                 | AstNode::DeepFocusStatement { .. }
                 | AstNode::WhileStatement { .. }
                 | AstNode::LoopStatement { .. }
+                | AstNode::ForEachStatement { .. }
                 | AstNode::SuspendStatement
                 | AstNode::ReturnStatement(_)
-                | AstNode::BreakStatement
-                | AstNode::ContinueStatement
+                | AstNode::YieldStatement(_)
+                | AstNode::LabeledStatement { .. }
+                | AstNode::BreakStatement(_)
+                | AstNode::ContinueStatement(_)
                 | AstNode::OscillateStatement { .. }
+                | AstNode::TryStatement { .. }
+                | AstNode::UsingStatement { .. }
+                | AstNode::RaiseStatement { .. }
+                | AstNode::AssertStatement { .. }
         )
     }
 
     /// Check if the node is a declaration
     pub fn is_declaration(&self) -> bool {
+        if let AstNode::Spanned { node, .. } = self {
+            return node.is_declaration();
+        }
         matches!(
             self,
             AstNode::VariableDeclaration { .. }
+                | AstNode::DestructuringDeclaration { .. }
                 | AstNode::AnchorDeclaration { .. }
                 | AstNode::FunctionDeclaration { .. }
                 | AstNode::TriggerDeclaration { .. }
                 | AstNode::SessionDeclaration { .. }
                 | AstNode::TranceifyDeclaration { .. }
+                | AstNode::InterfaceDeclaration { .. }
+                | AstNode::EnumDeclaration { .. }
+                | AstNode::ImportDeclaration { .. }
+                | AstNode::ExportDeclaration(_)
         )
     }
+
+    /// Name introduced by a declaration (`None` for other nodes)
+    pub fn declared_name(&self) -> Option<&str> {
+        match self {
+            AstNode::Spanned { node, .. } | AstNode::ExportDeclaration(node) => {
+                node.declared_name()
+            }
+            AstNode::VariableDeclaration { name, .. }
+            | AstNode::AnchorDeclaration { name, .. }
+            | AstNode::FunctionDeclaration { name, .. }
+            | AstNode::TriggerDeclaration { name, .. }
+            | AstNode::SessionDeclaration { name, .. }
+            | AstNode::TranceifyDeclaration { name, .. }
+            | AstNode::InterfaceDeclaration { name, .. }
+            | AstNode::EnumDeclaration { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Whether the statement `emerge`s a value, making its suggestion a generator.
+    ///
+    /// Nested suggestions and closures are not searched: their `emerge`
+    /// statements belong to them.
+    pub fn contains_yield(&self) -> bool {
+        match self {
+            AstNode::Spanned { node, .. } => node.contains_yield(),
+            AstNode::YieldStatement(_) => true,
+            AstNode::IfStatement {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                is_generator_body(then_branch)
+                    || else_branch.as_deref().is_some_and(is_generator_body)
+            }
+            AstNode::DeepFocusStatement { body, .. }
+            | AstNode::WhileStatement { body, .. }
+            | AstNode::LoopStatement { body, .. }
+            | AstNode::ForEachStatement { body, .. }
+            | AstNode::UsingStatement { body, .. } => is_generator_body(body),
+            AstNode::LabeledStatement { body, .. } => body.contains_yield(),
+            AstNode::TryStatement {
+                body,
+                catch_body,
+                finally_body,
+                ..
+            } => {
+                is_generator_body(body)
+                    || catch_body.as_deref().is_some_and(is_generator_body)
+                    || finally_body.as_deref().is_some_and(is_generator_body)
+            }
+            _ => false,
+        }
+    }
+
+    /// Operand of the `await` a statement consists of: `await x;`,
+    /// `induce v = await x;`, `v = await x;` and `awaken await x;`.
+    ///
+    /// A `mesmerize` call is suspended at these awaits and completes the
+    /// statement with the settled value once it resumes.
+    pub fn suspending_await(&self) -> Option<&AstNode> {
+        let awaited = match self {
+            AstNode::Spanned { node, .. } => return node.suspending_await(),
+            AstNode::ExpressionStatement(expr) => match expr.unspanned() {
+                AstNode::AssignmentExpression { target, value }
+                    if matches!(target.unspanned(), AstNode::Identifier(_)) =>
+                {
+                    value
+                }
+                _ => expr,
+            },
+            AstNode::VariableDeclaration {
+                initializer: Some(initializer),
+                ..
+            }
+            | AstNode::DestructuringDeclaration { initializer, .. } => initializer,
+            AstNode::ReturnStatement(Some(value)) => value,
+            _ => return None,
+        };
+        match awaited.unspanned() {
+            AstNode::AwaitExpression { expression } => Some(expression),
+            _ => None,
+        }
+    }
+
+    /// Whether a `mesmerize` call can be suspended inside the statement: it
+    /// is a [suspending await](AstNode::suspending_await) or control flow
+    /// containing one.
+    ///
+    /// `attempt` and `using` blocks are not searched; awaits inside them and
+    /// inside larger expressions wait without suspending the call.
+    pub fn contains_await(&self) -> bool {
+        match self {
+            AstNode::Spanned { node, .. } => node.contains_await(),
+            AstNode::IfStatement {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                then_branch.iter().any(AstNode::contains_await)
+                    || else_branch
+                        .as_deref()
+                        .is_some_and(|branch| branch.iter().any(AstNode::contains_await))
+            }
+            AstNode::DeepFocusStatement { body, .. }
+            | AstNode::WhileStatement { body, .. }
+            | AstNode::LoopStatement { body, .. }
+            | AstNode::ForEachStatement { body, .. } => body.iter().any(AstNode::contains_await),
+            AstNode::LabeledStatement { body, .. } => body.contains_await(),
+            other => other.suspending_await().is_some(),
+        }
+    }
+}
+
+/// Whether a suggestion body `emerge`s values (the suggestion is a generator)
+pub fn is_generator_body(body: &[AstNode]) -> bool {
+    body.iter().any(AstNode::contains_yield)
+}
+
+/// Method name of an operator overload (`operator+`)
+pub fn operator_method_name(operator: &str) -> String {
+    format!("operator{}", operator)
+}
+
+/// Operator overloaded by a method (`operator+` → `+`); `None` for other methods
+pub fn overloaded_operator(method_name: &str) -> Option<&'static str> {
+    let operator = method_name.strip_prefix("operator")?;
+    overloadable_operator(operator).filter(|symbol| *symbol == operator)
+}
+
+/// Symbol of an operator sessions and tranceify types can overload.
+///
+/// Hypnotic synonyms resolve to their symbol (`lookAtTheWatch` → `>`);
+/// `None` for operators that cannot be overloaded.
+pub fn overloadable_operator(operator: &str) -> Option<&'static str> {
+    let symbol = match operator.to_ascii_lowercase().as_str() {
+        "+" => "+",
+        "-" => "-",
+        "*" => "*",
+        "/" => "/",
+        "%" => "%",
+        "==" | "youarefeelingverysleepy" => "==",
+        "!=" | "youcannotresist" | "notsodeep" => "!=",
+        ">" | "lookatthewatch" => ">",
+        "<" | "fallundermyspell" => "<",
+        ">=" | "deeplygreater" | "youreyesaregettingheavy" => ">=",
+        "<=" | "deeplyless" | "goingdeeper" => "<=",
+        _ => return None,
+    };
+    Some(symbol)
 }
 
 /// Visibility for session members
@@ -303,6 +726,8 @@ pub enum SessionVisibility {
 pub enum SessionMember {
     Field(SessionField),
     Method(SessionMethod),
+    /// `finale { ... }`: runs once when the instance is released
+    Finale(Vec<AstNode>),
 }
 
 /// Session field definition within the AST
@@ -319,12 +744,38 @@ pub struct SessionField {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SessionMethod {
     pub name: String,
+    /// Type parameters of a generic method (`suggestion map<U>(...)`)
+    pub type_parameters: Vec<String>,
     pub parameters: Vec<Parameter>,
     pub return_type: Option<String>,
     pub body: Vec<AstNode>,
     pub visibility: SessionVisibility,
     pub is_static: bool,
     pub is_constructor: bool,
+    /// Declared with `mesmerize` (calls return promises)
+    pub is_async: bool,
+}
+
+/// Members that may appear inside an interface declaration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum InterfaceMember {
+    Field(TranceifyField),
+    Method(InterfaceMethod),
+}
+
+/// Method signature required by an interface
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InterfaceMethod {
+    pub name: String,
+    pub parameters: Vec<Parameter>,
+    pub return_type: Option<String>,
+}
+
+/// Variant of an enum declaration with the types of its payload values
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnumVariant {
+    pub name: String,
+    pub payload: Vec<String>,
 }
 
 /// Pattern for matching in entrain expressions
@@ -339,7 +790,8 @@ pub enum Pattern {
         name: Option<String>,
         type_annotation: String,
     },
-    /// Record destructuring pattern (e.g., when HypnoGuest { name, isInTrance: true })
+    /// Record destructuring pattern (e.g., when HypnoGuest { name, isInTrance: true });
+    /// an empty `type_name` (`{ name, age }`) matches any record or dictionary
     Record {
         type_name: String,
         fields: Vec<RecordFieldPattern>,
@@ -349,6 +801,71 @@ pub enum Pattern {
         elements: Vec<Pattern>,
         rest: Option<String>,
     },
+    /// Enum variant pattern (e.g., when State.Running(speed), when State.Idle)
+    Variant {
+        enum_name: String,
+        variant: String,
+        payload: Vec<Pattern>,
+    },
+}
+
+impl std::fmt::Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pattern::Literal(literal) => match literal.unspanned() {
+                AstNode::NumberLiteral(value) => write!(f, "{}", value),
+                AstNode::IntegerLiteral(value) => write!(f, "{}i", value),
+                AstNode::StringLiteral(value) => write!(f, "{:?}", value),
+                AstNode::BooleanLiteral(value) => write!(f, "{}", value),
+                AstNode::NullLiteral => write!(f, "null"),
+                _ => write!(f, "<literal>"),
+            },
+            Pattern::Identifier(name) => write!(f, "{}", name),
+            Pattern::Typed {
+                name: Some(name),
+                type_annotation,
+            } => write!(f, "{}: {}", name, type_annotation),
+            Pattern::Typed {
+                name: None,
+                type_annotation,
+            } => write!(f, "{}", type_annotation),
+            Pattern::Record { type_name, fields } => {
+                if !type_name.is_empty() {
+                    write!(f, "{} ", type_name)?;
+                }
+                write!(f, "{{ ")?;
+                for (index, field) in fields.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    match &field.pattern {
+                        Some(pattern) => write!(f, "{}: {}", field.name, pattern)?,
+                        None => write!(f, "{}", field.name)?,
+                    }
+                }
+                write!(f, " }}")
+            }
+            Pattern::Array { elements, rest } => {
+                let mut parts: Vec<String> = elements.iter().map(Pattern::to_string).collect();
+                if let Some(rest) = rest {
+                    parts.push(format!("...{}", rest));
+                }
+                write!(f, "[{}]", parts.join(", "))
+            }
+            Pattern::Variant {
+                enum_name,
+                variant,
+                payload,
+            } => {
+                write!(f, "{}.{}", enum_name, variant)?;
+                if !payload.is_empty() {
+                    let parts: Vec<String> = payload.iter().map(Pattern::to_string).collect();
+                    write!(f, "({})", parts.join(", "))?;
+                }
+                Ok(())
+            }
+        }
+    }
 }
 
 /// Field pattern in record destructuring
@@ -379,3 +896,19 @@ pub struct RecordFieldInit {
     pub name: String,
     pub value: Box<AstNode>,
 }
+
+/// Key-value entry in a dictionary literal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DictionaryEntry {
+    pub key: Box<AstNode>,
+    pub value: Box<AstNode>,
+}
+
+/// Segment of an interpolated string
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum InterpolationPart {
+    /// Literal text between interpolations
+    Text(String),
+    /// Embedded `${...}` expression
+    Expression(Box<AstNode>),
+}
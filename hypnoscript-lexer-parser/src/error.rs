@@ -0,0 +1,103 @@
+use crate::token::{Token, TokenType};
+use std::fmt;
+
+/// Structured parse error with source position
+///
+/// Produced by [`Parser`](crate::Parser) whenever the token stream does not
+/// match the grammar. Carries enough information for tooling to point at the
+/// offending code instead of dumping a raw token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// Human readable description of the problem
+    pub message: String,
+    /// Source file the tokens came from (set by the caller, if known)
+    pub file: Option<String>,
+    /// 1-based line of the offending token
+    pub line: usize,
+    /// 1-based column of the offending token
+    pub column: usize,
+    /// Token type the parser was looking for, if a specific one was required
+    pub expected: Option<TokenType>,
+    /// Token that was actually encountered
+    pub found: Option<Token>,
+}
+
+impl ParseError {
+    /// Create an error located at the given token
+    pub fn at(token: &Token, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            file: None,
+            line: token.line,
+            column: token.column,
+            expected: None,
+            found: Some(token.clone()),
+        }
+    }
+
+    /// Record which token type was expected
+    pub fn with_expected(mut self, expected: TokenType) -> Self {
+        self.expected = Some(expected);
+        self
+    }
+
+    /// Attach the source file name
+    pub fn with_file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:{}:{}: ", file, self.line, self.column)?;
+        }
+
+        write!(f, "{}", self.message)?;
+
+        if let Some(found) = &self.found {
+            if found.token_type == TokenType::Eof {
+                write!(f, ", found end of file")?;
+            } else {
+                write!(f, ", found '{}'", found.lexeme)?;
+            }
+        }
+
+        if self.file.is_none() {
+            write!(f, " at line {}, column {}", self.line, self.column)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_with_file() {
+        let token = Token::new(TokenType::Identifier, "oops".to_string(), 3, 7);
+        let error = ParseError::at(&token, "Expected ';' after expression")
+            .with_expected(TokenType::Semicolon)
+            .with_file("demo.hyp");
+        assert_eq!(
+            error.to_string(),
+            "demo.hyp:3:7: Expected ';' after expression, found 'oops'"
+        );
+        assert_eq!(error.expected, Some(TokenType::Semicolon));
+    }
+
+    #[test]
+    fn test_display_without_file() {
+        let token = Token::new(TokenType::Eof, String::new(), 5, 1);
+        let error = ParseError::at(&token, "Expected '}' after block");
+        assert_eq!(
+            error.to_string(),
+            "Expected '}' after block, found end of file at line 5, column 1"
+        );
+    }
+}
@@ -29,6 +29,21 @@ impl Lexer {
                 break;
             }
 
+            self.lex_token(&mut tokens)?;
+        }
+
+        tokens.push(Token::new(
+            TokenType::Eof,
+            "".to_string(),
+            self.line,
+            self.column,
+        ));
+        Ok(tokens)
+    }
+
+    /// Lex a single token starting at the current (non-whitespace) position
+    fn lex_token(&mut self, tokens: &mut Vec<Token>) -> Result<(), String> {
+        {
             let start_column = self.column;
             let c = self.advance();
 
@@ -38,12 +53,22 @@ impl Lexer {
                 tokens.push(Token::new(token_type, lexeme, self.line, start_column));
             } else if c.is_numeric() {
                 let number = self.read_number(c);
-                tokens.push(Token::new(
-                    TokenType::NumberLiteral,
-                    number,
-                    self.line,
-                    start_column,
-                ));
+                // An `i` suffix marks an integer literal (`42i`)
+                let token_type = if self.peek() == 'i'
+                    && !(self.peek_next().is_alphanumeric() || self.peek_next() == '_')
+                {
+                    self.advance();
+                    if number.contains('.') {
+                        return Err(format!(
+                            "Integer literal '{}i' cannot have a fractional part at line {}, column {}",
+                            number, self.line, start_column
+                        ));
+                    }
+                    TokenType::IntegerLiteral
+                } else {
+                    TokenType::NumberLiteral
+                };
+                tokens.push(Token::new(token_type, number, self.line, start_column));
             } else {
                 match c {
                     '=' => {
@@ -70,29 +95,83 @@ impl Lexer {
                             ));
                         }
                     }
-                    '+' => tokens.push(Token::new(
-                        TokenType::Plus,
-                        "+".to_string(),
-                        self.line,
-                        start_column,
-                    )),
-                    '-' => tokens.push(Token::new(
-                        TokenType::Minus,
-                        "-".to_string(),
-                        self.line,
-                        start_column,
-                    )),
-                    '*' => tokens.push(Token::new(
-                        TokenType::Asterisk,
-                        "*".to_string(),
-                        self.line,
-                        start_column,
-                    )),
+                    '+' => {
+                        if self.match_char('+') {
+                            tokens.push(Token::new(
+                                TokenType::PlusPlus,
+                                "++".to_string(),
+                                self.line,
+                                start_column,
+                            ));
+                        } else if self.match_char('=') {
+                            tokens.push(Token::new(
+                                TokenType::PlusEquals,
+                                "+=".to_string(),
+                                self.line,
+                                start_column,
+                            ));
+                        } else {
+                            tokens.push(Token::new(
+                                TokenType::Plus,
+                                "+".to_string(),
+                                self.line,
+                                start_column,
+                            ));
+                        }
+                    }
+                    '-' => {
+                        if self.match_char('-') {
+                            tokens.push(Token::new(
+                                TokenType::MinusMinus,
+                                "--".to_string(),
+                                self.line,
+                                start_column,
+                            ));
+                        } else if self.match_char('=') {
+                            tokens.push(Token::new(
+                                TokenType::MinusEquals,
+                                "-=".to_string(),
+                                self.line,
+                                start_column,
+                            ));
+                        } else {
+                            tokens.push(Token::new(
+                                TokenType::Minus,
+                                "-".to_string(),
+                                self.line,
+                                start_column,
+                            ));
+                        }
+                    }
+                    '*' => {
+                        if self.match_char('=') {
+                            tokens.push(Token::new(
+                                TokenType::AsteriskEquals,
+                                "*=".to_string(),
+                                self.line,
+                                start_column,
+                            ));
+                        } else {
+                            tokens.push(Token::new(
+                                TokenType::Asterisk,
+                                "*".to_string(),
+                                self.line,
+                                start_column,
+                            ));
+                        }
+                    }
                     '/' => {
                         if self.match_char('/') {
                             self.skip_line_comment();
                         } else if self.match_char('*') {
                             self.skip_block_comment();
+                        } else if self.match_char('=') {
+                            tokens.push(Token::new(
+                                TokenType::SlashEquals,
+                                "/=".to_string(),
+                                self.line,
+                                start_column,
+                            ));
                         } else {
                             tokens.push(Token::new(
                                 TokenType::Slash,
@@ -102,12 +181,23 @@ impl Lexer {
                             ));
                         }
                     }
-                    '%' => tokens.push(Token::new(
-                        TokenType::Percent,
-                        "%".to_string(),
-                        self.line,
-                        start_column,
-                    )),
+                    '%' => {
+                        if self.match_char('=') {
+                            tokens.push(Token::new(
+                                TokenType::PercentEquals,
+                                "%=".to_string(),
+                                self.line,
+                                start_column,
+                            ));
+                        } else {
+                            tokens.push(Token::new(
+                                TokenType::Percent,
+                                "%".to_string(),
+                                self.line,
+                                start_column,
+                            ));
+                        }
+                    }
                     '>' => {
                         if self.match_char('=') {
                             tokens.push(Token::new(
@@ -133,6 +223,13 @@ impl Lexer {
                                 self.line,
                                 start_column,
                             ));
+                        } else if self.match_char('<') {
+                            tokens.push(Token::new(
+                                TokenType::LessLess,
+                                "<<".to_string(),
+                                self.line,
+                                start_column,
+                            ));
                         } else {
                             tokens.push(Token::new(
                                 TokenType::Less,
@@ -193,14 +290,35 @@ impl Lexer {
                             ));
                         }
                     }
+                    '^' => tokens.push(Token::new(
+                        TokenType::Caret,
+                        "^".to_string(),
+                        self.line,
+                        start_column,
+                    )),
+                    '~' => tokens.push(Token::new(
+                        TokenType::Tilde,
+                        "~".to_string(),
+                        self.line,
+                        start_column,
+                    )),
                     '?' => {
                         if self.match_char('?') {
-                            tokens.push(Token::new(
-                                TokenType::QuestionQuestion,
-                                "??".to_string(),
-                                self.line,
-                                start_column,
-                            ));
+                            if self.match_char('=') {
+                                tokens.push(Token::new(
+                                    TokenType::QuestionQuestionEquals,
+                                    "??=".to_string(),
+                                    self.line,
+                                    start_column,
+                                ));
+                            } else {
+                                tokens.push(Token::new(
+                                    TokenType::QuestionQuestion,
+                                    "??".to_string(),
+                                    self.line,
+                                    start_column,
+                                ));
+                            }
                         } else if self.match_char('.') {
                             tokens.push(Token::new(
                                 TokenType::QuestionDot,
@@ -272,10 +390,17 @@ impl Lexer {
                         start_column,
                     )),
                     '.' => {
-                        if self.match_char('.') && self.match_char('.') {
+                        if self.match_char('.') {
+                            let (token_type, lexeme) = if self.match_char('.') {
+                                (TokenType::DotDotDot, "...")
+                            } else if self.match_char('=') {
+                                (TokenType::DotDotEquals, "..=")
+                            } else {
+                                (TokenType::DotDot, "..")
+                            };
                             tokens.push(Token::new(
-                                TokenType::DotDotDot,
-                                "...".to_string(),
+                                token_type,
+                                lexeme.to_string(),
                                 self.line,
                                 start_column,
                             ));
@@ -289,13 +414,7 @@ impl Lexer {
                         }
                     }
                     '"' => {
-                        let string_val = self.read_string()?;
-                        tokens.push(Token::new(
-                            TokenType::StringLiteral,
-                            string_val,
-                            self.line,
-                            start_column,
-                        ));
+                        self.lex_string(tokens, start_column)?;
                     }
                     _ => {
                         return Err(format!(
@@ -307,13 +426,7 @@ impl Lexer {
             }
         }
 
-        tokens.push(Token::new(
-            TokenType::Eof,
-            "".to_string(),
-            self.line,
-            self.column,
-        ));
-        Ok(tokens)
+        Ok(())
     }
 
     fn is_at_end(&self) -> bool {
@@ -335,6 +448,14 @@ impl Lexer {
         }
     }
 
+    fn peek_next(&self) -> char {
+        if self.pos + 1 >= self.source.len() {
+            '\0'
+        } else {
+            self.source[self.pos + 1]
+        }
+    }
+
     fn match_char(&mut self, expected: char) -> bool {
         if self.is_at_end() || self.peek() != expected {
             false
@@ -406,7 +527,9 @@ impl Lexer {
 
         while !self.is_at_end() {
             let c = self.peek();
-            if c.is_numeric() || c == '.' {
+            // A '.' only continues the number when digits follow, so `0..10` is a range
+            let is_fraction = c == '.' && !number.contains('.') && self.peek_next().is_numeric();
+            if c.is_numeric() || is_fraction {
                 number.push(c);
                 self.advance();
             } else {
@@ -417,14 +540,48 @@ impl Lexer {
         number
     }
 
-    fn read_string(&mut self) -> Result<String, String> {
+    /// Lex a string literal (the opening quote has been consumed).
+    ///
+    /// Plain strings become a single `StringLiteral` token. Strings containing
+    /// `${...}` are split into a `StringTemplateHead`, the tokens of each
+    /// embedded expression, `StringTemplateMiddle` parts between them and a
+    /// closing `StringTemplateTail`. Use `\${` for a literal `${`.
+    fn lex_string(&mut self, tokens: &mut Vec<Token>, start_column: usize) -> Result<(), String> {
         let mut string = String::new();
+        let mut part_line = self.line;
+        let mut part_column = start_column;
+        let mut in_template = false;
 
         while !self.is_at_end() {
             let c = self.peek();
             if c == '"' {
                 self.advance();
-                return Ok(string);
+                let token_type = if in_template {
+                    TokenType::StringTemplateTail
+                } else {
+                    TokenType::StringLiteral
+                };
+                tokens.push(Token::new(token_type, string, part_line, part_column));
+                return Ok(());
+            } else if c == '$' && self.peek_next() == '{' {
+                let token_type = if in_template {
+                    TokenType::StringTemplateMiddle
+                } else {
+                    TokenType::StringTemplateHead
+                };
+                tokens.push(Token::new(
+                    token_type,
+                    std::mem::take(&mut string),
+                    part_line,
+                    part_column,
+                ));
+                self.advance();
+                self.advance();
+                in_template = true;
+
+                self.lex_interpolation(tokens)?;
+                part_line = self.line;
+                part_column = self.column - 1;
             } else if c == '\\' {
                 self.advance();
                 if !self.is_at_end() {
@@ -451,6 +608,33 @@ impl Lexer {
         Err(format!("Unterminated string at line {}", self.line))
     }
 
+    /// Lex the tokens of an embedded `${...}` expression up to its closing brace
+    fn lex_interpolation(&mut self, tokens: &mut Vec<Token>) -> Result<(), String> {
+        let mut depth = 0usize;
+
+        loop {
+            self.skip_whitespace();
+            if self.is_at_end() {
+                return Err(format!(
+                    "Unterminated string interpolation at line {}",
+                    self.line
+                ));
+            }
+
+            match self.peek() {
+                '}' if depth == 0 => {
+                    self.advance();
+                    return Ok(());
+                }
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+
+            self.lex_token(tokens)?;
+        }
+    }
+
     fn keyword_or_identifier(&self, s: &str) -> (TokenType, String) {
         if let Some(definition) = TokenType::keyword_definition(s) {
             (definition.token, definition.canonical_lexeme.to_string())
@@ -480,6 +664,92 @@ mod tests {
         assert_eq!(tokens[0].lexeme, "Hello, World!");
     }
 
+    #[test]
+    fn test_compound_assignment_and_update_operators() {
+        let mut lexer = Lexer::new("a += 1; b -= 2; c *= 3; d /= 4; e %= 5; f ??= 6; g++; --h;");
+        let tokens = lexer.lex().unwrap();
+        let operators: Vec<TokenType> = tokens
+            .into_iter()
+            .map(|token| token.token_type)
+            .filter(TokenType::is_operator)
+            .collect();
+        assert_eq!(
+            operators,
+            vec![
+                TokenType::PlusEquals,
+                TokenType::MinusEquals,
+                TokenType::AsteriskEquals,
+                TokenType::SlashEquals,
+                TokenType::PercentEquals,
+                TokenType::QuestionQuestionEquals,
+                TokenType::PlusPlus,
+                TokenType::MinusMinus,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_range_operators() {
+        let mut lexer = Lexer::new("0..10 1..=2.5 [...xs]");
+        let tokens = lexer.lex().unwrap();
+        let kinds: Vec<(TokenType, &str)> = tokens
+            .iter()
+            .map(|token| (token.token_type, token.lexeme.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (TokenType::NumberLiteral, "0"),
+                (TokenType::DotDot, ".."),
+                (TokenType::NumberLiteral, "10"),
+                (TokenType::NumberLiteral, "1"),
+                (TokenType::DotDotEquals, "..="),
+                (TokenType::NumberLiteral, "2.5"),
+                (TokenType::LBracket, "["),
+                (TokenType::DotDotDot, "..."),
+                (TokenType::Identifier, "xs"),
+                (TokenType::RBracket, "]"),
+                (TokenType::Eof, ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_integer_literals_and_bitwise_operators() {
+        let mut lexer = Lexer::new("42i & 3 | ~x ^ 1 << 2 >> y");
+        let tokens = lexer.lex().unwrap();
+        let kinds: Vec<(TokenType, &str)> = tokens
+            .iter()
+            .map(|token| (token.token_type, token.lexeme.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (TokenType::IntegerLiteral, "42"),
+                (TokenType::Ampersand, "&"),
+                (TokenType::NumberLiteral, "3"),
+                (TokenType::Pipe, "|"),
+                (TokenType::Tilde, "~"),
+                (TokenType::Identifier, "x"),
+                (TokenType::Caret, "^"),
+                (TokenType::NumberLiteral, "1"),
+                (TokenType::LessLess, "<<"),
+                (TokenType::NumberLiteral, "2"),
+                (TokenType::Greater, ">"),
+                (TokenType::Greater, ">"),
+                (TokenType::Identifier, "y"),
+                (TokenType::Eof, ""),
+            ]
+        );
+
+        // Only a standalone `i` is a suffix, and it needs a whole number
+        let tokens = Lexer::new("3in").lex().unwrap();
+        assert_eq!(tokens[0].token_type, TokenType::NumberLiteral);
+        assert_eq!(tokens[1].lexeme, "in");
+        let error = Lexer::new("1.5i").lex().unwrap_err();
+        assert!(error.contains("cannot have a fractional part"));
+    }
+
     #[test]
     fn test_operator_synonym_tokenization() {
         let mut lexer = Lexer::new("if (a youAreFeelingVerySleepy b) { }");
@@ -490,4 +760,35 @@ mod tests {
             .expect("synonym token not found");
         assert_eq!(synonym.lexeme, "youAreFeelingVerySleepy");
     }
+
+    #[test]
+    fn test_string_interpolation_parts() {
+        let mut lexer = Lexer::new(r#""Hello ${name}, depth ${level * 2}!""#);
+        let tokens = lexer.lex().unwrap();
+        let kinds: Vec<(TokenType, &str)> = tokens
+            .iter()
+            .map(|token| (token.token_type, token.lexeme.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (TokenType::StringTemplateHead, "Hello "),
+                (TokenType::Identifier, "name"),
+                (TokenType::StringTemplateMiddle, ", depth "),
+                (TokenType::Identifier, "level"),
+                (TokenType::Asterisk, "*"),
+                (TokenType::NumberLiteral, "2"),
+                (TokenType::StringTemplateTail, "!"),
+                (TokenType::Eof, ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_escaped_interpolation_stays_literal() {
+        let mut lexer = Lexer::new(r#""cost: \${price}""#);
+        let tokens = lexer.lex().unwrap();
+        assert_eq!(tokens[0].token_type, TokenType::StringLiteral);
+        assert_eq!(tokens[0].lexeme, "cost: ${price}");
+    }
 }
@@ -3,11 +3,14 @@
 //! This module provides the lexer and parser for the HypnoScript language.
 
 pub mod ast;
+pub mod error;
 pub mod lexer;
 pub mod parser;
 pub mod token;
 
 // Re-export commonly used types
+pub use ast::Span;
+pub use error::ParseError;
 pub use lexer::Lexer;
 pub use parser::Parser;
 pub use token::{Token, TokenType};
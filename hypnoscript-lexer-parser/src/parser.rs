@@ -1,7 +1,10 @@
 use crate::ast::{
-    AstNode, EntrainCase, Parameter, Pattern, RecordFieldInit, RecordFieldPattern, SessionField,
-    SessionMember, SessionMethod, SessionVisibility, TranceifyField, VariableStorage,
+    AstNode, DictionaryEntry, EntrainCase, EnumVariant, InterfaceMember, InterfaceMethod,
+    InterpolationPart, Parameter, Pattern, RecordFieldInit, RecordFieldPattern, SessionField,
+    SessionMember, SessionMethod, SessionVisibility, Span, TranceifyField, VariableStorage,
+    operator_method_name, overloadable_operator, overloaded_operator,
 };
+use crate::error::ParseError;
 use crate::token::{Token, TokenType};
 
 /// Parser for HypnoScript language.
@@ -44,6 +47,8 @@ use crate::token::{Token, TokenType};
 pub struct Parser {
     tokens: Vec<Token>,
     current: usize,
+    /// Diagnostics collected while recovering from syntax errors
+    errors: Vec<ParseError>,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -61,91 +66,219 @@ type LoopHeaderComponents = (
 impl Parser {
     /// Create a new parser
     pub fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, current: 0 }
+        Self {
+            tokens,
+            current: 0,
+            errors: Vec::new(),
+        }
     }
 
     /// Parse a complete program
-    pub fn parse_program(&mut self) -> Result<AstNode, String> {
+    ///
+    /// Returns the first syntax error if the program is malformed. Use
+    /// [`Parser::parse_program_with_diagnostics`] to get every error together
+    /// with the partial AST.
+    pub fn parse_program(&mut self) -> Result<AstNode, ParseError> {
+        let (program, mut errors) = self.parse_program_with_diagnostics();
+        if errors.is_empty() {
+            Ok(program)
+        } else {
+            Err(errors.remove(0))
+        }
+    }
+
+    /// Parse a complete program, recovering from syntax errors
+    ///
+    /// On a syntax error the parser skips ahead to the next `;`, `}` or
+    /// statement keyword and continues, so a single run reports every error.
+    /// Statements that failed to parse are left out of the returned AST.
+    pub fn parse_program_with_diagnostics(&mut self) -> (AstNode, Vec<ParseError>) {
+        self.errors.clear();
+        let statements = self.parse_program_structure();
+        (
+            AstNode::Program(statements),
+            std::mem::take(&mut self.errors),
+        )
+    }
+
+    fn parse_program_structure(&mut self) -> Vec<AstNode> {
         // Program must start with Focus
         if !self.check(&TokenType::Focus) {
-            return Err("Program must start with 'Focus'".to_string());
+            self.errors
+                .push(self.error("Program must start with 'Focus'"));
+            return Vec::new();
         }
         self.advance();
 
         // Expect opening brace
         if !self.match_token(&TokenType::LBrace) {
-            return Err("Expected '{' after 'Focus'".to_string());
+            self.errors.push(self.error("Expected '{' after 'Focus'"));
+            return Vec::new();
         }
 
         // Parse program body
-        let statements = self.parse_block_statements(BlockContext::Program)?;
+        let statements = self.parse_block_statements(BlockContext::Program);
 
         // Expect closing brace
         if !self.match_token(&TokenType::RBrace) {
-            return Err("Expected '}' before 'Relax'".to_string());
-        }
-
-        // Program must end with Relax
-        if !self.check(&TokenType::Relax) {
-            return Err("Program must end with 'Relax'".to_string());
+            self.errors.push(self.error("Expected '}' before 'Relax'"));
+        } else if !self.check(&TokenType::Relax) {
+            // Program must end with Relax
+            self.errors
+                .push(self.error("Program must end with 'Relax'"));
+        } else {
+            self.advance();
         }
-        self.advance();
 
-        Ok(AstNode::Program(statements))
+        statements
     }
 
-    /// Parse block statements
-    fn parse_block_statements(&mut self, context: BlockContext) -> Result<Vec<AstNode>, String> {
+    /// Parse block statements, recording errors and resynchronizing on failure
+    fn parse_block_statements(&mut self, context: BlockContext) -> Vec<AstNode> {
         let mut statements = Vec::new();
 
         while !self.is_at_end() && !self.check(&TokenType::RBrace) && !self.check(&TokenType::Relax)
         {
-            // entrance block (constructor/setup)
-            if self.match_token(&TokenType::Entrance) {
-                if context != BlockContext::Program {
-                    return Err("'entrance' blocks are only allowed at the top level".to_string());
+            let start = self.current;
+            match self.parse_block_item(context) {
+                Ok(statement) => statements.push(statement),
+                Err(error) => {
+                    self.errors.push(error);
+                    self.synchronize(start);
                 }
-                if !self.match_token(&TokenType::LBrace) {
-                    return Err("Expected '{' after 'entrance'".to_string());
-                }
-                let mut entrance_statements = Vec::new();
-                while !self.is_at_end() && !self.check(&TokenType::RBrace) {
-                    entrance_statements.push(self.parse_statement(BlockContext::Regular)?);
-                }
-                if !self.match_token(&TokenType::RBrace) {
-                    return Err("Expected '}' after entrance block".to_string());
-                }
-                statements.push(AstNode::EntranceBlock(entrance_statements));
-                continue;
             }
+        }
 
-            // finale block (destructor/cleanup)
-            if self.match_token(&TokenType::Finale) {
-                if context != BlockContext::Program {
-                    return Err("'finale' blocks are only allowed at the top level".to_string());
-                }
-                if !self.match_token(&TokenType::LBrace) {
-                    return Err("Expected '{' after 'finale'".to_string());
-                }
-                let mut finale_statements = Vec::new();
-                while !self.is_at_end() && !self.check(&TokenType::RBrace) {
-                    finale_statements.push(self.parse_statement(BlockContext::Regular)?);
+        statements
+    }
+
+    /// Parse a statement or an entrance/finale block
+    fn parse_block_item(&mut self, context: BlockContext) -> Result<AstNode, ParseError> {
+        let start = self.peek().clone();
+
+        // entrance block (constructor/setup)
+        if self.match_token(&TokenType::Entrance) {
+            if context != BlockContext::Program {
+                self.errors.push(ParseError::at(
+                    &self.previous(),
+                    "'entrance' blocks are only allowed at the top level",
+                ));
+            }
+            self.consume(&TokenType::LBrace, "Expected '{' after 'entrance'")?;
+            let entrance_statements = self.parse_block_statements(BlockContext::Regular);
+            self.consume(&TokenType::RBrace, "Expected '}' after entrance block")?;
+            return Ok(self.spanned(&start, AstNode::EntranceBlock(entrance_statements)));
+        }
+
+        // finale block (destructor/cleanup)
+        if self.match_token(&TokenType::Finale) {
+            if context != BlockContext::Program {
+                self.errors.push(ParseError::at(
+                    &self.previous(),
+                    "'finale' blocks are only allowed at the top level",
+                ));
+            }
+            self.consume(&TokenType::LBrace, "Expected '{' after 'finale'")?;
+            let finale_statements = self.parse_block_statements(BlockContext::Regular);
+            self.consume(&TokenType::RBrace, "Expected '}' after finale block")?;
+            return Ok(self.spanned(&start, AstNode::FinaleBlock(finale_statements)));
+        }
+
+        self.parse_statement(context)
+    }
+
+    /// Skip tokens until a likely statement boundary (panic-mode recovery)
+    ///
+    /// Stops after a `;`, or before a `}`, `Relax` or a statement keyword.
+    /// Blocks opened while skipping are skipped as a whole, so a `}` only
+    /// ends recovery when it closes the enclosing block. Always consumes at
+    /// least one token when the failed statement made no progress, so
+    /// recovery cannot loop forever.
+    fn synchronize(&mut self, statement_start: usize) {
+        let mut depth = 0usize;
+        if self.current == statement_start {
+            self.advance_tracking_braces(&mut depth);
+        }
+
+        while !self.is_at_end() && !self.check(&TokenType::Relax) {
+            if depth == 0 {
+                if self.previous().token_type == TokenType::Semicolon {
+                    return;
                 }
-                if !self.match_token(&TokenType::RBrace) {
-                    return Err("Expected '}' after finale block".to_string());
+
+                let next = &self.peek().token_type;
+                if *next == TokenType::RBrace || Self::starts_statement(next) {
+                    return;
                 }
-                statements.push(AstNode::FinaleBlock(finale_statements));
-                continue;
             }
 
-            statements.push(self.parse_statement(context)?);
+            self.advance_tracking_braces(&mut depth);
+        }
+    }
+
+    /// Advance one token during recovery, keeping count of open `{`
+    fn advance_tracking_braces(&mut self, depth: &mut usize) {
+        match self.peek().token_type {
+            TokenType::LBrace => *depth += 1,
+            TokenType::RBrace => *depth = depth.saturating_sub(1),
+            _ => {}
         }
+        self.advance();
+    }
+
+    /// Whether a token begins a statement (used as a recovery point)
+    fn starts_statement(token_type: &TokenType) -> bool {
+        matches!(
+            token_type,
+            TokenType::SharedTrance
+                | TokenType::Induce
+                | TokenType::Implant
+                | TokenType::Embed
+                | TokenType::Freeze
+                | TokenType::Anchor
+                | TokenType::If
+                | TokenType::While
+                | TokenType::Loop
+                | TokenType::Pendulum
+                | TokenType::Suspend
+                | TokenType::Attempt
+                | TokenType::Using
+                | TokenType::Disrupt
+                | TokenType::MindLink
+                | TokenType::Expose
+                | TokenType::Assert
+                | TokenType::Suggestion
+                | TokenType::Mesmerize
+                | TokenType::Trigger
+                | TokenType::Session
+                | TokenType::Tranceify
+                | TokenType::Interface
+                | TokenType::Enum
+                | TokenType::Observe
+                | TokenType::Whisper
+                | TokenType::Command
+                | TokenType::Murmur
+                | TokenType::Awaken
+                | TokenType::Emerge
+                | TokenType::Snap
+                | TokenType::Sink
+                | TokenType::SinkTo
+                | TokenType::Label
+                | TokenType::Oscillate
+                | TokenType::Entrance
+                | TokenType::Finale
+        )
+    }
 
-        Ok(statements)
+    /// Parse a single statement and record its source span
+    fn parse_statement(&mut self, context: BlockContext) -> Result<AstNode, ParseError> {
+        let start = self.peek().clone();
+        let statement = self.parse_statement_kind(context)?;
+        Ok(self.spanned(&start, statement))
     }
 
-    /// Parse a single statement
-    fn parse_statement(&mut self, context: BlockContext) -> Result<AstNode, String> {
+    /// Parse the statement itself (without span information)
+    fn parse_statement_kind(&mut self, context: BlockContext) -> Result<AstNode, ParseError> {
         // Variable declaration - induce, implant, embed, freeze
         if self.match_token(&TokenType::SharedTrance) {
             if self.match_token(&TokenType::Induce)
@@ -157,7 +290,7 @@ impl Parser {
             }
 
             return Err(
-                "'sharedTrance' must be followed by induce/implant/embed/freeze".to_string(),
+                self.error("'sharedTrance' must be followed by induce/implant/embed/freeze")
             );
         }
 
@@ -169,6 +302,36 @@ impl Parser {
             return self.parse_var_declaration(VariableStorage::Local);
         }
 
+        // Module import
+        if self.match_token(&TokenType::MindLink) {
+            if context != BlockContext::Program {
+                self.errors.push(ParseError::at(
+                    &self.previous(),
+                    "'mindLink' imports are only allowed at the top level",
+                ));
+            }
+            return self.parse_import_declaration();
+        }
+
+        // Exported top-level declaration
+        if self.match_token(&TokenType::Expose) {
+            let keyword = self.previous();
+            if context != BlockContext::Program {
+                self.errors.push(ParseError::at(
+                    &keyword,
+                    "'expose' is only allowed on top-level declarations",
+                ));
+            }
+            let declaration = self.parse_statement_kind(context)?;
+            if declaration.declared_name().is_none() {
+                return Err(ParseError::at(
+                    &keyword,
+                    "'expose' must be followed by a declaration",
+                ));
+            }
+            return Ok(AstNode::ExportDeclaration(Box::new(declaration)));
+        }
+
         // Anchor declaration - saves variable state
         if self.match_token(&TokenType::Anchor) {
             return self.parse_anchor_declaration();
@@ -200,15 +363,52 @@ impl Parser {
             return Ok(AstNode::SuspendStatement);
         }
 
-        // Function declaration
-        if self.match_token(&TokenType::Suggestion) {
-            return self.parse_function_declaration();
+        // Error handling
+        if self.match_token(&TokenType::Attempt) {
+            return self.parse_try_statement();
+        }
+
+        if self.match_token(&TokenType::Disrupt) {
+            return self.parse_raise_statement();
+        }
+
+        // Scoped resource
+        if self.match_token(&TokenType::Using) {
+            return self.parse_using_statement();
+        }
+
+        // Assertion
+        if self.match_token(&TokenType::Assert) {
+            return self.parse_assert_statement();
+        }
+
+        // Function declaration (anonymous `suggestion (...)` is an expression)
+        if self.check(&TokenType::Suggestion)
+            && matches!(
+                self.peek_next().map(|tok| &tok.token_type),
+                Some(TokenType::Identifier)
+            )
+        {
+            self.advance();
+            return self.parse_function_declaration(false);
+        }
+
+        // Async function declaration
+        if self.match_token(&TokenType::Mesmerize) {
+            self.consume(
+                &TokenType::Suggestion,
+                "Expected 'suggestion' after 'mesmerize'",
+            )?;
+            return self.parse_function_declaration(true);
         }
 
         // Trigger declaration (event handler/callback)
         if self.match_token(&TokenType::Trigger) {
             if context != BlockContext::Program {
-                return Err("Triggers can only be declared at the top level".to_string());
+                self.errors.push(ParseError::at(
+                    &self.previous(),
+                    "Triggers can only be declared at the top level",
+                ));
             }
             return self.parse_trigger_declaration();
         }
@@ -223,6 +423,16 @@ impl Parser {
             return self.parse_tranceify_declaration();
         }
 
+        // Interface declaration (contract for sessions and records)
+        if self.match_token(&TokenType::Interface) {
+            return self.parse_interface_declaration();
+        }
+
+        // Enum declaration (sum type with optional payloads)
+        if self.match_token(&TokenType::Enum) {
+            return self.parse_enum_declaration();
+        }
+
         // Output statements
         if self.match_token(&TokenType::Observe) {
             return self.parse_observe_statement();
@@ -246,16 +456,43 @@ impl Parser {
             return self.parse_return_statement();
         }
 
+        // Hand a value out of a generator suggestion
+        if self.match_token(&TokenType::Emerge) {
+            let value = self.parse_expression()?;
+            self.consume(&TokenType::Semicolon, "Expected ';' after 'emerge'")?;
+            return Ok(AstNode::YieldStatement(Box::new(value)));
+        }
+
+        // Labeled loop: `outer: loop { ... }` or `label outer: loop { ... }`
+        if self.check(&TokenType::Label)
+            || (self.check(&TokenType::Identifier)
+                && matches!(
+                    self.peek_next().map(|tok| &tok.token_type),
+                    Some(TokenType::Colon)
+                ))
+        {
+            return self.parse_labeled_statement(context);
+        }
+
         // Break
         if self.match_token(&TokenType::Snap) {
+            let label = self.parse_jump_label(false)?;
             self.consume(&TokenType::Semicolon, "Expected ';' after 'snap'")?;
-            return Ok(AstNode::BreakStatement);
+            return Ok(AstNode::BreakStatement(label));
         }
 
         // Continue
         if self.match_token(&TokenType::Sink) {
+            let label = self.parse_jump_label(false)?;
             self.consume(&TokenType::Semicolon, "Expected ';' after 'sink'")?;
-            return Ok(AstNode::ContinueStatement);
+            return Ok(AstNode::ContinueStatement(label));
+        }
+
+        // Jump to the next iteration of a labeled loop
+        if self.match_token(&TokenType::SinkTo) {
+            let label = self.parse_jump_label(true)?;
+            self.consume(&TokenType::Semicolon, "Expected ';' after 'sinkTo' label")?;
+            return Ok(AstNode::ContinueStatement(label));
         }
 
         // Oscillate statement (toggle boolean)
@@ -269,22 +506,64 @@ impl Parser {
         Ok(AstNode::ExpressionStatement(Box::new(expr)))
     }
 
+    /// Parse a labeled loop: `outer: loop { ... }`
+    fn parse_labeled_statement(&mut self, context: BlockContext) -> Result<AstNode, ParseError> {
+        self.match_token(&TokenType::Label);
+        let label = self
+            .consume(&TokenType::Identifier, "Expected label name")?
+            .lexeme
+            .clone();
+        self.consume(&TokenType::Colon, "Expected ':' after label")?;
+
+        if !matches!(
+            self.peek().token_type,
+            TokenType::Loop | TokenType::Pendulum | TokenType::While
+        ) {
+            return Err(self.error("Only loops can be labeled"));
+        }
+        let body = self.parse_statement_kind(context)?;
+
+        Ok(AstNode::LabeledStatement {
+            label,
+            body: Box::new(body),
+        })
+    }
+
+    /// Parse the optional (or, for `sinkTo`, required) label of a jump
+    fn parse_jump_label(&mut self, required: bool) -> Result<Option<String>, ParseError> {
+        if self.check(&TokenType::Identifier) {
+            return Ok(Some(self.advance().lexeme.clone()));
+        }
+        if required {
+            return Err(self.error("Expected loop label after 'sinkTo'"));
+        }
+        Ok(None)
+    }
+
     /// Parse variable declaration (induce/implant/freeze)
     /// - induce: standard variable (like let/var)
     /// - implant: alternative variable declaration
     /// - freeze: constant (like const)
-    fn parse_var_declaration(&mut self, storage: VariableStorage) -> Result<AstNode, String> {
+    fn parse_var_declaration(&mut self, storage: VariableStorage) -> Result<AstNode, ParseError> {
         // Determine if this is a constant (freeze) or variable (induce/implant)
         let is_constant = self.previous().token_type == TokenType::Freeze;
 
+        // `[a, b]`, `{ a, b }` or `Person { a, b }` start a destructuring pattern
+        let typed_record = self.check(&TokenType::Identifier)
+            && self
+                .peek_next()
+                .is_some_and(|token| token.token_type == TokenType::LBrace);
+        if self.check(&TokenType::LBracket) || self.check(&TokenType::LBrace) || typed_record {
+            return self.parse_destructuring_declaration(is_constant, storage);
+        }
+
         let name = self
             .consume(&TokenType::Identifier, "Expected variable name")?
             .lexeme
             .clone();
 
         let type_annotation = if self.match_token(&TokenType::Colon) {
-            let type_token = self.advance();
-            Some(type_token.lexeme.clone())
+            Some(self.parse_type_annotation()?)
         } else {
             None
         };
@@ -309,9 +588,44 @@ impl Parser {
         })
     }
 
+    /// Parse a destructuring declaration
+    /// Examples: `induce [first, ...rest] = items;`, `freeze { name, age } = person;`
+    fn parse_destructuring_declaration(
+        &mut self,
+        is_constant: bool,
+        storage: VariableStorage,
+    ) -> Result<AstNode, ParseError> {
+        let pattern = self.parse_pattern()?;
+
+        let type_annotation = if self.match_token(&TokenType::Colon) {
+            Some(self.parse_type_annotation()?)
+        } else {
+            None
+        };
+
+        self.consume(
+            &TokenType::Equals,
+            "Expected '=' after destructuring pattern",
+        )?;
+        let initializer = Box::new(self.parse_expression()?);
+
+        self.consume(
+            &TokenType::Semicolon,
+            "Expected ';' after variable declaration",
+        )?;
+
+        Ok(AstNode::DestructuringDeclaration {
+            pattern,
+            type_annotation,
+            initializer,
+            is_constant,
+            storage,
+        })
+    }
+
     /// Parse anchor declaration (saves variable state)
     /// Example: anchor savedValue = currentValue;
-    fn parse_anchor_declaration(&mut self) -> Result<AstNode, String> {
+    fn parse_anchor_declaration(&mut self) -> Result<AstNode, ParseError> {
         let name = self
             .consume(&TokenType::Identifier, "Expected anchor name")?
             .lexeme
@@ -331,7 +645,7 @@ impl Parser {
 
     /// Parse oscillate statement (toggle boolean)
     /// Example: oscillate myFlag;
-    fn parse_oscillate_statement(&mut self) -> Result<AstNode, String> {
+    fn parse_oscillate_statement(&mut self) -> Result<AstNode, ParseError> {
         let target = Box::new(self.parse_primary()?);
 
         self.consume(
@@ -343,28 +657,28 @@ impl Parser {
     }
 
     /// Parse whisper statement (output without newline)
-    fn parse_whisper_statement(&mut self) -> Result<AstNode, String> {
+    fn parse_whisper_statement(&mut self) -> Result<AstNode, ParseError> {
         let expr = self.parse_expression()?;
         self.consume(&TokenType::Semicolon, "Expected ';' after whisper")?;
         Ok(AstNode::WhisperStatement(Box::new(expr)))
     }
 
     /// Parse command statement (imperative output)
-    fn parse_command_statement(&mut self) -> Result<AstNode, String> {
+    fn parse_command_statement(&mut self) -> Result<AstNode, ParseError> {
         let expr = self.parse_expression()?;
         self.consume(&TokenType::Semicolon, "Expected ';' after command")?;
         Ok(AstNode::CommandStatement(Box::new(expr)))
     }
 
     /// Parse murmur statement (quiet/debug output)
-    fn parse_murmur_statement(&mut self) -> Result<AstNode, String> {
+    fn parse_murmur_statement(&mut self) -> Result<AstNode, ParseError> {
         let expr = self.parse_expression()?;
         self.consume(&TokenType::Semicolon, "Expected ';' after murmur")?;
         Ok(AstNode::MurmurStatement(Box::new(expr)))
     }
 
     /// Parse trigger declaration (event handler/callback)
-    fn parse_trigger_declaration(&mut self) -> Result<AstNode, String> {
+    fn parse_trigger_declaration(&mut self) -> Result<AstNode, ParseError> {
         let name = self
             .consume(&TokenType::Identifier, "Expected trigger name")?
             .lexeme
@@ -377,41 +691,18 @@ impl Parser {
 
         self.consume(&TokenType::LParen, "Expected '(' after 'suggestion'")?;
 
-        // Parse parameters (inline to avoid duplication)
-        let mut parameters = Vec::new();
-        if !self.check(&TokenType::RParen) {
-            loop {
-                let param_name = self
-                    .consume(&TokenType::Identifier, "Expected parameter name")?
-                    .lexeme
-                    .clone();
-                let type_annotation = if self.match_token(&TokenType::Colon) {
-                    let type_token = self.advance();
-                    Some(type_token.lexeme.clone())
-                } else {
-                    None
-                };
-                parameters.push(Parameter::new(param_name, type_annotation));
-
-                if !self.match_token(&TokenType::Comma) {
-                    break;
-                }
-            }
-        }
-
-        self.consume(&TokenType::RParen, "Expected ')' after parameters")?;
+        let parameters = self.parse_parameters()?;
 
         // Optional return type
         let return_type = if self.match_token(&TokenType::Colon) {
-            let type_token = self.advance();
-            Some(type_token.lexeme.clone())
+            Some(self.parse_type_annotation()?)
         } else {
             None
         };
 
         // Parse body
         self.consume(&TokenType::LBrace, "Expected '{' before trigger body")?;
-        let body = self.parse_block_statements(BlockContext::Regular)?;
+        let body = self.parse_block_statements(BlockContext::Regular);
         self.consume(&TokenType::RBrace, "Expected '}' after trigger body")?;
 
         Ok(AstNode::TriggerDeclaration {
@@ -423,7 +714,7 @@ impl Parser {
     }
 
     /// Parse if statement
-    fn parse_if_statement(&mut self) -> Result<AstNode, String> {
+    fn parse_if_statement(&mut self) -> Result<AstNode, ParseError> {
         self.consume(&TokenType::LParen, "Expected '(' after 'if'")?;
         let condition = Box::new(self.parse_expression()?);
         self.consume(&TokenType::RParen, "Expected ')' after if condition")?;
@@ -432,7 +723,7 @@ impl Parser {
         self.match_token(&TokenType::DeepFocus);
 
         self.consume(&TokenType::LBrace, "Expected '{' after if condition")?;
-        let then_branch = self.parse_block_statements(BlockContext::Regular)?;
+        let then_branch = self.parse_block_statements(BlockContext::Regular);
         self.consume(&TokenType::RBrace, "Expected '}' after if block")?;
 
         let else_branch = if self.match_token(&TokenType::Else) {
@@ -441,7 +732,7 @@ impl Parser {
                 Some(vec![self.parse_if_statement()?])
             } else {
                 self.consume(&TokenType::LBrace, "Expected '{' after 'else'")?;
-                let else_statements = self.parse_block_statements(BlockContext::Regular)?;
+                let else_statements = self.parse_block_statements(BlockContext::Regular);
                 self.consume(&TokenType::RBrace, "Expected '}' after else block")?;
                 Some(else_statements)
             }
@@ -457,30 +748,184 @@ impl Parser {
     }
 
     /// Parse while statement
-    fn parse_while_statement(&mut self) -> Result<AstNode, String> {
+    fn parse_while_statement(&mut self) -> Result<AstNode, ParseError> {
         self.consume(&TokenType::LParen, "Expected '(' after 'while'")?;
         let condition = Box::new(self.parse_expression()?);
         self.consume(&TokenType::RParen, "Expected ')' after while condition")?;
 
         self.consume(&TokenType::LBrace, "Expected '{' after while condition")?;
-        let body = self.parse_block_statements(BlockContext::Regular)?;
+        let body = self.parse_block_statements(BlockContext::Regular);
         self.consume(&TokenType::RBrace, "Expected '}' after while block")?;
 
         Ok(AstNode::WhileStatement { condition, body })
     }
 
+    /// Parse import: mindLink { a, b } from "./module.hyp";
+    fn parse_import_declaration(&mut self) -> Result<AstNode, ParseError> {
+        self.consume(&TokenType::LBrace, "Expected '{' after 'mindLink'")?;
+
+        let mut names = Vec::new();
+        if !self.check(&TokenType::RBrace) {
+            loop {
+                let name = self.consume(&TokenType::Identifier, "Expected imported name")?;
+                names.push(name.lexeme);
+                if !self.match_token(&TokenType::Comma) {
+                    break;
+                }
+            }
+        }
+
+        self.consume(&TokenType::RBrace, "Expected '}' after imported names")?;
+        self.consume(&TokenType::From, "Expected 'from' after import list")?;
+        let source = self
+            .consume(&TokenType::StringLiteral, "Expected module path string")?
+            .lexeme;
+        self.consume(&TokenType::Semicolon, "Expected ';' after mindLink import")?;
+
+        Ok(AstNode::ImportDeclaration { names, source })
+    }
+
+    /// Parse attempt statement: attempt { ... } recover (err) { ... } finally { ... }
+    fn parse_try_statement(&mut self) -> Result<AstNode, ParseError> {
+        self.consume(&TokenType::LBrace, "Expected '{' after 'attempt'")?;
+        let body = self.parse_block_statements(BlockContext::Regular);
+        self.consume(&TokenType::RBrace, "Expected '}' after attempt block")?;
+
+        let mut catch_binding = None;
+        let mut catch_body = None;
+        if self.match_token(&TokenType::Recover) {
+            if self.match_token(&TokenType::LParen) {
+                let name = self.consume(&TokenType::Identifier, "Expected error variable name")?;
+                catch_binding = Some(name.lexeme);
+                self.consume(&TokenType::RParen, "Expected ')' after error variable")?;
+            }
+            self.consume(&TokenType::LBrace, "Expected '{' after 'recover'")?;
+            catch_body = Some(self.parse_block_statements(BlockContext::Regular));
+            self.consume(&TokenType::RBrace, "Expected '}' after recover block")?;
+        }
+
+        let finally_body = if self.match_token(&TokenType::Finally) {
+            self.consume(&TokenType::LBrace, "Expected '{' after 'finally'")?;
+            let statements = self.parse_block_statements(BlockContext::Regular);
+            self.consume(&TokenType::RBrace, "Expected '}' after finally block")?;
+            Some(statements)
+        } else {
+            None
+        };
+
+        if catch_body.is_none() && finally_body.is_none() {
+            return Err(self.error("Expected 'recover' or 'finally' after attempt block"));
+        }
+
+        Ok(AstNode::TryStatement {
+            body,
+            catch_binding,
+            catch_body,
+            finally_body,
+        })
+    }
+
+    /// Parse using statement: using (name = resource) { ... }
+    fn parse_using_statement(&mut self) -> Result<AstNode, ParseError> {
+        self.consume(&TokenType::LParen, "Expected '(' after 'using'")?;
+        let name = self
+            .consume(
+                &TokenType::Identifier,
+                "Expected resource name after 'using ('",
+            )?
+            .lexeme
+            .clone();
+        self.consume(&TokenType::Equals, "Expected '=' after resource name")?;
+        let resource = self.parse_expression()?;
+        self.consume(&TokenType::RParen, "Expected ')' after using resource")?;
+
+        self.consume(&TokenType::LBrace, "Expected '{' after using header")?;
+        let body = self.parse_block_statements(BlockContext::Regular);
+        self.consume(&TokenType::RBrace, "Expected '}' after using block")?;
+
+        Ok(AstNode::UsingStatement {
+            name,
+            resource: Box::new(resource),
+            body,
+        })
+    }
+
+    /// Parse assert statement: assert condition[, message];
+    fn parse_assert_statement(&mut self) -> Result<AstNode, ParseError> {
+        let start = self.current;
+        let condition = Box::new(self.parse_expression()?);
+        let source = self.source_text(start, self.current);
+
+        let message = if self.match_token(&TokenType::Comma) {
+            Some(Box::new(self.parse_expression()?))
+        } else {
+            None
+        };
+        self.consume(&TokenType::Semicolon, "Expected ';' after assert statement")?;
+
+        Ok(AstNode::AssertStatement {
+            condition,
+            message,
+            source,
+        })
+    }
+
+    /// Reconstruct the source text of the tokens in `start..end`
+    ///
+    /// Tokens are joined with a single space wherever the original source had
+    /// whitespace between them.
+    fn source_text(&self, start: usize, end: usize) -> String {
+        let mut text = String::new();
+        let mut previous_end: Option<(usize, usize)> = None;
+
+        for token in &self.tokens[start..end] {
+            let lexeme = match token.token_type {
+                TokenType::StringLiteral => format!("{:?}", token.lexeme),
+                _ => token.lexeme.clone(),
+            };
+            if let Some((line, column)) = previous_end
+                && (token.line != line || token.column > column)
+            {
+                text.push(' ');
+            }
+            previous_end = Some((token.line, token.column + lexeme.chars().count()));
+            text.push_str(&lexeme);
+        }
+
+        text
+    }
+
+    /// Parse disrupt statement: disrupt message[, payload];
+    fn parse_raise_statement(&mut self) -> Result<AstNode, ParseError> {
+        let message = Box::new(self.parse_expression()?);
+        let payload = if self.match_token(&TokenType::Comma) {
+            Some(Box::new(self.parse_expression()?))
+        } else {
+            None
+        };
+        self.consume(
+            &TokenType::Semicolon,
+            "Expected ';' after disrupt statement",
+        )?;
+
+        Ok(AstNode::RaiseStatement { message, payload })
+    }
+
     /// Parse loop/pendulum statements (C-style for loop)
     fn parse_loop_statement(
         &mut self,
         keyword: &str,
         require_header: bool,
         require_condition: bool,
-    ) -> Result<AstNode, String> {
+    ) -> Result<AstNode, ParseError> {
         let has_header = if self.match_token(&TokenType::LParen) {
+            if self.at_for_each_header() {
+                return self.parse_for_each_statement(keyword);
+            }
             true
         } else {
             if require_header {
-                return Err(format!("Expected '(' after '{}'", keyword));
+                return Err(self.error(format!("Expected '(' after '{}'", keyword)));
             }
             false
         };
@@ -495,7 +940,7 @@ impl Parser {
             &TokenType::LBrace,
             &format!("Expected '{{' after '{}' loop header", keyword),
         )?;
-        let body = self.parse_block_statements(BlockContext::Regular)?;
+        let body = self.parse_block_statements(BlockContext::Regular);
         self.consume(
             &TokenType::RBrace,
             &format!("Expected '}}' after '{}' loop block", keyword),
@@ -509,11 +954,79 @@ impl Parser {
         })
     }
 
+    /// Whether the loop header is `induce name [, name] in ...`
+    fn at_for_each_header(&self) -> bool {
+        let token = |offset: usize| self.tokens.get(self.current + offset);
+        if !token(0).is_some_and(|token| {
+            matches!(
+                token.token_type,
+                TokenType::Induce | TokenType::Implant | TokenType::Embed | TokenType::Freeze
+            )
+        }) {
+            return false;
+        }
+        let in_offset = if token(2).is_some_and(|token| token.token_type == TokenType::Comma) {
+            4
+        } else {
+            2
+        };
+        token(in_offset).is_some_and(|token| {
+            token.token_type == TokenType::Identifier && token.lexeme.eq_ignore_ascii_case("in")
+        })
+    }
+
+    /// Parse the rest of `loop (induce item in items) { ... }` after the '('
+    fn parse_for_each_statement(&mut self, keyword: &str) -> Result<AstNode, ParseError> {
+        let is_constant = self.advance().token_type == TokenType::Freeze;
+        let first = self
+            .consume(&TokenType::Identifier, "Expected loop variable name")?
+            .lexeme
+            .clone();
+        let (key, value) = if self.match_token(&TokenType::Comma) {
+            let second = self
+                .consume(
+                    &TokenType::Identifier,
+                    "Expected loop variable name after ','",
+                )?
+                .lexeme
+                .clone();
+            (Some(first), second)
+        } else {
+            (None, first)
+        };
+        // Contextual keyword, checked by at_for_each_header
+        self.advance();
+
+        let iterable = self.parse_expression()?;
+        self.consume(
+            &TokenType::RParen,
+            &format!("Expected ')' after '{}' iterable", keyword),
+        )?;
+
+        self.consume(
+            &TokenType::LBrace,
+            &format!("Expected '{{' after '{}' loop header", keyword),
+        )?;
+        let body = self.parse_block_statements(BlockContext::Regular);
+        self.consume(
+            &TokenType::RBrace,
+            &format!("Expected '}}' after '{}' loop block", keyword),
+        )?;
+
+        Ok(AstNode::ForEachStatement {
+            key,
+            value,
+            is_constant,
+            iterable: Box::new(iterable),
+            body,
+        })
+    }
+
     fn parse_loop_header(
         &mut self,
         keyword: &str,
         require_condition: bool,
-    ) -> Result<LoopHeaderComponents, String> {
+    ) -> Result<LoopHeaderComponents, ParseError> {
         // Parse init (variable declaration or expression)
         let init = if self.check(&TokenType::Semicolon) {
             None
@@ -534,7 +1047,7 @@ impl Parser {
         };
 
         if require_condition && condition.is_none() {
-            return Err(format!("{} loop requires a condition expression", keyword));
+            return Err(self.error(format!("{} loop requires a condition expression", keyword)));
         }
 
         self.consume(
@@ -558,7 +1071,7 @@ impl Parser {
         Ok((init, condition, update))
     }
 
-    fn parse_loop_init_statement(&mut self) -> Result<Option<Box<AstNode>>, String> {
+    fn parse_loop_init_statement(&mut self) -> Result<Option<Box<AstNode>>, ParseError> {
         if self.match_token(&TokenType::Induce)
             || self.match_token(&TokenType::Implant)
             || self.match_token(&TokenType::Embed)
@@ -571,8 +1084,7 @@ impl Parser {
                 .clone();
 
             let type_annotation = if self.match_token(&TokenType::Colon) {
-                let type_token = self.advance();
-                Some(type_token.lexeme.clone())
+                Some(self.parse_type_annotation()?)
             } else {
                 None
             };
@@ -600,88 +1112,333 @@ impl Parser {
         Ok(Some(Box::new(AstNode::ExpressionStatement(Box::new(expr)))))
     }
 
-    /// Parse function declaration
-    fn parse_function_declaration(&mut self) -> Result<AstNode, String> {
+    /// Parse function declaration (`is_async` for `mesmerize suggestion`)
+    fn parse_function_declaration(&mut self, is_async: bool) -> Result<AstNode, ParseError> {
         let name = self
             .consume(&TokenType::Identifier, "Expected function name")?
             .lexeme
             .clone();
+        let type_parameters = self.parse_type_parameters()?;
 
         self.consume(&TokenType::LParen, "Expected '(' after function name")?;
 
-        let mut parameters = Vec::new();
-        if !self.check(&TokenType::RParen) {
-            loop {
-                let param_name = self
-                    .consume(&TokenType::Identifier, "Expected parameter name")?
-                    .lexeme
-                    .clone();
-                let type_annotation = if self.match_token(&TokenType::Colon) {
-                    let type_token = self.advance();
-                    Some(type_token.lexeme.clone())
-                } else {
-                    None
-                };
-                parameters.push(Parameter::new(param_name, type_annotation));
-
-                if !self.match_token(&TokenType::Comma) {
-                    break;
-                }
-            }
-        }
-
-        self.consume(&TokenType::RParen, "Expected ')' after parameters")?;
+        let parameters = self.parse_parameters()?;
 
         let return_type = if self.match_token(&TokenType::Colon) {
-            let type_token = self.advance();
-            Some(type_token.lexeme.clone())
+            Some(self.parse_type_annotation()?)
         } else {
             None
         };
 
         self.consume(&TokenType::LBrace, "Expected '{' after function signature")?;
-        let body = self.parse_block_statements(BlockContext::Regular)?;
+        let body = self.parse_block_statements(BlockContext::Regular);
         self.consume(&TokenType::RBrace, "Expected '}' after function body")?;
 
         Ok(AstNode::FunctionDeclaration {
             name,
+            type_parameters,
             parameters,
             return_type,
             body,
+            is_async,
         })
     }
 
-    /// Parse session declaration
-    fn parse_session_declaration(&mut self) -> Result<AstNode, String> {
-        let name = self
-            .consume(&TokenType::Identifier, "Expected session name")?
-            .lexeme
-            .clone();
-
-        self.consume(&TokenType::LBrace, "Expected '{' after session name")?;
-
-        let mut members = Vec::new();
-        while !self.check(&TokenType::RBrace) && !self.is_at_end() {
-            members.push(self.parse_session_member()?);
+    /// Parse an optional type parameter list: `<T, U>`
+    fn parse_type_parameters(&mut self) -> Result<Vec<String>, ParseError> {
+        let mut type_parameters: Vec<String> = Vec::new();
+        if !self.match_token(&TokenType::Less) {
+            return Ok(type_parameters);
         }
 
-        self.consume(&TokenType::RBrace, "Expected '}' after session body")?;
+        loop {
+            let name = self
+                .consume(&TokenType::Identifier, "Expected type parameter name")?
+                .lexeme
+                .clone();
+            if type_parameters.contains(&name) {
+                return Err(self.error(format!("Duplicate type parameter '{}'", name)));
+            }
+            type_parameters.push(name);
+            if !self.match_token(&TokenType::Comma) {
+                break;
+            }
+        }
+
+        self.consume(&TokenType::Greater, "Expected '>' after type parameters")?;
+        Ok(type_parameters)
+    }
+
+    /// Parse a parameter list after the opening '(' (consumes the closing ')')
+    fn parse_parameters(&mut self) -> Result<Vec<Parameter>, ParseError> {
+        let mut parameters = Vec::new();
+        if !self.check(&TokenType::RParen) {
+            loop {
+                let is_rest = self.match_token(&TokenType::DotDotDot);
+                // Destructured parameters are named after their pattern
+                let pattern = if !is_rest
+                    && (self.check(&TokenType::LBracket) || self.check(&TokenType::LBrace))
+                {
+                    Some(self.parse_pattern()?)
+                } else {
+                    None
+                };
+                let param_name = match &pattern {
+                    Some(pattern) => pattern.to_string(),
+                    None => self
+                        .consume(&TokenType::Identifier, "Expected parameter name")?
+                        .lexeme
+                        .clone(),
+                };
+                let type_annotation = if self.match_token(&TokenType::Colon) {
+                    Some(self.parse_type_annotation()?)
+                } else {
+                    None
+                };
+                let mut parameter = Parameter::new(param_name, type_annotation);
+                if let Some(pattern) = pattern {
+                    parameter = parameter.with_pattern(pattern);
+                }
+
+                if self.match_token(&TokenType::Equals) {
+                    if is_rest {
+                        return Err(self.error("Rest parameter cannot have a default value"));
+                    }
+                    parameter = parameter.with_default(self.parse_expression()?);
+                } else if !is_rest
+                    && parameters
+                        .iter()
+                        .any(|param: &Parameter| param.default_value.is_some())
+                {
+                    return Err(self.error(format!(
+                        "Parameter '{}' without default value cannot follow parameters with defaults",
+                        parameter.name
+                    )));
+                }
+                if is_rest {
+                    parameter = parameter.rest();
+                }
+                parameters.push(parameter);
+
+                if !self.match_token(&TokenType::Comma) {
+                    break;
+                }
+                if is_rest {
+                    return Err(self.error("Rest parameter must be the last parameter"));
+                }
+            }
+        }
+
+        self.consume(&TokenType::RParen, "Expected ')' after parameters")?;
+        Ok(parameters)
+    }
+
+    /// Parse an anonymous suggestion expression (closure)
+    /// Examples: `suggestion (x) => x * 2`, `suggestion (a: number): number { awaken a; }`
+    /// Note: The 'suggestion' keyword has already been consumed
+    fn parse_function_expression(&mut self) -> Result<AstNode, ParseError> {
+        self.consume(&TokenType::LParen, "Expected '(' after 'suggestion'")?;
+        let parameters = self.parse_parameters()?;
+
+        let return_type = if self.match_token(&TokenType::Colon) {
+            Some(self.parse_type_annotation()?)
+        } else {
+            None
+        };
+
+        let body = if self.match_token(&TokenType::Arrow) {
+            let expr = self.parse_expression()?;
+            vec![AstNode::ReturnStatement(Some(Box::new(expr)))]
+        } else {
+            self.consume(
+                &TokenType::LBrace,
+                "Expected '=>' or '{' after suggestion parameters",
+            )?;
+            let body = self.parse_block_statements(BlockContext::Regular);
+            self.consume(&TokenType::RBrace, "Expected '}' after suggestion body")?;
+            body
+        };
+
+        Ok(AstNode::FunctionExpression {
+            parameters,
+            return_type,
+            body,
+        })
+    }
+
+    /// Parse session declaration
+    fn parse_session_declaration(&mut self) -> Result<AstNode, ParseError> {
+        let name = self
+            .consume(&TokenType::Identifier, "Expected session name")?
+            .lexeme
+            .clone();
+        let type_parameters = self.parse_type_parameters()?;
+        let parent = if self.match_token(&TokenType::Extends) {
+            Some(self.parse_type_member()?)
+        } else {
+            None
+        };
+        let mut interfaces = Vec::new();
+        if self.match_token(&TokenType::Implements) {
+            loop {
+                interfaces.push(
+                    self.consume(&TokenType::Identifier, "Expected interface name")?
+                        .lexeme
+                        .clone(),
+                );
+                if !self.match_token(&TokenType::Comma) {
+                    break;
+                }
+            }
+        }
+
+        self.consume(&TokenType::LBrace, "Expected '{' after session name")?;
+
+        let mut members = Vec::new();
+        while !self.check(&TokenType::RBrace) && !self.is_at_end() {
+            members.push(self.parse_session_member()?);
+        }
+
+        self.consume(&TokenType::RBrace, "Expected '}' after session body")?;
+
+        Ok(AstNode::SessionDeclaration {
+            name,
+            type_parameters,
+            parent,
+            interfaces,
+            members,
+        })
+    }
+
+    /// Parse interface declaration
+    /// Example: interface Plugin { name: string; suggestion run(input: string): string; }
+    fn parse_interface_declaration(&mut self) -> Result<AstNode, ParseError> {
+        let name = self
+            .consume(&TokenType::Identifier, "Expected interface name")?
+            .lexeme
+            .clone();
+
+        self.consume(&TokenType::LBrace, "Expected '{' after interface name")?;
+
+        let mut members = Vec::new();
+        while !self.check(&TokenType::RBrace) && !self.is_at_end() {
+            if self.match_token(&TokenType::Suggestion) {
+                let method_name = self
+                    .consume(&TokenType::Identifier, "Expected method name in interface")?
+                    .lexeme
+                    .clone();
+                self.consume(&TokenType::LParen, "Expected '(' after method name")?;
+                let parameters = self.parse_parameters()?;
+                let return_type = if self.match_token(&TokenType::Colon) {
+                    Some(self.parse_type_annotation()?)
+                } else {
+                    None
+                };
+                members.push(InterfaceMember::Method(InterfaceMethod {
+                    name: method_name,
+                    parameters,
+                    return_type,
+                }));
+            } else {
+                let field_name = self
+                    .consume(
+                        &TokenType::Identifier,
+                        "Expected field or 'suggestion' in interface",
+                    )?
+                    .lexeme
+                    .clone();
+                self.consume(&TokenType::Colon, "Expected ':' after field name")?;
+                let type_annotation = self.parse_type_annotation()?;
+                members.push(InterfaceMember::Field(TranceifyField {
+                    name: field_name,
+                    type_annotation,
+                }));
+            }
+
+            self.consume(&TokenType::Semicolon, "Expected ';' after interface member")?;
+        }
+
+        self.consume(&TokenType::RBrace, "Expected '}' after interface body")?;
+
+        Ok(AstNode::InterfaceDeclaration { name, members })
+    }
+
+    /// Parse enum declaration
+    /// Example: enum State { Idle, Running(number), Failed(string, number) }
+    fn parse_enum_declaration(&mut self) -> Result<AstNode, ParseError> {
+        let name = self
+            .consume(&TokenType::Identifier, "Expected enum name")?
+            .lexeme
+            .clone();
+
+        self.consume(&TokenType::LBrace, "Expected '{' after enum name")?;
+
+        let mut variants = Vec::new();
+        while !self.check(&TokenType::RBrace) && !self.is_at_end() {
+            let variant_name = self
+                .consume(&TokenType::Identifier, "Expected variant name in enum")?
+                .lexeme
+                .clone();
+
+            let mut payload = Vec::new();
+            if self.match_token(&TokenType::LParen) {
+                if !self.check(&TokenType::RParen) {
+                    loop {
+                        payload.push(self.parse_type_annotation()?);
+                        if !self.match_token(&TokenType::Comma) {
+                            break;
+                        }
+                    }
+                }
+                self.consume(&TokenType::RParen, "Expected ')' after variant payload")?;
+            }
+
+            variants.push(EnumVariant {
+                name: variant_name,
+                payload,
+            });
+
+            if !self.match_token(&TokenType::Comma) {
+                break;
+            }
+        }
+
+        self.consume(&TokenType::RBrace, "Expected '}' after enum body")?;
 
-        Ok(AstNode::SessionDeclaration { name, members })
+        Ok(AstNode::EnumDeclaration { name, variants })
     }
 
     /// Parse tranceify declaration (record/struct type definition)
     /// Example: tranceify Person { name: string; age: number; isInTrance: boolean; }
-    fn parse_tranceify_declaration(&mut self) -> Result<AstNode, String> {
+    fn parse_tranceify_declaration(&mut self) -> Result<AstNode, ParseError> {
         let name = self
             .consume(&TokenType::Identifier, "Expected tranceify type name")?
             .lexeme
             .clone();
+        let type_parameters = self.parse_type_parameters()?;
 
         self.consume(&TokenType::LBrace, "Expected '{' after tranceify name")?;
 
         let mut fields = Vec::new();
+        let mut operators = Vec::new();
         while !self.check(&TokenType::RBrace) && !self.is_at_end() {
+            if self.check(&TokenType::Suggestion) {
+                let SessionMember::Method(method) = self.parse_session_method(false, None)? else {
+                    unreachable!("parse_session_method returns methods");
+                };
+                if overloaded_operator(&method.name).is_none() {
+                    return Err(ParseError::at(
+                        &self.previous(),
+                        format!(
+                            "Tranceify type '{}' can only declare operator methods, found '{}'",
+                            name, method.name
+                        ),
+                    ));
+                }
+                operators.push(method);
+                continue;
+            }
+
             let field_name = self
                 .consume(&TokenType::Identifier, "Expected field name")?
                 .lexeme
@@ -704,13 +1461,18 @@ impl Parser {
 
         self.consume(&TokenType::RBrace, "Expected '}' after tranceify body")?;
 
-        Ok(AstNode::TranceifyDeclaration { name, fields })
+        Ok(AstNode::TranceifyDeclaration {
+            name,
+            type_parameters,
+            fields,
+            operators,
+        })
     }
 
     /// Parse record literal (instance of a tranceify type)
     /// Example: Person { name: "Alice", age: 30, isInTrance: true }
     /// Note: The opening '{' has already been consumed
-    fn parse_record_literal(&mut self, type_name: String) -> Result<AstNode, String> {
+    fn parse_record_literal(&mut self, type_name: String) -> Result<AstNode, ParseError> {
         let mut fields = Vec::new();
 
         if !self.check(&TokenType::RBrace) {
@@ -746,8 +1508,39 @@ impl Parser {
         Ok(AstNode::RecordLiteral { type_name, fields })
     }
 
+    /// Parse dictionary literal
+    /// Example: { "name": "Alice", 42: true }
+    /// Note: The opening '{' has already been consumed
+    fn parse_dictionary_literal(&mut self) -> Result<AstNode, ParseError> {
+        let mut entries = Vec::new();
+
+        while !self.check(&TokenType::RBrace) {
+            let key = Box::new(self.parse_expression()?);
+            self.consume(&TokenType::Colon, "Expected ':' after dictionary key")?;
+            let value = Box::new(self.parse_expression()?);
+            entries.push(DictionaryEntry { key, value });
+
+            // Allow trailing comma
+            if !self.match_token(&TokenType::Comma) {
+                break;
+            }
+        }
+
+        self.consume(&TokenType::RBrace, "Expected '}' after dictionary entries")?;
+
+        Ok(AstNode::DictionaryLiteral(entries))
+    }
+
     /// Parse an individual session member (field or method)
-    fn parse_session_member(&mut self) -> Result<SessionMember, String> {
+    fn parse_session_member(&mut self) -> Result<SessionMember, ParseError> {
+        // Destructor: finale { ... }
+        if self.match_token(&TokenType::Finale) {
+            self.consume(&TokenType::LBrace, "Expected '{' after 'finale'")?;
+            let body = self.parse_block_statements(BlockContext::Regular);
+            self.consume(&TokenType::RBrace, "Expected '}' after finale block")?;
+            return Ok(SessionMember::Finale(body));
+        }
+
         let mut is_static = false;
         if self.match_token(&TokenType::Dominant) {
             is_static = true;
@@ -765,6 +1558,7 @@ impl Parser {
             if self.check(&TokenType::Suggestion)
                 || self.check(&TokenType::ImperativeSuggestion)
                 || self.check(&TokenType::DominantSuggestion)
+                || self.check(&TokenType::Mesmerize)
             {
                 return self.parse_session_method(is_static, Some(visibility));
             } else {
@@ -780,15 +1574,14 @@ impl Parser {
         &mut self,
         is_static: bool,
         visibility: SessionVisibility,
-    ) -> Result<SessionMember, String> {
+    ) -> Result<SessionMember, ParseError> {
         let name = self
             .consume(&TokenType::Identifier, "Expected field name in session")?
             .lexeme
             .clone();
 
         let type_annotation = if self.match_token(&TokenType::Colon) {
-            let type_token = self.advance();
-            Some(type_token.lexeme.clone())
+            Some(self.parse_type_annotation()?)
         } else {
             None
         };
@@ -817,8 +1610,9 @@ impl Parser {
         &mut self,
         mut is_static: bool,
         visibility: Option<SessionVisibility>,
-    ) -> Result<SessionMember, String> {
+    ) -> Result<SessionMember, ParseError> {
         let visibility = visibility.unwrap_or(SessionVisibility::Public);
+        let is_async = self.match_token(&TokenType::Mesmerize);
 
         let method_token = if self.match_token(&TokenType::Suggestion) {
             Some(TokenType::Suggestion)
@@ -832,68 +1626,96 @@ impl Parser {
         };
 
         if method_token.is_none() {
-            return Err("Expected 'suggestion' inside session".to_string());
+            return Err(self.error("Expected 'suggestion' inside session"));
         }
 
         let mut is_constructor = false;
+        let mut operator = None;
         let name = if self.match_token(&TokenType::Constructor) {
+            if is_async {
+                return Err(self.error("Constructors cannot be declared with 'mesmerize'"));
+            }
             is_constructor = true;
             "constructor".to_string()
+        } else if let Some(symbol) = self.parse_operator_method_name()? {
+            operator = Some(symbol);
+            operator_method_name(symbol)
         } else {
             self.consume(&TokenType::Identifier, "Expected method name")?
                 .lexeme
                 .clone()
         };
+        let type_parameters = self.parse_type_parameters()?;
+        if is_constructor && !type_parameters.is_empty() {
+            return Err(self.error("Constructors cannot declare type parameters"));
+        }
 
         self.consume(&TokenType::LParen, "Expected '(' after method name")?;
 
-        let mut parameters = Vec::new();
-        if !self.check(&TokenType::RParen) {
-            loop {
-                let param_name = self
-                    .consume(&TokenType::Identifier, "Expected parameter name")?
-                    .lexeme
-                    .clone();
-                let type_annotation = if self.match_token(&TokenType::Colon) {
-                    let type_token = self.advance();
-                    Some(type_token.lexeme.clone())
-                } else {
-                    None
-                };
-                parameters.push(Parameter::new(param_name, type_annotation));
-
-                if !self.match_token(&TokenType::Comma) {
-                    break;
-                }
-            }
+        let parameters = self.parse_parameters()?;
+        if operator.is_some()
+            && (is_static || is_async || parameters.len() != 1 || parameters[0].is_rest)
+        {
+            return Err(self.error(format!(
+                "Operator method '{}' must be an instance method with exactly one parameter",
+                name
+            )));
         }
 
-        self.consume(&TokenType::RParen, "Expected ')' after parameters")?;
-
         let return_type = if self.match_token(&TokenType::Colon) {
-            let type_token = self.advance();
-            Some(type_token.lexeme.clone())
+            Some(self.parse_type_annotation()?)
         } else {
             None
         };
 
         self.consume(&TokenType::LBrace, "Expected '{' after method signature")?;
-        let body = self.parse_block_statements(BlockContext::Regular)?;
+        let body = self.parse_block_statements(BlockContext::Regular);
         self.consume(&TokenType::RBrace, "Expected '}' after method body")?;
 
         Ok(SessionMember::Method(SessionMethod {
             name,
+            type_parameters,
             parameters,
             return_type,
             body,
             visibility,
             is_static,
             is_constructor,
+            is_async,
         }))
     }
 
+    /// Parse the operator of an overload (`operator +`, `operator lookAtTheWatch`).
+    ///
+    /// Returns `None` if the method is not an operator overload; a method
+    /// called `operator` stays an ordinary method.
+    fn parse_operator_method_name(&mut self) -> Result<Option<&'static str>, ParseError> {
+        let is_overload = self.check(&TokenType::Identifier)
+            && self.peek().lexeme == "operator"
+            && self
+                .peek_next()
+                .is_some_and(|token| token.token_type.is_operator())
+            && self
+                .tokens
+                .get(self.current + 2)
+                .is_some_and(|token| token.token_type == TokenType::LParen);
+        if !is_overload {
+            return Ok(None);
+        }
+
+        self.advance();
+        let token = self.advance();
+        match overloadable_operator(&token.lexeme) {
+            Some(operator) => Ok(Some(operator)),
+            None => Err(ParseError::at(
+                &token,
+                format!("Operator '{}' cannot be overloaded", token.lexeme),
+            )),
+        }
+    }
+
     /// Parse observe statement
-    fn parse_observe_statement(&mut self) -> Result<AstNode, String> {
+    fn parse_observe_statement(&mut self) -> Result<AstNode, ParseError> {
         let expr = Box::new(self.parse_expression()?);
         self.consume(
             &TokenType::Semicolon,
@@ -903,7 +1725,7 @@ impl Parser {
     }
 
     /// Parse return statement
-    fn parse_return_statement(&mut self) -> Result<AstNode, String> {
+    fn parse_return_statement(&mut self) -> Result<AstNode, ParseError> {
         let value = if !self.check(&TokenType::Semicolon) {
             Some(Box::new(self.parse_expression()?))
         } else {
@@ -914,12 +1736,12 @@ impl Parser {
     }
 
     /// Parse expression
-    fn parse_expression(&mut self) -> Result<AstNode, String> {
+    fn parse_expression(&mut self) -> Result<AstNode, ParseError> {
         self.parse_assignment()
     }
 
     /// Parse assignment
-    fn parse_assignment(&mut self) -> Result<AstNode, String> {
+    fn parse_assignment(&mut self) -> Result<AstNode, ParseError> {
         let expr = self.parse_nullish_coalescing()?;
 
         if self.match_token(&TokenType::Equals) {
@@ -930,11 +1752,30 @@ impl Parser {
             });
         }
 
+        if self.match_tokens(&[
+            TokenType::PlusEquals,
+            TokenType::MinusEquals,
+            TokenType::AsteriskEquals,
+            TokenType::SlashEquals,
+            TokenType::PercentEquals,
+            TokenType::QuestionQuestionEquals,
+        ]) {
+            // `+=` applies `+`, `??=` applies `??`
+            let lexeme = self.previous().lexeme;
+            let operator = lexeme[..lexeme.len() - 1].to_string();
+            let value = Box::new(self.parse_assignment()?);
+            return Ok(AstNode::CompoundAssignmentExpression {
+                target: Box::new(expr),
+                operator,
+                value,
+            });
+        }
+
         Ok(expr)
     }
 
     /// Parse nullish coalescing (?? or lucidFallback)
-    fn parse_nullish_coalescing(&mut self) -> Result<AstNode, String> {
+    fn parse_nullish_coalescing(&mut self) -> Result<AstNode, ParseError> {
         let mut left = self.parse_logical_or()?;
 
         while self.match_tokens(&[TokenType::QuestionQuestion, TokenType::LucidFallback]) {
@@ -949,7 +1790,7 @@ impl Parser {
     }
 
     /// Parse logical OR
-    fn parse_logical_or(&mut self) -> Result<AstNode, String> {
+    fn parse_logical_or(&mut self) -> Result<AstNode, ParseError> {
         let mut left = self.parse_logical_and()?;
 
         while self.match_tokens(&[TokenType::PipePipe, TokenType::ResistanceIsFutile]) {
@@ -966,7 +1807,7 @@ impl Parser {
     }
 
     /// Parse logical AND
-    fn parse_logical_and(&mut self) -> Result<AstNode, String> {
+    fn parse_logical_and(&mut self) -> Result<AstNode, ParseError> {
         let mut left = self.parse_equality()?;
 
         while self.match_tokens(&[TokenType::AmpAmp, TokenType::UnderMyControl]) {
@@ -983,7 +1824,7 @@ impl Parser {
     }
 
     /// Parse equality
-    fn parse_equality(&mut self) -> Result<AstNode, String> {
+    fn parse_equality(&mut self) -> Result<AstNode, ParseError> {
         let mut left = self.parse_comparison()?;
 
         while self.match_tokens(&[
@@ -1006,8 +1847,8 @@ impl Parser {
     }
 
     /// Parse comparison
-    fn parse_comparison(&mut self) -> Result<AstNode, String> {
-        let mut left = self.parse_term()?;
+    fn parse_comparison(&mut self) -> Result<AstNode, ParseError> {
+        let mut left = self.parse_range()?;
 
         while self.match_tokens(&[
             TokenType::Greater,
@@ -1022,7 +1863,7 @@ impl Parser {
             TokenType::DeeplyLess,
         ]) {
             let operator = self.previous().lexeme.clone();
-            let right = Box::new(self.parse_term()?);
+            let right = Box::new(self.parse_range()?);
             left = AstNode::BinaryExpression {
                 left: Box::new(left),
                 operator,
@@ -1033,8 +1874,123 @@ impl Parser {
         Ok(left)
     }
 
+    /// Parse range (start..end, start..=end, optionally followed by `step n`)
+    fn parse_range(&mut self) -> Result<AstNode, ParseError> {
+        let start = self.parse_bitwise_or()?;
+
+        if !self.match_tokens(&[TokenType::DotDot, TokenType::DotDotEquals]) {
+            return Ok(start);
+        }
+        let inclusive = self.previous().token_type == TokenType::DotDotEquals;
+        let end = self.parse_bitwise_or()?;
+
+        // `step` is a contextual keyword, so it stays usable as a name
+        let step = if self.check(&TokenType::Identifier)
+            && self.peek().lexeme.eq_ignore_ascii_case("step")
+        {
+            self.advance();
+            Some(Box::new(self.parse_bitwise_or()?))
+        } else {
+            None
+        };
+
+        Ok(AstNode::RangeExpression {
+            start: Box::new(start),
+            end: Box::new(end),
+            inclusive,
+            step,
+        })
+    }
+
+    /// Parse bitwise or (`|`)
+    fn parse_bitwise_or(&mut self) -> Result<AstNode, ParseError> {
+        let mut left = self.parse_bitwise_xor()?;
+
+        while self.match_token(&TokenType::Pipe) {
+            let right = Box::new(self.parse_bitwise_xor()?);
+            left = AstNode::BinaryExpression {
+                left: Box::new(left),
+                operator: "|".to_string(),
+                right,
+            };
+        }
+
+        Ok(left)
+    }
+
+    /// Parse bitwise xor (`^`)
+    fn parse_bitwise_xor(&mut self) -> Result<AstNode, ParseError> {
+        let mut left = self.parse_bitwise_and()?;
+
+        while self.match_token(&TokenType::Caret) {
+            let right = Box::new(self.parse_bitwise_and()?);
+            left = AstNode::BinaryExpression {
+                left: Box::new(left),
+                operator: "^".to_string(),
+                right,
+            };
+        }
+
+        Ok(left)
+    }
+
+    /// Parse bitwise and (`&`)
+    fn parse_bitwise_and(&mut self) -> Result<AstNode, ParseError> {
+        let mut left = self.parse_shift()?;
+
+        while self.match_token(&TokenType::Ampersand) {
+            let right = Box::new(self.parse_shift()?);
+            left = AstNode::BinaryExpression {
+                left: Box::new(left),
+                operator: "&".to_string(),
+                right,
+            };
+        }
+
+        Ok(left)
+    }
+
+    /// Parse shifts (`<<`, `>>`)
+    fn parse_shift(&mut self) -> Result<AstNode, ParseError> {
+        let mut left = self.parse_term()?;
+
+        loop {
+            let operator = if self.match_token(&TokenType::LessLess) {
+                "<<"
+            } else if self.at_right_shift() {
+                self.advance();
+                self.advance();
+                ">>"
+            } else {
+                break;
+            };
+            let right = Box::new(self.parse_term()?);
+            left = AstNode::BinaryExpression {
+                left: Box::new(left),
+                operator: operator.to_string(),
+                right,
+            };
+        }
+
+        Ok(left)
+    }
+
+    /// Whether the next tokens are two adjacent `>`, which form `>>`.
+    ///
+    /// The lexer never produces a `>>` token so that nested type arguments
+    /// (`Array<Array<number>>`) keep closing one level per `>`.
+    fn at_right_shift(&self) -> bool {
+        let current = self.peek();
+        current.token_type == TokenType::Greater
+            && self.peek_next().is_some_and(|next| {
+                next.token_type == TokenType::Greater
+                    && next.line == current.line
+                    && next.column == current.column + 1
+            })
+    }
+
     /// Parse term (addition/subtraction)
-    fn parse_term(&mut self) -> Result<AstNode, String> {
+    fn parse_term(&mut self) -> Result<AstNode, ParseError> {
         let mut left = self.parse_factor()?;
 
         while self.match_tokens(&[TokenType::Plus, TokenType::Minus]) {
@@ -1051,7 +2007,7 @@ impl Parser {
     }
 
     /// Parse factor (multiplication/division/modulo)
-    fn parse_factor(&mut self) -> Result<AstNode, String> {
+    fn parse_factor(&mut self) -> Result<AstNode, ParseError> {
         let mut left = self.parse_unary()?;
 
         while self.match_tokens(&[TokenType::Asterisk, TokenType::Slash, TokenType::Percent]) {
@@ -1068,24 +2024,42 @@ impl Parser {
     }
 
     /// Parse unary
-    fn parse_unary(&mut self) -> Result<AstNode, String> {
+    fn parse_unary(&mut self) -> Result<AstNode, ParseError> {
         // Handle await/surrenderTo
         if self.match_tokens(&[TokenType::Await, TokenType::SurrenderTo]) {
             let expression = Box::new(self.parse_unary()?);
             return Ok(AstNode::AwaitExpression { expression });
         }
 
-        if self.match_tokens(&[TokenType::Bang, TokenType::Minus]) {
+        if self.match_tokens(&[TokenType::Bang, TokenType::Minus, TokenType::Tilde]) {
             let operator = self.previous().lexeme.clone();
             let operand = Box::new(self.parse_unary()?);
             return Ok(AstNode::UnaryExpression { operator, operand });
         }
 
-        self.parse_call()
-    }
-
-    /// Parse call expression
-    fn parse_call(&mut self) -> Result<AstNode, String> {
+        if self.match_tokens(&[TokenType::PlusPlus, TokenType::MinusMinus]) {
+            let operator = self.previous().lexeme.clone();
+            let target = Box::new(self.parse_unary()?);
+            return Ok(AstNode::UpdateExpression {
+                target,
+                operator,
+                prefix: true,
+            });
+        }
+
+        let expr = self.parse_call()?;
+        if self.match_tokens(&[TokenType::PlusPlus, TokenType::MinusMinus]) {
+            return Ok(AstNode::UpdateExpression {
+                target: Box::new(expr),
+                operator: self.previous().lexeme.clone(),
+                prefix: false,
+            });
+        }
+        Ok(expr)
+    }
+
+    /// Parse call expression
+    fn parse_call(&mut self) -> Result<AstNode, ParseError> {
         let mut expr = self.parse_primary()?;
 
         loop {
@@ -1108,7 +2082,7 @@ impl Parser {
                         index,
                     };
                 } else {
-                    return Err("Expected property name or '[' after '?.'".to_string());
+                    return Err(self.error("Expected property name or '[' after '?.'"));
                 }
             } else if self.match_token(&TokenType::Dot) {
                 let property = self
@@ -1135,12 +2109,31 @@ impl Parser {
     }
 
     /// Finish parsing a call expression
-    fn finish_call(&mut self, callee: AstNode) -> Result<AstNode, String> {
+    fn finish_call(&mut self, callee: AstNode) -> Result<AstNode, ParseError> {
         let mut arguments = Vec::new();
 
         if !self.check(&TokenType::RParen) {
             loop {
-                arguments.push(self.parse_expression()?);
+                let is_named = self.check(&TokenType::Identifier)
+                    && self
+                        .peek_next()
+                        .is_some_and(|token| token.token_type == TokenType::Colon);
+                if is_named {
+                    let name = self.advance().lexeme;
+                    self.advance();
+                    let value = self.parse_expression()?;
+                    arguments.push(AstNode::NamedArgument {
+                        name,
+                        value: Box::new(value),
+                    });
+                } else if arguments
+                    .iter()
+                    .any(|arg| matches!(arg, AstNode::NamedArgument { .. }))
+                {
+                    return Err(self.error("Positional arguments cannot follow named arguments"));
+                } else {
+                    arguments.push(self.parse_spreadable_expression()?);
+                }
                 if !self.match_token(&TokenType::Comma) {
                     break;
                 }
@@ -1155,29 +2148,87 @@ impl Parser {
         })
     }
 
+    /// Parse an array element or call argument, which may be spread with `...`
+    fn parse_spreadable_expression(&mut self) -> Result<AstNode, ParseError> {
+        if self.match_token(&TokenType::DotDotDot) {
+            let expr = self.parse_expression()?;
+            return Ok(AstNode::SpreadElement(Box::new(expr)));
+        }
+        self.parse_expression()
+    }
+
+    /// Parse interpolated string: "text ${expr} text ${expr} text"
+    fn parse_string_interpolation(&mut self) -> Result<AstNode, ParseError> {
+        let head = self.advance();
+        let mut parts = Vec::new();
+        if !head.lexeme.is_empty() {
+            parts.push(InterpolationPart::Text(head.lexeme));
+        }
+
+        loop {
+            let expression = self.parse_expression()?;
+            parts.push(InterpolationPart::Expression(Box::new(expression)));
+
+            let continues = self.check(&TokenType::StringTemplateMiddle);
+            let text = if continues {
+                self.advance()
+            } else {
+                self.consume(
+                    &TokenType::StringTemplateTail,
+                    "Expected '}' to close string interpolation",
+                )?
+            };
+            if !text.lexeme.is_empty() {
+                parts.push(InterpolationPart::Text(text.lexeme));
+            }
+
+            if !continues {
+                break;
+            }
+        }
+
+        Ok(AstNode::StringInterpolation(parts))
+    }
+
     /// Parse primary expression
-    fn parse_primary(&mut self) -> Result<AstNode, String> {
+    fn parse_primary(&mut self) -> Result<AstNode, ParseError> {
         // Entrain (pattern matching) expression
         if self.check(&TokenType::Entrain) {
             return self.parse_entrain_expression();
         }
 
+        // Anonymous suggestion (closure)
+        if self.match_token(&TokenType::Suggestion) {
+            return self.parse_function_expression();
+        }
+
         // Number literal
         if self.check(&TokenType::NumberLiteral) {
             let token = self.advance();
             let value = token
                 .lexeme
                 .parse::<f64>()
-                .map_err(|_| format!("Invalid number: {}", token.lexeme))?;
+                .map_err(|_| ParseError::at(&token, format!("Invalid number: {}", token.lexeme)))?;
             return Ok(AstNode::NumberLiteral(value));
         }
 
+        // Integer literal
+        if self.check(&TokenType::IntegerLiteral) {
+            let token = self.advance();
+            return Ok(AstNode::IntegerLiteral(self.integer_value(&token)?));
+        }
+
         // String literal
         if self.check(&TokenType::StringLiteral) {
             let token = self.advance();
             return Ok(AstNode::StringLiteral(token.lexeme.clone()));
         }
 
+        // Interpolated string
+        if self.check(&TokenType::StringTemplateHead) {
+            return self.parse_string_interpolation();
+        }
+
         // Boolean literals
         if self.match_token(&TokenType::True) {
             return Ok(AstNode::BooleanLiteral(true));
@@ -1185,6 +2236,9 @@ impl Parser {
         if self.match_token(&TokenType::False) {
             return Ok(AstNode::BooleanLiteral(false));
         }
+        if self.match_token(&TokenType::Null) {
+            return Ok(AstNode::NullLiteral);
+        }
 
         // Identifier or Record Literal
         if self.check(&TokenType::Identifier) {
@@ -1212,7 +2266,7 @@ impl Parser {
             let mut elements = Vec::new();
             if !self.check(&TokenType::RBracket) {
                 loop {
-                    elements.push(self.parse_expression()?);
+                    elements.push(self.parse_spreadable_expression()?);
                     if !self.match_token(&TokenType::Comma) {
                         break;
                     }
@@ -1222,6 +2276,11 @@ impl Parser {
             return Ok(AstNode::ArrayLiteral(elements));
         }
 
+        // Dictionary literal
+        if self.match_token(&TokenType::LBrace) {
+            return self.parse_dictionary_literal();
+        }
+
         // Grouped expression
         if self.match_token(&TokenType::LParen) {
             let expr = self.parse_expression()?;
@@ -1229,11 +2288,11 @@ impl Parser {
             return Ok(expr);
         }
 
-        Err(format!("Unexpected token: {:?}", self.peek()))
+        Err(self.error("Unexpected token"))
     }
 
     /// Parse entrain (pattern matching) expression
-    fn parse_entrain_expression(&mut self) -> Result<AstNode, String> {
+    fn parse_entrain_expression(&mut self) -> Result<AstNode, ParseError> {
         self.consume(&TokenType::Entrain, "Expected 'entrain'")?;
         let subject = Box::new(self.parse_expression()?);
         self.consume(&TokenType::LBrace, "Expected '{' after entrain subject")?;
@@ -1283,16 +2342,22 @@ impl Parser {
     }
 
     /// Parse pattern for matching
-    fn parse_pattern(&mut self) -> Result<Pattern, String> {
+    fn parse_pattern(&mut self) -> Result<Pattern, ParseError> {
         // Literal patterns
         if self.check(&TokenType::NumberLiteral) {
             let token = self.advance();
             let value = token
                 .lexeme
                 .parse::<f64>()
-                .map_err(|_| format!("Invalid number: {}", token.lexeme))?;
+                .map_err(|_| ParseError::at(&token, format!("Invalid number: {}", token.lexeme)))?;
             return Ok(Pattern::Literal(Box::new(AstNode::NumberLiteral(value))));
         }
+        if self.check(&TokenType::IntegerLiteral) {
+            let token = self.advance();
+            return Ok(Pattern::Literal(Box::new(AstNode::IntegerLiteral(
+                self.integer_value(&token)?,
+            ))));
+        }
 
         if self.check(&TokenType::StringLiteral) {
             let token = self.advance();
@@ -1309,6 +2374,10 @@ impl Parser {
             return Ok(Pattern::Literal(Box::new(AstNode::BooleanLiteral(false))));
         }
 
+        if self.match_token(&TokenType::Null) {
+            return Ok(Pattern::Literal(Box::new(AstNode::NullLiteral)));
+        }
+
         // Array pattern: [first, second, ...rest]
         if self.match_token(&TokenType::LBracket) {
             let mut elements = Vec::new();
@@ -1336,6 +2405,11 @@ impl Parser {
             return Ok(Pattern::Array { elements, rest });
         }
 
+        // Anonymous record pattern: { field1, field2 }
+        if self.match_token(&TokenType::LBrace) {
+            return self.parse_record_pattern(String::new());
+        }
+
         // Record pattern or identifier with type annotation
         if self.check(&TokenType::Identifier) {
             let name = self.advance().lexeme.clone();
@@ -1349,56 +2423,82 @@ impl Parser {
                 });
             }
 
-            // Check for record pattern: TypeName { field1, field2 }
-            if self.match_token(&TokenType::LBrace) {
-                let type_name = name.clone();
-                let mut fields = Vec::new();
-
-                if !self.check(&TokenType::RBrace) {
-                    loop {
-                        let field_name = self
-                            .consume(
-                                &TokenType::Identifier,
-                                "Expected field name in record pattern",
-                            )?
-                            .lexeme
-                            .clone();
-
-                        let pattern = if self.match_token(&TokenType::Colon) {
-                            Some(Box::new(self.parse_pattern()?))
-                        } else {
-                            None
-                        };
-
-                        fields.push(RecordFieldPattern {
-                            name: field_name,
-                            pattern,
-                        });
-
-                        if !self.match_token(&TokenType::Comma) {
-                            break;
+            // Check for variant pattern: Enum.Variant or Enum.Variant(payload, ...)
+            if self.match_token(&TokenType::Dot) {
+                let variant = self
+                    .consume(&TokenType::Identifier, "Expected variant name after '.'")?
+                    .lexeme
+                    .clone();
+                let mut payload = Vec::new();
+                if self.match_token(&TokenType::LParen) {
+                    if !self.check(&TokenType::RParen) {
+                        loop {
+                            payload.push(self.parse_pattern()?);
+                            if !self.match_token(&TokenType::Comma) {
+                                break;
+                            }
                         }
                     }
+                    self.consume(&TokenType::RParen, "Expected ')' after variant pattern")?;
                 }
+                return Ok(Pattern::Variant {
+                    enum_name: name,
+                    variant,
+                    payload,
+                });
+            }
 
-                self.consume(&TokenType::RBrace, "Expected '}' after record pattern")?;
-                return Ok(Pattern::Record { type_name, fields });
+            // Check for record pattern: TypeName { field1, field2 }
+            if self.match_token(&TokenType::LBrace) {
+                return self.parse_record_pattern(name);
             }
 
             // Simple identifier binding
             return Ok(Pattern::Identifier(name));
         }
 
-        Err(format!("Expected pattern, got {:?}", self.peek()))
+        Err(self.error("Expected pattern"))
+    }
+
+    /// Parse the fields of a record pattern after its opening '{'
+    fn parse_record_pattern(&mut self, type_name: String) -> Result<Pattern, ParseError> {
+        let mut fields = Vec::new();
+
+        if !self.check(&TokenType::RBrace) {
+            loop {
+                let field_name = self
+                    .consume(
+                        &TokenType::Identifier,
+                        "Expected field name in record pattern",
+                    )?
+                    .lexeme
+                    .clone();
+
+                let pattern = if self.match_token(&TokenType::Colon) {
+                    Some(Box::new(self.parse_pattern()?))
+                } else {
+                    None
+                };
+
+                fields.push(RecordFieldPattern {
+                    name: field_name,
+                    pattern,
+                });
+
+                if !self.match_token(&TokenType::Comma) {
+                    break;
+                }
+            }
+        }
+
+        self.consume(&TokenType::RBrace, "Expected '}' after record pattern")?;
+        Ok(Pattern::Record { type_name, fields })
     }
 
     /// Parse body of an entrain case (can be block or single expression)
-    fn parse_entrain_body(&mut self) -> Result<Vec<AstNode>, String> {
+    fn parse_entrain_body(&mut self) -> Result<Vec<AstNode>, ParseError> {
         if self.match_token(&TokenType::LBrace) {
-            let mut statements = Vec::new();
-            while !self.check(&TokenType::RBrace) && !self.is_at_end() {
-                statements.push(self.parse_statement(BlockContext::Regular)?);
-            }
+            let statements = self.parse_block_statements(BlockContext::Regular);
             self.consume(&TokenType::RBrace, "Expected '}' after block")?;
             Ok(statements)
         } else {
@@ -1408,9 +2508,43 @@ impl Parser {
     }
 
     /// Parse type annotation (returns the type as a string)
-    fn parse_type_annotation(&mut self) -> Result<String, String> {
-        // Accept identifiers and type keywords (number, string, boolean)
+    ///
+    /// Union members are joined with ` | `; optional members (`lucid T` or
+    /// `T?`) are written as `lucid T`.
+    fn parse_type_annotation(&mut self) -> Result<String, ParseError> {
+        let mut members = vec![self.parse_type_member()?];
+        while self.match_token(&TokenType::Pipe) {
+            members.push(self.parse_type_member()?);
+        }
+        Ok(members.join(" | "))
+    }
+
+    /// Parse a single member of a type annotation
+    fn parse_type_member(&mut self) -> Result<String, ParseError> {
+        if self.match_token(&TokenType::Lucid) {
+            return Ok(format!("lucid {}", self.parse_type_member()?));
+        }
+
+        // Accept identifiers, type keywords (number, string, boolean), literal
+        // types and parenthesized unions
         let type_name = match self.peek().token_type {
+            TokenType::LParen => {
+                self.advance();
+                let inner = self.parse_type_annotation()?;
+                self.consume(&TokenType::RParen, "Expected ')' after type")?;
+                format!("({})", inner)
+            }
+            TokenType::StringLiteral => format!("{:?}", self.advance().lexeme),
+            TokenType::NumberLiteral => self.advance().lexeme.clone(),
+            TokenType::Minus
+                if matches!(
+                    self.peek_next().map(|tok| &tok.token_type),
+                    Some(TokenType::NumberLiteral)
+                ) =>
+            {
+                self.advance();
+                format!("-{}", self.advance().lexeme)
+            }
             TokenType::Identifier => self.advance().lexeme.clone(),
             TokenType::Number => {
                 self.advance();
@@ -1424,8 +2558,42 @@ impl Parser {
                 self.advance();
                 "boolean".to_string()
             }
-            _ => return Err(format!("Expected type annotation, got {:?}", self.peek())),
+            // Other type keywords (e.g. `trance`) keep their spelling
+            _ if self.peek().token_type.is_keyword() => self.advance().lexeme.clone(),
+            _ => return Err(self.error("Expected type annotation")),
         };
+
+        // Generic arguments: Dictionary<string, number>
+        let mut type_name = type_name;
+        if self.match_token(&TokenType::Less) {
+            let mut arguments = Vec::new();
+            loop {
+                arguments.push(self.parse_type_annotation()?);
+                if !self.match_token(&TokenType::Comma) {
+                    break;
+                }
+            }
+            self.consume(&TokenType::Greater, "Expected '>' after type arguments")?;
+            type_name = format!("{}<{}>", type_name, arguments.join(", "));
+        }
+
+        // Array suffixes: number[], string[][]
+        while self.check(&TokenType::LBracket)
+            && matches!(
+                self.peek_next().map(|tok| &tok.token_type),
+                Some(TokenType::RBracket)
+            )
+        {
+            self.advance();
+            self.advance();
+            type_name.push_str("[]");
+        }
+
+        // Optional suffix: string?
+        if self.match_token(&TokenType::QuestionMark) {
+            type_name = format!("lucid {}", type_name);
+        }
+
         Ok(type_name)
     }
 
@@ -1439,6 +2607,19 @@ impl Parser {
         }
     }
 
+    /// Value of an integer literal token, which has to fit into 64 bits
+    fn integer_value(&self, token: &Token) -> Result<i64, ParseError> {
+        token.lexeme.parse::<i64>().map_err(|_| {
+            ParseError::at(
+                token,
+                format!(
+                    "Integer literal {}i does not fit into 64 bits",
+                    token.lexeme
+                ),
+            )
+        })
+    }
+
     fn match_tokens(&mut self, types: &[TokenType]) -> bool {
         for t in types {
             if self.check(t) {
@@ -1484,11 +2665,31 @@ impl Parser {
         }
     }
 
-    fn consume(&mut self, token_type: &TokenType, message: &str) -> Result<Token, String> {
+    fn consume(&mut self, token_type: &TokenType, message: &str) -> Result<Token, ParseError> {
         if self.check(token_type) {
             Ok(self.advance())
         } else {
-            Err(format!("{} at line {}", message, self.peek().line))
+            Err(self.error(message).with_expected(*token_type))
+        }
+    }
+
+    /// Build an error pointing at the current token
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::at(self.peek(), message)
+    }
+
+    /// Wrap a node with the span from `start` to the last consumed token
+    fn spanned(&self, start: &Token, node: AstNode) -> AstNode {
+        let end = self.previous();
+        let span = Span::new(
+            start.line,
+            start.column,
+            end.line,
+            end.column + end.lexeme.chars().count(),
+        );
+        AstNode::Spanned {
+            span,
+            node: Box::new(node),
         }
     }
 }
@@ -1598,7 +2799,11 @@ Focus {
         let ast = parser.parse_program();
         assert!(ast.is_err());
         let error = ast.err().unwrap();
-        assert!(error.contains("Triggers can only be declared at the top level"));
+        assert!(
+            error
+                .message
+                .contains("Triggers can only be declared at the top level")
+        );
     }
 
     #[test]
@@ -1618,6 +2823,1183 @@ Focus {
         let ast = parser.parse_program();
         assert!(ast.is_err());
         let error = ast.err().unwrap();
-        assert!(error.contains("'entrance' blocks are only allowed at the top level"));
+        assert!(
+            error
+                .message
+                .contains("'entrance' blocks are only allowed at the top level")
+        );
+    }
+
+    #[test]
+    fn test_statements_carry_source_spans() {
+        let source = "Focus {\n    induce x: number = 42;\n    observe x;\n} Relax";
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        let span = statements[1].span().expect("statement should carry a span");
+        assert_eq!((span.line, span.column), (3, 5));
+        assert!(matches!(
+            statements[1].unspanned(),
+            AstNode::ObserveStatement(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_error_reports_position_and_tokens() {
+        let source = "Focus {\n    induce x = 1\n    observe x;\n} Relax";
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let error = parser.parse_program().unwrap_err();
+
+        assert_eq!(error.line, 3);
+        assert_eq!(error.column, 5);
+        assert_eq!(error.expected, Some(TokenType::Semicolon));
+        assert_eq!(
+            error.found.as_ref().map(|token| token.lexeme.as_str()),
+            Some("observe")
+        );
+    }
+
+    #[test]
+    fn test_recovery_reports_all_errors_with_partial_ast() {
+        let source = r#"
+Focus {
+    induce a: number = ;
+    induce b: number = 2;
+    observe (b;
+    if (b > 1) {
+        induce c = 3
+    }
+    observe b;
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let (ast, errors) = parser.parse_program_with_diagnostics();
+
+        assert_eq!(errors.len(), 3, "errors: {:?}", errors);
+        assert_eq!(
+            errors.iter().map(|error| error.line).collect::<Vec<_>>(),
+            vec![3, 5, 8]
+        );
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        // `b`, the `if` (with its broken body dropped) and the final observe survive
+        assert_eq!(statements.len(), 3);
+        assert!(matches!(
+            statements[2].unspanned(),
+            AstNode::ObserveStatement(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_anonymous_suggestion_expressions() {
+        let source = r#"
+Focus {
+    induce double = suggestion (x: number): number => x * 2;
+    induce log = suggestion (message) {
+        observe message;
+    };
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        let AstNode::VariableDeclaration {
+            initializer: Some(init),
+            ..
+        } = statements[0].unspanned()
+        else {
+            panic!("expected variable declaration");
+        };
+        match init.as_ref() {
+            AstNode::FunctionExpression {
+                parameters,
+                return_type,
+                body,
+            } => {
+                assert_eq!(parameters.len(), 1);
+                assert_eq!(return_type.as_deref(), Some("number"));
+                assert!(matches!(
+                    body.as_slice(),
+                    [AstNode::ReturnStatement(Some(_))]
+                ));
+            }
+            other => panic!("expected function expression, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_string_interpolation() {
+        let source = r#"Focus { observe "Hello ${name}, depth ${level * 2}"; } Relax"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        let AstNode::ObserveStatement(value) = statements[0].unspanned() else {
+            panic!("expected observe statement");
+        };
+        let AstNode::StringInterpolation(parts) = value.as_ref() else {
+            panic!("expected string interpolation, got {:?}", value);
+        };
+        assert_eq!(parts.len(), 4);
+        assert_eq!(parts[0], InterpolationPart::Text("Hello ".to_string()));
+        assert!(matches!(&parts[1], InterpolationPart::Expression(expr)
+            if **expr == AstNode::Identifier("name".to_string())));
+        assert_eq!(parts[2], InterpolationPart::Text(", depth ".to_string()));
+        assert!(matches!(&parts[3], InterpolationPart::Expression(expr)
+            if matches!(expr.as_ref(), AstNode::BinaryExpression { .. })));
+    }
+
+    #[test]
+    fn test_unclosed_interpolation_is_reported() {
+        let source = r#"Focus { observe "value ${a b}"; } Relax"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let error = parser.parse_program().unwrap_err();
+        assert!(error.message.contains("close string interpolation"));
+    }
+
+    #[test]
+    fn test_recovery_skips_unclosed_blocks() {
+        let source = r#"
+Focus {
+    loop (induce i: number = 0; i < 3; i = i + 1) {
+        induce plan = i;
+
+        deepFocus (plan > 1) {
+            observe plan;
+            snap;
+        }
+    }
+
+    loop (induce j: number = 0; j < 3; j = j + 1) {
+        observe j;
+    }
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let (ast, errors) = parser.parse_program_with_diagnostics();
+
+        assert_eq!(
+            errors
+                .iter()
+                .map(|error| (error.line, error.message.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(6, "Unexpected token")],
+            "errors: {:?}",
+            errors
+        );
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        // The statements after the broken block are still parsed
+        assert_eq!(statements.len(), 2);
+        assert!(matches!(
+            statements[1].unspanned(),
+            AstNode::LoopStatement { .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_attempt_recover_finally() {
+        let source = r#"
+Focus {
+    try {
+        disrupt "boom", 42;
+    } catch (err) {
+        observe err.message;
+    } finally {
+        observe "done";
+    }
+    attempt { observe 1; } recover { observe 2; }
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        match statements[0].unspanned() {
+            AstNode::TryStatement {
+                body,
+                catch_binding,
+                catch_body,
+                finally_body,
+            } => {
+                assert!(matches!(
+                    body[0].unspanned(),
+                    AstNode::RaiseStatement {
+                        payload: Some(_),
+                        ..
+                    }
+                ));
+                assert_eq!(catch_binding.as_deref(), Some("err"));
+                assert_eq!(catch_body.as_ref().map(Vec::len), Some(1));
+                assert_eq!(finally_body.as_ref().map(Vec::len), Some(1));
+            }
+            other => panic!("expected try statement, got {:?}", other),
+        }
+        assert!(matches!(
+            statements[1].unspanned(),
+            AstNode::TryStatement {
+                catch_binding: None,
+                finally_body: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_attempt_requires_handler() {
+        let mut lexer = Lexer::new("Focus { attempt { observe 1; } observe 2; } Relax");
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let error = parser.parse_program().unwrap_err();
+        assert!(error.message.contains("'recover' or 'finally'"));
+    }
+
+    #[test]
+    fn test_parse_mindlink_and_expose() {
+        let source = r#"
+Focus {
+    mindLink { DefaultProfile, SessionProfile } from "./shared/runtime_state.hyp";
+    expose suggestion greet(name: string) {
+        observe name;
+    }
+    expose freeze VERSION = 2;
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        assert_eq!(
+            statements[0].unspanned(),
+            &AstNode::ImportDeclaration {
+                names: vec!["DefaultProfile".to_string(), "SessionProfile".to_string()],
+                source: "./shared/runtime_state.hyp".to_string(),
+            }
+        );
+        assert!(matches!(
+            statements[1].unspanned(),
+            AstNode::ExportDeclaration(inner)
+                if matches!(inner.as_ref(), AstNode::FunctionDeclaration { .. })
+        ));
+        assert_eq!(statements[2].declared_name(), Some("VERSION"));
+    }
+
+    #[test]
+    fn test_expose_requires_declaration() {
+        let mut lexer = Lexer::new("Focus { expose observe 1; } Relax");
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let error = parser.parse_program().unwrap_err();
+        assert!(error.message.contains("must be followed by a declaration"));
+    }
+
+    #[test]
+    fn test_parse_assert_statement_keeps_source_text() {
+        let source = r#"Focus { assert Length(name) == 3, "name too short"; assert ready; } Relax"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        match statements[0].unspanned() {
+            AstNode::AssertStatement {
+                condition,
+                message,
+                source,
+            } => {
+                assert!(matches!(
+                    condition.as_ref(),
+                    AstNode::BinaryExpression { .. }
+                ));
+                assert_eq!(
+                    message.as_deref(),
+                    Some(&AstNode::StringLiteral("name too short".to_string()))
+                );
+                assert_eq!(source, "Length(name) == 3");
+            }
+            other => panic!("expected assert statement, got {:?}", other),
+        }
+        assert!(matches!(
+            statements[1].unspanned(),
+            AstNode::AssertStatement { message: None, source, .. } if source == "ready"
+        ));
+    }
+
+    #[test]
+    fn test_parse_dictionary_literal_and_type() {
+        let source = r#"Focus { induce ages: Dictionary<string, number> = { "Ada": 36, "Alan": 41, }; induce empty = {}; } Relax"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        match statements[0].unspanned() {
+            AstNode::VariableDeclaration {
+                type_annotation,
+                initializer: Some(initializer),
+                ..
+            } => {
+                assert_eq!(
+                    type_annotation.as_deref(),
+                    Some("Dictionary<string, number>")
+                );
+                let AstNode::DictionaryLiteral(entries) = initializer.as_ref() else {
+                    panic!("expected dictionary literal, got {:?}", initializer);
+                };
+                assert_eq!(entries.len(), 2);
+                assert_eq!(
+                    entries[1].key.as_ref(),
+                    &AstNode::StringLiteral("Alan".to_string())
+                );
+                assert_eq!(entries[1].value.as_ref(), &AstNode::NumberLiteral(41.0));
+            }
+            other => panic!("expected variable declaration, got {:?}", other),
+        }
+        assert!(matches!(
+            statements[1].unspanned(),
+            AstNode::VariableDeclaration { initializer: Some(init), .. }
+                if matches!(init.as_ref(), AstNode::DictionaryLiteral(entries) if entries.is_empty())
+        ));
+    }
+
+    #[test]
+    fn test_parse_labeled_loops_and_jumps() {
+        let source = r#"
+Focus {
+    outer: loop {
+        label inner: while (true) {
+            snap outer;
+            sink inner;
+            sinkTo outer;
+            snap;
+        }
+    }
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        let AstNode::LabeledStatement { label, body } = statements[0].unspanned() else {
+            panic!("expected labeled loop, got {:?}", statements[0]);
+        };
+        assert_eq!(label, "outer");
+        let AstNode::LoopStatement { body, .. } = body.as_ref() else {
+            panic!("expected loop body, got {:?}", body);
+        };
+        let AstNode::LabeledStatement { label, body } = body[0].unspanned() else {
+            panic!("expected labeled while, got {:?}", body[0]);
+        };
+        assert_eq!(label, "inner");
+        let AstNode::WhileStatement { body, .. } = body.as_ref() else {
+            panic!("expected while body, got {:?}", body);
+        };
+        let jumps: Vec<&AstNode> = body.iter().map(AstNode::unspanned).collect();
+        assert_eq!(
+            jumps,
+            vec![
+                &AstNode::BreakStatement(Some("outer".to_string())),
+                &AstNode::ContinueStatement(Some("inner".to_string())),
+                &AstNode::ContinueStatement(Some("outer".to_string())),
+                &AstNode::BreakStatement(None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_label_requires_loop() {
+        let source = "Focus { here: observe 1; sinkTo; } Relax";
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let (_, errors) = parser.parse_program_with_diagnostics();
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert!(errors[0].message.contains("Only loops can be labeled"));
+        assert!(
+            errors[1]
+                .message
+                .contains("Expected loop label after 'sinkTo'")
+        );
+    }
+
+    #[test]
+    fn test_parse_spread_in_arrays_and_calls() {
+        let source = "Focus { induce all = [...first, 0, ...second]; forward(...all, 1); } Relax";
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        let spread =
+            |name: &str| AstNode::SpreadElement(Box::new(AstNode::Identifier(name.to_string())));
+        match statements[0].unspanned() {
+            AstNode::VariableDeclaration {
+                initializer: Some(initializer),
+                ..
+            } => assert_eq!(
+                initializer.as_ref(),
+                &AstNode::ArrayLiteral(vec![
+                    spread("first"),
+                    AstNode::NumberLiteral(0.0),
+                    spread("second"),
+                ])
+            ),
+            other => panic!("expected variable declaration, got {:?}", other),
+        }
+        match statements[1].unspanned() {
+            AstNode::ExpressionStatement(expr) => match expr.as_ref() {
+                AstNode::CallExpression { arguments, .. } => {
+                    assert_eq!(arguments, &vec![spread("all"), AstNode::NumberLiteral(1.0)]);
+                }
+                other => panic!("expected call, got {:?}", other),
+            },
+            other => panic!("expected expression statement, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_default_rest_and_named_arguments() {
+        let source = r#"
+Focus {
+    suggestion greet(name: string = "guest", ...tags: string[]) { }
+    greet(name: "Ada");
+} Relax
+"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.lex().unwrap();
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        let AstNode::FunctionDeclaration { parameters, .. } = statements[0].unspanned() else {
+            panic!("expected function declaration, got {:?}", statements[0]);
+        };
+        assert_eq!(
+            parameters,
+            &vec![
+                Parameter::new("name".to_string(), Some("string".to_string()))
+                    .with_default(AstNode::StringLiteral("guest".to_string())),
+                Parameter::new("tags".to_string(), Some("string[]".to_string())).rest(),
+            ]
+        );
+
+        match statements[1].unspanned() {
+            AstNode::ExpressionStatement(expr) => match expr.as_ref() {
+                AstNode::CallExpression { arguments, .. } => assert_eq!(
+                    arguments,
+                    &vec![AstNode::NamedArgument {
+                        name: "name".to_string(),
+                        value: Box::new(AstNode::StringLiteral("Ada".to_string())),
+                    }]
+                ),
+                other => panic!("expected call, got {:?}", other),
+            },
+            other => panic!("expected expression statement, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_parameter_and_argument_order_errors() {
+        for (source, expected) in [
+            (
+                "Focus { suggestion f(...rest, last) { } } Relax",
+                "Rest parameter must be the last parameter",
+            ),
+            (
+                "Focus { suggestion f(a = 1, b) { } } Relax",
+                "Parameter 'b' without default value cannot follow parameters with defaults",
+            ),
+            (
+                "Focus { suggestion f(...rest = []) { } } Relax",
+                "Rest parameter cannot have a default value",
+            ),
+            (
+                "Focus { f(a: 1, 2); } Relax",
+                "Positional arguments cannot follow named arguments",
+            ),
+        ] {
+            let tokens = Lexer::new(source).lex().unwrap();
+            let error = Parser::new(tokens).parse_program().unwrap_err();
+            assert!(error.message.contains(expected), "{}: {:?}", source, error);
+        }
+    }
+
+    #[test]
+    fn test_parse_mesmerize_suggestions() {
+        let source = r#"
+Focus {
+    mesmerize suggestion load(): string { awaken "x"; }
+    session Loader {
+        expose mesmerize suggestion fetch() { }
+    }
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        assert!(matches!(
+            statements[0].unspanned(),
+            AstNode::FunctionDeclaration { is_async: true, .. }
+        ));
+        let AstNode::SessionDeclaration { members, .. } = statements[1].unspanned() else {
+            panic!("expected session, got {:?}", statements[1]);
+        };
+        assert!(matches!(&members[0], SessionMember::Method(method) if method.is_async));
+
+        let source = "Focus { session S { mesmerize suggestion constructor() { } } } Relax";
+        let tokens = Lexer::new(source).lex().unwrap();
+        let error = Parser::new(tokens).parse_program().unwrap_err();
+        assert!(
+            error
+                .message
+                .contains("Constructors cannot be declared with 'mesmerize'")
+        );
+    }
+
+    #[test]
+    fn test_parse_generic_declarations() {
+        let source = r#"
+Focus {
+    suggestion first<T>(items: T[]): T { awaken items[0]; }
+    tranceify Pair<A, B> { left: A; right: B; }
+    session Stack<T> {
+        expose items: T[];
+        expose suggestion map<U>(items: U[]): Stack<U> { awaken Stack(items); }
+    }
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        let AstNode::FunctionDeclaration {
+            type_parameters,
+            return_type,
+            ..
+        } = statements[0].unspanned()
+        else {
+            panic!("expected function, got {:?}", statements[0]);
+        };
+        assert_eq!(type_parameters, &["T"]);
+        assert_eq!(return_type.as_deref(), Some("T"));
+        assert!(matches!(
+            statements[1].unspanned(),
+            AstNode::TranceifyDeclaration { type_parameters, .. } if type_parameters == &["A", "B"]
+        ));
+        let AstNode::SessionDeclaration {
+            type_parameters,
+            members,
+            ..
+        } = statements[2].unspanned()
+        else {
+            panic!("expected session, got {:?}", statements[2]);
+        };
+        assert_eq!(type_parameters, &["T"]);
+        let SessionMember::Method(method) = &members[1] else {
+            panic!("expected method, got {:?}", members[1]);
+        };
+        assert_eq!(method.type_parameters, ["U"]);
+        assert_eq!(method.return_type.as_deref(), Some("Stack<U>"));
+
+        for (source, message) in [
+            (
+                "Focus { suggestion f<T, T>(x: T) { } } Relax",
+                "Duplicate type parameter 'T'",
+            ),
+            (
+                "Focus { session S { suggestion constructor<T>() { } } } Relax",
+                "Constructors cannot declare type parameters",
+            ),
+        ] {
+            let tokens = Lexer::new(source).lex().unwrap();
+            let error = Parser::new(tokens).parse_program().unwrap_err();
+            assert!(error.message.contains(message), "{}", error.message);
+        }
+    }
+
+    #[test]
+    fn test_parse_union_optional_and_literal_types() {
+        let source = r#"
+Focus {
+    induce mode: "on" | "off" = "on";
+    induce count: lucid number = null;
+    induce name: string? = null;
+    induce level: 1 | -1 | Box<T>[] = 1;
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        let annotations: Vec<_> = statements
+            .iter()
+            .map(|statement| match statement.unspanned() {
+                AstNode::VariableDeclaration {
+                    type_annotation, ..
+                } => type_annotation.clone().unwrap(),
+                other => panic!("expected declaration, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            annotations,
+            [
+                "\"on\" | \"off\"",
+                "lucid number",
+                "lucid string",
+                "1 | -1 | Box<T>[]",
+            ]
+        );
+        assert!(matches!(
+            statements[1].unspanned(),
+            AstNode::VariableDeclaration { initializer: Some(init), .. }
+                if matches!(init.unspanned(), AstNode::NullLiteral)
+        ));
+    }
+
+    #[test]
+    fn test_parse_session_extends() {
+        let source = r#"
+Focus {
+    session Admin extends User { }
+    session Shelf<T> extends Stack<T> { }
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        let parents: Vec<_> = statements
+            .iter()
+            .map(|statement| match statement.unspanned() {
+                AstNode::SessionDeclaration { parent, .. } => parent.clone(),
+                other => panic!("expected session, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            parents,
+            [Some("User".to_string()), Some("Stack<T>".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_parse_interfaces() {
+        let source = r#"
+Focus {
+    interface Plugin {
+        name: string;
+        suggestion run(input: string): string;
+    }
+    session Echo extends Base implements Plugin, Named { }
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        let AstNode::InterfaceDeclaration { name, members } = statements[0].unspanned() else {
+            panic!("expected interface, got {:?}", statements[0]);
+        };
+        assert_eq!(name, "Plugin");
+        assert!(matches!(
+            &members[0],
+            InterfaceMember::Field(field) if field.name == "name" && field.type_annotation == "string"
+        ));
+        let InterfaceMember::Method(method) = &members[1] else {
+            panic!("expected method, got {:?}", members[1]);
+        };
+        assert_eq!(method.name, "run");
+        assert_eq!(method.parameters.len(), 1);
+        assert_eq!(method.return_type.as_deref(), Some("string"));
+
+        assert!(matches!(
+            statements[1].unspanned(),
+            AstNode::SessionDeclaration { parent: Some(parent), interfaces, .. }
+                if parent == "Base" && interfaces == &["Plugin", "Named"]
+        ));
+    }
+
+    #[test]
+    fn test_parse_compound_assignment_and_update() {
+        let source = r#"
+Focus {
+    totals[index] += 2 * 3;
+    name ??= "guest";
+    induce before = counter++;
+    induce after = --counter;
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        let AstNode::ExpressionStatement(expr) = statements[0].unspanned() else {
+            panic!("expected expression statement, got {:?}", statements[0]);
+        };
+        assert!(matches!(
+            expr.as_ref(),
+            AstNode::CompoundAssignmentExpression { target, operator, value }
+                if matches!(target.as_ref(), AstNode::IndexExpression { .. })
+                    && operator == "+"
+                    && matches!(value.as_ref(), AstNode::BinaryExpression { .. })
+        ));
+        let AstNode::ExpressionStatement(expr) = statements[1].unspanned() else {
+            panic!("expected expression statement, got {:?}", statements[1]);
+        };
+        assert!(matches!(
+            expr.as_ref(),
+            AstNode::CompoundAssignmentExpression { operator, .. } if operator == "??"
+        ));
+        assert!(matches!(
+            statements[2].unspanned(),
+            AstNode::VariableDeclaration { initializer: Some(init), .. }
+                if matches!(init.as_ref(), AstNode::UpdateExpression { operator, prefix: false, .. } if operator == "++")
+        ));
+        assert!(matches!(
+            statements[3].unspanned(),
+            AstNode::VariableDeclaration { initializer: Some(init), .. }
+                if matches!(init.as_ref(), AstNode::UpdateExpression { operator, prefix: true, .. } if operator == "--")
+        ));
+    }
+
+    #[test]
+    fn test_parse_enum_and_variant_patterns() {
+        let source = r#"
+Focus {
+    enum State { Idle, Running(number), Failed(string, number), }
+    induce text = entrain state {
+        when State.Running(speed) => "running";
+        when State.Failed(_, code) => "failed";
+        when State.Idle => "idle";
+    };
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        let AstNode::EnumDeclaration { name, variants } = statements[0].unspanned() else {
+            panic!("expected enum, got {:?}", statements[0]);
+        };
+        assert_eq!(name, "State");
+        assert_eq!(variants.len(), 3);
+        assert!(variants[0].payload.is_empty());
+        assert_eq!(variants[2].payload, vec!["string", "number"]);
+
+        let AstNode::VariableDeclaration {
+            initializer: Some(initializer),
+            ..
+        } = statements[1].unspanned()
+        else {
+            panic!("expected variable declaration, got {:?}", statements[1]);
+        };
+        let AstNode::EntrainExpression { cases, .. } = initializer.as_ref() else {
+            panic!("expected entrain, got {initializer:?}");
+        };
+        assert!(matches!(
+            &cases[0].pattern,
+            Pattern::Variant { enum_name, variant, payload }
+                if enum_name == "State" && variant == "Running"
+                    && payload == &[Pattern::Identifier("speed".to_string())]
+        ));
+        assert!(matches!(
+            &cases[2].pattern,
+            Pattern::Variant { variant, payload, .. } if variant == "Idle" && payload.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_parse_session_finale_and_using() {
+        let source = r#"
+Focus {
+    session Log {
+        finale { observe "closed"; }
+        expose suggestion write(line: string) { observe line; }
+    }
+    using (log = Log()) {
+        log.write("hi");
+    }
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        let AstNode::SessionDeclaration { members, .. } = statements[0].unspanned() else {
+            panic!("expected session, got {:?}", statements[0]);
+        };
+        assert!(matches!(&members[0], SessionMember::Finale(body) if body.len() == 1));
+        assert!(matches!(&members[1], SessionMember::Method(method) if method.name == "write"));
+
+        let AstNode::UsingStatement {
+            name,
+            resource,
+            body,
+        } = statements[1].unspanned()
+        else {
+            panic!("expected using statement, got {:?}", statements[1]);
+        };
+        assert_eq!(name, "log");
+        assert!(matches!(resource.as_ref(), AstNode::CallExpression { .. }));
+        assert_eq!(body.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_for_each_and_ranges() {
+        let source = r#"
+Focus {
+    loop (induce key, value in scores) { }
+    pendulum (freeze n in 10..=0 step -2) { }
+    induce span = 0..count + 1;
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        assert!(matches!(
+            statements[0].unspanned(),
+            AstNode::ForEachStatement { key: Some(key), value, is_constant: false, .. }
+                if key == "key" && value == "value"
+        ));
+
+        let AstNode::ForEachStatement {
+            key: None,
+            is_constant: true,
+            iterable,
+            ..
+        } = statements[1].unspanned()
+        else {
+            panic!("expected for-each, got {:?}", statements[1]);
+        };
+        assert!(matches!(
+            iterable.as_ref(),
+            AstNode::RangeExpression {
+                inclusive: true,
+                step: Some(_),
+                ..
+            }
+        ));
+
+        let AstNode::VariableDeclaration {
+            initializer: Some(initializer),
+            ..
+        } = statements[2].unspanned()
+        else {
+            panic!("expected variable declaration, got {:?}", statements[2]);
+        };
+        assert!(matches!(
+            initializer.as_ref(),
+            AstNode::RangeExpression { end, inclusive: false, step: None, .. }
+                if matches!(end.as_ref(), AstNode::BinaryExpression { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_integer_literals_and_bitwise_precedence() {
+        let source = r#"
+Focus {
+    induce mask = flags | bits & 1i << 2 >> shift ^ ~0i;
+    induce nested: Dictionary<string, Array<number>> = {};
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        let AstNode::VariableDeclaration {
+            initializer: Some(initializer),
+            ..
+        } = statements[0].unspanned()
+        else {
+            panic!("expected variable declaration, got {:?}", statements[0]);
+        };
+        // `|` binds loosest, then `^`, `&` and the shifts
+        let AstNode::BinaryExpression {
+            operator, right, ..
+        } = initializer.as_ref()
+        else {
+            panic!("expected binary expression, got {:?}", initializer);
+        };
+        assert_eq!(operator, "|");
+        let AstNode::BinaryExpression {
+            left: xor_left,
+            operator,
+            right: xor_right,
+        } = right.as_ref()
+        else {
+            panic!("expected xor, got {:?}", right);
+        };
+        assert_eq!(operator, "^");
+        assert!(matches!(
+            xor_right.as_ref(),
+            AstNode::UnaryExpression { operator, operand }
+                if operator == "~" && **operand == AstNode::IntegerLiteral(0)
+        ));
+        let AstNode::BinaryExpression {
+            operator, right, ..
+        } = xor_left.as_ref()
+        else {
+            panic!("expected bitwise and, got {:?}", xor_left);
+        };
+        assert_eq!(operator, "&");
+        assert!(matches!(
+            right.as_ref(),
+            AstNode::BinaryExpression { left, operator, .. }
+                if operator == ">>"
+                    && matches!(left.as_ref(), AstNode::BinaryExpression { operator, .. } if operator == "<<")
+        ));
+
+        assert!(matches!(
+            statements[1].unspanned(),
+            AstNode::VariableDeclaration { type_annotation: Some(annotation), .. }
+                if annotation == "Dictionary<string, Array<number>>"
+        ));
+
+        let source = "Focus { induce big = 9223372036854775808i; } Relax";
+        let error = Parser::new(Lexer::new(source).lex().unwrap())
+            .parse_program()
+            .unwrap_err();
+        assert!(error.to_string().contains("does not fit into 64 bits"));
+    }
+
+    #[test]
+    fn test_parse_destructuring_declarations_and_parameters() {
+        let source = r#"
+Focus {
+    induce [first, ...rest] = items;
+    freeze { name, age: years } = person;
+    induce Person { name: who }: Person = person;
+    suggestion sum([a, b]: number[], { scale } = defaults): number {
+        awaken (a + b) * scale;
+    }
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        let AstNode::DestructuringDeclaration {
+            pattern,
+            is_constant: false,
+            ..
+        } = statements[0].unspanned()
+        else {
+            panic!(
+                "expected destructuring declaration, got {:?}",
+                statements[0]
+            );
+        };
+        assert_eq!(
+            *pattern,
+            Pattern::Array {
+                elements: vec![Pattern::Identifier("first".to_string())],
+                rest: Some("rest".to_string()),
+            }
+        );
+        let AstNode::DestructuringDeclaration {
+            pattern,
+            is_constant: true,
+            ..
+        } = statements[1].unspanned()
+        else {
+            panic!("expected frozen destructuring, got {:?}", statements[1]);
+        };
+        assert_eq!(pattern.to_string(), "{ name, age: years }");
+        assert!(matches!(
+            statements[2].unspanned(),
+            AstNode::DestructuringDeclaration {
+                pattern: Pattern::Record { type_name, .. },
+                type_annotation: Some(annotation),
+                ..
+            } if type_name == "Person" && annotation == "Person"
+        ));
+
+        let AstNode::FunctionDeclaration { parameters, .. } = statements[3].unspanned() else {
+            panic!("expected suggestion, got {:?}", statements[3]);
+        };
+        assert_eq!(parameters[0].name, "[a, b]");
+        assert!(matches!(parameters[0].pattern, Some(Pattern::Array { .. })));
+        assert_eq!(parameters[1].name, "{ scale }");
+        assert!(parameters[1].default_value.is_some());
+
+        let tokens = Lexer::new("Focus { induce [a, b]; } Relax").lex().unwrap();
+        let error = Parser::new(tokens).parse_program().unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Expected '=' after destructuring pattern")
+        );
+    }
+
+    #[test]
+    fn test_parse_operator_methods() {
+        let source = r#"
+Focus {
+    session Vector {
+        suggestion operator +(other: Vector): Vector { awaken other; }
+        suggestion operator lookAtTheWatch(other: Vector): boolean { awaken true; }
+        suggestion operator(value: number): number { awaken value; }
+    }
+    tranceify Money {
+        cents: number;
+        suggestion operator ==(other: Money): boolean { awaken true; }
+    }
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        let AstNode::SessionDeclaration { members, .. } = statements[0].unspanned() else {
+            panic!("expected session, got {:?}", statements[0]);
+        };
+        let names: Vec<&str> = members
+            .iter()
+            .map(|member| match member {
+                SessionMember::Method(method) => method.name.as_str(),
+                other => panic!("expected method, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(names, ["operator+", "operator>", "operator"]);
+
+        let AstNode::TranceifyDeclaration {
+            fields, operators, ..
+        } = statements[1].unspanned()
+        else {
+            panic!("expected tranceify, got {:?}", statements[1]);
+        };
+        assert_eq!(fields.len(), 1);
+        assert_eq!(operators[0].name, "operator==");
+
+        for (source, message) in [
+            (
+                "Focus { session V { suggestion operator &&(o: V) { } } } Relax",
+                "Operator '&&' cannot be overloaded",
+            ),
+            (
+                "Focus { session V { suggestion operator +(a: V, b: V) { } } } Relax",
+                "Operator method 'operator+' must be an instance method with exactly one parameter",
+            ),
+            (
+                "Focus { tranceify M { suggestion total(): number { awaken 0; } } } Relax",
+                "Tranceify type 'M' can only declare operator methods, found 'total'",
+            ),
+        ] {
+            let tokens = Lexer::new(source).lex().unwrap();
+            let error = Parser::new(tokens).parse_program().unwrap_err();
+            assert!(error.to_string().contains(message), "{source}: {error}");
+        }
+    }
+
+    #[test]
+    fn test_parse_generator_suggestions() {
+        let source = r#"
+Focus {
+    suggestion evens(limit: number): Iterator<number> {
+        loop (induce i in 0..limit) {
+            if (i % 2 == 0) { emerge i; }
+        }
+    }
+    suggestion words(): Iterator<string> { yield "a"; }
+    suggestion plain(): number { induce f = suggestion() { emerge 1; }; awaken 1; }
+} Relax
+"#;
+        let tokens = Lexer::new(source).lex().unwrap();
+        let ast = Parser::new(tokens).parse_program().unwrap();
+
+        let AstNode::Program(statements) = ast else {
+            panic!("expected program node");
+        };
+        let generators: Vec<bool> = statements
+            .iter()
+            .map(|statement| match statement.unspanned() {
+                AstNode::FunctionDeclaration { body, .. } => crate::ast::is_generator_body(body),
+                other => panic!("expected suggestion, got {:?}", other),
+            })
+            .collect();
+        // `emerge` inside a closure makes the closure a generator, not its suggestion
+        assert_eq!(generators, [true, true, false]);
+
+        let AstNode::FunctionDeclaration { body, .. } = statements[1].unspanned() else {
+            panic!("expected suggestion");
+        };
+        assert!(matches!(
+            body[0].unspanned(),
+            AstNode::YieldStatement(value) if **value == AstNode::StringLiteral("a".to_string())
+        ));
     }
 }
@@ -36,6 +36,11 @@ pub enum TokenType {
     SinkTo,    // goto
     Oscillate, // toggle boolean
     Suspend,   // Pause without fixed end
+    Attempt,   // try
+    Recover,   // catch
+    Finally,   // finally (cleanup after attempt)
+    Disrupt,   // throw/raise an error
+    Using,     // scoped resource with deterministic cleanup
 
     // Functions
     Suggestion,           // Standard function
@@ -44,12 +49,14 @@ pub enum TokenType {
     DominantSuggestion,   // Static function modifier
     Mesmerize,            // Async function modifier
     Awaken,               // return
+    Emerge,               // yield (generator suggestions)
     Await,                // await async
     SurrenderTo,          // await (synonym)
     Call,
 
     // Object-oriented programming
     Session,
+    Extends, // inheritance
     Constructor,
     Expose,   // public
     Conceal,  // private
@@ -57,6 +64,9 @@ pub enum TokenType {
 
     // Structures
     Tranceify,
+    Interface,  // method and field contract
+    Implements, // session conforms to interfaces
+    Enum,       // sum type with variants
 
     // I/O
     Observe,        // Standard output with newline
@@ -103,21 +113,36 @@ pub enum TokenType {
     Asterisk,
     Slash,
     Percent,
-    Bang,             // !
-    AmpAmp,           // &&
-    PipePipe,         // ||
-    QuestionMark,     // ?
-    QuestionDot,      // ?.
-    QuestionQuestion, // ??
-    Pipe,             // | (for union types)
-    Ampersand,        // & (for intersection types)
-    Arrow,            // => (for pattern matching)
+    Bang,                   // !
+    AmpAmp,                 // &&
+    PipePipe,               // ||
+    QuestionMark,           // ?
+    QuestionDot,            // ?.
+    QuestionQuestion,       // ??
+    PlusEquals,             // +=
+    MinusEquals,            // -=
+    AsteriskEquals,         // *=
+    SlashEquals,            // /=
+    PercentEquals,          // %=
+    QuestionQuestionEquals, // ??=
+    PlusPlus,               // ++
+    MinusMinus,             // --
+    Pipe,                   // | (union types, bitwise or)
+    Ampersand,              // & (intersection types, bitwise and)
+    Caret,                  // ^ (bitwise xor)
+    Tilde,                  // ~ (bitwise not)
+    LessLess,               // << (`>>` is two adjacent `>` tokens, see the parser)
+    Arrow,                  // => (for pattern matching)
 
     // Literals and identifiers
     Identifier,
     NumberLiteral,
+    IntegerLiteral, // 42i
     StringLiteral,
     BooleanLiteral,
+    StringTemplateHead,   // "text before the first ${
+    StringTemplateMiddle, // }text between interpolations${
+    StringTemplateTail,   // }text after the last interpolation"
 
     // Types
     Number,
@@ -129,6 +154,7 @@ pub enum TokenType {
     // Boolean literals
     True,
     False,
+    Null,
 
     // Delimiters and brackets
     LParen,   // (
@@ -140,11 +166,13 @@ pub enum TokenType {
     LAngle,   // < (for generics)
     RAngle,   // > (for generics)
     Comma,
-    Colon,     // :
-    Semicolon, // ;
-    Dot,       // .
-    DotDotDot, // ... (spread operator)
-    Equals,    // =
+    Colon,        // :
+    Semicolon,    // ;
+    Dot,          // .
+    DotDotDot,    // ... (spread operator)
+    DotDot,       // .. (exclusive range)
+    DotDotEquals, // ..= (inclusive range)
+    Equals,       // =
 
     // End of file
     Eof,
@@ -367,6 +395,62 @@ static KEYWORD_DEFINITIONS: Lazy<HashMap<&'static str, KeywordDefinition>> = Laz
             canonical_lexeme: "suspend",
         },
     );
+    map.insert(
+        "attempt",
+        KeywordDefinition {
+            token: Attempt,
+            canonical_lexeme: "attempt",
+        },
+    );
+    map.insert(
+        "try",
+        KeywordDefinition {
+            token: Attempt,
+            canonical_lexeme: "attempt",
+        },
+    );
+    map.insert(
+        "recover",
+        KeywordDefinition {
+            token: Recover,
+            canonical_lexeme: "recover",
+        },
+    );
+    map.insert(
+        "catch",
+        KeywordDefinition {
+            token: Recover,
+            canonical_lexeme: "recover",
+        },
+    );
+    map.insert(
+        "finally",
+        KeywordDefinition {
+            token: Finally,
+            canonical_lexeme: "finally",
+        },
+    );
+    map.insert(
+        "using",
+        KeywordDefinition {
+            token: Using,
+            canonical_lexeme: "using",
+        },
+    );
+    map.insert(
+        "disrupt",
+        KeywordDefinition {
+            token: Disrupt,
+            canonical_lexeme: "disrupt",
+        },
+    );
+    map.insert(
+        "throw",
+        KeywordDefinition {
+            token: Disrupt,
+            canonical_lexeme: "disrupt",
+        },
+    );
 
     // Functions
     map.insert(
@@ -411,6 +495,20 @@ static KEYWORD_DEFINITIONS: Lazy<HashMap<&'static str, KeywordDefinition>> = Laz
             canonical_lexeme: "awaken",
         },
     );
+    map.insert(
+        "emerge",
+        KeywordDefinition {
+            token: Emerge,
+            canonical_lexeme: "emerge",
+        },
+    );
+    map.insert(
+        "yield",
+        KeywordDefinition {
+            token: Emerge,
+            canonical_lexeme: "emerge",
+        },
+    );
     map.insert(
         "await",
         KeywordDefinition {
@@ -448,6 +546,13 @@ static KEYWORD_DEFINITIONS: Lazy<HashMap<&'static str, KeywordDefinition>> = Laz
             canonical_lexeme: "session",
         },
     );
+    map.insert(
+        "extends",
+        KeywordDefinition {
+            token: Extends,
+            canonical_lexeme: "extends",
+        },
+    );
     map.insert(
         "constructor",
         KeywordDefinition {
@@ -485,6 +590,27 @@ static KEYWORD_DEFINITIONS: Lazy<HashMap<&'static str, KeywordDefinition>> = Laz
             canonical_lexeme: "tranceify",
         },
     );
+    map.insert(
+        "interface",
+        KeywordDefinition {
+            token: Interface,
+            canonical_lexeme: "interface",
+        },
+    );
+    map.insert(
+        "enum",
+        KeywordDefinition {
+            token: Enum,
+            canonical_lexeme: "enum",
+        },
+    );
+    map.insert(
+        "implements",
+        KeywordDefinition {
+            token: Implements,
+            canonical_lexeme: "implements",
+        },
+    );
     map.insert(
         "observe",
         KeywordDefinition {
@@ -720,6 +846,14 @@ static KEYWORD_DEFINITIONS: Lazy<HashMap<&'static str, KeywordDefinition>> = Laz
         },
     );
 
+    map.insert(
+        "null",
+        KeywordDefinition {
+            token: Null,
+            canonical_lexeme: "null",
+        },
+    );
+
     map.insert(
         "assert",
         KeywordDefinition {
@@ -762,21 +896,31 @@ impl TokenType {
                 | TokenType::SinkTo
                 | TokenType::Oscillate
                 | TokenType::Suspend
+                | TokenType::Attempt
+                | TokenType::Recover
+                | TokenType::Finally
+                | TokenType::Using
+                | TokenType::Disrupt
                 | TokenType::Suggestion
                 | TokenType::Trigger
                 | TokenType::ImperativeSuggestion
                 | TokenType::DominantSuggestion
                 | TokenType::Mesmerize
                 | TokenType::Awaken
+                | TokenType::Emerge
                 | TokenType::Await
                 | TokenType::SurrenderTo
                 | TokenType::Call
                 | TokenType::Session
+                | TokenType::Extends
                 | TokenType::Constructor
                 | TokenType::Expose
                 | TokenType::Conceal
                 | TokenType::Dominant
                 | TokenType::Tranceify
+                | TokenType::Interface
+                | TokenType::Implements
+                | TokenType::Enum
                 | TokenType::Observe
                 | TokenType::Whisper
                 | TokenType::Command
@@ -792,6 +936,7 @@ impl TokenType {
                 | TokenType::Assert
                 | TokenType::True
                 | TokenType::False
+                | TokenType::Null
         )
     }
 
@@ -829,8 +974,21 @@ impl TokenType {
                 | TokenType::QuestionMark
                 | TokenType::QuestionDot
                 | TokenType::QuestionQuestion
+                | TokenType::PlusEquals
+                | TokenType::MinusEquals
+                | TokenType::AsteriskEquals
+                | TokenType::SlashEquals
+                | TokenType::PercentEquals
+                | TokenType::QuestionQuestionEquals
+                | TokenType::PlusPlus
+                | TokenType::MinusMinus
+                | TokenType::DotDot
+                | TokenType::DotDotEquals
                 | TokenType::Pipe
                 | TokenType::Ampersand
+                | TokenType::Caret
+                | TokenType::Tilde
+                | TokenType::LessLess
                 | TokenType::Arrow
         )
     }
@@ -840,10 +998,12 @@ impl TokenType {
         matches!(
             self,
             TokenType::NumberLiteral
+                | TokenType::IntegerLiteral
                 | TokenType::StringLiteral
                 | TokenType::BooleanLiteral
                 | TokenType::True
                 | TokenType::False
+                | TokenType::Null
         )
     }
 
@@ -1,26 +1,34 @@
 //! Dictionary/Map builtin functions for HypnoScript.
 //!
 //! This module provides operations for working with key-value collections (dictionaries/maps).
-//! In HypnoScript, dictionaries are represented as `Record` types or as string-based JSON objects.
+//! Dictionaries are native ordered maps ([`Dictionary`]) that keep their entries in
+//! insertion order, so iteration, `DictKeys` and `DictValues` are deterministic.
 //!
 //! # Features
 //! - Key-value pair operations
 //! - Dictionary merging and transformation
 //! - Key/value extraction and filtering
-//! - JSON-based dictionary operations
+//! - JSON conversion (preserving the key order of the source document)
 //! - Full i18n support for error messages
 
+use indexmap::IndexMap;
 use serde_json::Value as JsonValue;
-use std::collections::HashMap;
+use std::hash::Hash;
 
 use crate::builtin_trait::{BuiltinError, BuiltinModule, BuiltinResult};
 use crate::localization::LocalizedMessage;
 
+/// Ordered key-value map backing HypnoScript dictionaries.
+///
+/// Entries iterate in insertion order. Overwriting an existing key keeps its
+/// position; removing a key preserves the order of the remaining entries.
+pub type Dictionary<K, V> = IndexMap<K, V>;
+
 /// Dictionary/Map manipulation functions.
 ///
 /// This struct provides static methods for working with key-value collections.
-/// All operations are designed to work with both native Rust HashMaps and
-/// JSON-based dictionary representations.
+/// The operations are generic over key and value types so that the interpreter
+/// can use them directly with its own runtime values.
 pub struct DictionaryBuiltins;
 
 impl BuiltinModule for DictionaryBuiltins {
@@ -63,6 +71,7 @@ impl BuiltinModule for DictionaryBuiltins {
             "DictIsEmpty",
             "DictRemove",
             "DictClear",
+            "DictEntries",
             "DictMerge",
             "DictFilter",
             "DictMap",
@@ -73,253 +82,177 @@ impl BuiltinModule for DictionaryBuiltins {
 }
 
 impl DictionaryBuiltins {
-    /// Creates a new empty dictionary (as JSON string).
-    ///
-    /// # Returns
-    /// Empty JSON object string `"{}"`
+    /// Creates a new empty dictionary.
     ///
     /// # Example
     /// ```rust
     /// use hypnoscript_runtime::DictionaryBuiltins;
-    /// let dict = DictionaryBuiltins::create();
-    /// assert_eq!(dict, "{}");
+    /// let dict = DictionaryBuiltins::create::<String, f64>();
+    /// assert!(dict.is_empty());
     /// ```
-    pub fn create() -> String {
-        "{}".to_string()
+    pub fn create<K, V>() -> Dictionary<K, V> {
+        Dictionary::default()
     }
 
     /// Gets a value from a dictionary by key.
     ///
-    /// # Arguments
-    /// * `dict_json` - Dictionary as JSON string
-    /// * `key` - Key to look up
-    ///
     /// # Returns
-    /// Value as string, or empty string if key not found
+    /// The stored value, or `None` if the key is not present
     ///
     /// # Example
     /// ```rust
     /// use hypnoscript_runtime::DictionaryBuiltins;
-    /// let dict = r#"{"name": "Alice", "age": 30}"#;
-    /// let name = DictionaryBuiltins::get(dict, "name").unwrap();
-    /// assert_eq!(name, "Alice");
+    /// let dict = DictionaryBuiltins::set(&DictionaryBuiltins::create(), "age", 30.0);
+    /// assert_eq!(DictionaryBuiltins::get(&dict, &"age"), Some(30.0));
     /// ```
-    pub fn get(dict_json: &str, key: &str) -> BuiltinResult<String> {
-        let dict: JsonValue = serde_json::from_str(dict_json)
-            .map_err(|e| BuiltinError::new("dict", "parse_error", vec![e.to_string()]))?;
-
-        if let Some(obj) = dict.as_object()
-            && let Some(value) = obj.get(key)
-        {
-            return Ok(value_to_string(value));
-        }
-
-        Ok(String::new())
+    pub fn get<K: Hash + Eq, V: Clone>(dict: &Dictionary<K, V>, key: &K) -> Option<V> {
+        dict.get(key).cloned()
     }
 
-    /// Sets a key-value pair in a dictionary.
+    /// Returns a copy of the dictionary with `key` set to `value`.
     ///
-    /// # Arguments
-    /// * `dict_json` - Dictionary as JSON string
-    /// * `key` - Key to set
-    /// * `value` - Value to set (as string)
-    ///
-    /// # Returns
-    /// Updated dictionary as JSON string
-    ///
-    /// # Example
-    /// ```rust
-    /// use hypnoscript_runtime::DictionaryBuiltins;
-    /// let dict = "{}";
-    /// let updated = DictionaryBuiltins::set(dict, "name", "Bob").unwrap();
-    /// assert!(updated.contains("Bob"));
-    /// ```
-    pub fn set(dict_json: &str, key: &str, value: &str) -> BuiltinResult<String> {
-        let mut dict: JsonValue = serde_json::from_str(dict_json)
-            .map_err(|e| BuiltinError::new("dict", "parse_error", vec![e.to_string()]))?;
-
-        if let Some(obj) = dict.as_object_mut() {
-            // Try to parse value as JSON, otherwise use as string
-            let json_value = serde_json::from_str(value)
-                .unwrap_or_else(|_| JsonValue::String(value.to_string()));
-            obj.insert(key.to_string(), json_value);
-        }
-
-        serde_json::to_string(&dict)
-            .map_err(|e| BuiltinError::new("dict", "serialize_error", vec![e.to_string()]))
+    /// An existing key keeps its position; a new key is appended.
+    pub fn set<K: Hash + Eq + Clone, V: Clone>(
+        dict: &Dictionary<K, V>,
+        key: K,
+        value: V,
+    ) -> Dictionary<K, V> {
+        let mut updated = dict.clone();
+        updated.insert(key, value);
+        updated
     }
 
     /// Checks if a dictionary contains a key.
-    ///
-    /// # Arguments
-    /// * `dict_json` - Dictionary as JSON string
-    /// * `key` - Key to check
-    ///
-    /// # Returns
-    /// `true` if key exists, `false` otherwise
-    pub fn has_key(dict_json: &str, key: &str) -> BuiltinResult<bool> {
-        let dict: JsonValue = serde_json::from_str(dict_json)
-            .map_err(|e| BuiltinError::new("dict", "parse_error", vec![e.to_string()]))?;
-
-        Ok(dict.as_object().is_some_and(|obj| obj.contains_key(key)))
+    pub fn has_key<K: Hash + Eq, V>(dict: &Dictionary<K, V>, key: &K) -> bool {
+        dict.contains_key(key)
     }
 
-    /// Returns all keys from a dictionary.
-    ///
-    /// # Arguments
-    /// * `dict_json` - Dictionary as JSON string
-    ///
-    /// # Returns
-    /// Vector of all keys as strings
-    pub fn keys(dict_json: &str) -> BuiltinResult<Vec<String>> {
-        let dict: JsonValue = serde_json::from_str(dict_json)
-            .map_err(|e| BuiltinError::new("dict", "parse_error", vec![e.to_string()]))?;
-
-        Ok(dict
-            .as_object()
-            .map(|obj| obj.keys().cloned().collect())
-            .unwrap_or_default())
+    /// Returns all keys in insertion order.
+    pub fn keys<K: Clone, V>(dict: &Dictionary<K, V>) -> Vec<K> {
+        dict.keys().cloned().collect()
     }
 
-    /// Returns all values from a dictionary.
-    ///
-    /// # Arguments
-    /// * `dict_json` - Dictionary as JSON string
-    ///
-    /// # Returns
-    /// Vector of all values as strings
-    pub fn values(dict_json: &str) -> BuiltinResult<Vec<String>> {
-        let dict: JsonValue = serde_json::from_str(dict_json)
-            .map_err(|e| BuiltinError::new("dict", "parse_error", vec![e.to_string()]))?;
+    /// Returns all values in insertion order.
+    pub fn values<K, V: Clone>(dict: &Dictionary<K, V>) -> Vec<V> {
+        dict.values().cloned().collect()
+    }
 
-        Ok(dict
-            .as_object()
-            .map(|obj| obj.values().map(value_to_string).collect())
-            .unwrap_or_default())
+    /// Returns all `(key, value)` pairs in insertion order.
+    pub fn entries<K: Clone, V: Clone>(dict: &Dictionary<K, V>) -> Vec<(K, V)> {
+        dict.iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
     }
 
     /// Returns the number of key-value pairs in a dictionary.
-    ///
-    /// # Arguments
-    /// * `dict_json` - Dictionary as JSON string
-    ///
-    /// # Returns
-    /// Number of entries in the dictionary
-    pub fn size(dict_json: &str) -> BuiltinResult<usize> {
-        let dict: JsonValue = serde_json::from_str(dict_json)
-            .map_err(|e| BuiltinError::new("dict", "parse_error", vec![e.to_string()]))?;
-
-        Ok(dict.as_object().map_or(0, |obj| obj.len()))
+    pub fn size<K, V>(dict: &Dictionary<K, V>) -> usize {
+        dict.len()
     }
 
     /// Checks if a dictionary is empty.
-    ///
-    /// # Arguments
-    /// * `dict_json` - Dictionary as JSON string
-    ///
-    /// # Returns
-    /// `true` if dictionary has no entries, `false` otherwise
-    pub fn is_empty(dict_json: &str) -> BuiltinResult<bool> {
-        Ok(Self::size(dict_json)? == 0)
+    pub fn is_empty<K, V>(dict: &Dictionary<K, V>) -> bool {
+        dict.is_empty()
     }
 
-    /// Removes a key-value pair from a dictionary.
-    ///
-    /// # Arguments
-    /// * `dict_json` - Dictionary as JSON string
-    /// * `key` - Key to remove
+    /// Returns a copy of the dictionary without `key`.
     ///
-    /// # Returns
-    /// Updated dictionary as JSON string
-    pub fn remove(dict_json: &str, key: &str) -> BuiltinResult<String> {
-        let mut dict: JsonValue = serde_json::from_str(dict_json)
-            .map_err(|e| BuiltinError::new("dict", "parse_error", vec![e.to_string()]))?;
-
-        if let Some(obj) = dict.as_object_mut() {
-            obj.remove(key);
-        }
-
-        serde_json::to_string(&dict)
-            .map_err(|e| BuiltinError::new("dict", "serialize_error", vec![e.to_string()]))
+    /// The remaining entries keep their relative order.
+    pub fn remove<K: Hash + Eq + Clone, V: Clone>(
+        dict: &Dictionary<K, V>,
+        key: &K,
+    ) -> Dictionary<K, V> {
+        let mut updated = dict.clone();
+        updated.shift_remove(key);
+        updated
     }
 
-    /// Clears all entries from a dictionary.
-    ///
-    /// # Arguments
-    /// * `dict_json` - Dictionary as JSON string
-    ///
-    /// # Returns
-    /// Empty dictionary as JSON string
-    pub fn clear(_dict_json: &str) -> String {
-        "{}".to_string()
+    /// Returns an empty dictionary of the same type.
+    pub fn clear<K, V>(_dict: &Dictionary<K, V>) -> Dictionary<K, V> {
+        Dictionary::default()
     }
 
     /// Merges two dictionaries (second overrides first on key conflicts).
     ///
-    /// # Arguments
-    /// * `dict1_json` - First dictionary as JSON string
-    /// * `dict2_json` - Second dictionary as JSON string
-    ///
-    /// # Returns
-    /// Merged dictionary as JSON string
-    pub fn merge(dict1_json: &str, dict2_json: &str) -> BuiltinResult<String> {
-        let mut dict1: JsonValue = serde_json::from_str(dict1_json)
-            .map_err(|e| BuiltinError::new("dict", "parse_error", vec![e.to_string()]))?;
-        let dict2: JsonValue = serde_json::from_str(dict2_json)
-            .map_err(|e| BuiltinError::new("dict", "parse_error", vec![e.to_string()]))?;
+    /// Keys of `first` keep their position; keys only present in `second`
+    /// are appended in their order.
+    pub fn merge<K: Hash + Eq + Clone, V: Clone>(
+        first: &Dictionary<K, V>,
+        second: &Dictionary<K, V>,
+    ) -> Dictionary<K, V> {
+        let mut merged = first.clone();
+        for (key, value) in second {
+            merged.insert(key.clone(), value.clone());
+        }
+        merged
+    }
 
-        if let (Some(obj1), Some(obj2)) = (dict1.as_object_mut(), dict2.as_object()) {
-            for (key, value) in obj2 {
-                obj1.insert(key.clone(), value.clone());
+    /// Keeps only the entries for which `predicate` returns `Ok(true)`.
+    ///
+    /// The predicate is fallible so that interpreter callbacks can propagate
+    /// their errors.
+    pub fn filter<K, V, E, F>(
+        dict: &Dictionary<K, V>,
+        mut predicate: F,
+    ) -> Result<Dictionary<K, V>, E>
+    where
+        K: Hash + Eq + Clone,
+        V: Clone,
+        F: FnMut(&K, &V) -> Result<bool, E>,
+    {
+        let mut filtered = Dictionary::default();
+        for (key, value) in dict {
+            if predicate(key, value)? {
+                filtered.insert(key.clone(), value.clone());
             }
         }
-
-        serde_json::to_string(&dict1)
-            .map_err(|e| BuiltinError::new("dict", "serialize_error", vec![e.to_string()]))
+        Ok(filtered)
     }
 
-    /// Converts a Rust HashMap to JSON string.
-    ///
-    /// # Arguments
-    /// * `map` - HashMap with string keys and values
-    ///
-    /// # Returns
-    /// JSON representation of the map
-    pub fn from_hashmap(map: &HashMap<String, String>) -> BuiltinResult<String> {
-        serde_json::to_string(map)
-            .map_err(|e| BuiltinError::new("dict", "serialize_error", vec![e.to_string()]))
+    /// Transforms every value with `f`, keeping keys and order.
+    pub fn map<K, V, U, E, F>(dict: &Dictionary<K, V>, mut f: F) -> Result<Dictionary<K, U>, E>
+    where
+        K: Hash + Eq + Clone,
+        F: FnMut(&K, &V) -> Result<U, E>,
+    {
+        let mut mapped = Dictionary::with_capacity(dict.len());
+        for (key, value) in dict {
+            mapped.insert(key.clone(), f(key, value)?);
+        }
+        Ok(mapped)
     }
 
-    /// Converts a JSON string to a Rust HashMap.
+    /// Parses a JSON object into a dictionary.
     ///
-    /// # Arguments
-    /// * `dict_json` - Dictionary as JSON string
+    /// Keys keep the order in which they appear in the document.
     ///
-    /// # Returns
-    /// HashMap with string keys and values
-    pub fn to_hashmap(dict_json: &str) -> BuiltinResult<HashMap<String, String>> {
-        let dict: JsonValue = serde_json::from_str(dict_json)
+    /// # Example
+    /// ```rust
+    /// use hypnoscript_runtime::DictionaryBuiltins;
+    /// let dict = DictionaryBuiltins::from_json(r#"{"b": 1, "a": 2}"#).unwrap();
+    /// assert_eq!(DictionaryBuiltins::keys(&dict), vec!["b", "a"]);
+    /// ```
+    pub fn from_json(json: &str) -> BuiltinResult<Dictionary<String, JsonValue>> {
+        let value: JsonValue = serde_json::from_str(json)
             .map_err(|e| BuiltinError::new("dict", "parse_error", vec![e.to_string()]))?;
 
-        let mut map = HashMap::new();
-        if let Some(obj) = dict.as_object() {
-            for (key, value) in obj {
-                map.insert(key.clone(), value_to_string(value));
-            }
+        match value {
+            JsonValue::Object(object) => Ok(object.into_iter().collect()),
+            other => Err(BuiltinError::new(
+                "dict",
+                "not_an_object",
+                vec![other.to_string()],
+            )),
         }
-
-        Ok(map)
     }
-}
 
-/// Helper function to convert JSON values to strings.
-fn value_to_string(value: &JsonValue) -> String {
-    match value {
-        JsonValue::String(s) => s.clone(),
-        JsonValue::Number(n) => n.to_string(),
-        JsonValue::Bool(b) => b.to_string(),
-        JsonValue::Null => "null".to_string(),
-        _ => value.to_string(),
+    /// Serializes a dictionary of JSON values into a JSON object string.
+    pub fn to_json(dict: &Dictionary<String, JsonValue>) -> BuiltinResult<String> {
+        let object: serde_json::Map<String, JsonValue> = dict
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        serde_json::to_string(&JsonValue::Object(object))
+            .map_err(|e| BuiltinError::new("dict", "serialize_error", vec![e.to_string()]))
     }
 }
 
@@ -327,66 +260,99 @@ fn value_to_string(value: &JsonValue) -> String {
 mod tests {
     use super::*;
 
+    fn sample() -> Dictionary<String, f64> {
+        let mut dict = DictionaryBuiltins::create();
+        dict.insert("b".to_string(), 2.0);
+        dict.insert("a".to_string(), 1.0);
+        dict.insert("c".to_string(), 3.0);
+        dict
+    }
+
     #[test]
     fn test_create_empty_dict() {
-        let dict = DictionaryBuiltins::create();
-        assert_eq!(dict, "{}");
+        let dict = DictionaryBuiltins::create::<String, f64>();
+        assert!(DictionaryBuiltins::is_empty(&dict));
+        assert_eq!(DictionaryBuiltins::size(&dict), 0);
     }
 
     #[test]
     fn test_set_and_get() {
-        let dict = DictionaryBuiltins::create();
-        let dict = DictionaryBuiltins::set(&dict, "name", "Alice").unwrap();
-        let name = DictionaryBuiltins::get(&dict, "name").unwrap();
-        assert_eq!(name, "Alice");
+        let dict = DictionaryBuiltins::set(&DictionaryBuiltins::create(), "name", "Alice");
+        assert_eq!(DictionaryBuiltins::get(&dict, &"name"), Some("Alice"));
+        assert_eq!(DictionaryBuiltins::get(&dict, &"email"), None);
     }
 
     #[test]
     fn test_has_key() {
-        let dict = r#"{"name": "Bob", "age": "25"}"#;
-        assert!(DictionaryBuiltins::has_key(dict, "name").unwrap());
-        assert!(!DictionaryBuiltins::has_key(dict, "email").unwrap());
+        let dict = sample();
+        assert!(DictionaryBuiltins::has_key(&dict, &"a".to_string()));
+        assert!(!DictionaryBuiltins::has_key(&dict, &"z".to_string()));
     }
 
     #[test]
-    fn test_keys_and_values() {
-        let dict = r#"{"a": "1", "b": "2", "c": "3"}"#;
-        let keys = DictionaryBuiltins::keys(dict).unwrap();
-        let values = DictionaryBuiltins::values(dict).unwrap();
-
-        assert_eq!(keys.len(), 3);
-        assert_eq!(values.len(), 3);
-        assert!(keys.contains(&"a".to_string()));
+    fn test_keys_and_values_keep_insertion_order() {
+        let dict = sample();
+        assert_eq!(DictionaryBuiltins::keys(&dict), vec!["b", "a", "c"]);
+        assert_eq!(DictionaryBuiltins::values(&dict), vec![2.0, 1.0, 3.0]);
+
+        // Overwriting keeps the original position
+        let dict = DictionaryBuiltins::set(&dict, "b".to_string(), 20.0);
+        assert_eq!(
+            DictionaryBuiltins::entries(&dict)[0],
+            ("b".to_string(), 20.0)
+        );
     }
 
     #[test]
-    fn test_size_and_is_empty() {
-        let dict = DictionaryBuiltins::create();
-        assert!(DictionaryBuiltins::is_empty(&dict).unwrap());
-        assert_eq!(DictionaryBuiltins::size(&dict).unwrap(), 0);
-
-        let dict = DictionaryBuiltins::set(&dict, "key", "value").unwrap();
-        assert!(!DictionaryBuiltins::is_empty(&dict).unwrap());
-        assert_eq!(DictionaryBuiltins::size(&dict).unwrap(), 1);
+    fn test_remove_preserves_order() {
+        let dict = DictionaryBuiltins::remove(&sample(), &"b".to_string());
+        assert_eq!(DictionaryBuiltins::keys(&dict), vec!["a", "c"]);
+        assert!(DictionaryBuiltins::is_empty(&DictionaryBuiltins::clear(
+            &dict
+        )));
     }
 
     #[test]
-    fn test_remove() {
-        let dict = r#"{"a": "1", "b": "2"}"#;
-        let dict = DictionaryBuiltins::remove(dict, "a").unwrap();
-        assert!(!DictionaryBuiltins::has_key(&dict, "a").unwrap());
-        assert!(DictionaryBuiltins::has_key(&dict, "b").unwrap());
+    fn test_merge() {
+        let mut other = Dictionary::new();
+        other.insert("a".to_string(), 10.0);
+        other.insert("d".to_string(), 4.0);
+        let merged = DictionaryBuiltins::merge(&sample(), &other);
+
+        assert_eq!(DictionaryBuiltins::keys(&merged), vec!["b", "a", "c", "d"]);
+        assert_eq!(
+            DictionaryBuiltins::get(&merged, &"a".to_string()),
+            Some(10.0)
+        );
     }
 
     #[test]
-    fn test_merge() {
-        let dict1 = r#"{"a": "1", "b": "2"}"#;
-        let dict2 = r#"{"b": "3", "c": "4"}"#;
-        let merged = DictionaryBuiltins::merge(dict1, dict2).unwrap();
+    fn test_filter_and_map() {
+        let filtered =
+            DictionaryBuiltins::filter(&sample(), |_, value| Ok::<_, ()>(*value > 1.0)).unwrap();
+        assert_eq!(DictionaryBuiltins::keys(&filtered), vec!["b", "c"]);
+
+        let mapped = DictionaryBuiltins::map(&sample(), |key, value| {
+            Ok::<_, ()>(format!("{key}={value}"))
+        })
+        .unwrap();
+        assert_eq!(
+            DictionaryBuiltins::values(&mapped),
+            vec!["b=2", "a=1", "c=3"]
+        );
+    }
 
-        assert_eq!(DictionaryBuiltins::get(&merged, "a").unwrap(), "1");
-        assert_eq!(DictionaryBuiltins::get(&merged, "b").unwrap(), "3"); // Overridden
-        assert_eq!(DictionaryBuiltins::get(&merged, "c").unwrap(), "4");
+    #[test]
+    fn test_json_round_trip_keeps_order_and_types() {
+        let dict =
+            DictionaryBuiltins::from_json(r#"{"name": "Bob", "age": 25, "tags": [1]}"#).unwrap();
+        assert_eq!(DictionaryBuiltins::keys(&dict), vec!["name", "age", "tags"]);
+        assert_eq!(dict["age"], JsonValue::from(25));
+        assert_eq!(
+            DictionaryBuiltins::to_json(&dict).unwrap(),
+            r#"{"name":"Bob","age":25,"tags":[1]}"#
+        );
+        assert!(DictionaryBuiltins::from_json("[1, 2]").is_err());
     }
 
     #[test]
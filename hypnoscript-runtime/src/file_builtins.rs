@@ -37,6 +37,7 @@ impl BuiltinModule for FileBuiltins {
             "WriteFile",
             "AppendFile",
             "ReadLines",
+            "StreamLines",
             "WriteLines",
             "FileExists",
             "IsFile",
@@ -95,6 +96,12 @@ impl FileBuiltins {
         reader.lines().collect()
     }
 
+    /// Open a file for reading line by line without loading it into memory
+    pub fn stream_lines(path: &str) -> io::Result<io::Lines<BufReader<fs::File>>> {
+        let file = fs::File::open(path)?;
+        Ok(BufReader::new(file).lines())
+    }
+
     /// Write lines to a file using `\n` separators
     pub fn write_lines(path: &str, lines: &[String]) -> io::Result<()> {
         let path_ref = Path::new(path);
@@ -307,6 +314,20 @@ mod tests {
         let _ = fs::remove_file(test_file);
     }
 
+    #[test]
+    fn test_stream_lines() {
+        let test_file = unique_test_file();
+        let path = test_file.to_string_lossy().into_owned();
+        fs::write(&test_file, "eins\nzwei\r\ndrei").unwrap();
+        let streamed: Vec<String> = FileBuiltins::stream_lines(&path)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(streamed, vec!["eins", "zwei", "drei"]);
+        assert!(FileBuiltins::stream_lines(&format!("{}.missing", path)).is_err());
+        let _ = fs::remove_file(test_file);
+    }
+
     #[test]
     fn test_copy_directory_recursive() {
         let source = unique_test_directory();
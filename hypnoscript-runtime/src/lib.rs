@@ -33,7 +33,7 @@ pub use collection_builtins::CollectionBuiltins;
 pub use core_builtins::CoreBuiltins;
 pub use data_builtins::{CsvOptions, DataBuiltins, JsonQueryOptions};
 pub use deepmind_builtins::DeepMindBuiltins;
-pub use dictionary_builtins::DictionaryBuiltins;
+pub use dictionary_builtins::{Dictionary, DictionaryBuiltins};
 pub use file_builtins::FileBuiltins;
 pub use hashing_builtins::HashingBuiltins;
 pub use localization::{Locale, LocalizedMessage, detect_locale};